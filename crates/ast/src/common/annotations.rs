@@ -4,7 +4,7 @@ use alloc::vec::Vec;
 
 use crate::common::Identifier;
 use crate::error::Error;
-use crate::{FxBuildHasher, IndexMap};
+use crate::{FxBuildHasher, IndexMap, owned};
 
 /// The value of an annotation.
 #[derive(Clone, Eq, PartialEq, Debug, Hash)]
@@ -13,6 +13,17 @@ pub enum AnnotationValue<'a> {
     String(Cow<'a, str>),
 }
 
+impl AnnotationValue<'_> {
+    /// Converts into a value that owns its string.
+    #[must_use]
+    pub fn into_owned(self) -> AnnotationValue<'static> {
+        match self {
+            Self::Empty => AnnotationValue::Empty,
+            Self::String(value) => AnnotationValue::String(owned(value)),
+        }
+    }
+}
+
 /// A collection of annotations keyed by identifier.
 #[derive(Clone, Debug)]
 pub struct Annotations<'a>(IndexMap<Identifier<'a>, AnnotationValue<'a>>);
@@ -46,12 +57,23 @@ impl<'a> Annotations<'a> {
 
     /// Returns the annotation value for the given key.
     #[must_use]
-    pub fn get(&self, key: Identifier<'a>) -> Option<&AnnotationValue<'a>> {
-        self.0.get(&key)
+    pub fn get(&self, key: &Identifier<'a>) -> Option<&AnnotationValue<'a>> {
+        self.0.get(key)
     }
 
     /// Returns an iterator over annotation key-value pairs.
-    pub fn iter(&self) -> impl Iterator<Item = (Identifier<'a>, &AnnotationValue<'a>)> {
-        self.0.iter().map(|(key, value)| (*key, value))
+    pub fn iter(&self) -> impl Iterator<Item = (&Identifier<'a>, &AnnotationValue<'a>)> {
+        self.0.iter()
+    }
+
+    /// Converts into annotations that own their keys and values.
+    #[must_use]
+    pub fn into_owned(self) -> Annotations<'static> {
+        Annotations(
+            self.0
+                .into_iter()
+                .map(|(key, value)| (key.into_owned(), value.into_owned()))
+                .collect(),
+        )
     }
 }
@@ -1,11 +1,12 @@
+use alloc::borrow::Cow;
 use alloc::string::String;
 use core::fmt;
 
 use crate::error::Error;
 
 /// A non-empty identifier string.
-#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
-pub struct Identifier<'a>(&'a str);
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
+pub struct Identifier<'a>(Cow<'a, str>);
 
 impl<'a> Identifier<'a> {
     /// Creates an identifier from a string.
@@ -41,18 +42,24 @@ impl<'a> Identifier<'a> {
             });
         }
 
-        Ok(Self(value))
+        Ok(Self(Cow::Borrowed(value)))
     }
 
     /// Returns the identifier string.
     #[must_use]
-    pub const fn as_str(&self) -> &'a str {
-        self.0
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Converts into an identifier that owns its string.
+    #[must_use]
+    pub fn into_owned(self) -> Identifier<'static> {
+        Identifier(Cow::Owned(self.0.into_owned()))
     }
 }
 
 impl fmt::Display for Identifier<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(self.0)
+        f.write_str(&self.0)
     }
 }
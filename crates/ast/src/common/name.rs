@@ -2,6 +2,7 @@ use alloc::vec::Vec;
 use core::fmt;
 
 use crate::common::Identifier;
+use crate::error::Error;
 
 /// A qualified path like `Namespace::Type`.
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
@@ -26,6 +27,24 @@ impl<'a> Name<'a> {
         }
     }
 
+    /// Parses a `::` separated name like `Namespace::Type`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any segment is not a valid identifier.
+    pub fn parse(text: &'a str) -> Result<Self, Error> {
+        let mut segments = text.split("::").map(Identifier::new);
+        let mut path = Vec::new();
+        let mut basename = segments.next().ok_or(Error::Empty)??;
+
+        for segment in segments {
+            path.push(basename);
+            basename = segment?;
+        }
+
+        Ok(Self { path, basename })
+    }
+
     /// Returns the namespace path segments (excluding the basename).
     #[must_use]
     pub fn path(&self) -> &[Identifier<'a>] {
@@ -34,8 +53,17 @@ impl<'a> Name<'a> {
 
     /// Returns the basename (final segment).
     #[must_use]
-    pub const fn basename(&self) -> Identifier<'a> {
-        self.basename
+    pub const fn basename(&self) -> &Identifier<'a> {
+        &self.basename
+    }
+
+    /// Converts into a name that owns its segments.
+    #[must_use]
+    pub fn into_owned(self) -> Name<'static> {
+        Name {
+            path: self.path.into_iter().map(Identifier::into_owned).collect(),
+            basename: self.basename.into_owned(),
+        }
     }
 }
 
@@ -1,9 +1,12 @@
 use alloc::format;
 use alloc::string::String;
 use core::fmt;
+use core::ops::Range;
 
 use duramen_diagnostic::Diagnostic;
 
+use crate::policy::SlotKind;
+
 /// An error produced during AST node construction.
 #[derive(Clone, Debug)]
 pub enum Error {
@@ -21,6 +24,25 @@ pub enum Error {
     IntegerOverflow { text: String },
     /// An identifier contains invalid characters.
     InvalidIdentifier { name: String },
+    /// A template slot has no value when linking.
+    MissingSlot { slot: SlotKind },
+    /// A value was provided for a slot the template does not have.
+    UnexpectedSlot { slot: SlotKind },
+    /// A template with the given ID does not exist.
+    UnknownTemplate { id: String },
+    /// A policy or template with the given ID already exists.
+    ///
+    /// Spans are those of the new and the existing ID, such as their `@id` annotations, when
+    /// known.
+    DuplicatePolicyId {
+        id: String,
+        span: Option<Range<usize>>,
+        first: Option<Range<usize>>,
+    },
+    /// A policy or template with the given ID does not exist.
+    UnknownPolicy { id: String },
+    /// A template cannot be removed while policies are linked to it.
+    TemplateHasLinks { id: String },
 }
 
 impl fmt::Display for Error {
@@ -41,6 +63,16 @@ impl fmt::Display for Error {
             Self::InvalidIdentifier { name } => {
                 write!(f, "`{name}` is not a valid identifier")
             }
+            Self::MissingSlot { slot } => write!(f, "no value provided for slot `{slot}`"),
+            Self::UnexpectedSlot { slot } => {
+                write!(f, "template does not have the slot `{slot}`")
+            }
+            Self::UnknownTemplate { id } => write!(f, "template `{id}` does not exist"),
+            Self::DuplicatePolicyId { id, .. } => write!(f, "duplicate policy id `{id}`"),
+            Self::UnknownPolicy { id } => write!(f, "policy `{id}` does not exist"),
+            Self::TemplateHasLinks { id } => {
+                write!(f, "template `{id}` still has linked policies")
+            }
         }
     }
 }
@@ -55,6 +87,20 @@ impl From<Error> for Diagnostic {
             Error::InvalidIdentifier { .. } => Self::error(format!("{value}")).with_note(
                 "identifiers must start with `_` or a letter, followed by `_`, letters, or digits",
             ),
+            Error::DuplicatePolicyId { span, first, .. } => {
+                let mut diagnostic = Self::error(format!("{value}"));
+                if let Some(first) = first {
+                    diagnostic = diagnostic.with_context(first.clone(), "first used here");
+                }
+
+                if let Some(span) = span {
+                    diagnostic = diagnostic.with_label(span.clone(), "duplicate policy id");
+                }
+
+                diagnostic
+            }
+            Error::TemplateHasLinks { .. } => Self::error(format!("{value}"))
+                .with_note("remove the linked policies before the template"),
             _ => Self::error(format!("{value}")),
         }
     }
@@ -0,0 +1,414 @@
+//! Rewriting of policies and schemas by value.
+//!
+//! Each `fold_*` method defaults to the matching free function, which folds the children of the
+//! node and rebuilds it. Spans are kept on rebuilt nodes. Entries of sets, such as `action in [...]`
+//! lists and entity parents, are merged if they fold to the same value.
+//!
+//! ```
+//! use duramen_ast::fold::{self, Fold};
+//! use duramen_ast::{Expression, ExpressionKind};
+//!
+//! /// Rewrites `!(!e)` to `e`.
+//! struct DoubleNegation;
+//!
+//! impl<'a> Fold<'a> for DoubleNegation {
+//!     fn fold_expression(&mut self, expression: Expression<'a>) -> Expression<'a> {
+//!         let expression = fold::fold_expression(self, expression);
+//!         if let ExpressionKind::UnaryApp { operand, .. } = expression.kind()
+//!             && let ExpressionKind::UnaryApp { operand, .. } = operand.kind()
+//!         {
+//!             return (**operand).clone();
+//!         }
+//!
+//!         expression
+//!     }
+//! }
+//! ```
+
+use alloc::boxed::Box;
+
+use crate::common::Name;
+use crate::policy::{
+    ActionConstraint, Condition, EntityOrSlot, EntityReference, Expression, ExpressionKind,
+    Literal, Policy, ScopeConstraint,
+};
+use crate::schema::{
+    ActionDeclaration, AppliesTo, AttributeDeclaration, ContextType, Declaration,
+    EntityDeclaration, EntityKind, Namespace, Schema, TypeDeclaration, TypeExpression,
+};
+
+/// A folder that rewrites policies and schemas by value.
+pub trait Fold<'a> {
+    fn fold_policy(&mut self, policy: Policy<'a>) -> Policy<'a> {
+        fold_policy(self, policy)
+    }
+
+    fn fold_scope_constraint(&mut self, constraint: ScopeConstraint<'a>) -> ScopeConstraint<'a> {
+        fold_scope_constraint(self, constraint)
+    }
+
+    fn fold_action_constraint(&mut self, constraint: ActionConstraint<'a>) -> ActionConstraint<'a> {
+        fold_action_constraint(self, constraint)
+    }
+
+    fn fold_condition(&mut self, condition: Condition<'a>) -> Condition<'a> {
+        fold_condition(self, condition)
+    }
+
+    fn fold_expression(&mut self, expression: Expression<'a>) -> Expression<'a> {
+        fold_expression(self, expression)
+    }
+
+    fn fold_literal(&mut self, literal: Literal<'a>) -> Literal<'a> {
+        fold_literal(self, literal)
+    }
+
+    fn fold_entity_reference(&mut self, reference: EntityReference<'a>) -> EntityReference<'a> {
+        fold_entity_reference(self, reference)
+    }
+
+    /// Folds a type name, such as an entity type or a reference to a schema type.
+    fn fold_name(&mut self, name: Name<'a>) -> Name<'a> {
+        name
+    }
+
+    fn fold_schema(&mut self, schema: Schema<'a>) -> Schema<'a> {
+        fold_schema(self, schema)
+    }
+
+    fn fold_namespace(&mut self, namespace: Namespace<'a>) -> Namespace<'a> {
+        fold_namespace(self, namespace)
+    }
+
+    fn fold_declaration(&mut self, declaration: Declaration<'a>) -> Declaration<'a> {
+        fold_declaration(self, declaration)
+    }
+
+    fn fold_entity_declaration(
+        &mut self,
+        declaration: EntityDeclaration<'a>,
+    ) -> EntityDeclaration<'a> {
+        fold_entity_declaration(self, declaration)
+    }
+
+    fn fold_action_declaration(
+        &mut self,
+        declaration: ActionDeclaration<'a>,
+    ) -> ActionDeclaration<'a> {
+        fold_action_declaration(self, declaration)
+    }
+
+    fn fold_type_declaration(&mut self, declaration: TypeDeclaration<'a>) -> TypeDeclaration<'a> {
+        fold_type_declaration(self, declaration)
+    }
+
+    fn fold_applies_to(&mut self, applies_to: AppliesTo<'a>) -> AppliesTo<'a> {
+        fold_applies_to(self, applies_to)
+    }
+
+    /// Folds a record attribute, an entity attribute or an action attribute.
+    fn fold_attribute_declaration(
+        &mut self,
+        _name: &str,
+        declaration: AttributeDeclaration<'a>,
+    ) -> AttributeDeclaration<'a> {
+        fold_attribute_declaration(self, declaration)
+    }
+
+    fn fold_type_expression(&mut self, expression: TypeExpression<'a>) -> TypeExpression<'a> {
+        fold_type_expression(self, expression)
+    }
+}
+
+pub fn fold_policy<'a, F: Fold<'a> + ?Sized>(folder: &mut F, policy: Policy<'a>) -> Policy<'a> {
+    policy
+        .map_principal(|principal| {
+            principal.map_constraint(|constraint| folder.fold_scope_constraint(constraint))
+        })
+        .map_action(|constraint| folder.fold_action_constraint(constraint))
+        .map_resource(|resource| {
+            resource.map_constraint(|constraint| folder.fold_scope_constraint(constraint))
+        })
+        .map_conditions(|condition| folder.fold_condition(condition))
+}
+
+pub fn fold_scope_constraint<'a, F: Fold<'a> + ?Sized>(
+    folder: &mut F,
+    constraint: ScopeConstraint<'a>,
+) -> ScopeConstraint<'a> {
+    match constraint {
+        ScopeConstraint::Any => ScopeConstraint::Any,
+        ScopeConstraint::Equal(entity) => ScopeConstraint::Equal(target(folder, entity)),
+        ScopeConstraint::In(entity) => ScopeConstraint::In(target(folder, entity)),
+        ScopeConstraint::Is(name) => ScopeConstraint::Is(folder.fold_name(name)),
+        ScopeConstraint::IsIn(name, entity) => {
+            let entity = target(folder, entity);
+            ScopeConstraint::IsIn(folder.fold_name(name), entity)
+        }
+    }
+}
+
+fn target<'a, F: Fold<'a> + ?Sized>(folder: &mut F, target: EntityOrSlot<'a>) -> EntityOrSlot<'a> {
+    match target {
+        EntityOrSlot::Entity(reference) => {
+            EntityOrSlot::Entity(folder.fold_entity_reference(reference))
+        }
+        EntityOrSlot::Slot => EntityOrSlot::Slot,
+    }
+}
+
+pub fn fold_action_constraint<'a, F: Fold<'a> + ?Sized>(
+    folder: &mut F,
+    constraint: ActionConstraint<'a>,
+) -> ActionConstraint<'a> {
+    match constraint {
+        ActionConstraint::Any => ActionConstraint::Any,
+        ActionConstraint::Equal(reference) => {
+            ActionConstraint::Equal(folder.fold_entity_reference(reference))
+        }
+        ActionConstraint::In(list) => {
+            ActionConstraint::In(list.map(|reference| folder.fold_entity_reference(reference)))
+        }
+    }
+}
+
+pub fn fold_condition<'a, F: Fold<'a> + ?Sized>(
+    folder: &mut F,
+    condition: Condition<'a>,
+) -> Condition<'a> {
+    let kind = condition.kind();
+    Condition::new(kind, folder.fold_expression(condition.into_body()))
+}
+
+pub fn fold_expression<'a, F: Fold<'a> + ?Sized>(
+    folder: &mut F,
+    expression: Expression<'a>,
+) -> Expression<'a> {
+    let span = expression.span();
+
+    let kind = match expression.into_kind() {
+        ExpressionKind::Literal(literal) => ExpressionKind::Literal(folder.fold_literal(literal)),
+        kind @ (ExpressionKind::Variable(_) | ExpressionKind::Slot(_)) => kind,
+        ExpressionKind::If {
+            test,
+            consequent,
+            alternate,
+        } => ExpressionKind::If {
+            test: boxed(folder, test),
+            consequent: boxed(folder, consequent),
+            alternate: boxed(folder, alternate),
+        },
+        ExpressionKind::And { left, right } => ExpressionKind::And {
+            left: boxed(folder, left),
+            right: boxed(folder, right),
+        },
+        ExpressionKind::Or { left, right } => ExpressionKind::Or {
+            left: boxed(folder, left),
+            right: boxed(folder, right),
+        },
+        ExpressionKind::UnaryApp { operator, operand } => ExpressionKind::UnaryApp {
+            operator,
+            operand: boxed(folder, operand),
+        },
+        ExpressionKind::BinaryApp {
+            operator,
+            left,
+            right,
+        } => ExpressionKind::BinaryApp {
+            operator,
+            left: boxed(folder, left),
+            right: boxed(folder, right),
+        },
+        ExpressionKind::GetAttribute {
+            expression,
+            attribute,
+        } => ExpressionKind::GetAttribute {
+            expression: boxed(folder, expression),
+            attribute,
+        },
+        ExpressionKind::HasAttribute {
+            expression,
+            attribute,
+        } => ExpressionKind::HasAttribute {
+            expression: boxed(folder, expression),
+            attribute,
+        },
+        ExpressionKind::Like {
+            expression,
+            pattern,
+        } => ExpressionKind::Like {
+            expression: boxed(folder, expression),
+            pattern,
+        },
+        ExpressionKind::Is { expression, kind } => {
+            let expression = boxed(folder, expression);
+            ExpressionKind::Is {
+                expression,
+                kind: folder.fold_name(kind),
+            }
+        }
+        ExpressionKind::IsIn {
+            expression,
+            kind,
+            target,
+        } => {
+            let expression = boxed(folder, expression);
+            let kind = folder.fold_name(kind);
+            ExpressionKind::IsIn {
+                expression,
+                kind,
+                target: boxed(folder, target),
+            }
+        }
+        ExpressionKind::ExtensionCall {
+            function,
+            arguments,
+        } => ExpressionKind::ExtensionCall {
+            function,
+            arguments: arguments
+                .into_iter()
+                .map(|argument| folder.fold_expression(argument))
+                .collect(),
+        },
+        ExpressionKind::Set(elements) => ExpressionKind::Set(
+            elements
+                .into_iter()
+                .map(|element| folder.fold_expression(element))
+                .collect(),
+        ),
+        ExpressionKind::Record(record) => {
+            ExpressionKind::Record(record.map_values(|value| folder.fold_expression(value)))
+        }
+    };
+
+    let rebuilt = Expression::new(kind);
+    match span {
+        Some(span) => rebuilt.with_span(span),
+        None => rebuilt,
+    }
+}
+
+fn boxed<'a, F: Fold<'a> + ?Sized>(
+    folder: &mut F,
+    mut expression: Box<Expression<'a>>,
+) -> Box<Expression<'a>> {
+    *expression = folder.fold_expression(*expression);
+    expression
+}
+
+pub fn fold_literal<'a, F: Fold<'a> + ?Sized>(folder: &mut F, literal: Literal<'a>) -> Literal<'a> {
+    match literal {
+        Literal::Entity(reference) => Literal::Entity(folder.fold_entity_reference(reference)),
+        literal @ (Literal::Bool(_) | Literal::Integer(_) | Literal::String(_)) => literal,
+    }
+}
+
+pub fn fold_entity_reference<'a, F: Fold<'a> + ?Sized>(
+    folder: &mut F,
+    reference: EntityReference<'a>,
+) -> EntityReference<'a> {
+    let (kind, id) = reference.into_parts();
+    EntityReference::new(folder.fold_name(kind), id)
+}
+
+pub fn fold_schema<'a, F: Fold<'a> + ?Sized>(folder: &mut F, schema: Schema<'a>) -> Schema<'a> {
+    schema.map_namespaces(|namespace| folder.fold_namespace(namespace))
+}
+
+pub fn fold_namespace<'a, F: Fold<'a> + ?Sized>(
+    folder: &mut F,
+    namespace: Namespace<'a>,
+) -> Namespace<'a> {
+    namespace.map_declarations(|declaration| folder.fold_declaration(declaration))
+}
+
+pub fn fold_declaration<'a, F: Fold<'a> + ?Sized>(
+    folder: &mut F,
+    declaration: Declaration<'a>,
+) -> Declaration<'a> {
+    match declaration {
+        Declaration::Entity(entity) => Declaration::Entity(folder.fold_entity_declaration(entity)),
+        Declaration::Action(action) => Declaration::Action(folder.fold_action_declaration(action)),
+        Declaration::Type(declaration) => {
+            Declaration::Type(folder.fold_type_declaration(declaration))
+        }
+    }
+}
+
+pub fn fold_entity_declaration<'a, F: Fold<'a> + ?Sized>(
+    folder: &mut F,
+    declaration: EntityDeclaration<'a>,
+) -> EntityDeclaration<'a> {
+    declaration.map_kind(|kind| match kind {
+        EntityKind::Standard(standard) => EntityKind::Standard(
+            standard
+                .map_parents(|parent| folder.fold_name(parent))
+                .map_attributes(|name, attribute| {
+                    folder.fold_attribute_declaration(name, attribute)
+                })
+                .map_tags(|tags| folder.fold_type_expression(tags)),
+        ),
+        EntityKind::Enum(variants) => EntityKind::Enum(variants),
+    })
+}
+
+pub fn fold_action_declaration<'a, F: Fold<'a> + ?Sized>(
+    folder: &mut F,
+    declaration: ActionDeclaration<'a>,
+) -> ActionDeclaration<'a> {
+    declaration
+        .map_applies_to(|applies_to| folder.fold_applies_to(applies_to))
+        .map_attributes(|name, attribute| folder.fold_attribute_declaration(name, attribute))
+}
+
+pub fn fold_type_declaration<'a, F: Fold<'a> + ?Sized>(
+    folder: &mut F,
+    declaration: TypeDeclaration<'a>,
+) -> TypeDeclaration<'a> {
+    declaration.map_definition(|definition| folder.fold_type_expression(definition))
+}
+
+pub fn fold_applies_to<'a, F: Fold<'a> + ?Sized>(
+    folder: &mut F,
+    applies_to: AppliesTo<'a>,
+) -> AppliesTo<'a> {
+    applies_to
+        .map_types(|name| folder.fold_name(name))
+        .map_context(|context| match context {
+            ContextType::Reference(name) => ContextType::Reference(folder.fold_name(name)),
+            ContextType::Record(record) => {
+                ContextType::Record(record.map_attributes(|name, attribute| {
+                    folder.fold_attribute_declaration(name, attribute)
+                }))
+            }
+        })
+}
+
+pub fn fold_attribute_declaration<'a, F: Fold<'a> + ?Sized>(
+    folder: &mut F,
+    declaration: AttributeDeclaration<'a>,
+) -> AttributeDeclaration<'a> {
+    declaration.map_definition(|definition| folder.fold_type_expression(definition))
+}
+
+pub fn fold_type_expression<'a, F: Fold<'a> + ?Sized>(
+    folder: &mut F,
+    expression: TypeExpression<'a>,
+) -> TypeExpression<'a> {
+    match expression {
+        TypeExpression::Reference(name) => TypeExpression::Reference(folder.fold_name(name)),
+        TypeExpression::Set(mut element) => {
+            *element = folder.fold_type_expression(*element);
+            TypeExpression::Set(element)
+        }
+        TypeExpression::Record(record) => {
+            TypeExpression::Record(record.map_attributes(|name, attribute| {
+                folder.fold_attribute_declaration(name, attribute)
+            }))
+        }
+        TypeExpression::Entity(types) => {
+            TypeExpression::Entity(types.map(|name| folder.fold_name(name)))
+        }
+        TypeExpression::Enum(variants) => TypeExpression::Enum(variants),
+    }
+}
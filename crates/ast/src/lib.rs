@@ -5,6 +5,8 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+use alloc::borrow::Cow;
+
 pub(crate) use rustc_hash::FxBuildHasher;
 
 pub(crate) type IndexMap<K, V> = indexmap::IndexMap<K, V, FxBuildHasher>;
@@ -17,6 +19,9 @@ pub use common::*;
 mod error;
 pub use error::Error;
 
+pub mod fold;
+pub use fold::Fold;
+
 /// Reserved type names in schema context.
 pub(crate) const RESERVED_TYPE_NAMES: &[&str] = &[
     "Bool",
@@ -28,8 +33,22 @@ pub(crate) const RESERVED_TYPE_NAMES: &[&str] = &[
     "Extension",
 ];
 
+/// Copies borrowed text, so it no longer points into the source.
+pub(crate) fn owned(text: Cow<'_, str>) -> Cow<'static, str> {
+    Cow::Owned(text.into_owned())
+}
+
 mod policy;
 pub use policy::*;
 
+mod quote;
+pub use quote::{Quoted, QuotedPattern};
+
 mod schema;
 pub use schema::*;
+
+pub mod visit;
+pub use visit::Visit;
+
+pub mod visit_mut;
+pub use visit_mut::VisitMut;
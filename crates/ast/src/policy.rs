@@ -1,6 +1,11 @@
+use alloc::string::ToString as _;
 use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
 
-use crate::common::Annotations;
+use crate::common::{AnnotationValue, Annotations};
+use crate::error::Error;
+use crate::quote;
 
 mod action_constraint;
 pub use action_constraint::ActionConstraint;
@@ -44,6 +49,12 @@ pub use pattern_element::PatternElement;
 mod policies;
 pub use policies::Policies;
 
+mod policy_id;
+pub use policy_id::PolicyId;
+
+mod policy_set;
+pub use policy_set::PolicySet;
+
 mod principal_constraint;
 pub use principal_constraint::PrincipalConstraint;
 
@@ -62,6 +73,9 @@ pub use slot_kind::SlotKind;
 mod string_literal;
 pub use string_literal::StringLiteral;
 
+mod template_link;
+pub use template_link::TemplateLink;
+
 mod unary_operator;
 pub use unary_operator::UnaryOperator;
 
@@ -69,14 +83,17 @@ mod variable;
 pub use variable::Variable;
 
 /// A Cedar policy.
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct Policy<'a> {
     annotations: Annotations<'a>,
     effect: Effect,
     principal: PrincipalConstraint<'a>,
     action: ActionConstraint<'a>,
+    action_span: Option<Range<usize>>,
     resource: ResourceConstraint<'a>,
     conditions: Vec<Condition<'a>>,
+    span: Option<Range<usize>>,
+    id_span: Option<Range<usize>>,
 }
 
 impl<'a> Policy<'a> {
@@ -95,11 +112,35 @@ impl<'a> Policy<'a> {
             effect,
             principal,
             action,
+            action_span: None,
             resource,
             conditions,
+            span: None,
+            id_span: None,
         }
     }
 
+    /// Sets the byte span of this policy in the source text.
+    #[must_use]
+    pub const fn with_span(mut self, span: Range<usize>) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// Sets the byte span of the source the policy ID is taken from, such as its `@id` annotation.
+    #[must_use]
+    pub const fn with_id_span(mut self, span: Range<usize>) -> Self {
+        self.id_span = Some(span);
+        self
+    }
+
+    /// Sets the byte span of the action constraint in the source text.
+    #[must_use]
+    pub const fn with_action_span(mut self, span: Range<usize>) -> Self {
+        self.action_span = Some(span);
+        self
+    }
+
     /// Returns the policy annotations.
     #[must_use]
     pub const fn annotations(&self) -> &Annotations<'a> {
@@ -118,21 +159,244 @@ impl<'a> Policy<'a> {
         &self.principal
     }
 
+    /// Returns the principal constraint mutably.
+    #[must_use]
+    pub const fn principal_mut(&mut self) -> &mut PrincipalConstraint<'a> {
+        &mut self.principal
+    }
+
     /// Returns the action constraint.
     #[must_use]
     pub const fn action(&self) -> &ActionConstraint<'a> {
         &self.action
     }
 
+    /// Returns the action constraint mutably.
+    #[must_use]
+    pub const fn action_mut(&mut self) -> &mut ActionConstraint<'a> {
+        &mut self.action
+    }
+
+    /// Returns the byte span of the action constraint in the source text, if known.
+    #[must_use]
+    pub fn action_span(&self) -> Option<Range<usize>> {
+        self.action_span.clone()
+    }
+
     /// Returns the resource constraint.
     #[must_use]
     pub const fn resource(&self) -> &ResourceConstraint<'a> {
         &self.resource
     }
 
+    /// Returns the resource constraint mutably.
+    #[must_use]
+    pub const fn resource_mut(&mut self) -> &mut ResourceConstraint<'a> {
+        &mut self.resource
+    }
+
     /// Returns the policy conditions.
     #[must_use]
     pub fn conditions(&self) -> &[Condition<'a>] {
         &self.conditions
     }
+
+    /// Returns the policy conditions mutably.
+    #[must_use]
+    pub fn conditions_mut(&mut self) -> &mut [Condition<'a>] {
+        &mut self.conditions
+    }
+
+    /// Returns the byte span of this policy in the source text, if known.
+    #[must_use]
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    /// Returns the byte span of the source the policy ID is taken from, if known.
+    #[must_use]
+    pub fn id_span(&self) -> Option<Range<usize>> {
+        self.id_span.clone()
+    }
+
+    /// Converts into a policy that no longer borrows the source, so it can outlive it.
+    ///
+    /// Spans are kept, but only make sense against the original source text.
+    #[must_use]
+    pub fn into_owned(self) -> Policy<'static> {
+        Policy {
+            annotations: self.annotations.into_owned(),
+            effect: self.effect,
+            principal: self.principal.into_owned(),
+            action: self.action.into_owned(),
+            action_span: self.action_span,
+            resource: self.resource.into_owned(),
+            conditions: self
+                .conditions
+                .into_iter()
+                .map(Condition::into_owned)
+                .collect(),
+            span: self.span,
+            id_span: self.id_span,
+        }
+    }
+
+    /// Maps the principal constraint by value.
+    pub(crate) fn map_principal<F: FnOnce(PrincipalConstraint<'a>) -> PrincipalConstraint<'a>>(
+        mut self,
+        map: F,
+    ) -> Self {
+        self.principal = map(self.principal);
+        self
+    }
+
+    /// Maps the action constraint by value, keeping its span.
+    pub(crate) fn map_action<F: FnOnce(ActionConstraint<'a>) -> ActionConstraint<'a>>(
+        mut self,
+        map: F,
+    ) -> Self {
+        self.action = map(self.action);
+        self
+    }
+
+    /// Maps the resource constraint by value.
+    pub(crate) fn map_resource<F: FnOnce(ResourceConstraint<'a>) -> ResourceConstraint<'a>>(
+        mut self,
+        map: F,
+    ) -> Self {
+        self.resource = map(self.resource);
+        self
+    }
+
+    /// Maps each condition by value.
+    pub(crate) fn map_conditions<F: FnMut(Condition<'a>) -> Condition<'a>>(
+        mut self,
+        map: F,
+    ) -> Self {
+        self.conditions = self.conditions.into_iter().map(map).collect();
+        self
+    }
+
+    /// Returns whether this policy is a template, with slots in its scope.
+    #[must_use]
+    pub fn is_template(&self) -> bool {
+        self.slots().next().is_some()
+    }
+
+    /// Returns the slots in the scope of this policy.
+    pub fn slots(&self) -> impl Iterator<Item = SlotKind> {
+        let principal = self
+            .principal
+            .constraint()
+            .has_slot()
+            .then_some(SlotKind::Principal);
+
+        let resource = self
+            .resource
+            .constraint()
+            .has_slot()
+            .then_some(SlotKind::Resource);
+
+        principal.into_iter().chain(resource)
+    }
+
+    /// Creates a policy from this template by replacing each slot with an entity.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a slot has no value, a value is given for a slot the template does not
+    /// have, or a slot is given more than once.
+    pub fn link(&self, values: &[(SlotKind, EntityReference<'a>)]) -> Result<Self, Error> {
+        let mut principal = None;
+        let mut resource = None;
+
+        for (slot, entity) in values {
+            let slot = *slot;
+            if !self.slots().any(|existing| existing == slot) {
+                return Err(Error::UnexpectedSlot { slot });
+            }
+
+            let target = match slot {
+                SlotKind::Principal => &mut principal,
+                SlotKind::Resource => &mut resource,
+            };
+
+            if target.replace(entity.clone()).is_some() {
+                return Err(Error::DuplicateKey {
+                    key: slot.to_string(),
+                });
+            }
+        }
+
+        let principal = self
+            .principal
+            .constraint()
+            .link(SlotKind::Principal, principal)?;
+
+        let resource = self
+            .resource
+            .constraint()
+            .link(SlotKind::Resource, resource)?;
+
+        Ok(Self {
+            annotations: self.annotations.clone(),
+            effect: self.effect,
+            principal: self.principal.with_constraint(principal),
+            action: self.action.clone(),
+            action_span: self.action_span.clone(),
+            resource: self.resource.with_constraint(resource),
+            conditions: self.conditions.clone(),
+            span: self.span.clone(),
+            // A linked policy takes its ID from the link, not from the template.
+            id_span: None,
+        })
+    }
+}
+
+impl fmt::Debug for Policy<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Policy")
+            .field("annotations", &self.annotations)
+            .field("effect", &self.effect)
+            .field("principal", &self.principal)
+            .field("action", &self.action)
+            .field("resource", &self.resource)
+            .field("conditions", &self.conditions)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Writes valid Cedar source, with annotations and conditions on their own lines.
+///
+/// ```cedar
+/// @id("view")
+/// permit (principal == User::"alice", action, resource)
+/// when { resource.public };
+/// ```
+impl fmt::Display for Policy<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (key, value) in self.annotations.iter() {
+            write!(f, "@{key}")?;
+
+            if let AnnotationValue::String(value) = value {
+                f.write_str("(")?;
+                quote::string(f, value)?;
+                f.write_str(")")?;
+            }
+
+            f.write_str("\n")?;
+        }
+
+        write!(
+            f,
+            "{} ({}, {}, {})",
+            self.effect, self.principal, self.action, self.resource
+        )?;
+
+        for condition in &self.conditions {
+            write!(f, "\n{condition}")?;
+        }
+
+        f.write_str(";")
+    }
 }
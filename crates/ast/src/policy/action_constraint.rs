@@ -1,3 +1,5 @@
+use core::fmt;
+
 use crate::policy::{ActionList, EntityReference};
 
 /// A constraint on the action scope.
@@ -25,4 +27,24 @@ impl<'a> ActionConstraint<'a> {
             .into_iter()
             .chain(list.into_iter().flat_map(ActionList::iter))
     }
+
+    /// Converts into a constraint that owns its entity references.
+    #[must_use]
+    pub fn into_owned(self) -> ActionConstraint<'static> {
+        match self {
+            Self::Any => ActionConstraint::Any,
+            Self::Equal(entity) => ActionConstraint::Equal(entity.into_owned()),
+            Self::In(list) => ActionConstraint::In(list.into_owned()),
+        }
+    }
+}
+
+impl fmt::Display for ActionConstraint<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Any => f.write_str("action"),
+            Self::Equal(entity) => write!(f, "action == {entity}"),
+            Self::In(list) => write!(f, "action in {list}"),
+        }
+    }
 }
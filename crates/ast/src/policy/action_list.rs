@@ -1,5 +1,8 @@
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::fmt;
+
+use mitsein::iter1::IntoIterator1 as _;
 
 use crate::error::Error;
 use crate::policy::EntityReference;
@@ -39,4 +42,39 @@ impl<'a> ActionList<'a> {
     pub fn iter(&self) -> impl Iterator<Item = &EntityReference<'a>> {
         self.actions.iter1().into_iter()
     }
+
+    /// Maps each entity reference, merging any that become equal.
+    pub(crate) fn map<F: FnMut(EntityReference<'a>) -> EntityReference<'a>>(self, map: F) -> Self {
+        Self {
+            actions: self.actions.into_iter1().map(map).collect1(),
+        }
+    }
+
+    /// Converts into a list of owned entity references.
+    #[must_use]
+    pub fn into_owned(self) -> ActionList<'static> {
+        ActionList {
+            actions: self
+                .actions
+                .into_iter1()
+                .map(EntityReference::into_owned)
+                .collect1(),
+        }
+    }
+}
+
+impl fmt::Display for ActionList<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+
+        for (index, action) in self.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+
+            action.fmt(f)?;
+        }
+
+        f.write_str("]")
+    }
 }
@@ -1,3 +1,5 @@
+use core::fmt;
+
 /// A boolean literal value.
 #[derive(Clone, Copy, Eq, PartialEq, Debug, Hash)]
 pub struct BoolLiteral(bool);
@@ -15,3 +17,9 @@ impl BoolLiteral {
         self.0
     }
 }
+
+impl fmt::Display for BoolLiteral {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
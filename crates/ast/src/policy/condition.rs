@@ -43,4 +43,31 @@ impl<'a> Condition<'a> {
     pub const fn body(&self) -> &Expression<'a> {
         &self.body
     }
+
+    /// Returns the condition body expression mutably.
+    #[must_use]
+    pub const fn body_mut(&mut self) -> &mut Expression<'a> {
+        &mut self.body
+    }
+
+    /// Consumes the condition, returning the body expression.
+    #[must_use]
+    pub fn into_body(self) -> Expression<'a> {
+        self.body
+    }
+
+    /// Converts into a condition whose body owns its text.
+    #[must_use]
+    pub fn into_owned(self) -> Condition<'static> {
+        Condition {
+            kind: self.kind,
+            body: self.body.into_owned(),
+        }
+    }
+}
+
+impl fmt::Display for Condition<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {{ {} }}", self.kind, self.body)
+    }
 }
@@ -6,3 +6,14 @@ pub enum EntityOrSlot<'a> {
     Entity(EntityReference<'a>),
     Slot,
 }
+
+impl EntityOrSlot<'_> {
+    /// Converts into an owned entity reference or slot.
+    #[must_use]
+    pub fn into_owned(self) -> EntityOrSlot<'static> {
+        match self {
+            Self::Entity(reference) => EntityOrSlot::Entity(reference.into_owned()),
+            Self::Slot => EntityOrSlot::Slot,
+        }
+    }
+}
@@ -2,6 +2,7 @@ use alloc::borrow::Cow;
 use core::fmt;
 
 use crate::common::Name;
+use crate::{owned, quote};
 
 /// A concrete entity reference like `User::"alice"`.
 #[derive(Clone, Eq, PartialEq, Debug, Hash)]
@@ -23,15 +24,37 @@ impl<'a> EntityReference<'a> {
         &self.kind
     }
 
+    /// Returns the entity kind mutably.
+    #[must_use]
+    pub const fn kind_mut(&mut self) -> &mut Name<'a> {
+        &mut self.kind
+    }
+
     /// Returns the entity identifier (e.g. `"alice"`).
     #[must_use]
     pub fn id(&self) -> &str {
         &self.id
     }
+
+    /// Consumes the reference, returning the entity kind and identifier.
+    #[must_use]
+    pub fn into_parts(self) -> (Name<'a>, Cow<'a, str>) {
+        (self.kind, self.id)
+    }
+
+    /// Converts into a reference that owns its kind and identifier.
+    #[must_use]
+    pub fn into_owned(self) -> EntityReference<'static> {
+        EntityReference {
+            kind: self.kind.into_owned(),
+            id: owned(self.id),
+        }
+    }
 }
 
 impl fmt::Display for EntityReference<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}::\"{}\"", self.kind, self.id)
+        write!(f, "{}::", self.kind)?;
+        quote::string(f, &self.id)
     }
 }
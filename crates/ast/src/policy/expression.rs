@@ -1,24 +1,35 @@
 use alloc::borrow::Cow;
 use alloc::boxed::Box;
 use alloc::vec::Vec;
+use core::fmt::{self, Write as _};
+use core::ops::Range;
 
 use crate::common::Name;
 use crate::policy::{
     BinaryOperator, BoolLiteral, EntityReference, IntegerLiteral, Literal, Pattern,
     RecordExpression, SlotKind, StringLiteral, UnaryOperator, Variable,
 };
+use crate::{owned, quote};
 
 /// An expression node.
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct Expression<'a> {
     kind: ExpressionKind<'a>,
+    span: Option<Range<usize>>,
 }
 
 impl<'a> Expression<'a> {
     /// Creates a new expression.
     #[must_use]
     pub const fn new(kind: ExpressionKind<'a>) -> Self {
-        Self { kind }
+        Self { kind, span: None }
+    }
+
+    /// Sets the byte span of this expression in the source text.
+    #[must_use]
+    pub const fn with_span(mut self, span: Range<usize>) -> Self {
+        self.span = Some(span);
+        self
     }
 
     /// Returns the expression kind.
@@ -27,6 +38,33 @@ impl<'a> Expression<'a> {
         &self.kind
     }
 
+    /// Returns the expression kind mutably.
+    #[must_use]
+    pub const fn kind_mut(&mut self) -> &mut ExpressionKind<'a> {
+        &mut self.kind
+    }
+
+    /// Consumes the expression, returning its kind.
+    #[must_use]
+    pub fn into_kind(self) -> ExpressionKind<'a> {
+        self.kind
+    }
+
+    /// Returns the byte span of this expression in the source text, if known.
+    #[must_use]
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    /// Converts into an expression that owns all its text, keeping spans.
+    #[must_use]
+    pub fn into_owned(self) -> Expression<'static> {
+        Expression {
+            kind: self.kind.into_owned(),
+            span: self.span,
+        }
+    }
+
     /// Creates a boolean literal expression.
     #[must_use]
     pub const fn bool(value: bool) -> Self {
@@ -182,6 +220,15 @@ impl<'a> Expression<'a> {
     }
 }
 
+/// Leaves out the span, so that expressions lowered from text and JSON print the same.
+impl fmt::Debug for Expression<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Expression")
+            .field("kind", &self.kind)
+            .finish_non_exhaustive()
+    }
+}
+
 /// The kind of expression.
 #[derive(Clone, Debug)]
 pub enum ExpressionKind<'a> {
@@ -238,3 +285,311 @@ pub enum ExpressionKind<'a> {
     Set(Vec<Expression<'a>>),
     Record(RecordExpression<'a>),
 }
+
+impl ExpressionKind<'_> {
+    /// Converts into an expression kind that owns all its text.
+    #[must_use]
+    pub fn into_owned(self) -> ExpressionKind<'static> {
+        let boxed = |expression: Box<Expression<'_>>| Box::new(expression.into_owned());
+        let all = |expressions: Vec<Expression<'_>>| {
+            expressions
+                .into_iter()
+                .map(Expression::into_owned)
+                .collect()
+        };
+
+        match self {
+            Self::Literal(literal) => ExpressionKind::Literal(literal.into_owned()),
+            Self::Variable(variable) => ExpressionKind::Variable(variable),
+            Self::Slot(slot) => ExpressionKind::Slot(slot),
+            Self::If {
+                test,
+                consequent,
+                alternate,
+            } => ExpressionKind::If {
+                test: boxed(test),
+                consequent: boxed(consequent),
+                alternate: boxed(alternate),
+            },
+            Self::And { left, right } => ExpressionKind::And {
+                left: boxed(left),
+                right: boxed(right),
+            },
+            Self::Or { left, right } => ExpressionKind::Or {
+                left: boxed(left),
+                right: boxed(right),
+            },
+            Self::UnaryApp { operator, operand } => ExpressionKind::UnaryApp {
+                operator,
+                operand: boxed(operand),
+            },
+            Self::BinaryApp {
+                operator,
+                left,
+                right,
+            } => ExpressionKind::BinaryApp {
+                operator,
+                left: boxed(left),
+                right: boxed(right),
+            },
+            Self::GetAttribute {
+                expression,
+                attribute,
+            } => ExpressionKind::GetAttribute {
+                expression: boxed(expression),
+                attribute: owned(attribute),
+            },
+            Self::HasAttribute {
+                expression,
+                attribute,
+            } => ExpressionKind::HasAttribute {
+                expression: boxed(expression),
+                attribute: owned(attribute),
+            },
+            Self::Like {
+                expression,
+                pattern,
+            } => ExpressionKind::Like {
+                expression: boxed(expression),
+                pattern: pattern.into_owned(),
+            },
+            Self::Is { expression, kind } => ExpressionKind::Is {
+                expression: boxed(expression),
+                kind: kind.into_owned(),
+            },
+            Self::IsIn {
+                expression,
+                kind,
+                target,
+            } => ExpressionKind::IsIn {
+                expression: boxed(expression),
+                kind: kind.into_owned(),
+                target: boxed(target),
+            },
+            Self::ExtensionCall {
+                function,
+                arguments,
+            } => ExpressionKind::ExtensionCall {
+                function: function.into_owned(),
+                arguments: all(arguments),
+            },
+            Self::Set(elements) => ExpressionKind::Set(all(elements)),
+            Self::Record(record) => ExpressionKind::Record(record.into_owned()),
+        }
+    }
+}
+
+/// Extension functions written as function calls, rather than as methods on their first argument.
+const CONSTRUCTORS: &[&str] = &["ip", "decimal", "datetime", "duration"];
+
+/// How tightly an expression binds, from loosest to tightest.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug)]
+enum Precedence {
+    If,
+    Or,
+    And,
+    Relation,
+    Sum,
+    Product,
+    Unary,
+    Member,
+}
+
+impl Expression<'_> {
+    const fn precedence(&self) -> Precedence {
+        match &self.kind {
+            ExpressionKind::If { .. } => Precedence::If,
+            ExpressionKind::Or { .. } => Precedence::Or,
+            ExpressionKind::And { .. } => Precedence::And,
+            ExpressionKind::BinaryApp { operator, .. } => match operator {
+                BinaryOperator::Equal
+                | BinaryOperator::NotEqual
+                | BinaryOperator::Less
+                | BinaryOperator::LessEqual
+                | BinaryOperator::Greater
+                | BinaryOperator::GreaterEqual
+                | BinaryOperator::In => Precedence::Relation,
+                BinaryOperator::Add | BinaryOperator::Subtract => Precedence::Sum,
+                BinaryOperator::Multiply => Precedence::Product,
+                BinaryOperator::Contains
+                | BinaryOperator::ContainsAll
+                | BinaryOperator::ContainsAny
+                | BinaryOperator::GetTag
+                | BinaryOperator::HasTag => Precedence::Member,
+            },
+            ExpressionKind::HasAttribute { .. }
+            | ExpressionKind::Like { .. }
+            | ExpressionKind::Is { .. }
+            | ExpressionKind::IsIn { .. } => Precedence::Relation,
+            ExpressionKind::UnaryApp {
+                operator: UnaryOperator::Not | UnaryOperator::Negate,
+                ..
+            } => Precedence::Unary,
+            ExpressionKind::Literal(Literal::Integer(literal)) if literal.value() < 0 => {
+                Precedence::Unary
+            }
+            _ => Precedence::Member,
+        }
+    }
+
+    /// Writes this expression, in parentheses if it binds looser than `minimum`.
+    fn write(&self, f: &mut fmt::Formatter<'_>, minimum: Precedence) -> fmt::Result {
+        if self.precedence() < minimum {
+            f.write_char('(')?;
+            self.write_kind(f)?;
+            f.write_char(')')
+        } else {
+            self.write_kind(f)
+        }
+    }
+
+    fn write_kind(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ExpressionKind::Literal(literal) => write!(f, "{literal}"),
+            ExpressionKind::Variable(variable) => write!(f, "{variable}"),
+            ExpressionKind::Slot(slot) => write!(f, "{slot}"),
+            ExpressionKind::If {
+                test,
+                consequent,
+                alternate,
+            } => write!(f, "if {test} then {consequent} else {alternate}"),
+            ExpressionKind::And { left, right } => {
+                left.write(f, Precedence::And)?;
+                f.write_str(" && ")?;
+                right.write(f, Precedence::Relation)
+            }
+            ExpressionKind::Or { left, right } => {
+                left.write(f, Precedence::Or)?;
+                f.write_str(" || ")?;
+                right.write(f, Precedence::And)
+            }
+            ExpressionKind::UnaryApp { operator, operand } => match operator {
+                UnaryOperator::IsEmpty => {
+                    operand.write(f, Precedence::Member)?;
+                    f.write_str(".isEmpty()")
+                }
+                UnaryOperator::Not | UnaryOperator::Negate => {
+                    write!(f, "{operator}")?;
+
+                    // `-1` would be read back as a negative literal.
+                    if matches!(operand.kind, ExpressionKind::Literal(Literal::Integer(_))) {
+                        write!(f, "({operand})")
+                    } else {
+                        operand.write(f, Precedence::Member)
+                    }
+                }
+            },
+            ExpressionKind::BinaryApp {
+                operator,
+                left,
+                right,
+            } => match self.precedence() {
+                Precedence::Member => {
+                    left.write(f, Precedence::Member)?;
+                    write!(f, ".{operator}({right})")
+                }
+                Precedence::Relation => {
+                    left.write(f, Precedence::Sum)?;
+                    write!(f, " {operator} ")?;
+                    right.write(f, Precedence::Sum)
+                }
+                Precedence::Sum => {
+                    left.write(f, Precedence::Sum)?;
+                    write!(f, " {operator} ")?;
+                    right.write(f, Precedence::Product)
+                }
+                _ => {
+                    left.write(f, Precedence::Product)?;
+                    write!(f, " {operator} ")?;
+                    right.write(f, Precedence::Unary)
+                }
+            },
+            ExpressionKind::GetAttribute {
+                expression,
+                attribute,
+            } => {
+                expression.write(f, Precedence::Member)?;
+
+                if quote::is_identifier(attribute) {
+                    write!(f, ".{attribute}")
+                } else {
+                    f.write_char('[')?;
+                    quote::string(f, attribute)?;
+                    f.write_char(']')
+                }
+            }
+            ExpressionKind::HasAttribute {
+                expression,
+                attribute,
+            } => {
+                expression.write(f, Precedence::Sum)?;
+                f.write_str(" has ")?;
+                quote::key(f, attribute)
+            }
+            ExpressionKind::Like {
+                expression,
+                pattern,
+            } => {
+                expression.write(f, Precedence::Sum)?;
+                write!(f, " like {pattern}")
+            }
+            ExpressionKind::Is { expression, kind } => {
+                expression.write(f, Precedence::Sum)?;
+                write!(f, " is {kind}")
+            }
+            ExpressionKind::IsIn {
+                expression,
+                kind,
+                target,
+            } => {
+                expression.write(f, Precedence::Sum)?;
+                write!(f, " is {kind} in ")?;
+                target.write(f, Precedence::Sum)
+            }
+            ExpressionKind::ExtensionCall {
+                function,
+                arguments,
+            } => match arguments.split_first() {
+                Some((receiver, arguments))
+                    if function.path().is_empty()
+                        && !CONSTRUCTORS.contains(&function.basename().as_str()) =>
+                {
+                    receiver.write(f, Precedence::Member)?;
+                    write!(f, ".{function}(")?;
+                    Self::write_list(f, arguments)?;
+                    f.write_char(')')
+                }
+                _ => {
+                    write!(f, "{function}(")?;
+                    Self::write_list(f, arguments)?;
+                    f.write_char(')')
+                }
+            },
+            ExpressionKind::Set(elements) => {
+                f.write_char('[')?;
+                Self::write_list(f, elements)?;
+                f.write_char(']')
+            }
+            ExpressionKind::Record(record) => write!(f, "{record}"),
+        }
+    }
+
+    fn write_list(f: &mut fmt::Formatter<'_>, expressions: &[Self]) -> fmt::Result {
+        for (index, expression) in expressions.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+
+            write!(f, "{expression}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes valid Cedar source, with only the parentheses needed to keep the same structure.
+impl fmt::Display for Expression<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, Precedence::If)
+    }
+}
@@ -1,4 +1,5 @@
 use alloc::string::String;
+use core::fmt;
 
 use crate::error::Error;
 
@@ -26,3 +27,9 @@ impl IntegerLiteral {
         self.0
     }
 }
+
+impl fmt::Display for IntegerLiteral {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
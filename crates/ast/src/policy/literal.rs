@@ -1,3 +1,5 @@
+use core::fmt;
+
 use crate::policy::{BoolLiteral, EntityReference, IntegerLiteral, StringLiteral};
 
 /// A literal value.
@@ -8,3 +10,27 @@ pub enum Literal<'a> {
     String(StringLiteral<'a>),
     Entity(EntityReference<'a>),
 }
+
+impl Literal<'_> {
+    /// Converts into a literal that owns its text.
+    #[must_use]
+    pub fn into_owned(self) -> Literal<'static> {
+        match self {
+            Self::Bool(literal) => Literal::Bool(literal),
+            Self::Integer(literal) => Literal::Integer(literal),
+            Self::String(literal) => Literal::String(literal.into_owned()),
+            Self::Entity(reference) => Literal::Entity(reference.into_owned()),
+        }
+    }
+}
+
+impl fmt::Display for Literal<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool(literal) => literal.fmt(f),
+            Self::Integer(literal) => literal.fmt(f),
+            Self::String(literal) => literal.fmt(f),
+            Self::Entity(reference) => reference.fmt(f),
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use alloc::vec::Vec;
+use core::fmt;
 
 use crate::policy::PatternElement;
+use crate::quote;
 
 /// A glob-like pattern used in `like` expressions.
 #[derive(Clone, Eq, PartialEq, Debug, Hash)]
@@ -20,4 +22,22 @@ impl<'a> Pattern<'a> {
     pub fn elements(&self) -> &[PatternElement<'a>] {
         &self.elements
     }
+
+    /// Converts into a pattern that owns its text.
+    #[must_use]
+    pub fn into_owned(self) -> Pattern<'static> {
+        Pattern {
+            elements: self
+                .elements
+                .into_iter()
+                .map(PatternElement::into_owned)
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for Pattern<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        quote::pattern(f, &self.elements)
+    }
 }
@@ -1,8 +1,21 @@
 use alloc::borrow::Cow;
 
+use crate::owned;
+
 /// An element of a `like` pattern.
 #[derive(Clone, Eq, PartialEq, Debug, Hash)]
 pub enum PatternElement<'a> {
     Literal(Cow<'a, str>),
     Wildcard,
 }
+
+impl PatternElement<'_> {
+    /// Converts into an element that owns its text.
+    #[must_use]
+    pub fn into_owned(self) -> PatternElement<'static> {
+        match self {
+            Self::Literal(text) => PatternElement::Literal(owned(text)),
+            Self::Wildcard => PatternElement::Wildcard,
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use alloc::vec::Vec;
+use core::fmt;
 
 use crate::policy::Policy;
 
@@ -20,4 +21,33 @@ impl<'a> Policies<'a> {
     pub fn policies(&self) -> &[Policy<'a>] {
         &self.policies
     }
+
+    /// Consumes the collection, returning the policies.
+    #[must_use]
+    pub fn into_policies(self) -> Vec<Policy<'a>> {
+        self.policies
+    }
+
+    /// Converts into a collection that no longer borrows the source.
+    #[must_use]
+    pub fn into_owned(self) -> Policies<'static> {
+        Policies {
+            policies: self.policies.into_iter().map(Policy::into_owned).collect(),
+        }
+    }
+}
+
+/// Writes each policy, separated by blank lines.
+impl fmt::Display for Policies<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, policy) in self.policies.iter().enumerate() {
+            if index > 0 {
+                f.write_str("\n\n")?;
+            }
+
+            policy.fmt(f)?;
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,41 @@
+use alloc::borrow::Cow;
+use core::borrow::Borrow;
+use core::fmt;
+
+use crate::owned;
+
+/// The identifier of a policy within a policy set.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
+pub struct PolicyId<'a>(Cow<'a, str>);
+
+impl<'a> PolicyId<'a> {
+    /// Creates a policy ID.
+    #[must_use]
+    pub const fn new(id: Cow<'a, str>) -> Self {
+        Self(id)
+    }
+
+    /// Returns the ID as a string.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Converts into an ID that owns its string.
+    #[must_use]
+    pub fn into_owned(self) -> PolicyId<'static> {
+        PolicyId(owned(self.0))
+    }
+}
+
+impl fmt::Display for PolicyId<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Borrow<str> for PolicyId<'_> {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
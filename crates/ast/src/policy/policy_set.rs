@@ -0,0 +1,254 @@
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::common::{AnnotationValue, Identifier};
+use crate::error::Error;
+use crate::policy::{EntityReference, Policies, Policy, PolicyId, SlotKind, TemplateLink};
+use crate::{FxBuildHasher, IndexMap};
+
+/// A collection of policies and templates keyed by ID.
+///
+/// Policies, templates and policies linked from templates share a single ID namespace.
+#[derive(Clone, Debug, Default)]
+pub struct PolicySet<'a> {
+    policies: IndexMap<PolicyId<'a>, Policy<'a>>,
+    templates: IndexMap<PolicyId<'a>, Policy<'a>>,
+    links: IndexMap<PolicyId<'a>, TemplateLink<'a>>,
+}
+
+impl<'a> PolicySet<'a> {
+    /// Creates an empty policy set.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Creates a policy set, assigning each policy an ID.
+    ///
+    /// The ID is taken from the `@id` annotation, or generated as `policy0`, `policy1`, ...
+    /// from the position of the policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if two policies have the same ID.
+    pub fn new(policies: Policies<'a>) -> Result<Self, Error> {
+        let policies = policies.into_policies();
+
+        let mut set = Self {
+            policies: IndexMap::with_capacity_and_hasher(policies.len(), FxBuildHasher),
+            ..Self::default()
+        };
+
+        for (index, policy) in policies.into_iter().enumerate() {
+            let id = Self::assign_id(&policy, index);
+            set.insert(id, policy)?;
+        }
+
+        Ok(set)
+    }
+
+    /// Returns the ID for a policy, from its `@id` annotation or its position.
+    #[must_use]
+    pub fn assign_id(policy: &Policy<'a>, index: usize) -> PolicyId<'a> {
+        let annotation = Identifier::new("id")
+            .ok()
+            .and_then(|key| policy.annotations().get(&key));
+
+        match annotation {
+            Some(AnnotationValue::String(id)) => PolicyId::new(id.clone()),
+            Some(AnnotationValue::Empty) => PolicyId::new(Cow::Borrowed("")),
+            None => PolicyId::new(Cow::Owned(format!("policy{index}"))),
+        }
+    }
+
+    /// Inserts a policy or template under the given ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a policy or template with this ID already exists.
+    pub fn insert(&mut self, id: PolicyId<'a>, policy: Policy<'a>) -> Result<(), Error> {
+        self.check_unused(&id, Self::id_span(&policy))?;
+
+        if policy.is_template() {
+            self.templates.insert(id, policy);
+        } else {
+            self.policies.insert(id, policy);
+        }
+
+        Ok(())
+    }
+
+    /// Links a template, replacing its slots with entities, and inserts the result under `id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the template does not exist, `id` is already used, or the values do
+    /// not match the slots of the template.
+    pub fn link(
+        &mut self,
+        template: &str,
+        id: PolicyId<'a>,
+        values: Vec<(SlotKind, EntityReference<'a>)>,
+    ) -> Result<(), Error> {
+        let Some((template, _)) = self.templates.get_key_value(template) else {
+            return Err(Error::UnknownTemplate {
+                id: String::from(template),
+            });
+        };
+
+        self.insert_link(id, TemplateLink::new(template.clone(), values))
+    }
+
+    /// Inserts the policy created by a template link under `id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the template does not exist, `id` is already used, or the values do
+    /// not match the slots of the template.
+    pub fn insert_link(&mut self, id: PolicyId<'a>, link: TemplateLink<'a>) -> Result<(), Error> {
+        let Some(template) = self.templates.get(link.template().as_str()) else {
+            return Err(Error::UnknownTemplate {
+                id: String::from(link.template().as_str()),
+            });
+        };
+
+        let mut linked = template.link(link.values())?;
+        if let Some(span) = link.span() {
+            linked = linked.with_id_span(span);
+        }
+
+        self.check_unused(&id, link.span())?;
+
+        self.links.insert(id.clone(), link);
+        self.policies.insert(id, linked);
+
+        Ok(())
+    }
+
+    /// Returns the span to report for the ID of a policy: its `@id` annotation, or the policy.
+    fn id_span(policy: &Policy<'a>) -> Option<Range<usize>> {
+        policy.id_span().or_else(|| policy.span())
+    }
+
+    /// Checks that `id` is not used yet; `span` is where the new ID is written.
+    fn check_unused(&self, id: &PolicyId<'a>, span: Option<Range<usize>>) -> Result<(), Error> {
+        if let Some(existing) = self.get(id.as_str()) {
+            return Err(Error::DuplicatePolicyId {
+                id: String::from(id.as_str()),
+                span,
+                first: Self::id_span(existing),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Removes the policy or template with the given ID, preserving the order of the others.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no policy or template has this ID, or if it is a template that still
+    /// has linked policies.
+    pub fn remove(&mut self, id: &str) -> Result<Policy<'a>, Error> {
+        if let Some(policy) = self.policies.shift_remove(id) {
+            self.links.shift_remove(id);
+            return Ok(policy);
+        }
+
+        if self
+            .links
+            .values()
+            .any(|link| link.template().as_str() == id)
+        {
+            return Err(Error::TemplateHasLinks {
+                id: String::from(id),
+            });
+        }
+
+        self.templates
+            .shift_remove(id)
+            .ok_or_else(|| Error::UnknownPolicy {
+                id: String::from(id),
+            })
+    }
+
+    /// Returns the policy or template with the given ID.
+    #[must_use]
+    pub fn get(&self, id: &str) -> Option<&Policy<'a>> {
+        self.policies.get(id).or_else(|| self.templates.get(id))
+    }
+
+    /// Returns the ID of the template a linked policy was created from.
+    #[must_use]
+    pub fn template_of(&self, id: &str) -> Option<&PolicyId<'a>> {
+        self.links.get(id).map(TemplateLink::template)
+    }
+
+    /// Returns the template link a linked policy was created from.
+    #[must_use]
+    pub fn link_of(&self, id: &str) -> Option<&TemplateLink<'a>> {
+        self.links.get(id)
+    }
+
+    /// Returns whether a policy or template with the given ID exists.
+    #[must_use]
+    pub fn contains(&self, id: &str) -> bool {
+        self.policies.contains_key(id) || self.templates.contains_key(id)
+    }
+
+    /// Returns the number of policies, including linked policies but not templates.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.policies.len()
+    }
+
+    /// Returns whether the set has no policies, ignoring templates.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.policies.is_empty()
+    }
+
+    /// Returns an iterator over policies and their IDs, in insertion order.
+    ///
+    /// This includes linked policies but not templates.
+    pub fn iter(&self) -> impl Iterator<Item = (&PolicyId<'a>, &Policy<'a>)> {
+        self.policies.iter()
+    }
+
+    /// Returns an iterator over linked policy IDs and their template links, in insertion order.
+    pub fn links(&self) -> impl Iterator<Item = (&PolicyId<'a>, &TemplateLink<'a>)> {
+        self.links.iter()
+    }
+
+    /// Returns an iterator over templates and their IDs, in insertion order.
+    pub fn templates(&self) -> impl Iterator<Item = (&PolicyId<'a>, &Policy<'a>)> {
+        self.templates.iter()
+    }
+
+    /// Converts into a policy set that no longer borrows the source.
+    ///
+    /// The result can be kept after the source text is dropped, or shared between threads.
+    #[must_use]
+    pub fn into_owned(self) -> PolicySet<'static> {
+        PolicySet {
+            policies: self
+                .policies
+                .into_iter()
+                .map(|(id, policy)| (id.into_owned(), policy.into_owned()))
+                .collect(),
+            templates: self
+                .templates
+                .into_iter()
+                .map(|(id, policy)| (id.into_owned(), policy.into_owned()))
+                .collect(),
+            links: self
+                .links
+                .into_iter()
+                .map(|(id, link)| (id.into_owned(), link.into_owned()))
+                .collect(),
+        }
+    }
+}
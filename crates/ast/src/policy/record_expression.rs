@@ -1,9 +1,10 @@
 use alloc::borrow::Cow;
 use alloc::vec::Vec;
+use core::fmt;
 
 use crate::error::Error;
 use crate::policy::Expression;
-use crate::{FxBuildHasher, IndexMap};
+use crate::{FxBuildHasher, IndexMap, owned, quote};
 
 /// An ordered record of key-value expression pairs.
 #[derive(Clone, Debug)]
@@ -51,4 +52,53 @@ impl<'a> RecordExpression<'a> {
     pub fn iter(&self) -> impl Iterator<Item = (&str, &Expression<'a>)> {
         self.entries.iter().map(|(key, value)| (&**key, value))
     }
+
+    /// Returns an iterator over key-value pairs, with mutable values.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&str, &mut Expression<'a>)> {
+        self.entries.iter_mut().map(|(key, value)| (&**key, value))
+    }
+
+    /// Maps each value by value, keeping the keys.
+    pub(crate) fn map_values<F: FnMut(Expression<'a>) -> Expression<'a>>(self, mut map: F) -> Self {
+        Self {
+            entries: self
+                .entries
+                .into_iter()
+                .map(|(key, value)| (key, map(value)))
+                .collect(),
+        }
+    }
+
+    /// Converts into a record that owns its keys and values.
+    #[must_use]
+    pub fn into_owned(self) -> RecordExpression<'static> {
+        RecordExpression {
+            entries: self
+                .entries
+                .into_iter()
+                .map(|(key, value)| (owned(key), value.into_owned()))
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for RecordExpression<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.entries.is_empty() {
+            return f.write_str("{}");
+        }
+
+        f.write_str("{ ")?;
+
+        for (index, (key, value)) in self.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+
+            quote::key(f, key)?;
+            write!(f, ": {value}")?;
+        }
+
+        f.write_str(" }")
+    }
 }
@@ -1,19 +1,103 @@
-use crate::policy::ScopeConstraint;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Range;
+
+use crate::policy::{ScopeConstraint, SlotKind, Variable};
 
 /// A constraint on the resource scope.
-#[derive(Clone, Eq, PartialEq, Debug, Hash)]
-pub struct ResourceConstraint<'a>(ScopeConstraint<'a>);
+#[derive(Clone)]
+pub struct ResourceConstraint<'a> {
+    constraint: ScopeConstraint<'a>,
+    span: Option<Range<usize>>,
+}
 
 impl<'a> ResourceConstraint<'a> {
     /// Creates a new resource constraint.
     #[must_use]
     pub const fn new(constraint: ScopeConstraint<'a>) -> Self {
-        Self(constraint)
+        Self {
+            constraint,
+            span: None,
+        }
+    }
+
+    /// Sets the byte span of this constraint in the source text.
+    #[must_use]
+    pub const fn with_span(mut self, span: Range<usize>) -> Self {
+        self.span = Some(span);
+        self
     }
 
     /// Returns the scope constraint.
     #[must_use]
     pub const fn constraint(&self) -> &ScopeConstraint<'a> {
-        &self.0
+        &self.constraint
+    }
+
+    /// Returns the scope constraint mutably.
+    #[must_use]
+    pub const fn constraint_mut(&mut self) -> &mut ScopeConstraint<'a> {
+        &mut self.constraint
+    }
+
+    /// Returns the byte span of this constraint in the source text, if known.
+    #[must_use]
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    /// Converts into a constraint that no longer borrows the source, keeping the span.
+    #[must_use]
+    pub fn into_owned(self) -> ResourceConstraint<'static> {
+        ResourceConstraint {
+            constraint: self.constraint.into_owned(),
+            span: self.span,
+        }
+    }
+
+    /// Replaces the scope constraint, keeping the span.
+    pub(crate) fn with_constraint(&self, constraint: ScopeConstraint<'a>) -> Self {
+        Self {
+            constraint,
+            span: self.span.clone(),
+        }
+    }
+
+    /// Maps the scope constraint by value, keeping the span.
+    pub(crate) fn map_constraint<F: FnOnce(ScopeConstraint<'a>) -> ScopeConstraint<'a>>(
+        mut self,
+        map: F,
+    ) -> Self {
+        self.constraint = map(self.constraint);
+        self
+    }
+}
+
+impl fmt::Debug for ResourceConstraint<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ResourceConstraint")
+            .field(&self.constraint)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for ResourceConstraint<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.constraint == other.constraint
+    }
+}
+
+impl Eq for ResourceConstraint<'_> {}
+
+impl Hash for ResourceConstraint<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.constraint.hash(state);
+    }
+}
+
+impl fmt::Display for ResourceConstraint<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.constraint
+            .write(f, Variable::Resource, SlotKind::Resource)
     }
 }
@@ -1,5 +1,8 @@
+use core::fmt;
+
 use crate::common::Name;
-use crate::policy::EntityOrSlot;
+use crate::error::Error;
+use crate::policy::{EntityOrSlot, EntityReference, SlotKind, Variable};
 
 /// A constraint on a principal or resource scope.
 #[derive(Clone, Eq, PartialEq, Debug, Hash)]
@@ -10,3 +13,83 @@ pub enum ScopeConstraint<'a> {
     Is(Name<'a>),
     IsIn(Name<'a>, EntityOrSlot<'a>),
 }
+
+impl<'a> ScopeConstraint<'a> {
+    /// Returns whether this constraint refers to a template slot.
+    #[must_use]
+    pub const fn has_slot(&self) -> bool {
+        matches!(
+            self,
+            Self::Equal(EntityOrSlot::Slot)
+                | Self::In(EntityOrSlot::Slot)
+                | Self::IsIn(_, EntityOrSlot::Slot)
+        )
+    }
+
+    /// Replaces the slot in this constraint with an entity.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this constraint has a slot and `entity` is `None`.
+    pub fn link(&self, slot: SlotKind, entity: Option<EntityReference<'a>>) -> Result<Self, Error> {
+        if !self.has_slot() {
+            return Ok(self.clone());
+        }
+
+        let entity = EntityOrSlot::Entity(entity.ok_or(Error::MissingSlot { slot })?);
+        let linked = match self {
+            Self::Equal(_) => Self::Equal(entity),
+            Self::In(_) => Self::In(entity),
+            Self::IsIn(name, _) => Self::IsIn(name.clone(), entity),
+            Self::Any | Self::Is(_) => self.clone(),
+        };
+
+        Ok(linked)
+    }
+
+    /// Converts into a constraint that owns its names and entity references.
+    #[must_use]
+    pub fn into_owned(self) -> ScopeConstraint<'static> {
+        match self {
+            Self::Any => ScopeConstraint::Any,
+            Self::Equal(target) => ScopeConstraint::Equal(target.into_owned()),
+            Self::In(target) => ScopeConstraint::In(target.into_owned()),
+            Self::Is(name) => ScopeConstraint::Is(name.into_owned()),
+            Self::IsIn(name, target) => {
+                ScopeConstraint::IsIn(name.into_owned(), target.into_owned())
+            }
+        }
+    }
+
+    /// Writes this constraint on `variable`, with `slot` standing for a template slot.
+    pub(crate) fn write(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        variable: Variable,
+        slot: SlotKind,
+    ) -> fmt::Result {
+        let target = |f: &mut fmt::Formatter<'_>, target: &EntityOrSlot<'_>| match target {
+            EntityOrSlot::Entity(entity) => write!(f, "{entity}"),
+            EntityOrSlot::Slot => write!(f, "{slot}"),
+        };
+
+        write!(f, "{variable}")?;
+
+        match self {
+            Self::Any => Ok(()),
+            Self::Equal(entity) => {
+                f.write_str(" == ")?;
+                target(f, entity)
+            }
+            Self::In(entity) => {
+                f.write_str(" in ")?;
+                target(f, entity)
+            }
+            Self::Is(name) => write!(f, " is {name}"),
+            Self::IsIn(name, entity) => {
+                write!(f, " is {name} in ")?;
+                target(f, entity)
+            }
+        }
+    }
+}
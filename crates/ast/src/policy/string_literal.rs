@@ -1,4 +1,7 @@
 use alloc::borrow::Cow;
+use core::fmt;
+
+use crate::{owned, quote};
 
 /// A string literal value.
 #[derive(Clone, Eq, PartialEq, Debug, Hash)]
@@ -16,4 +19,16 @@ impl<'a> StringLiteral<'a> {
     pub fn value(&self) -> &str {
         &self.0
     }
+
+    /// Converts into a literal that owns its string.
+    #[must_use]
+    pub fn into_owned(self) -> StringLiteral<'static> {
+        StringLiteral(owned(self.0))
+    }
+}
+
+impl fmt::Display for StringLiteral<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        quote::string(f, &self.0)
+    }
 }
@@ -0,0 +1,63 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::policy::{EntityReference, PolicyId, SlotKind};
+
+/// The template and slot values a linked policy was created from.
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+pub struct TemplateLink<'a> {
+    template: PolicyId<'a>,
+    values: Vec<(SlotKind, EntityReference<'a>)>,
+    span: Option<Range<usize>>,
+}
+
+impl<'a> TemplateLink<'a> {
+    /// Creates a template link.
+    #[must_use]
+    pub const fn new(template: PolicyId<'a>, values: Vec<(SlotKind, EntityReference<'a>)>) -> Self {
+        Self {
+            template,
+            values,
+            span: None,
+        }
+    }
+
+    /// Sets the byte span of the ID of the linked policy in the source text.
+    #[must_use]
+    pub const fn with_span(mut self, span: Range<usize>) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// Returns the ID of the template.
+    #[must_use]
+    pub const fn template(&self) -> &PolicyId<'a> {
+        &self.template
+    }
+
+    /// Returns the entity each slot was linked to.
+    #[must_use]
+    pub fn values(&self) -> &[(SlotKind, EntityReference<'a>)] {
+        &self.values
+    }
+
+    /// Returns the byte span of the ID of the linked policy in the source text, if known.
+    #[must_use]
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    /// Converts into a link that owns its template ID and slot values.
+    #[must_use]
+    pub fn into_owned(self) -> TemplateLink<'static> {
+        TemplateLink {
+            template: self.template.into_owned(),
+            values: self
+                .values
+                .into_iter()
+                .map(|(slot, entity)| (slot, entity.into_owned()))
+                .collect(),
+            span: self.span,
+        }
+    }
+}
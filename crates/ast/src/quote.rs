@@ -0,0 +1,89 @@
+use core::fmt::{self, Write as _};
+
+use crate::common::Identifier;
+use crate::policy::PatternElement;
+
+/// Words that cannot be used as identifiers in policies.
+const RESERVED_WORDS: &[&str] = &[
+    "true", "false", "if", "then", "else", "in", "is", "like", "has",
+];
+
+/// Returns whether a value can be written as a bare identifier.
+pub fn is_identifier(value: &str) -> bool {
+    Identifier::new(value).is_ok() && !RESERVED_WORDS.contains(&value)
+}
+
+/// Displays a value as a quoted string literal, escaped as in Cedar source.
+#[derive(Clone, Copy, Debug)]
+pub struct Quoted<'a>(pub &'a str);
+
+impl fmt::Display for Quoted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        string(f, self.0)
+    }
+}
+
+/// Displays `like` pattern elements as a quoted pattern literal, escaping literal `*` as `\*`.
+#[derive(Clone, Copy, Debug)]
+pub struct QuotedPattern<'a, 'b>(pub &'a [PatternElement<'b>]);
+
+impl fmt::Display for QuotedPattern<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        pattern(f, self.0)
+    }
+}
+
+/// Writes a value as a quoted string literal.
+pub fn string(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    f.write_char('"')?;
+
+    for char in value.chars() {
+        escaped(f, char)?;
+    }
+
+    f.write_char('"')
+}
+
+/// Writes a value as a bare identifier if possible, or as a quoted string otherwise.
+pub fn key(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    if is_identifier(value) {
+        f.write_str(value)
+    } else {
+        string(f, value)
+    }
+}
+
+/// Writes a `like` pattern, escaping literal `*` as `\*`.
+pub fn pattern(f: &mut fmt::Formatter<'_>, elements: &[PatternElement<'_>]) -> fmt::Result {
+    f.write_char('"')?;
+
+    for element in elements {
+        match element {
+            PatternElement::Literal(literal) => {
+                for char in literal.chars() {
+                    if char == '*' {
+                        f.write_str("\\*")?;
+                    } else {
+                        escaped(f, char)?;
+                    }
+                }
+            }
+            PatternElement::Wildcard => f.write_char('*')?,
+        }
+    }
+
+    f.write_char('"')
+}
+
+fn escaped(f: &mut fmt::Formatter<'_>, char: char) -> fmt::Result {
+    match char {
+        '"' => f.write_str("\\\""),
+        '\\' => f.write_str("\\\\"),
+        '\n' => f.write_str("\\n"),
+        '\r' => f.write_str("\\r"),
+        '\t' => f.write_str("\\t"),
+        '\0' => f.write_str("\\0"),
+        char if char.is_control() => write!(f, "\\u{{{:x}}}", u32::from(char)),
+        char => f.write_char(char),
+    }
+}
@@ -66,4 +66,31 @@ impl<'a> Schema<'a> {
     pub fn namespaces(&self) -> &[Namespace<'a>] {
         &self.namespaces
     }
+
+    /// Returns the namespaces mutably.
+    #[must_use]
+    pub fn namespaces_mut(&mut self) -> &mut [Namespace<'a>] {
+        &mut self.namespaces
+    }
+
+    /// Maps each namespace by value.
+    pub(crate) fn map_namespaces<F: FnMut(Namespace<'a>) -> Namespace<'a>>(
+        mut self,
+        map: F,
+    ) -> Self {
+        self.namespaces = self.namespaces.into_iter().map(map).collect();
+        self
+    }
+
+    /// Converts into a schema that no longer borrows the source, so it can outlive it.
+    #[must_use]
+    pub fn into_owned(self) -> Schema<'static> {
+        Schema {
+            namespaces: self
+                .namespaces
+                .into_iter()
+                .map(Namespace::into_owned)
+                .collect(),
+        }
+    }
 }
@@ -1,20 +1,25 @@
 use alloc::borrow::Cow;
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+use mitsein::iter1::IntoIterator1 as _;
 
 use crate::common::Annotations;
 use crate::error::Error;
 use crate::schema::{ActionReference, AppliesTo, AttributeDeclaration};
-use crate::{FxBuildHasher, IndexMap, IndexSet, IndexSet1};
+use crate::{FxBuildHasher, IndexMap, IndexSet, IndexSet1, owned};
 
 /// A declaration of one or more actions.
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct ActionDeclaration<'a> {
     annotations: Annotations<'a>,
     names: IndexSet1<Cow<'a, str>>,
     parents: IndexSet<ActionReference<'a>>,
     applies_to: Option<AppliesTo<'a>>,
     attributes: IndexMap<Cow<'a, str>, AttributeDeclaration<'a>>,
+    span: Option<Range<usize>>,
 }
 
 impl<'a> ActionDeclaration<'a> {
@@ -80,9 +85,17 @@ impl<'a> ActionDeclaration<'a> {
             parents: parent_set,
             applies_to,
             attributes: map,
+            span: None,
         })
     }
 
+    /// Sets the byte span of this declaration in the source text.
+    #[must_use]
+    pub const fn with_span(mut self, span: Range<usize>) -> Self {
+        self.span = Some(span);
+        self
+    }
+
     /// Returns the action annotations.
     #[must_use]
     pub const fn annotations(&self) -> &Annotations<'a> {
@@ -105,6 +118,12 @@ impl<'a> ActionDeclaration<'a> {
         self.applies_to.as_ref()
     }
 
+    /// Returns the applies-to clause mutably, if any.
+    #[must_use]
+    pub const fn applies_to_mut(&mut self) -> Option<&mut AppliesTo<'a>> {
+        self.applies_to.as_mut()
+    }
+
     /// Returns the attribute declaration for the given name.
     #[must_use]
     pub fn attribute(&self, name: &str) -> Option<&AttributeDeclaration<'a>> {
@@ -115,4 +134,79 @@ impl<'a> ActionDeclaration<'a> {
     pub fn attributes(&self) -> impl Iterator<Item = (&str, &AttributeDeclaration<'a>)> {
         self.attributes.iter().map(|(key, value)| (&**key, value))
     }
+
+    /// Returns an iterator over attribute name-declaration pairs, with mutable declarations.
+    pub fn attributes_mut(
+        &mut self,
+    ) -> impl Iterator<Item = (&str, &mut AttributeDeclaration<'a>)> {
+        self.attributes
+            .iter_mut()
+            .map(|(key, value)| (&**key, value))
+    }
+
+    /// Maps the applies-to clause by value, if any, keeping the span.
+    pub(crate) fn map_applies_to<F: FnOnce(AppliesTo<'a>) -> AppliesTo<'a>>(
+        mut self,
+        map: F,
+    ) -> Self {
+        self.applies_to = self.applies_to.map(map);
+        self
+    }
+
+    /// Maps each attribute declaration by value, keeping the names and the span.
+    pub(crate) fn map_attributes<
+        F: FnMut(&str, AttributeDeclaration<'a>) -> AttributeDeclaration<'a>,
+    >(
+        mut self,
+        mut map: F,
+    ) -> Self {
+        self.attributes = self
+            .attributes
+            .into_iter()
+            .map(|(key, value)| {
+                let value = map(&key, value);
+                (key, value)
+            })
+            .collect();
+        self
+    }
+
+    /// Returns the byte span of this declaration in the source text, if known.
+    #[must_use]
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    /// Converts into a declaration that no longer borrows the source, keeping the span.
+    #[must_use]
+    pub fn into_owned(self) -> ActionDeclaration<'static> {
+        ActionDeclaration {
+            annotations: self.annotations.into_owned(),
+            names: self.names.into_iter1().map(owned).collect1(),
+            parents: self
+                .parents
+                .into_iter()
+                .map(ActionReference::into_owned)
+                .collect(),
+            applies_to: self.applies_to.map(AppliesTo::into_owned),
+            attributes: self
+                .attributes
+                .into_iter()
+                .map(|(key, value)| (owned(key), value.into_owned()))
+                .collect(),
+            span: self.span,
+        }
+    }
+}
+
+impl fmt::Debug for ActionDeclaration<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ActionDeclaration")
+            .field("annotations", &self.annotations)
+            .field("names", &self.names)
+            .field("parents", &self.parents)
+            .field("applies_to", &self.applies_to)
+            .field("attributes", &self.attributes)
+            .finish_non_exhaustive()
+    }
 }
@@ -1,6 +1,7 @@
 use alloc::borrow::Cow;
 
 use crate::common::Name;
+use crate::owned;
 
 /// A reference to an action entity.
 #[derive(Clone, Eq, PartialEq, Debug, Hash)]
@@ -27,4 +28,13 @@ impl<'a> ActionReference<'a> {
     pub fn id(&self) -> &str {
         &self.id
     }
+
+    /// Converts into a reference that owns its kind and identifier.
+    #[must_use]
+    pub fn into_owned(self) -> ActionReference<'static> {
+        ActionReference {
+            kind: self.kind.map(Name::into_owned),
+            id: owned(self.id),
+        }
+    }
 }
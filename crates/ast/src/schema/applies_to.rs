@@ -71,4 +71,36 @@ impl<'a> AppliesTo<'a> {
     pub const fn context(&self) -> Option<&ContextType<'a>> {
         self.context.as_ref()
     }
+
+    /// Returns the context type mutably, if any.
+    #[must_use]
+    pub const fn context_mut(&mut self) -> Option<&mut ContextType<'a>> {
+        self.context.as_mut()
+    }
+
+    /// Maps each principal and resource type, merging any that become equal.
+    pub(crate) fn map_types<F: FnMut(Name<'a>) -> Name<'a>>(mut self, mut map: F) -> Self {
+        self.principals = self.principals.into_iter().map(&mut map).collect();
+        self.resources = self.resources.into_iter().map(map).collect();
+        self
+    }
+
+    /// Maps the context type by value, if any.
+    pub(crate) fn map_context<F: FnOnce(ContextType<'a>) -> ContextType<'a>>(
+        mut self,
+        map: F,
+    ) -> Self {
+        self.context = self.context.map(map);
+        self
+    }
+
+    /// Converts into an applies-to clause that owns its type names.
+    #[must_use]
+    pub fn into_owned(self) -> AppliesTo<'static> {
+        AppliesTo {
+            principals: self.principals.into_iter().map(Name::into_owned).collect(),
+            resources: self.resources.into_iter().map(Name::into_owned).collect(),
+            context: self.context.map(ContextType::into_owned),
+        }
+    }
 }
@@ -41,4 +41,29 @@ impl<'a> AttributeDeclaration<'a> {
     pub const fn definition(&self) -> &TypeExpression<'a> {
         &self.definition
     }
+
+    /// Returns the attribute type definition mutably.
+    #[must_use]
+    pub const fn definition_mut(&mut self) -> &mut TypeExpression<'a> {
+        &mut self.definition
+    }
+
+    /// Maps the attribute type definition by value.
+    pub(crate) fn map_definition<F: FnOnce(TypeExpression<'a>) -> TypeExpression<'a>>(
+        mut self,
+        map: F,
+    ) -> Self {
+        self.definition = map(self.definition);
+        self
+    }
+
+    /// Converts into a declaration that owns its annotations and type.
+    #[must_use]
+    pub fn into_owned(self) -> AttributeDeclaration<'static> {
+        AttributeDeclaration {
+            annotations: self.annotations.into_owned(),
+            optionality: self.optionality,
+            definition: self.definition.into_owned(),
+        }
+    }
 }
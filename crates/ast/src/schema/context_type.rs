@@ -12,3 +12,14 @@ pub enum ContextType<'a> {
     /// An inline record definition (e.g. `{ key: String }`).
     Record(RecordType<'a>),
 }
+
+impl ContextType<'_> {
+    /// Converts into a context type that owns its names.
+    #[must_use]
+    pub fn into_owned(self) -> ContextType<'static> {
+        match self {
+            Self::Reference(name) => ContextType::Reference(name.into_owned()),
+            Self::Record(record) => ContextType::Record(record.into_owned()),
+        }
+    }
+}
@@ -7,3 +7,15 @@ pub enum Declaration<'a> {
     Action(ActionDeclaration<'a>),
     Type(TypeDeclaration<'a>),
 }
+
+impl Declaration<'_> {
+    /// Converts into a declaration that no longer borrows the source.
+    #[must_use]
+    pub fn into_owned(self) -> Declaration<'static> {
+        match self {
+            Self::Entity(entity) => Declaration::Entity(entity.into_owned()),
+            Self::Action(action) => Declaration::Action(action.into_owned()),
+            Self::Type(declaration) => Declaration::Type(declaration.into_owned()),
+        }
+    }
+}
@@ -1,5 +1,9 @@
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+use mitsein::iter1::IntoIterator1 as _;
 
 use crate::common::{Annotations, Identifier};
 use crate::error::Error;
@@ -7,11 +11,12 @@ use crate::schema::EntityKind;
 use crate::{FxBuildHasher, IndexSet, IndexSet1, RESERVED_TYPE_NAMES};
 
 /// A declaration of one or more entity types.
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct EntityDeclaration<'a> {
     annotations: Annotations<'a>,
     names: IndexSet1<Identifier<'a>>,
     kind: EntityKind<'a>,
+    span: Option<Range<usize>>,
 }
 
 impl<'a> EntityDeclaration<'a> {
@@ -49,9 +54,17 @@ impl<'a> EntityDeclaration<'a> {
             annotations,
             names,
             kind,
+            span: None,
         })
     }
 
+    /// Sets the byte span of this declaration in the source text.
+    #[must_use]
+    pub const fn with_span(mut self, span: Range<usize>) -> Self {
+        self.span = Some(span);
+        self
+    }
+
     /// Returns the entity annotations.
     #[must_use]
     pub const fn annotations(&self) -> &Annotations<'a> {
@@ -68,4 +81,47 @@ impl<'a> EntityDeclaration<'a> {
     pub const fn kind(&self) -> &EntityKind<'a> {
         &self.kind
     }
+
+    /// Returns the entity kind mutably.
+    #[must_use]
+    pub const fn kind_mut(&mut self) -> &mut EntityKind<'a> {
+        &mut self.kind
+    }
+
+    /// Maps the entity kind by value, keeping the span.
+    pub(crate) fn map_kind<F: FnOnce(EntityKind<'a>) -> EntityKind<'a>>(mut self, map: F) -> Self {
+        self.kind = map(self.kind);
+        self
+    }
+
+    /// Returns the byte span of this declaration in the source text, if known.
+    #[must_use]
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    /// Converts into a declaration that no longer borrows the source, keeping the span.
+    #[must_use]
+    pub fn into_owned(self) -> EntityDeclaration<'static> {
+        EntityDeclaration {
+            annotations: self.annotations.into_owned(),
+            names: self
+                .names
+                .into_iter1()
+                .map(Identifier::into_owned)
+                .collect1(),
+            kind: self.kind.into_owned(),
+            span: self.span,
+        }
+    }
+}
+
+impl fmt::Debug for EntityDeclaration<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EntityDeclaration")
+            .field("annotations", &self.annotations)
+            .field("names", &self.names)
+            .field("kind", &self.kind)
+            .finish_non_exhaustive()
+    }
 }
@@ -6,3 +6,14 @@ pub enum EntityKind<'a> {
     Standard(StandardEntity<'a>),
     Enum(EnumType<'a>),
 }
+
+impl EntityKind<'_> {
+    /// Converts into an entity kind that owns all its names.
+    #[must_use]
+    pub fn into_owned(self) -> EntityKind<'static> {
+        match self {
+            Self::Standard(standard) => EntityKind::Standard(standard.into_owned()),
+            Self::Enum(variants) => EntityKind::Enum(variants.into_owned()),
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use alloc::string::String;
 use alloc::vec::Vec;
 
+use mitsein::iter1::IntoIterator1 as _;
+
 use crate::common::Name;
 use crate::error::Error;
 use crate::{FxBuildHasher, IndexSet, IndexSet1};
@@ -45,4 +47,19 @@ impl<'a> EntityTypeSet<'a> {
     pub fn iter(&self) -> impl Iterator<Item = &Name<'a>> {
         self.types.iter1().into_iter()
     }
+
+    /// Maps each type name, merging any that become equal.
+    pub(crate) fn map<F: FnMut(Name<'a>) -> Name<'a>>(self, map: F) -> Self {
+        Self {
+            types: self.types.into_iter1().map(map).collect1(),
+        }
+    }
+
+    /// Converts into a set of owned type names.
+    #[must_use]
+    pub fn into_owned(self) -> EntityTypeSet<'static> {
+        EntityTypeSet {
+            types: self.types.into_iter1().map(Name::into_owned).collect1(),
+        }
+    }
 }
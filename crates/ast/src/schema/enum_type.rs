@@ -2,8 +2,10 @@ use alloc::borrow::Cow;
 use alloc::string::String;
 use alloc::vec::Vec;
 
+use mitsein::iter1::IntoIterator1 as _;
+
 use crate::error::Error;
-use crate::{FxBuildHasher, IndexSet, IndexSet1};
+use crate::{FxBuildHasher, IndexSet, IndexSet1, owned};
 
 /// An enum type with a set of variant names.
 #[derive(Clone, Debug)]
@@ -39,4 +41,12 @@ impl<'a> EnumType<'a> {
     pub fn variants(&self) -> impl Iterator<Item = &str> {
         self.variants.iter1().into_iter().map(AsRef::as_ref)
     }
+
+    /// Converts into an enum type that owns its variant names.
+    #[must_use]
+    pub fn into_owned(self) -> EnumType<'static> {
+        EnumType {
+            variants: self.variants.into_iter1().map(owned).collect1(),
+        }
+    }
 }
@@ -1,7 +1,7 @@
 use alloc::string::String;
 use alloc::vec::Vec;
 
-use crate::common::{Annotations, Identifier, Name};
+use crate::common::{Annotations, Name};
 use crate::error::Error;
 use crate::schema::Declaration;
 use crate::{FxBuildHasher, IndexSet};
@@ -26,17 +26,17 @@ impl<'a> Namespace<'a> {
         declarations: Vec<Declaration<'a>>,
     ) -> Result<Self, Error> {
         let mut entity_names =
-            IndexSet::<Identifier<'a>>::with_capacity_and_hasher(declarations.len(), FxBuildHasher);
+            IndexSet::<&str>::with_capacity_and_hasher(declarations.len(), FxBuildHasher);
         let mut action_names =
             IndexSet::<&str>::with_capacity_and_hasher(declarations.len(), FxBuildHasher);
         let mut type_names =
-            IndexSet::<Identifier<'a>>::with_capacity_and_hasher(declarations.len(), FxBuildHasher);
+            IndexSet::<&str>::with_capacity_and_hasher(declarations.len(), FxBuildHasher);
 
         for declaration in &declarations {
             match declaration {
                 Declaration::Entity(entity) => {
                     for ident in entity.names() {
-                        if !entity_names.insert(*ident) {
+                        if !entity_names.insert(ident.as_str()) {
                             return Err(Error::DuplicateKey {
                                 key: String::from(ident.as_str()),
                             });
@@ -55,7 +55,7 @@ impl<'a> Namespace<'a> {
                 Declaration::Type(type_decl) => {
                     let ident = type_decl.name();
 
-                    if !type_names.insert(ident) {
+                    if !type_names.insert(ident.as_str()) {
                         return Err(Error::DuplicateKey {
                             key: String::from(ident.as_str()),
                         });
@@ -88,4 +88,33 @@ impl<'a> Namespace<'a> {
     pub fn declarations(&self) -> &[Declaration<'a>] {
         &self.declarations
     }
+
+    /// Returns the namespace declarations mutably.
+    #[must_use]
+    pub fn declarations_mut(&mut self) -> &mut [Declaration<'a>] {
+        &mut self.declarations
+    }
+
+    /// Maps each declaration by value.
+    pub(crate) fn map_declarations<F: FnMut(Declaration<'a>) -> Declaration<'a>>(
+        mut self,
+        map: F,
+    ) -> Self {
+        self.declarations = self.declarations.into_iter().map(map).collect();
+        self
+    }
+
+    /// Converts into a namespace that no longer borrows the source.
+    #[must_use]
+    pub fn into_owned(self) -> Namespace<'static> {
+        Namespace {
+            annotations: self.annotations.into_owned(),
+            name: self.name.map(Name::into_owned),
+            declarations: self
+                .declarations
+                .into_iter()
+                .map(Declaration::into_owned)
+                .collect(),
+        }
+    }
 }
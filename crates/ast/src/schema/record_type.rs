@@ -3,7 +3,7 @@ use alloc::vec::Vec;
 
 use crate::error::Error;
 use crate::schema::AttributeDeclaration;
-use crate::{FxBuildHasher, IndexMap};
+use crate::{FxBuildHasher, IndexMap, owned};
 
 /// An ordered record of attribute declarations.
 #[derive(Clone, Debug)]
@@ -51,4 +51,44 @@ impl<'a> RecordType<'a> {
     pub fn attributes(&self) -> impl Iterator<Item = (&str, &AttributeDeclaration<'a>)> {
         self.attributes.iter().map(|(key, value)| (&**key, value))
     }
+
+    /// Returns an iterator over attribute name-declaration pairs, with mutable declarations.
+    pub fn attributes_mut(
+        &mut self,
+    ) -> impl Iterator<Item = (&str, &mut AttributeDeclaration<'a>)> {
+        self.attributes
+            .iter_mut()
+            .map(|(key, value)| (&**key, value))
+    }
+
+    /// Maps each attribute declaration by value, keeping the names.
+    pub(crate) fn map_attributes<
+        F: FnMut(&str, AttributeDeclaration<'a>) -> AttributeDeclaration<'a>,
+    >(
+        self,
+        mut map: F,
+    ) -> Self {
+        Self {
+            attributes: self
+                .attributes
+                .into_iter()
+                .map(|(key, value)| {
+                    let value = map(&key, value);
+                    (key, value)
+                })
+                .collect(),
+        }
+    }
+
+    /// Converts into a record type that owns its attribute names and types.
+    #[must_use]
+    pub fn into_owned(self) -> RecordType<'static> {
+        RecordType {
+            attributes: self
+                .attributes
+                .into_iter()
+                .map(|(key, value)| (owned(key), value.into_owned()))
+                .collect(),
+        }
+    }
 }
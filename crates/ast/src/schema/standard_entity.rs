@@ -5,7 +5,7 @@ use alloc::vec::Vec;
 use crate::common::Name;
 use crate::error::Error;
 use crate::schema::{AttributeDeclaration, TypeExpression};
-use crate::{FxBuildHasher, IndexMap, IndexSet};
+use crate::{FxBuildHasher, IndexMap, IndexSet, owned};
 
 /// A standard entity with parents, attributes, and optional tags.
 #[derive(Clone, Debug)]
@@ -73,9 +73,71 @@ impl<'a> StandardEntity<'a> {
         self.attributes.iter().map(|(key, value)| (&**key, value))
     }
 
+    /// Returns an iterator over attribute name-declaration pairs, with mutable declarations.
+    pub fn attributes_mut(
+        &mut self,
+    ) -> impl Iterator<Item = (&str, &mut AttributeDeclaration<'a>)> {
+        self.attributes
+            .iter_mut()
+            .map(|(key, value)| (&**key, value))
+    }
+
     /// Returns the tag type expression, if any.
     #[must_use]
     pub const fn tags(&self) -> Option<&TypeExpression<'a>> {
         self.tags.as_ref()
     }
+
+    /// Returns the tag type expression mutably, if any.
+    #[must_use]
+    pub const fn tags_mut(&mut self) -> Option<&mut TypeExpression<'a>> {
+        self.tags.as_mut()
+    }
+
+    /// Maps each parent, merging any that become equal.
+    pub(crate) fn map_parents<F: FnMut(Name<'a>) -> Name<'a>>(mut self, map: F) -> Self {
+        self.parents = self.parents.into_iter().map(map).collect();
+        self
+    }
+
+    /// Maps each attribute declaration by value, keeping the names.
+    pub(crate) fn map_attributes<
+        F: FnMut(&str, AttributeDeclaration<'a>) -> AttributeDeclaration<'a>,
+    >(
+        mut self,
+        mut map: F,
+    ) -> Self {
+        self.attributes = self
+            .attributes
+            .into_iter()
+            .map(|(key, value)| {
+                let value = map(&key, value);
+                (key, value)
+            })
+            .collect();
+        self
+    }
+
+    /// Maps the tag type expression by value, if any.
+    pub(crate) fn map_tags<F: FnOnce(TypeExpression<'a>) -> TypeExpression<'a>>(
+        mut self,
+        map: F,
+    ) -> Self {
+        self.tags = self.tags.map(map);
+        self
+    }
+
+    /// Converts into an entity definition that owns all its names.
+    #[must_use]
+    pub fn into_owned(self) -> StandardEntity<'static> {
+        StandardEntity {
+            parents: self.parents.into_iter().map(Name::into_owned).collect(),
+            attributes: self
+                .attributes
+                .into_iter()
+                .map(|(key, value)| (owned(key), value.into_owned()))
+                .collect(),
+            tags: self.tags.map(TypeExpression::into_owned),
+        }
+    }
 }
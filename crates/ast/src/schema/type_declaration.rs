@@ -1,4 +1,6 @@
 use alloc::string::String;
+use core::fmt;
+use core::ops::Range;
 
 use crate::RESERVED_TYPE_NAMES;
 use crate::common::{Annotations, Identifier};
@@ -6,11 +8,12 @@ use crate::error::Error;
 use crate::schema::TypeExpression;
 
 /// A declaration of a named type.
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct TypeDeclaration<'a> {
     annotations: Annotations<'a>,
     name: Identifier<'a>,
     definition: TypeExpression<'a>,
+    span: Option<Range<usize>>,
 }
 
 impl<'a> TypeDeclaration<'a> {
@@ -34,9 +37,17 @@ impl<'a> TypeDeclaration<'a> {
             annotations,
             name,
             definition,
+            span: None,
         })
     }
 
+    /// Sets the byte span of this declaration in the source text.
+    #[must_use]
+    pub const fn with_span(mut self, span: Range<usize>) -> Self {
+        self.span = Some(span);
+        self
+    }
+
     /// Returns the type annotations.
     #[must_use]
     pub const fn annotations(&self) -> &Annotations<'a> {
@@ -45,8 +56,8 @@ impl<'a> TypeDeclaration<'a> {
 
     /// Returns the type name.
     #[must_use]
-    pub const fn name(&self) -> Identifier<'a> {
-        self.name
+    pub const fn name(&self) -> &Identifier<'a> {
+        &self.name
     }
 
     /// Returns the type definition.
@@ -54,4 +65,46 @@ impl<'a> TypeDeclaration<'a> {
     pub const fn definition(&self) -> &TypeExpression<'a> {
         &self.definition
     }
+
+    /// Returns the type definition mutably.
+    #[must_use]
+    pub const fn definition_mut(&mut self) -> &mut TypeExpression<'a> {
+        &mut self.definition
+    }
+
+    /// Maps the type definition by value, keeping the span.
+    pub(crate) fn map_definition<F: FnOnce(TypeExpression<'a>) -> TypeExpression<'a>>(
+        mut self,
+        map: F,
+    ) -> Self {
+        self.definition = map(self.definition);
+        self
+    }
+
+    /// Returns the byte span of this declaration in the source text, if known.
+    #[must_use]
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    /// Converts into a declaration that no longer borrows the source, keeping the span.
+    #[must_use]
+    pub fn into_owned(self) -> TypeDeclaration<'static> {
+        TypeDeclaration {
+            annotations: self.annotations.into_owned(),
+            name: self.name.into_owned(),
+            definition: self.definition.into_owned(),
+            span: self.span,
+        }
+    }
+}
+
+impl fmt::Debug for TypeDeclaration<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypeDeclaration")
+            .field("annotations", &self.annotations)
+            .field("name", &self.name)
+            .field("definition", &self.definition)
+            .finish_non_exhaustive()
+    }
 }
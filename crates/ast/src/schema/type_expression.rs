@@ -12,3 +12,17 @@ pub enum TypeExpression<'a> {
     Entity(EntityTypeSet<'a>),
     Enum(EnumType<'a>),
 }
+
+impl TypeExpression<'_> {
+    /// Converts into a type expression that owns all its names.
+    #[must_use]
+    pub fn into_owned(self) -> TypeExpression<'static> {
+        match self {
+            Self::Reference(name) => TypeExpression::Reference(name.into_owned()),
+            Self::Set(element) => TypeExpression::Set(Box::new(element.into_owned())),
+            Self::Record(record) => TypeExpression::Record(record.into_owned()),
+            Self::Entity(types) => TypeExpression::Entity(types.into_owned()),
+            Self::Enum(variants) => TypeExpression::Enum(variants.into_owned()),
+        }
+    }
+}
@@ -0,0 +1,355 @@
+//! Read-only traversal of policies and schemas.
+//!
+//! Each `visit_*` method defaults to the matching `walk_*` function, which visits the children of
+//! the node. Override a method to inspect a node, and call the `walk_*` function to keep
+//! descending.
+//!
+//! ```
+//! use duramen_ast::visit::{self, Visit};
+//! use duramen_ast::{Expression, ExpressionKind};
+//!
+//! /// Collects the attributes read by an expression.
+//! struct Attributes<'ast>(Vec<&'ast str>);
+//!
+//! impl<'ast> Visit<'ast> for Attributes<'ast> {
+//!     fn visit_expression(&mut self, expression: &'ast Expression<'ast>) {
+//!         if let ExpressionKind::GetAttribute { attribute, .. } = expression.kind() {
+//!             self.0.push(attribute);
+//!         }
+//!
+//!         visit::walk_expression(self, expression);
+//!     }
+//! }
+//! ```
+
+use crate::common::Name;
+use crate::policy::{
+    ActionConstraint, Condition, EntityOrSlot, EntityReference, Expression, ExpressionKind,
+    Literal, Policy, PrincipalConstraint, ResourceConstraint, ScopeConstraint,
+};
+use crate::schema::{
+    ActionDeclaration, AppliesTo, AttributeDeclaration, ContextType, Declaration,
+    EntityDeclaration, EntityKind, Namespace, Schema, TypeDeclaration, TypeExpression,
+};
+
+/// A read-only visitor over policies and schemas.
+pub trait Visit<'ast> {
+    fn visit_policy(&mut self, policy: &'ast Policy<'ast>) {
+        walk_policy(self, policy);
+    }
+
+    fn visit_principal_constraint(&mut self, constraint: &'ast PrincipalConstraint<'ast>) {
+        walk_principal_constraint(self, constraint);
+    }
+
+    fn visit_resource_constraint(&mut self, constraint: &'ast ResourceConstraint<'ast>) {
+        walk_resource_constraint(self, constraint);
+    }
+
+    fn visit_scope_constraint(&mut self, constraint: &'ast ScopeConstraint<'ast>) {
+        walk_scope_constraint(self, constraint);
+    }
+
+    fn visit_action_constraint(&mut self, constraint: &'ast ActionConstraint<'ast>) {
+        walk_action_constraint(self, constraint);
+    }
+
+    fn visit_condition(&mut self, condition: &'ast Condition<'ast>) {
+        walk_condition(self, condition);
+    }
+
+    fn visit_expression(&mut self, expression: &'ast Expression<'ast>) {
+        walk_expression(self, expression);
+    }
+
+    fn visit_literal(&mut self, literal: &'ast Literal<'ast>) {
+        walk_literal(self, literal);
+    }
+
+    fn visit_entity_reference(&mut self, reference: &'ast EntityReference<'ast>) {
+        walk_entity_reference(self, reference);
+    }
+
+    /// Visits a type name, such as an entity type or a reference to a schema type.
+    fn visit_name(&mut self, _name: &'ast Name<'ast>) {}
+
+    fn visit_schema(&mut self, schema: &'ast Schema<'ast>) {
+        walk_schema(self, schema);
+    }
+
+    fn visit_namespace(&mut self, namespace: &'ast Namespace<'ast>) {
+        walk_namespace(self, namespace);
+    }
+
+    fn visit_declaration(&mut self, declaration: &'ast Declaration<'ast>) {
+        walk_declaration(self, declaration);
+    }
+
+    fn visit_entity_declaration(&mut self, declaration: &'ast EntityDeclaration<'ast>) {
+        walk_entity_declaration(self, declaration);
+    }
+
+    fn visit_action_declaration(&mut self, declaration: &'ast ActionDeclaration<'ast>) {
+        walk_action_declaration(self, declaration);
+    }
+
+    fn visit_type_declaration(&mut self, declaration: &'ast TypeDeclaration<'ast>) {
+        walk_type_declaration(self, declaration);
+    }
+
+    fn visit_applies_to(&mut self, applies_to: &'ast AppliesTo<'ast>) {
+        walk_applies_to(self, applies_to);
+    }
+
+    /// Visits a record attribute, an entity attribute or an action attribute.
+    fn visit_attribute_declaration(
+        &mut self,
+        _name: &'ast str,
+        declaration: &'ast AttributeDeclaration<'ast>,
+    ) {
+        walk_attribute_declaration(self, declaration);
+    }
+
+    fn visit_type_expression(&mut self, expression: &'ast TypeExpression<'ast>) {
+        walk_type_expression(self, expression);
+    }
+}
+
+pub fn walk_policy<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, policy: &'ast Policy<'ast>) {
+    visitor.visit_principal_constraint(policy.principal());
+    visitor.visit_action_constraint(policy.action());
+    visitor.visit_resource_constraint(policy.resource());
+
+    for condition in policy.conditions() {
+        visitor.visit_condition(condition);
+    }
+}
+
+pub fn walk_principal_constraint<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    constraint: &'ast PrincipalConstraint<'ast>,
+) {
+    visitor.visit_scope_constraint(constraint.constraint());
+}
+
+pub fn walk_resource_constraint<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    constraint: &'ast ResourceConstraint<'ast>,
+) {
+    visitor.visit_scope_constraint(constraint.constraint());
+}
+
+pub fn walk_scope_constraint<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    constraint: &'ast ScopeConstraint<'ast>,
+) {
+    let (name, target) = match constraint {
+        ScopeConstraint::Any => (None, None),
+        ScopeConstraint::Equal(target) | ScopeConstraint::In(target) => (None, Some(target)),
+        ScopeConstraint::Is(name) => (Some(name), None),
+        ScopeConstraint::IsIn(name, target) => (Some(name), Some(target)),
+    };
+
+    if let Some(name) = name {
+        visitor.visit_name(name);
+    }
+
+    if let Some(EntityOrSlot::Entity(reference)) = target {
+        visitor.visit_entity_reference(reference);
+    }
+}
+
+pub fn walk_action_constraint<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    constraint: &'ast ActionConstraint<'ast>,
+) {
+    for reference in constraint.entities() {
+        visitor.visit_entity_reference(reference);
+    }
+}
+
+pub fn walk_condition<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    condition: &'ast Condition<'ast>,
+) {
+    visitor.visit_expression(condition.body());
+}
+
+pub fn walk_expression<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    expression: &'ast Expression<'ast>,
+) {
+    match expression.kind() {
+        ExpressionKind::Literal(literal) => visitor.visit_literal(literal),
+        ExpressionKind::Variable(_) | ExpressionKind::Slot(_) => {}
+        ExpressionKind::If {
+            test,
+            consequent,
+            alternate,
+        } => {
+            visitor.visit_expression(test);
+            visitor.visit_expression(consequent);
+            visitor.visit_expression(alternate);
+        }
+        ExpressionKind::And { left, right }
+        | ExpressionKind::Or { left, right }
+        | ExpressionKind::BinaryApp { left, right, .. } => {
+            visitor.visit_expression(left);
+            visitor.visit_expression(right);
+        }
+        ExpressionKind::UnaryApp { operand, .. } => visitor.visit_expression(operand),
+        ExpressionKind::GetAttribute { expression, .. }
+        | ExpressionKind::HasAttribute { expression, .. }
+        | ExpressionKind::Like { expression, .. } => visitor.visit_expression(expression),
+        ExpressionKind::Is { expression, kind } => {
+            visitor.visit_expression(expression);
+            visitor.visit_name(kind);
+        }
+        ExpressionKind::IsIn {
+            expression,
+            kind,
+            target,
+        } => {
+            visitor.visit_expression(expression);
+            visitor.visit_name(kind);
+            visitor.visit_expression(target);
+        }
+        ExpressionKind::ExtensionCall { arguments, .. } | ExpressionKind::Set(arguments) => {
+            for argument in arguments {
+                visitor.visit_expression(argument);
+            }
+        }
+        ExpressionKind::Record(record) => {
+            for (_, value) in record.iter() {
+                visitor.visit_expression(value);
+            }
+        }
+    }
+}
+
+pub fn walk_literal<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, literal: &'ast Literal<'ast>) {
+    if let Literal::Entity(reference) = literal {
+        visitor.visit_entity_reference(reference);
+    }
+}
+
+pub fn walk_entity_reference<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    reference: &'ast EntityReference<'ast>,
+) {
+    visitor.visit_name(reference.kind());
+}
+
+pub fn walk_schema<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, schema: &'ast Schema<'ast>) {
+    for namespace in schema.namespaces() {
+        visitor.visit_namespace(namespace);
+    }
+}
+
+pub fn walk_namespace<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    namespace: &'ast Namespace<'ast>,
+) {
+    for declaration in namespace.declarations() {
+        visitor.visit_declaration(declaration);
+    }
+}
+
+pub fn walk_declaration<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    declaration: &'ast Declaration<'ast>,
+) {
+    match declaration {
+        Declaration::Entity(entity) => visitor.visit_entity_declaration(entity),
+        Declaration::Action(action) => visitor.visit_action_declaration(action),
+        Declaration::Type(declaration) => visitor.visit_type_declaration(declaration),
+    }
+}
+
+pub fn walk_entity_declaration<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    declaration: &'ast EntityDeclaration<'ast>,
+) {
+    let EntityKind::Standard(standard) = declaration.kind() else {
+        return;
+    };
+
+    for parent in standard.parents() {
+        visitor.visit_name(parent);
+    }
+
+    for (name, attribute) in standard.attributes() {
+        visitor.visit_attribute_declaration(name, attribute);
+    }
+
+    if let Some(tags) = standard.tags() {
+        visitor.visit_type_expression(tags);
+    }
+}
+
+pub fn walk_action_declaration<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    declaration: &'ast ActionDeclaration<'ast>,
+) {
+    if let Some(applies_to) = declaration.applies_to() {
+        visitor.visit_applies_to(applies_to);
+    }
+
+    for (name, attribute) in declaration.attributes() {
+        visitor.visit_attribute_declaration(name, attribute);
+    }
+}
+
+pub fn walk_type_declaration<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    declaration: &'ast TypeDeclaration<'ast>,
+) {
+    visitor.visit_type_expression(declaration.definition());
+}
+
+pub fn walk_applies_to<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    applies_to: &'ast AppliesTo<'ast>,
+) {
+    for name in applies_to.principals().chain(applies_to.resources()) {
+        visitor.visit_name(name);
+    }
+
+    match applies_to.context() {
+        Some(ContextType::Reference(name)) => visitor.visit_name(name),
+        Some(ContextType::Record(record)) => {
+            for (name, attribute) in record.attributes() {
+                visitor.visit_attribute_declaration(name, attribute);
+            }
+        }
+        None => {}
+    }
+}
+
+pub fn walk_attribute_declaration<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    declaration: &'ast AttributeDeclaration<'ast>,
+) {
+    visitor.visit_type_expression(declaration.definition());
+}
+
+pub fn walk_type_expression<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    expression: &'ast TypeExpression<'ast>,
+) {
+    match expression {
+        TypeExpression::Reference(name) => visitor.visit_name(name),
+        TypeExpression::Set(element) => visitor.visit_type_expression(element),
+        TypeExpression::Record(record) => {
+            for (name, attribute) in record.attributes() {
+                visitor.visit_attribute_declaration(name, attribute);
+            }
+        }
+        TypeExpression::Entity(types) => {
+            for name in types.iter() {
+                visitor.visit_name(name);
+            }
+        }
+        TypeExpression::Enum(_) => {}
+    }
+}
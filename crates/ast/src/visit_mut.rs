@@ -0,0 +1,340 @@
+//! In-place traversal of policies and schemas.
+//!
+//! Works like [`Visit`](crate::visit::Visit), but hands out mutable references. Entries of sets,
+//! such as `action in [...]` lists and entity parents, are not visited, since changing them could
+//! introduce duplicates. Use [`Fold`](crate::fold::Fold) to rewrite those.
+//!
+//! ```
+//! use duramen_ast::visit_mut::{self, VisitMut};
+//! use duramen_ast::{BoolLiteral, Expression, ExpressionKind, Literal};
+//!
+//! /// Replaces every `false` literal with `true`.
+//! struct Truthy;
+//!
+//! impl<'a> VisitMut<'a> for Truthy {
+//!     fn visit_literal_mut(&mut self, literal: &mut Literal<'a>) {
+//!         if let Literal::Bool(value) = literal {
+//!             *value = BoolLiteral::new(true);
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::common::Name;
+use crate::policy::{
+    ActionConstraint, Condition, EntityOrSlot, EntityReference, Expression, ExpressionKind,
+    Literal, Policy, PrincipalConstraint, ResourceConstraint, ScopeConstraint,
+};
+use crate::schema::{
+    ActionDeclaration, AppliesTo, AttributeDeclaration, ContextType, Declaration,
+    EntityDeclaration, EntityKind, Namespace, Schema, TypeDeclaration, TypeExpression,
+};
+
+/// A visitor that can modify policies and schemas in place.
+pub trait VisitMut<'a> {
+    fn visit_policy_mut(&mut self, policy: &mut Policy<'a>) {
+        walk_policy_mut(self, policy);
+    }
+
+    fn visit_principal_constraint_mut(&mut self, constraint: &mut PrincipalConstraint<'a>) {
+        walk_principal_constraint_mut(self, constraint);
+    }
+
+    fn visit_resource_constraint_mut(&mut self, constraint: &mut ResourceConstraint<'a>) {
+        walk_resource_constraint_mut(self, constraint);
+    }
+
+    fn visit_scope_constraint_mut(&mut self, constraint: &mut ScopeConstraint<'a>) {
+        walk_scope_constraint_mut(self, constraint);
+    }
+
+    fn visit_action_constraint_mut(&mut self, constraint: &mut ActionConstraint<'a>) {
+        walk_action_constraint_mut(self, constraint);
+    }
+
+    fn visit_condition_mut(&mut self, condition: &mut Condition<'a>) {
+        walk_condition_mut(self, condition);
+    }
+
+    fn visit_expression_mut(&mut self, expression: &mut Expression<'a>) {
+        walk_expression_mut(self, expression);
+    }
+
+    fn visit_literal_mut(&mut self, literal: &mut Literal<'a>) {
+        walk_literal_mut(self, literal);
+    }
+
+    fn visit_entity_reference_mut(&mut self, reference: &mut EntityReference<'a>) {
+        walk_entity_reference_mut(self, reference);
+    }
+
+    /// Visits a type name, such as an entity type or a reference to a schema type.
+    fn visit_name_mut(&mut self, _name: &mut Name<'a>) {}
+
+    fn visit_schema_mut(&mut self, schema: &mut Schema<'a>) {
+        walk_schema_mut(self, schema);
+    }
+
+    fn visit_namespace_mut(&mut self, namespace: &mut Namespace<'a>) {
+        walk_namespace_mut(self, namespace);
+    }
+
+    fn visit_declaration_mut(&mut self, declaration: &mut Declaration<'a>) {
+        walk_declaration_mut(self, declaration);
+    }
+
+    fn visit_entity_declaration_mut(&mut self, declaration: &mut EntityDeclaration<'a>) {
+        walk_entity_declaration_mut(self, declaration);
+    }
+
+    fn visit_action_declaration_mut(&mut self, declaration: &mut ActionDeclaration<'a>) {
+        walk_action_declaration_mut(self, declaration);
+    }
+
+    fn visit_type_declaration_mut(&mut self, declaration: &mut TypeDeclaration<'a>) {
+        walk_type_declaration_mut(self, declaration);
+    }
+
+    fn visit_applies_to_mut(&mut self, applies_to: &mut AppliesTo<'a>) {
+        walk_applies_to_mut(self, applies_to);
+    }
+
+    /// Visits a record attribute, an entity attribute or an action attribute.
+    fn visit_attribute_declaration_mut(
+        &mut self,
+        _name: &str,
+        declaration: &mut AttributeDeclaration<'a>,
+    ) {
+        walk_attribute_declaration_mut(self, declaration);
+    }
+
+    fn visit_type_expression_mut(&mut self, expression: &mut TypeExpression<'a>) {
+        walk_type_expression_mut(self, expression);
+    }
+}
+
+pub fn walk_policy_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, policy: &mut Policy<'a>) {
+    visitor.visit_principal_constraint_mut(policy.principal_mut());
+    visitor.visit_action_constraint_mut(policy.action_mut());
+    visitor.visit_resource_constraint_mut(policy.resource_mut());
+
+    for condition in policy.conditions_mut() {
+        visitor.visit_condition_mut(condition);
+    }
+}
+
+pub fn walk_principal_constraint_mut<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    constraint: &mut PrincipalConstraint<'a>,
+) {
+    visitor.visit_scope_constraint_mut(constraint.constraint_mut());
+}
+
+pub fn walk_resource_constraint_mut<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    constraint: &mut ResourceConstraint<'a>,
+) {
+    visitor.visit_scope_constraint_mut(constraint.constraint_mut());
+}
+
+pub fn walk_scope_constraint_mut<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    constraint: &mut ScopeConstraint<'a>,
+) {
+    let (name, target) = match constraint {
+        ScopeConstraint::Any => (None, None),
+        ScopeConstraint::Equal(target) | ScopeConstraint::In(target) => (None, Some(target)),
+        ScopeConstraint::Is(name) => (Some(name), None),
+        ScopeConstraint::IsIn(name, target) => (Some(name), Some(target)),
+    };
+
+    if let Some(name) = name {
+        visitor.visit_name_mut(name);
+    }
+
+    if let Some(EntityOrSlot::Entity(reference)) = target {
+        visitor.visit_entity_reference_mut(reference);
+    }
+}
+
+pub fn walk_action_constraint_mut<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    constraint: &mut ActionConstraint<'a>,
+) {
+    if let ActionConstraint::Equal(reference) = constraint {
+        visitor.visit_entity_reference_mut(reference);
+    }
+}
+
+pub fn walk_condition_mut<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    condition: &mut Condition<'a>,
+) {
+    visitor.visit_expression_mut(condition.body_mut());
+}
+
+pub fn walk_expression_mut<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    expression: &mut Expression<'a>,
+) {
+    match expression.kind_mut() {
+        ExpressionKind::Literal(literal) => visitor.visit_literal_mut(literal),
+        ExpressionKind::Variable(_) | ExpressionKind::Slot(_) => {}
+        ExpressionKind::If {
+            test,
+            consequent,
+            alternate,
+        } => {
+            visitor.visit_expression_mut(test);
+            visitor.visit_expression_mut(consequent);
+            visitor.visit_expression_mut(alternate);
+        }
+        ExpressionKind::And { left, right }
+        | ExpressionKind::Or { left, right }
+        | ExpressionKind::BinaryApp { left, right, .. } => {
+            visitor.visit_expression_mut(left);
+            visitor.visit_expression_mut(right);
+        }
+        ExpressionKind::UnaryApp { operand, .. } => visitor.visit_expression_mut(operand),
+        ExpressionKind::GetAttribute { expression, .. }
+        | ExpressionKind::HasAttribute { expression, .. }
+        | ExpressionKind::Like { expression, .. } => visitor.visit_expression_mut(expression),
+        ExpressionKind::Is { expression, kind } => {
+            visitor.visit_expression_mut(expression);
+            visitor.visit_name_mut(kind);
+        }
+        ExpressionKind::IsIn {
+            expression,
+            kind,
+            target,
+        } => {
+            visitor.visit_expression_mut(expression);
+            visitor.visit_name_mut(kind);
+            visitor.visit_expression_mut(target);
+        }
+        ExpressionKind::ExtensionCall { arguments, .. } | ExpressionKind::Set(arguments) => {
+            for argument in arguments {
+                visitor.visit_expression_mut(argument);
+            }
+        }
+        ExpressionKind::Record(record) => {
+            for (_, value) in record.iter_mut() {
+                visitor.visit_expression_mut(value);
+            }
+        }
+    }
+}
+
+pub fn walk_literal_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, literal: &mut Literal<'a>) {
+    if let Literal::Entity(reference) = literal {
+        visitor.visit_entity_reference_mut(reference);
+    }
+}
+
+pub fn walk_entity_reference_mut<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    reference: &mut EntityReference<'a>,
+) {
+    visitor.visit_name_mut(reference.kind_mut());
+}
+
+pub fn walk_schema_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, schema: &mut Schema<'a>) {
+    for namespace in schema.namespaces_mut() {
+        visitor.visit_namespace_mut(namespace);
+    }
+}
+
+pub fn walk_namespace_mut<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    namespace: &mut Namespace<'a>,
+) {
+    for declaration in namespace.declarations_mut() {
+        visitor.visit_declaration_mut(declaration);
+    }
+}
+
+pub fn walk_declaration_mut<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    declaration: &mut Declaration<'a>,
+) {
+    match declaration {
+        Declaration::Entity(entity) => visitor.visit_entity_declaration_mut(entity),
+        Declaration::Action(action) => visitor.visit_action_declaration_mut(action),
+        Declaration::Type(declaration) => visitor.visit_type_declaration_mut(declaration),
+    }
+}
+
+pub fn walk_entity_declaration_mut<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    declaration: &mut EntityDeclaration<'a>,
+) {
+    let EntityKind::Standard(standard) = declaration.kind_mut() else {
+        return;
+    };
+
+    for (name, attribute) in standard.attributes_mut() {
+        visitor.visit_attribute_declaration_mut(name, attribute);
+    }
+
+    if let Some(tags) = standard.tags_mut() {
+        visitor.visit_type_expression_mut(tags);
+    }
+}
+
+pub fn walk_action_declaration_mut<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    declaration: &mut ActionDeclaration<'a>,
+) {
+    if let Some(applies_to) = declaration.applies_to_mut() {
+        visitor.visit_applies_to_mut(applies_to);
+    }
+
+    for (name, attribute) in declaration.attributes_mut() {
+        visitor.visit_attribute_declaration_mut(name, attribute);
+    }
+}
+
+pub fn walk_type_declaration_mut<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    declaration: &mut TypeDeclaration<'a>,
+) {
+    visitor.visit_type_expression_mut(declaration.definition_mut());
+}
+
+pub fn walk_applies_to_mut<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    applies_to: &mut AppliesTo<'a>,
+) {
+    match applies_to.context_mut() {
+        Some(ContextType::Reference(name)) => visitor.visit_name_mut(name),
+        Some(ContextType::Record(record)) => {
+            for (name, attribute) in record.attributes_mut() {
+                visitor.visit_attribute_declaration_mut(name, attribute);
+            }
+        }
+        None => {}
+    }
+}
+
+pub fn walk_attribute_declaration_mut<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    declaration: &mut AttributeDeclaration<'a>,
+) {
+    visitor.visit_type_expression_mut(declaration.definition_mut());
+}
+
+pub fn walk_type_expression_mut<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    expression: &mut TypeExpression<'a>,
+) {
+    match expression {
+        TypeExpression::Reference(name) => visitor.visit_name_mut(name),
+        TypeExpression::Set(element) => visitor.visit_type_expression_mut(element),
+        TypeExpression::Record(record) => {
+            for (name, attribute) in record.attributes_mut() {
+                visitor.visit_attribute_declaration_mut(name, attribute);
+            }
+        }
+        TypeExpression::Entity(_) | TypeExpression::Enum(_) => {}
+    }
+}
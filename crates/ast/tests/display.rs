@@ -0,0 +1,183 @@
+#![expect(clippy::unwrap_used, reason = "Tests")]
+
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString as _};
+use alloc::{format, vec};
+
+use duramen_ast::{
+    BinaryOperator, EntityReference, Expression, IntegerLiteral, Name, Pattern, PatternElement,
+    RecordExpression, UnaryOperator, Variable,
+};
+use duramen_lowerer::PolicyLowerer;
+use duramen_parser::PolicyParser;
+use duramen_test::insta::assert_snapshot;
+
+extern crate alloc;
+
+/// Displays the policies in `source`, asserting that the output lowers to the same AST.
+fn display(source: &str) -> String {
+    let (tree, diagnostics) = PolicyParser::parse(source);
+    let (policies, diagnostics) = PolicyLowerer::lower(&tree, diagnostics);
+    assert!(diagnostics.is_empty(), "{source}");
+
+    let output = policies.to_string();
+
+    let (tree, diagnostics) = PolicyParser::parse(&output);
+    let (reparsed, diagnostics) = PolicyLowerer::lower(&tree, diagnostics);
+    assert!(diagnostics.is_empty(), "{output}");
+    assert_eq!(format!("{reparsed:?}"), format!("{policies:?}"), "{output}");
+
+    output
+}
+
+/// Displays an expression, asserting that it lowers to the same AST.
+fn expression(expression: &Expression<'_>) -> String {
+    let output = expression.to_string();
+    let source = format!("permit (principal, action, resource) when {{ {output} }};");
+
+    let (tree, diagnostics) = PolicyParser::parse(&source);
+    let (policies, diagnostics) = PolicyLowerer::lower(&tree, diagnostics);
+    assert!(diagnostics.is_empty(), "{output}");
+
+    let body = policies.policies()[0].conditions()[0].body();
+    assert_eq!(format!("{body:?}"), format!("{expression:?}"), "{output}");
+
+    output
+}
+
+fn variable(name: &str) -> Expression<'_> {
+    Expression::get_attribute(Expression::variable(Variable::Context), Cow::Borrowed(name))
+}
+
+fn integer(value: i64) -> Expression<'static> {
+    Expression::integer(IntegerLiteral::new(&value.to_string()).unwrap())
+}
+
+#[test]
+fn policies() {
+    let output = display(
+        r#"
+        @id("first") @empty
+        permit (principal == User::"alice", action in [Action::"read", Action::"write"], resource is Photo in Album::"trip")
+        when { context.authenticated && resource.owner == principal }
+        unless { resource.private };
+
+        forbid (principal in ?principal, action == Action::"delete", resource is Photo);
+        "#,
+    );
+
+    assert_snapshot!(output, @r#"
+    @id("first")
+    @empty
+    permit (principal == User::"alice", action in [Action::"read", Action::"write"], resource is Photo in Album::"trip")
+    when { context.authenticated && resource.owner == principal }
+    unless { resource.private };
+
+    forbid (principal in ?principal, action == Action::"delete", resource is Photo);
+    "#);
+}
+
+#[test]
+fn precedence() {
+    let output = display(
+        r"
+        permit (principal, action, resource) when {
+            (context.a || context.b) && context.c && (context.d || (context.e && context.f)) &&
+            (1 + 2) * 3 == 1 - (2 - 3) &&
+            !(context.a && context.b) && -(1) < -1 && (!context.a).b && (1 + 2).contains(3) &&
+            (if context.a then 1 else 2) == 1 && (if context.a then context else context).b &&
+            (1 == 2) has c && (context has b) == true && context.b.isEmpty()
+        };
+        ",
+    );
+
+    assert_snapshot!(output, @r#"
+    permit (principal, action, resource)
+    when { (context.a || context.b) && context.c && (context.d || context.e && context.f) && (1 + 2) * 3 == 1 - (2 - 3) && !(context.a && context.b) && -(1) < -1 && (!context.a).b && (1 + 2).contains(3) && (if context.a then 1 else 2) == 1 && (if context.a then context else context).b && (1 == 2) has c && (context has b) == true && context.b.isEmpty() };
+    "#);
+}
+
+#[test]
+fn built() {
+    let nested = Expression::or(variable("a"), Expression::or(variable("b"), variable("c")));
+    assert_snapshot!(expression(&nested), @"context.a || (context.b || context.c)");
+
+    let double = Expression::unary(
+        UnaryOperator::Not,
+        Expression::unary(UnaryOperator::Not, variable("a")),
+    );
+    assert_snapshot!(expression(&double), @"!(!context.a)");
+
+    let negated = Expression::unary(UnaryOperator::Negate, integer(-5));
+    assert_snapshot!(expression(&negated), @"-(-5)");
+
+    let relation = Expression::binary(
+        BinaryOperator::Equal,
+        Expression::binary(BinaryOperator::Less, integer(1), integer(2)),
+        Expression::bool(true),
+    );
+    assert_snapshot!(expression(&relation), @"(1 < 2) == true");
+
+    let method = Expression::binary(
+        BinaryOperator::Contains,
+        Expression::binary(BinaryOperator::Multiply, integer(2), integer(3)),
+        integer(6),
+    );
+    assert_snapshot!(expression(&method), @"(2 * 3).contains(6)");
+}
+
+#[test]
+fn escaping() {
+    let string = Expression::string(Cow::Borrowed("say \"hi\"\n\t\\ \u{1}"));
+    assert_snapshot!(expression(&string), @r#""say \"hi\"\n\t\\ \u{1}""#);
+
+    let entity = Expression::entity(EntityReference::new(
+        Name::parse("App::User").unwrap(),
+        Cow::Borrowed("a\"b"),
+    ));
+    assert_snapshot!(expression(&entity), @r#"App::User::"a\"b""#);
+
+    let like = Expression::like(
+        variable("path"),
+        Pattern::new(vec![
+            PatternElement::Literal(Cow::Borrowed("a*b\\")),
+            PatternElement::Wildcard,
+            PatternElement::Literal(Cow::Borrowed("\"")),
+        ]),
+    );
+    assert_snapshot!(expression(&like), @r#"context.path like "a\*b\\*\"""#);
+
+    let record = Expression::record(
+        RecordExpression::new(vec![
+            (Cow::Borrowed("plain"), integer(1)),
+            (Cow::Borrowed("with space"), integer(2)),
+            (Cow::Borrowed("if"), integer(3)),
+        ])
+        .unwrap(),
+    );
+    assert_snapshot!(expression(&record), @r#"{ plain: 1, "with space": 2, "if": 3 }"#);
+
+    let has = Expression::has_attribute(
+        Expression::variable(Variable::Context),
+        Cow::Borrowed("with space"),
+    );
+    assert_snapshot!(expression(&has), @r#"context has "with space""#);
+}
+
+#[test]
+fn extensions() {
+    let output = display(
+        r#"
+        permit (principal, action, resource) when {
+            ip("10.0.0.1").isInRange(ip("10.0.0.0/8")) &&
+            decimal("1.5").lessThan(decimal("2.5")) &&
+            context.time.offset(duration("1h")) > datetime("2024-01-01")
+        };
+        "#,
+    );
+
+    assert_snapshot!(output, @r#"
+    permit (principal, action, resource)
+    when { ip("10.0.0.1").isInRange(ip("10.0.0.0/8")) && decimal("1.5").lessThan(decimal("2.5")) && context.time.offset(duration("1h")) > datetime("2024-01-01") };
+    "#);
+}
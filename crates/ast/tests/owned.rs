@@ -0,0 +1,105 @@
+#![expect(clippy::unwrap_used, reason = "Tests")]
+
+use alloc::string::{String, ToString as _};
+use alloc::sync::Arc;
+use alloc::{format, vec};
+
+use duramen_ast::{EntityReference, Name, PolicyId, PolicySet, Schema, SlotKind};
+use duramen_lowerer::{PolicyLowerer, SchemaLowerer};
+use duramen_parser::{PolicyParser, SchemaParser};
+use duramen_test::insta::assert_snapshot;
+
+extern crate alloc;
+
+/// Lowers a policy set from a source string that is dropped before returning.
+fn policy_set(source: String) -> (String, PolicySet<'static>) {
+    let (tree, diagnostics) = PolicyParser::parse(&source);
+    let (policies, diagnostics) = PolicyLowerer::lower(&tree, diagnostics);
+    assert!(diagnostics.is_empty(), "{source}");
+
+    let set = PolicySet::new(policies).unwrap();
+    let (borrowed, owned) = (format!("{set:?}"), set.into_owned());
+
+    drop(source);
+    (borrowed, owned)
+}
+
+/// Lowers a schema from a source string that is dropped before returning.
+fn schema(source: String) -> (String, Schema<'static>) {
+    let (tree, diagnostics) = SchemaParser::parse(&source);
+    let (schema, diagnostics) = SchemaLowerer::lower(&tree, diagnostics);
+    assert!(diagnostics.is_empty(), "{source}");
+
+    let (borrowed, owned) = (format!("{schema:?}"), schema.into_owned());
+
+    drop(source);
+    (borrowed, owned)
+}
+
+#[test]
+fn policies() {
+    let source = String::from(
+        r#"
+        @id("view")
+        permit (principal == User::"alice", action in [Action::"view"], resource)
+        when { context.tags.contains("a\"b") && resource.name like "*.jpg" }
+        unless { { "key": ip("10.0.0.1") }.key.isLoopback() };
+
+        @id("template")
+        forbid (principal in ?principal, action, resource is Photo in ?resource);
+        "#,
+    );
+
+    let (borrowed, mut set) = policy_set(source);
+    assert_eq!(format!("{set:?}"), borrowed);
+    assert_snapshot!(set.get("view").unwrap().to_string(), @r#"
+    @id("view")
+    permit (principal == User::"alice", action in [Action::"view"], resource)
+    when { context.tags.contains("a\"b") && resource.name like "*.jpg" }
+    unless { { key: ip("10.0.0.1") }.key.isLoopback() };
+    "#);
+
+    let id = String::from("linked");
+    let entity = EntityReference::new(Name::parse("User").unwrap(), id.clone().into());
+    let values = vec![
+        (SlotKind::Principal, entity.clone()),
+        (SlotKind::Resource, entity),
+    ];
+    set.link("template", PolicyId::new(id.into()), values)
+        .unwrap();
+
+    let set = Arc::new(set);
+    let shared = Arc::clone(&set);
+    let linked = std::thread::spawn(move || shared.get("linked").unwrap().to_string())
+        .join()
+        .unwrap();
+
+    assert_snapshot!(linked, @r#"
+    @id("template")
+    forbid (principal in User::"linked", action, resource is Photo in User::"linked");
+    "#);
+}
+
+#[test]
+fn schemas() {
+    let source = String::from(
+        "
+        @doc(\"acme\")
+        namespace Acme {
+            type Info = { owner: User, tags: Set<String> };
+            entity User in [Group] { info: Info, \"manager name\"?: User } tags Long;
+            entity Group, Team;
+            entity Status enum [\"on\", \"off\"];
+            action view, \"edit item\" in [Action::\"all\"] appliesTo {
+                principal: [User, Team],
+                resource: Group,
+                context: { ip: ipaddr }
+            };
+            action all;
+        }
+        ",
+    );
+
+    let (borrowed, schema) = schema(source);
+    assert_eq!(format!("{schema:?}"), borrowed);
+}
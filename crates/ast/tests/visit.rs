@@ -0,0 +1,271 @@
+#![expect(clippy::unwrap_used, reason = "Tests")]
+
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString as _};
+use alloc::vec::Vec;
+
+use duramen_ast::fold::{self, Fold};
+use duramen_ast::visit::{self, Visit};
+use duramen_ast::visit_mut::VisitMut;
+use duramen_ast::{
+    EntityReference, Expression, ExpressionKind, Identifier, Literal, Name, Policies, Policy,
+    Schema, Variable,
+};
+use duramen_lowerer::{PolicyLowerer, SchemaLowerer};
+use duramen_parser::{PolicyParser, SchemaParser};
+use duramen_test::insta::assert_snapshot;
+
+extern crate alloc;
+
+fn with_policies<T, F: FnOnce(Policies<'_>) -> T>(source: &str, f: F) -> T {
+    let (tree, diagnostics) = PolicyParser::parse(source);
+    let (policies, diagnostics) = PolicyLowerer::lower(&tree, diagnostics);
+    assert!(diagnostics.is_empty(), "{source}");
+
+    f(policies)
+}
+
+fn with_schema<T, F: FnOnce(Schema<'_>) -> T>(source: &str, f: F) -> T {
+    let (tree, diagnostics) = SchemaParser::parse(source);
+    let (schema, diagnostics) = SchemaLowerer::lower(&tree, diagnostics);
+    assert!(diagnostics.is_empty(), "{source}");
+
+    f(schema)
+}
+
+/// Collects the attributes read from `context`, and all entity references.
+#[derive(Default)]
+struct Usage<'ast> {
+    attributes: Vec<&'ast str>,
+    entities: Vec<&'ast EntityReference<'ast>>,
+}
+
+impl<'ast> Visit<'ast> for Usage<'ast> {
+    fn visit_expression(&mut self, expression: &'ast Expression<'ast>) {
+        if let ExpressionKind::GetAttribute {
+            expression: base,
+            attribute,
+        }
+        | ExpressionKind::HasAttribute {
+            expression: base,
+            attribute,
+        } = expression.kind()
+            && matches!(base.kind(), ExpressionKind::Variable(Variable::Context))
+        {
+            self.attributes.push(attribute);
+        }
+
+        visit::walk_expression(self, expression);
+    }
+
+    fn visit_entity_reference(&mut self, reference: &'ast EntityReference<'ast>) {
+        self.entities.push(reference);
+    }
+}
+
+#[test]
+fn visit_policy() {
+    let source = r#"
+        permit (
+            principal == User::"alice",
+            action in [Action::"read", Action::"write"],
+            resource is Photo in Album::"trip"
+        )
+        when { context has mfa && context.mfa }
+        unless { [Group::"banned"].contains(context.group) || { a: context.ip }.a == context.ip };
+    "#;
+
+    let (attributes, entities) = with_policies(source, |policies| {
+        let mut usage = Usage::default();
+        for policy in policies.policies() {
+            usage.visit_policy(policy);
+        }
+
+        let entities: Vec<_> = usage.entities.iter().map(ToString::to_string).collect();
+        (usage.attributes.join(", "), entities.join(", "))
+    });
+
+    assert_snapshot!(attributes, @"mfa, mfa, group, ip, ip");
+    assert_snapshot!(entities, @r#"User::"alice", Action::"read", Action::"write", Album::"trip", Group::"banned""#);
+}
+
+/// Collects every type name, in visiting order.
+#[derive(Default)]
+struct Names(Vec<String>);
+
+impl<'ast> Visit<'ast> for Names {
+    fn visit_name(&mut self, name: &'ast Name<'ast>) {
+        self.0.push(name.to_string());
+    }
+}
+
+const SCHEMA: &str = "
+        namespace Acme {
+            type Info = { owner: User, tags: Set<String> };
+            entity User in [Group] { info: Info, manager?: User } tags Long;
+            entity Group, Team;
+            entity Status enum [\"on\", \"off\"];
+            action view appliesTo {
+                principal: [User, Team],
+                resource: Group,
+                context: { ip: ipaddr }
+            };
+        }
+    ";
+
+/// Lists the type names of a schema, in visiting order.
+fn names(schema: &Schema<'_>) -> String {
+    let mut names = Names::default();
+    names.visit_schema(schema);
+    names.0.join(", ")
+}
+
+#[test]
+fn visit_schema() {
+    let names = with_schema(SCHEMA, |schema| names(&schema));
+    assert_snapshot!(names, @"User, String, Group, Info, User, Long, User, Team, Group, ipaddr");
+}
+
+/// Renames the `User` entity type to `Person`.
+struct Rename;
+
+impl<'a> VisitMut<'a> for Rename {
+    fn visit_name_mut(&mut self, name: &mut Name<'a>) {
+        if name.basename().as_str() == "User" {
+            *name = Name::unqualified(Identifier::new("Person").unwrap());
+        }
+    }
+}
+
+#[test]
+fn visit_mut() {
+    let source = r#"
+        permit (principal is User in Group::"a", action, resource == User::"b")
+        when { context.owner is User && User::"c" in context.users };
+    "#;
+
+    let output = with_policies(source, |policies| {
+        let mut policies = policies.into_policies();
+        for policy in &mut policies {
+            Rename.visit_policy_mut(policy);
+        }
+
+        Policies::new(policies).to_string()
+    });
+
+    assert_snapshot!(output, @r#"
+    permit (principal is Person in Group::"a", action, resource == Person::"b")
+    when { context.owner is Person && Person::"c" in context.users };
+    "#);
+}
+
+#[test]
+fn visit_schema_mut() {
+    let names = with_schema(SCHEMA, |mut schema| {
+        Rename.visit_schema_mut(&mut schema);
+        names(&schema)
+    });
+
+    // Sets of names, such as parents and applies-to types, are left alone.
+    assert_snapshot!(names, @"Person, String, Group, Info, Person, Long, User, Team, Group, ipaddr");
+}
+
+/// Rewrites `!(!e)` to `e` and `x || true` to `true`, keeping spans.
+struct Simplify;
+
+impl<'a> Fold<'a> for Simplify {
+    fn fold_expression(&mut self, expression: Expression<'a>) -> Expression<'a> {
+        let expression = fold::fold_expression(self, expression);
+
+        if let ExpressionKind::UnaryApp { operand, .. } = expression.kind()
+            && let ExpressionKind::UnaryApp { operand, .. } = operand.kind()
+        {
+            return (**operand).clone();
+        }
+
+        if let ExpressionKind::Or { right, .. } = expression.kind()
+            && let ExpressionKind::Literal(Literal::Bool(value)) = right.kind()
+            && value.value()
+        {
+            return (**right).clone();
+        }
+
+        expression
+    }
+
+    fn fold_entity_reference(&mut self, reference: EntityReference<'a>) -> EntityReference<'a> {
+        let (kind, id) = reference.into_parts();
+        EntityReference::new(kind, Cow::Owned(id.to_uppercase()))
+    }
+}
+
+#[test]
+fn fold() {
+    let source = r#"
+        permit (principal == User::"alice", action == Action::"view", resource)
+        when { !!context.a && (context.b || true) && User::"bob" in !!context.users };
+    "#;
+
+    let (output, span) = with_policies(source, |policies| {
+        let policies: Vec<Policy<'_>> = policies
+            .into_policies()
+            .into_iter()
+            .map(|policy| Simplify.fold_policy(policy))
+            .collect();
+
+        let body = policies[0].conditions()[0].body();
+        let span = body.span().unwrap();
+
+        (Policies::new(policies).to_string(), &source[span])
+    });
+
+    assert_snapshot!(output, @r#"
+    permit (principal == User::"ALICE", action == Action::"VIEW", resource)
+    when { context.a && true && User::"BOB" in context.users };
+    "#);
+    assert_snapshot!(span, @r#"!!context.a && (context.b || true) && User::"bob" in !!context.users"#);
+}
+
+/// Merges the `Team` entity type into `Group`.
+struct Merge;
+
+impl<'a> Fold<'a> for Merge {
+    fn fold_name(&mut self, name: Name<'a>) -> Name<'a> {
+        if name.basename().as_str() == "Team" {
+            return Name::unqualified(Identifier::new("Group").unwrap());
+        }
+
+        name
+    }
+}
+
+#[test]
+fn fold_action_list() {
+    let source = r#"
+        permit (principal, action in [Action::"read", Action::"Read", Action::"write"], resource);
+    "#;
+
+    let output = with_policies(source, |policies| {
+        let policies: Vec<Policy<'_>> = policies
+            .into_policies()
+            .into_iter()
+            .map(|policy| Simplify.fold_policy(policy))
+            .collect();
+
+        Policies::new(policies).to_string()
+    });
+
+    assert_snapshot!(output, @r#"permit (principal, action in [Action::"READ", Action::"WRITE"], resource);"#);
+}
+
+#[test]
+fn fold_schema() {
+    let source = "
+        entity User in [Group, Team] { team: Team, teams: Set<Team> };
+        entity Group, Team;
+        action view appliesTo { principal: [User, Team, Group], resource: [Team] };
+    ";
+
+    let names = with_schema(source, |schema| names(&Merge.fold_schema(schema)));
+    assert_snapshot!(names, @"Group, Group, Group, User, Group, Group");
+}
@@ -27,6 +27,10 @@ pub trait CstNode<'a>: Sized + 'a {
         self.syntax().range()
     }
 
+    fn trimmed_range(&self) -> Range<usize> {
+        self.syntax().trimmed_range()
+    }
+
     fn text(&self) -> &'a str {
         self.syntax().text()
     }
@@ -37,7 +37,9 @@ impl<'a> TypeDeclaration<'a> {
     /// Returns the type definition.
     #[must_use]
     pub fn definition(&self) -> Option<TypeExpression<'a>> {
-        self.node.children().find_map(TypeExpression::cast)
+        self.node
+            .after(Token::Assign)
+            .find_map(TypeExpression::cast)
     }
 
     /// Returns the `type` keyword token.
@@ -0,0 +1,47 @@
+#![expect(clippy::unwrap_used, reason = "Tests")]
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use duramen_cst::{CstNode as _, Schema};
+use duramen_parser::SchemaParser;
+use duramen_test::insta::assert_snapshot;
+
+extern crate alloc;
+
+/// Lists each top-level type declaration with its name and definition.
+fn type_declarations(source: &str) -> String {
+    let (tree, _diagnostics) = SchemaParser::parse(source);
+    let schema = tree.root().and_then(Schema::cast).unwrap();
+
+    schema
+        .type_declarations()
+        .map(|declaration| {
+            let name = declaration.name().map_or("", |name| name.text().trim());
+            let definition = declaration
+                .definition()
+                .map_or("", |definition| definition.text().trim());
+
+            format!("{name} = {definition}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn type_definition_follows_assign() {
+    let source = "
+        type Email = String;
+        type Emails = Set<Email>;
+        type Profile = { email: Email };
+        type Missing;
+    ";
+
+    assert_snapshot!(type_declarations(source), @r#"
+    Email = String
+    Emails = Set<Email>
+    Profile = { email: Email }
+    Missing =
+    "#);
+}
@@ -1,9 +1,9 @@
 use alloc::borrow::Cow;
-use alloc::string::String;
+use alloc::string::{String, ToString as _};
 use alloc::vec;
 use alloc::vec::Vec;
 
-use duramen_ast::PatternElement;
+use duramen_ast::{PatternElement, Quoted, QuotedPattern};
 use memchr::{memchr2, memchr3};
 
 use crate::error::EscapeError;
@@ -230,6 +230,23 @@ impl<'a> Escaper<'a> {
         }
     }
 
+    /// Quotes and escapes a string as a string literal.
+    ///
+    /// The result unescapes back to `value` with [`Escaper::unescape_str`].
+    #[must_use]
+    pub fn escape_str(value: &str) -> String {
+        Quoted(value).to_string()
+    }
+
+    /// Quotes and escapes pattern elements as a pattern literal, writing literal `*` as `\*`.
+    ///
+    /// The result unescapes back to `elements` with [`Escaper::unescape_pattern`],
+    /// except that adjacent literals are merged and empty literals are dropped.
+    #[must_use]
+    pub fn escape_pattern(elements: &[PatternElement<'_>]) -> String {
+        QuotedPattern(elements).to_string()
+    }
+
     /// Strips surrounding quotes from a string literal.
     #[must_use]
     pub fn strip_quotes(input: &str) -> &str {
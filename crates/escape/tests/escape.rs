@@ -1,8 +1,12 @@
 use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
 
 use duramen_ast::PatternElement;
 use duramen_escape::{EscapeError, Escaper};
 use duramen_test::insta::assert_snapshot;
+use duramen_test::rand::rngs::SmallRng;
+use duramen_test::rand::{Rng as _, SeedableRng as _};
 
 extern crate alloc;
 
@@ -157,3 +161,114 @@ fn unicode_out_of_range() {
     );
     assert_snapshot!(errors[0].to_string(), @"out of range unicode escape");
 }
+
+// -- escape --
+
+#[test]
+fn escape_str() {
+    assert_snapshot!(Escaper::escape_str("hello"), @r#""hello""#);
+    assert_snapshot!(
+        Escaper::escape_str("say \"hi\"\\\n\r\t\0\u{1}'*é"),
+        @r#""say \"hi\"\\\n\r\t\0\u{1}'*é""#
+    );
+}
+
+#[test]
+fn escape_pattern() {
+    let elements = [
+        PatternElement::Literal(Cow::Borrowed("a*b")),
+        PatternElement::Wildcard,
+        PatternElement::Literal(Cow::Borrowed("\\\"")),
+        PatternElement::Wildcard,
+        PatternElement::Wildcard,
+    ];
+
+    assert_snapshot!(Escaper::escape_pattern(&elements), @r#""a\*b*\\\"**""#);
+    assert_snapshot!(Escaper::escape_pattern(&[]), @r#""""#);
+}
+
+// -- round trip --
+
+const SEED: u64 = 42;
+const COUNT: usize = 10_000;
+
+/// Characters that need escaping, mixed with ordinary ones.
+const CHARS: &[char] = &[
+    'a',
+    'z',
+    'u',
+    'x',
+    '{',
+    '}',
+    '*',
+    '\\',
+    '"',
+    '\'',
+    '\n',
+    '\r',
+    '\t',
+    '\0',
+    '\u{1}',
+    '\u{7f}',
+    '\u{85}',
+    'é',
+    '\u{1F600}',
+];
+
+fn random_string(rng: &mut SmallRng) -> String {
+    let length = rng.random_range(0..12);
+    (0..length)
+        .map(|_| {
+            if rng.random_bool(0.8) {
+                CHARS[rng.random_range(0..CHARS.len())]
+            } else {
+                rng.random::<char>()
+            }
+        })
+        .collect()
+}
+
+/// Generates elements as the unescaper produces them, without empty or adjacent literals.
+fn random_pattern(rng: &mut SmallRng) -> Vec<PatternElement<'static>> {
+    let mut elements = Vec::new();
+
+    for _ in 0..rng.random_range(0..6) {
+        let literal = matches!(elements.last(), None | Some(PatternElement::Wildcard));
+        if literal && rng.random_bool(0.5) {
+            let mut string = random_string(rng);
+            if string.is_empty() {
+                string.push('*');
+            }
+
+            elements.push(PatternElement::Literal(Cow::Owned(string)));
+        } else {
+            elements.push(PatternElement::Wildcard);
+        }
+    }
+
+    elements
+}
+
+#[test]
+fn str_round_trip() {
+    let mut rng = SmallRng::seed_from_u64(SEED);
+
+    for _ in 0..COUNT {
+        let value = random_string(&mut rng);
+        let escaped = Escaper::escape_str(&value);
+        let unescaped = Escaper::new(&escaped).unescape_str().unwrap();
+        assert_eq!(unescaped, value, "{escaped}");
+    }
+}
+
+#[test]
+fn pattern_round_trip() {
+    let mut rng = SmallRng::seed_from_u64(SEED);
+
+    for _ in 0..COUNT {
+        let elements = random_pattern(&mut rng);
+        let escaped = Escaper::escape_pattern(&elements);
+        let unescaped = Escaper::new(&escaped).unescape_pattern().unwrap();
+        assert_eq!(unescaped, elements, "{escaped}");
+    }
+}
@@ -0,0 +1,182 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use duramen_ast::{
+    self as ast, ActionConstraint, ConditionKind, Effect, EntityOrSlot, ScopeConstraint, SlotKind,
+};
+use duramen_runtime::{Entities, EntityUid, Request};
+
+use crate::error::EvaluationError;
+use crate::evaluator::{Evaluator, expect_bool};
+
+mod decision;
+pub use decision::Decision;
+
+mod policy_error;
+pub use policy_error::PolicyError;
+
+mod response;
+pub use response::Response;
+
+/// Answers authorization requests against a set of policies.
+pub struct Authorizer;
+
+impl Authorizer {
+    /// Decides whether a request is allowed by the given policies.
+    ///
+    /// A request is allowed when at least one `permit` policy is satisfied and no `forbid` policy
+    /// is satisfied. Policies that fail to evaluate are skipped and reported in the response.
+    ///
+    /// Policies are identified by their `@id` annotation or their position, as in a policy set.
+    /// Templates are not evaluated.
+    #[must_use]
+    pub fn is_authorized<'a>(
+        request: &Request,
+        policies: &ast::Policies<'a>,
+        entities: &Entities,
+    ) -> Response<'a> {
+        let policies = policies.policies().iter().enumerate();
+        let policies = policies
+            .filter(|(_, policy)| !policy.is_template())
+            .map(|(index, policy)| (ast::PolicySet::assign_id(policy, index), policy));
+
+        Self::decide(request, policies, entities)
+    }
+
+    /// Decides whether a request is allowed by the policies of a policy set.
+    ///
+    /// Templates are not evaluated; their linked policies are.
+    #[must_use]
+    pub fn is_authorized_set<'a>(
+        request: &Request,
+        policies: &ast::PolicySet<'a>,
+        entities: &Entities,
+    ) -> Response<'a> {
+        let policies = policies.iter().map(|(id, policy)| (id.clone(), policy));
+        Self::decide(request, policies, entities)
+    }
+
+    fn decide<'a, 'p>(
+        request: &Request,
+        policies: impl Iterator<Item = (ast::PolicyId<'a>, &'p ast::Policy<'a>)>,
+        entities: &Entities,
+    ) -> Response<'a>
+    where
+        'a: 'p,
+    {
+        let evaluator = Evaluator::new(request, entities);
+
+        let mut permits = Vec::new();
+        let mut forbids = Vec::new();
+        let mut errors = Vec::new();
+
+        for (id, policy) in policies {
+            match Self::is_satisfied(&evaluator, request, policy) {
+                Ok(true) => match policy.effect() {
+                    Effect::Permit => permits.push(id),
+                    Effect::Forbid => forbids.push(id),
+                },
+                Ok(false) => {}
+                Err((error, span)) => {
+                    let error = PolicyError::new(id, error);
+                    errors.push(match span {
+                        Some(span) => error.with_span(span),
+                        None => error,
+                    });
+                }
+            }
+        }
+
+        if forbids.is_empty() && !permits.is_empty() {
+            Response::new(Decision::Allow, permits, errors)
+        } else {
+            Response::new(Decision::Deny, forbids, errors)
+        }
+    }
+
+    /// Evaluates the scope and conditions of a policy, left to right.
+    ///
+    /// Errors carry the span of the scope constraint or expression that failed.
+    fn is_satisfied(
+        evaluator: &Evaluator<'_>,
+        request: &Request,
+        policy: &ast::Policy<'_>,
+    ) -> Result<bool, (EvaluationError, Option<Range<usize>>)> {
+        let principal = policy.principal();
+        let resource = policy.resource();
+
+        let scope = Self::matches_scope(
+            evaluator,
+            request.principal(),
+            principal.constraint(),
+            SlotKind::Principal,
+        )
+        .map_err(|error| (error, principal.span()))?
+            && Self::matches_action(evaluator, request.action(), policy.action())
+            && Self::matches_scope(
+                evaluator,
+                request.resource(),
+                resource.constraint(),
+                SlotKind::Resource,
+            )
+            .map_err(|error| (error, resource.span()))?;
+
+        if !scope {
+            return Ok(false);
+        }
+
+        for condition in policy.conditions() {
+            let body = condition.body();
+            let value = evaluator.evaluate_spanned(body)?;
+            let value = expect_bool(&value).map_err(|error| (error, body.span()))?;
+            let satisfied = match condition.kind() {
+                ConditionKind::When => value,
+                ConditionKind::Unless => !value,
+            };
+
+            if !satisfied {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn matches_scope(
+        evaluator: &Evaluator<'_>,
+        uid: &EntityUid,
+        constraint: &ScopeConstraint<'_>,
+        slot: SlotKind,
+    ) -> Result<bool, EvaluationError> {
+        let target = |entity: &EntityOrSlot<'_>| match entity {
+            EntityOrSlot::Entity(entity) => Ok(EntityUid::from(entity)),
+            EntityOrSlot::Slot => Err(EvaluationError::UnlinkedSlot { slot }),
+        };
+
+        let matches = match constraint {
+            ScopeConstraint::Any => true,
+            ScopeConstraint::Equal(entity) => *uid == target(entity)?,
+            ScopeConstraint::In(entity) => evaluator.is_descendant(uid, &target(entity)?),
+            ScopeConstraint::Is(kind) => *uid.kind() == *kind,
+            ScopeConstraint::IsIn(kind, entity) => {
+                *uid.kind() == *kind && evaluator.is_descendant(uid, &target(entity)?)
+            }
+        };
+
+        Ok(matches)
+    }
+
+    fn matches_action(
+        evaluator: &Evaluator<'_>,
+        uid: &EntityUid,
+        constraint: &ActionConstraint<'_>,
+    ) -> bool {
+        match constraint {
+            ActionConstraint::Any => true,
+            ActionConstraint::Equal(entity) => *uid == EntityUid::from(entity),
+            ActionConstraint::In(actions) => actions
+                .iter()
+                .any(|action| evaluator.is_descendant(uid, &EntityUid::from(action))),
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use core::fmt;
+
+/// The outcome of an authorization request.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Hash)]
+pub enum Decision {
+    Allow,
+    Deny,
+}
+
+impl fmt::Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Allow => f.write_str("allow"),
+            Self::Deny => f.write_str("deny"),
+        }
+    }
+}
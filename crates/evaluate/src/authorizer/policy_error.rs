@@ -0,0 +1,77 @@
+use alloc::format;
+use core::fmt;
+use core::ops::Range;
+
+use duramen_ast::PolicyId;
+use duramen_diagnostic::Diagnostic;
+
+use crate::error::EvaluationError;
+
+/// An error raised while evaluating a single policy.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct PolicyError<'a> {
+    policy: PolicyId<'a>,
+    error: EvaluationError,
+    span: Option<Range<usize>>,
+}
+
+impl<'a> PolicyError<'a> {
+    /// Creates a policy error.
+    #[must_use]
+    pub const fn new(policy: PolicyId<'a>, error: EvaluationError) -> Self {
+        Self {
+            policy,
+            error,
+            span: None,
+        }
+    }
+
+    /// Sets the byte span of the expression or scope constraint that failed.
+    #[must_use]
+    pub const fn with_span(mut self, span: Range<usize>) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// Returns the ID of the policy that failed.
+    #[must_use]
+    pub const fn policy(&self) -> &PolicyId<'a> {
+        &self.policy
+    }
+
+    /// Returns the evaluation error.
+    #[must_use]
+    pub const fn error(&self) -> &EvaluationError {
+        &self.error
+    }
+
+    /// Returns the byte span of the expression or scope constraint that failed, if known.
+    #[must_use]
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+}
+
+impl fmt::Display for PolicyError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "error while evaluating policy `{}`: {}",
+            self.policy, self.error
+        )
+    }
+}
+
+impl core::error::Error for PolicyError<'_> {}
+
+impl From<PolicyError<'_>> for Diagnostic {
+    fn from(value: PolicyError<'_>) -> Self {
+        let message = format!("error while evaluating policy `{}`", value.policy);
+        let diagnostic = Self::from(value.error).with_note(message);
+
+        match value.span {
+            Some(span) => diagnostic.with_label(span, "while evaluating this expression"),
+            None => diagnostic,
+        }
+    }
+}
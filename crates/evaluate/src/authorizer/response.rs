@@ -0,0 +1,50 @@
+use alloc::vec::Vec;
+
+use duramen_ast::PolicyId;
+
+use crate::authorizer::{Decision, PolicyError};
+
+/// The result of an authorization request.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Response<'a> {
+    decision: Decision,
+    reasons: Vec<PolicyId<'a>>,
+    errors: Vec<PolicyError<'a>>,
+}
+
+impl<'a> Response<'a> {
+    /// Creates a response.
+    #[must_use]
+    pub const fn new(
+        decision: Decision,
+        reasons: Vec<PolicyId<'a>>,
+        errors: Vec<PolicyError<'a>>,
+    ) -> Self {
+        Self {
+            decision,
+            reasons,
+            errors,
+        }
+    }
+
+    /// Returns the decision.
+    #[must_use]
+    pub const fn decision(&self) -> Decision {
+        self.decision
+    }
+
+    /// Returns the IDs of the policies that determined the decision.
+    ///
+    /// For `Allow` these are the satisfied `permit` policies; for `Deny` the satisfied `forbid`
+    /// policies, which is empty when the request was denied by default.
+    #[must_use]
+    pub fn reasons(&self) -> &[PolicyId<'a>] {
+        &self.reasons
+    }
+
+    /// Returns the errors of policies that were skipped because they failed to evaluate.
+    #[must_use]
+    pub fn errors(&self) -> &[PolicyError<'a>] {
+        &self.errors
+    }
+}
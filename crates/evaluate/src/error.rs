@@ -0,0 +1,117 @@
+use alloc::format;
+use alloc::string::String;
+use core::fmt;
+
+use duramen_ast::SlotKind;
+use duramen_diagnostic::Diagnostic;
+use duramen_runtime::{EntityUid, ExtensionError, ValueKind};
+
+/// An error produced while evaluating an expression.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum EvaluationError {
+    /// An operand has the wrong type.
+    TypeMismatch {
+        expected: &'static [ValueKind],
+        found: ValueKind,
+    },
+    /// An integer operation overflowed.
+    IntegerOverflow { operator: &'static str },
+    /// A record or entity does not have the requested attribute.
+    MissingAttribute {
+        entity: Option<EntityUid>,
+        attribute: String,
+    },
+    /// An entity does not have the requested tag.
+    MissingTag { entity: EntityUid, tag: String },
+    /// An entity is referenced but not in the entity store.
+    UnknownEntity { uid: EntityUid },
+    /// A template slot was evaluated without being linked.
+    UnlinkedSlot { slot: SlotKind },
+    /// The extension function is not known.
+    UnknownFunction { name: String },
+    /// An extension function was called with the wrong number of arguments.
+    WrongArity {
+        function: String,
+        expected: usize,
+        found: usize,
+    },
+    /// An extension function overflowed.
+    ExtensionOverflow { function: &'static str },
+    /// An extension constructor received an invalid argument.
+    InvalidExtension { error: ExtensionError },
+}
+
+impl fmt::Display for EvaluationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TypeMismatch { expected, found } => {
+                f.write_str("type error: expected ")?;
+
+                for (index, kind) in expected.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(" or ")?;
+                    }
+
+                    write!(f, "{kind}")?;
+                }
+
+                write!(f, ", found {found}")
+            }
+            Self::IntegerOverflow { operator } => {
+                write!(f, "integer overflow while evaluating `{operator}`")
+            }
+            Self::MissingAttribute {
+                entity: Some(entity),
+                attribute,
+            } => write!(f, "`{entity}` does not have the attribute `{attribute}`"),
+            Self::MissingAttribute {
+                entity: None,
+                attribute,
+            } => write!(f, "record does not have the attribute `{attribute}`"),
+            Self::MissingTag { entity, tag } => {
+                write!(f, "`{entity}` does not have the tag `{tag}`")
+            }
+            Self::UnknownEntity { uid } => write!(f, "entity `{uid}` does not exist"),
+            Self::UnlinkedSlot { slot } => write!(f, "template slot `{slot}` is not linked"),
+            Self::UnknownFunction { name } => {
+                write!(f, "`{name}` is not a known extension function")
+            }
+            Self::WrongArity {
+                function,
+                expected,
+                found,
+            } => write!(
+                f,
+                "`{function}` expects {expected} argument(s), found {found}"
+            ),
+            Self::ExtensionOverflow { function } => {
+                write!(f, "overflow while evaluating `{function}`")
+            }
+            Self::InvalidExtension { error } => error.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for EvaluationError {}
+
+impl From<ExtensionError> for EvaluationError {
+    fn from(value: ExtensionError) -> Self {
+        match value {
+            ExtensionError::UnknownFunction { name } => Self::UnknownFunction { name },
+            error => Self::InvalidExtension { error },
+        }
+    }
+}
+
+impl From<EvaluationError> for Diagnostic {
+    fn from(value: EvaluationError) -> Self {
+        match value {
+            EvaluationError::InvalidExtension { error } => Self::from(error),
+            EvaluationError::MissingAttribute { .. } => Self::error(format!("{value}"))
+                .with_note("use `has` to check for optional attributes before accessing them"),
+            EvaluationError::MissingTag { .. } => Self::error(format!("{value}"))
+                .with_note("use `hasTag` to check for a tag before accessing it"),
+            _ => Self::error(format!("{value}")),
+        }
+    }
+}
@@ -0,0 +1,415 @@
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use duramen_ast::{self as ast, BinaryOperator, ExpressionKind, UnaryOperator, Variable};
+use duramen_runtime::{Entities, EntityUid, Record, Request, Set, Value, ValueKind};
+
+use crate::error::EvaluationError;
+use crate::{extension, pattern};
+
+/// Evaluates expressions against a request and an entity store.
+pub struct Evaluator<'e> {
+    request: &'e Request,
+    entities: &'e Entities,
+}
+
+impl<'e> Evaluator<'e> {
+    #[must_use]
+    pub const fn new(request: &'e Request, entities: &'e Entities) -> Self {
+        Self { request, entities }
+    }
+
+    /// Evaluates an expression to a value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the expression is ill-typed for the values it meets, overflows,
+    /// or accesses missing data.
+    pub fn evaluate(&self, expression: &ast::Expression<'_>) -> Result<Value, EvaluationError> {
+        self.spanned(expression).map_err(|failure| failure.error)
+    }
+
+    /// Evaluates an expression to a value, reporting the span of the innermost expression that
+    /// failed alongside the error.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Evaluator::evaluate`].
+    pub fn evaluate_spanned(
+        &self,
+        expression: &ast::Expression<'_>,
+    ) -> Result<Value, (EvaluationError, Option<Range<usize>>)> {
+        self.spanned(expression)
+            .map_err(|failure| (failure.error, failure.span))
+    }
+
+    /// Evaluates an expression, attaching its span to errors raised by the expression itself.
+    fn spanned(&self, expression: &ast::Expression<'_>) -> Result<Value, Failure> {
+        self.kind(expression).map_err(|mut failure| {
+            if failure.span.is_none() {
+                failure.span = expression.span();
+            }
+
+            failure
+        })
+    }
+
+    fn kind(&self, expression: &ast::Expression<'_>) -> Result<Value, Failure> {
+        match expression.kind() {
+            ExpressionKind::Literal(literal) => Ok(Value::from(literal)),
+            ExpressionKind::Variable(variable) => Ok(self.variable(*variable)),
+            ExpressionKind::Slot(slot) => Err(EvaluationError::UnlinkedSlot { slot: *slot }.into()),
+            ExpressionKind::If {
+                test,
+                consequent,
+                alternate,
+            } => {
+                if expect_bool(&self.spanned(test)?)? {
+                    self.spanned(consequent)
+                } else {
+                    self.spanned(alternate)
+                }
+            }
+            ExpressionKind::And { left, right } => {
+                if !expect_bool(&self.spanned(left)?)? {
+                    return Ok(Value::Bool(false));
+                }
+
+                Ok(Value::Bool(expect_bool(&self.spanned(right)?)?))
+            }
+            ExpressionKind::Or { left, right } => {
+                if expect_bool(&self.spanned(left)?)? {
+                    return Ok(Value::Bool(true));
+                }
+
+                Ok(Value::Bool(expect_bool(&self.spanned(right)?)?))
+            }
+            ExpressionKind::UnaryApp { operator, operand } => {
+                let operand = self.spanned(operand)?;
+                Ok(unary(*operator, &operand)?)
+            }
+            ExpressionKind::BinaryApp {
+                operator,
+                left,
+                right,
+            } => {
+                let left = self.spanned(left)?;
+                let right = self.spanned(right)?;
+                Ok(self.binary(*operator, &left, &right)?)
+            }
+            ExpressionKind::GetAttribute {
+                expression,
+                attribute,
+            } => {
+                let value = self.spanned(expression)?;
+                Ok(self.get_attribute(&value, attribute)?)
+            }
+            ExpressionKind::HasAttribute {
+                expression,
+                attribute,
+            } => {
+                let value = self.spanned(expression)?;
+                Ok(Value::Bool(self.has_attribute(&value, attribute)?))
+            }
+            ExpressionKind::Like {
+                expression,
+                pattern,
+            } => {
+                let value = self.spanned(expression)?;
+                let text = expect_str(&value)?;
+                Ok(Value::Bool(pattern::matches(pattern.elements(), text)))
+            }
+            ExpressionKind::Is { expression, kind } => {
+                let value = self.spanned(expression)?;
+                let uid = expect_entity(&value)?;
+                Ok(Value::Bool(*uid.kind() == *kind))
+            }
+            ExpressionKind::IsIn {
+                expression,
+                kind,
+                target,
+            } => {
+                let value = self.spanned(expression)?;
+                let uid = expect_entity(&value)?;
+                if *uid.kind() != *kind {
+                    return Ok(Value::Bool(false));
+                }
+
+                let target = self.spanned(target)?;
+                Ok(Value::Bool(self.is_in(uid, &target)?))
+            }
+            ExpressionKind::ExtensionCall {
+                function,
+                arguments,
+            } => {
+                let arguments = arguments
+                    .iter()
+                    .map(|argument| self.spanned(argument))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                if !function.path().is_empty() {
+                    return Err(EvaluationError::UnknownFunction {
+                        name: alloc::format!("{function}"),
+                    }
+                    .into());
+                }
+
+                Ok(extension::call(function.basename().as_str(), &arguments)?)
+            }
+            ExpressionKind::Set(elements) => elements
+                .iter()
+                .map(|element| self.spanned(element))
+                .collect::<Result<Set, _>>()
+                .map(Value::Set),
+            ExpressionKind::Record(record) => record
+                .iter()
+                .map(|(key, value)| Ok((Arc::from(key), self.spanned(value)?)))
+                .collect::<Result<Record, _>>()
+                .map(Value::Record),
+        }
+    }
+
+    fn variable(&self, variable: Variable) -> Value {
+        match variable {
+            Variable::Principal => Value::Entity(self.request.principal().clone()),
+            Variable::Action => Value::Entity(self.request.action().clone()),
+            Variable::Resource => Value::Entity(self.request.resource().clone()),
+            Variable::Context => Value::Record(self.request.context().clone()),
+        }
+    }
+
+    fn binary(
+        &self,
+        operator: BinaryOperator,
+        left: &Value,
+        right: &Value,
+    ) -> Result<Value, EvaluationError> {
+        let value = match operator {
+            BinaryOperator::Equal => Value::Bool(left == right),
+            BinaryOperator::NotEqual => Value::Bool(left != right),
+            BinaryOperator::Less => Value::Bool(compare(left, right)?.is_lt()),
+            BinaryOperator::LessEqual => Value::Bool(compare(left, right)?.is_le()),
+            BinaryOperator::Greater => Value::Bool(compare(left, right)?.is_gt()),
+            BinaryOperator::GreaterEqual => Value::Bool(compare(left, right)?.is_ge()),
+            BinaryOperator::In => Value::Bool(self.is_in(expect_entity(left)?, right)?),
+            BinaryOperator::Add => arithmetic(left, right, "+", i64::checked_add)?,
+            BinaryOperator::Subtract => arithmetic(left, right, "-", i64::checked_sub)?,
+            BinaryOperator::Multiply => arithmetic(left, right, "*", i64::checked_mul)?,
+            BinaryOperator::Contains => Value::Bool(expect_set(left)?.contains(right)),
+            BinaryOperator::ContainsAll => {
+                Value::Bool(expect_set(right)?.is_subset(expect_set(left)?))
+            }
+            BinaryOperator::ContainsAny => {
+                Value::Bool(!expect_set(left)?.is_disjoint(expect_set(right)?))
+            }
+            BinaryOperator::GetTag => {
+                let uid = expect_entity(left)?;
+                let tag = expect_str(right)?;
+                let entity = self
+                    .entities
+                    .get(uid)
+                    .ok_or_else(|| EvaluationError::UnknownEntity { uid: uid.clone() })?;
+
+                entity
+                    .tags()
+                    .get(tag)
+                    .cloned()
+                    .ok_or_else(|| EvaluationError::MissingTag {
+                        entity: uid.clone(),
+                        tag: String::from(tag),
+                    })?
+            }
+            BinaryOperator::HasTag => {
+                let uid = expect_entity(left)?;
+                let tag = expect_str(right)?;
+                Value::Bool(
+                    self.entities
+                        .get(uid)
+                        .is_some_and(|entity| entity.tags().contains_key(tag)),
+                )
+            }
+        };
+
+        Ok(value)
+    }
+
+    /// Returns whether `uid` is equal to, or a descendant of, the target entity or any entity in
+    /// the target set.
+    fn is_in(&self, uid: &EntityUid, target: &Value) -> Result<bool, EvaluationError> {
+        match target {
+            Value::Entity(target) => Ok(self.is_descendant(uid, target)),
+            Value::Set(targets) => {
+                let mut found = false;
+                for target in targets {
+                    found |= self.is_descendant(uid, expect_entity(target)?);
+                }
+
+                Ok(found)
+            }
+            _ => Err(EvaluationError::TypeMismatch {
+                expected: &[ValueKind::Entity, ValueKind::Set],
+                found: target.kind(),
+            }),
+        }
+    }
+
+    pub(crate) fn is_descendant(&self, uid: &EntityUid, target: &EntityUid) -> bool {
+        uid == target || self.entities.is_ancestor(target, uid)
+    }
+
+    fn get_attribute(&self, value: &Value, attribute: &str) -> Result<Value, EvaluationError> {
+        match value {
+            Value::Record(record) => {
+                record
+                    .get(attribute)
+                    .cloned()
+                    .ok_or_else(|| EvaluationError::MissingAttribute {
+                        entity: None,
+                        attribute: String::from(attribute),
+                    })
+            }
+            Value::Entity(uid) => {
+                let entity = self
+                    .entities
+                    .get(uid)
+                    .ok_or_else(|| EvaluationError::UnknownEntity { uid: uid.clone() })?;
+
+                entity.attributes().get(attribute).cloned().ok_or_else(|| {
+                    EvaluationError::MissingAttribute {
+                        entity: Some(uid.clone()),
+                        attribute: String::from(attribute),
+                    }
+                })
+            }
+            _ => Err(EvaluationError::TypeMismatch {
+                expected: &[ValueKind::Entity, ValueKind::Record],
+                found: value.kind(),
+            }),
+        }
+    }
+
+    fn has_attribute(&self, value: &Value, attribute: &str) -> Result<bool, EvaluationError> {
+        match value {
+            Value::Record(record) => Ok(record.contains_key(attribute)),
+            Value::Entity(uid) => Ok(self
+                .entities
+                .get(uid)
+                .is_some_and(|entity| entity.attributes().contains_key(attribute))),
+            _ => Err(EvaluationError::TypeMismatch {
+                expected: &[ValueKind::Entity, ValueKind::Record],
+                found: value.kind(),
+            }),
+        }
+    }
+}
+
+/// An evaluation error and the span of the innermost expression that raised it.
+struct Failure {
+    error: EvaluationError,
+    span: Option<Range<usize>>,
+}
+
+impl From<EvaluationError> for Failure {
+    fn from(value: EvaluationError) -> Self {
+        Self {
+            error: value,
+            span: None,
+        }
+    }
+}
+
+fn unary(operator: UnaryOperator, operand: &Value) -> Result<Value, EvaluationError> {
+    match operator {
+        UnaryOperator::Not => expect_bool(operand).map(|value| Value::Bool(!value)),
+        UnaryOperator::Negate => expect_long(operand)?
+            .checked_neg()
+            .map(Value::Long)
+            .ok_or(EvaluationError::IntegerOverflow { operator: "-" }),
+        UnaryOperator::IsEmpty => expect_set(operand).map(|set| Value::Bool(set.is_empty())),
+    }
+}
+
+fn arithmetic(
+    left: &Value,
+    right: &Value,
+    operator: &'static str,
+    apply: fn(i64, i64) -> Option<i64>,
+) -> Result<Value, EvaluationError> {
+    apply(expect_long(left)?, expect_long(right)?)
+        .map(Value::Long)
+        .ok_or(EvaluationError::IntegerOverflow { operator })
+}
+
+/// Orders two longs, datetimes or durations of the same kind.
+fn compare(left: &Value, right: &Value) -> Result<core::cmp::Ordering, EvaluationError> {
+    const COMPARABLE: &[ValueKind] = &[ValueKind::Long, ValueKind::Datetime, ValueKind::Duration];
+
+    let kind = left.kind();
+    if !COMPARABLE.contains(&kind) {
+        return Err(EvaluationError::TypeMismatch {
+            expected: COMPARABLE,
+            found: kind,
+        });
+    }
+
+    if right.kind() != kind {
+        return Err(EvaluationError::TypeMismatch {
+            expected: match kind {
+                ValueKind::Datetime => &[ValueKind::Datetime],
+                ValueKind::Duration => &[ValueKind::Duration],
+                _ => &[ValueKind::Long],
+            },
+            found: right.kind(),
+        });
+    }
+
+    Ok(left.cmp(right))
+}
+
+/// Returns the boolean in `value`, or a type error.
+pub fn expect_bool(value: &Value) -> Result<bool, EvaluationError> {
+    value
+        .as_bool()
+        .ok_or_else(|| EvaluationError::TypeMismatch {
+            expected: &[ValueKind::Bool],
+            found: value.kind(),
+        })
+}
+
+/// Returns the long in `value`, or a type error.
+pub fn expect_long(value: &Value) -> Result<i64, EvaluationError> {
+    value
+        .as_long()
+        .ok_or_else(|| EvaluationError::TypeMismatch {
+            expected: &[ValueKind::Long],
+            found: value.kind(),
+        })
+}
+
+/// Returns the string in `value`, or a type error.
+pub fn expect_str(value: &Value) -> Result<&str, EvaluationError> {
+    value.as_str().ok_or_else(|| EvaluationError::TypeMismatch {
+        expected: &[ValueKind::String],
+        found: value.kind(),
+    })
+}
+
+/// Returns the entity in `value`, or a type error.
+pub fn expect_entity(value: &Value) -> Result<&EntityUid, EvaluationError> {
+    value
+        .as_entity()
+        .ok_or_else(|| EvaluationError::TypeMismatch {
+            expected: &[ValueKind::Entity],
+            found: value.kind(),
+        })
+}
+
+/// Returns the set in `value`, or a type error.
+pub fn expect_set(value: &Value) -> Result<&Set, EvaluationError> {
+    value.as_set().ok_or_else(|| EvaluationError::TypeMismatch {
+        expected: &[ValueKind::Set],
+        found: value.kind(),
+    })
+}
@@ -0,0 +1,150 @@
+use alloc::string::String;
+
+use duramen_runtime::{Datetime, Decimal, Duration, Extension, IpAddress, Value, ValueKind};
+
+use crate::error::EvaluationError;
+use crate::evaluator::expect_str;
+
+/// Calls an extension function by name.
+pub fn call(name: &str, arguments: &[Value]) -> Result<Value, EvaluationError> {
+    let value = match name {
+        "decimal" | "ip" | "datetime" | "duration" => {
+            let [argument] = arity(name, arguments)?;
+            Value::Extension(Extension::new(name, expect_str(argument)?)?)
+        }
+
+        "lessThan" | "lessThanOrEqual" | "greaterThan" | "greaterThanOrEqual" => {
+            let [left, right] = arity(name, arguments)?;
+            let ordering = decimal(left)?.cmp(&decimal(right)?);
+
+            Value::Bool(match name {
+                "lessThan" => ordering.is_lt(),
+                "lessThanOrEqual" => ordering.is_le(),
+                "greaterThan" => ordering.is_gt(),
+                _ => ordering.is_ge(),
+            })
+        }
+
+        "isIpv4" | "isIpv6" | "isLoopback" | "isMulticast" => {
+            let [address] = arity(name, arguments)?;
+            let address = ip_address(address)?;
+
+            Value::Bool(match name {
+                "isIpv4" => address.is_ipv4(),
+                "isIpv6" => address.is_ipv6(),
+                "isLoopback" => address.is_loopback(),
+                _ => address.is_multicast(),
+            })
+        }
+        "isInRange" => {
+            let [address, range] = arity(name, arguments)?;
+            Value::Bool(ip_address(address)?.is_in_range(&ip_address(range)?))
+        }
+
+        "offset" => {
+            let [datetime_value, duration_value] = arity(name, arguments)?;
+            let result = datetime(datetime_value)?
+                .offset(duration(duration_value)?)
+                .ok_or(EvaluationError::ExtensionOverflow { function: "offset" })?;
+
+            Value::Extension(Extension::Datetime(result))
+        }
+        "durationSince" => {
+            let [left, right] = arity(name, arguments)?;
+            let result = datetime(left)?.duration_since(datetime(right)?).ok_or(
+                EvaluationError::ExtensionOverflow {
+                    function: "durationSince",
+                },
+            )?;
+
+            Value::Extension(Extension::Duration(result))
+        }
+        "toDate" => {
+            let [value] = arity(name, arguments)?;
+            let result = datetime(value)?
+                .to_date()
+                .ok_or(EvaluationError::ExtensionOverflow { function: "toDate" })?;
+
+            Value::Extension(Extension::Datetime(result))
+        }
+        "toTime" => {
+            let [value] = arity(name, arguments)?;
+            Value::Extension(Extension::Duration(datetime(value)?.to_time()))
+        }
+
+        "toMilliseconds" | "toSeconds" | "toMinutes" | "toHours" | "toDays" => {
+            let [value] = arity(name, arguments)?;
+            let value = duration(value)?;
+
+            Value::Long(match name {
+                "toMilliseconds" => value.to_milliseconds(),
+                "toSeconds" => value.to_seconds(),
+                "toMinutes" => value.to_minutes(),
+                "toHours" => value.to_hours(),
+                _ => value.to_days(),
+            })
+        }
+
+        _ => {
+            return Err(EvaluationError::UnknownFunction {
+                name: String::from(name),
+            });
+        }
+    };
+
+    Ok(value)
+}
+
+/// Checks that exactly `N` arguments were passed.
+fn arity<'v, const N: usize>(
+    name: &str,
+    arguments: &'v [Value],
+) -> Result<&'v [Value; N], EvaluationError> {
+    arguments
+        .try_into()
+        .map_err(|_err| EvaluationError::WrongArity {
+            function: String::from(name),
+            expected: N,
+            found: arguments.len(),
+        })
+}
+
+const fn decimal(value: &Value) -> Result<Decimal, EvaluationError> {
+    match value {
+        Value::Extension(Extension::Decimal(decimal)) => Ok(*decimal),
+        _ => Err(mismatch(ValueKind::Decimal, value)),
+    }
+}
+
+const fn ip_address(value: &Value) -> Result<IpAddress, EvaluationError> {
+    match value {
+        Value::Extension(Extension::IpAddress(address)) => Ok(*address),
+        _ => Err(mismatch(ValueKind::IpAddress, value)),
+    }
+}
+
+const fn datetime(value: &Value) -> Result<Datetime, EvaluationError> {
+    match value {
+        Value::Extension(Extension::Datetime(datetime)) => Ok(*datetime),
+        _ => Err(mismatch(ValueKind::Datetime, value)),
+    }
+}
+
+const fn duration(value: &Value) -> Result<Duration, EvaluationError> {
+    match value {
+        Value::Extension(Extension::Duration(duration)) => Ok(*duration),
+        _ => Err(mismatch(ValueKind::Duration, value)),
+    }
+}
+
+const fn mismatch(expected: ValueKind, value: &Value) -> EvaluationError {
+    EvaluationError::TypeMismatch {
+        expected: match expected {
+            ValueKind::Decimal => &[ValueKind::Decimal],
+            ValueKind::IpAddress => &[ValueKind::IpAddress],
+            ValueKind::Datetime => &[ValueKind::Datetime],
+            _ => &[ValueKind::Duration],
+        },
+        found: value.kind(),
+    }
+}
@@ -1,5 +1,18 @@
 #![cfg_attr(doc, doc = include_str!("../README.md"))]
 #![no_std]
+extern crate alloc;
 
 #[cfg(feature = "std")]
 extern crate std;
+
+mod authorizer;
+pub use authorizer::{Authorizer, Decision, PolicyError, Response};
+
+mod error;
+pub use error::EvaluationError;
+
+mod evaluator;
+pub use evaluator::Evaluator;
+
+mod extension;
+mod pattern;
@@ -0,0 +1,35 @@
+use duramen_ast::PatternElement;
+
+/// Returns whether `text` matches a `like` pattern, where wildcards match any sequence of characters.
+pub fn matches(elements: &[PatternElement<'_>], text: &str) -> bool {
+    let mut rest = text;
+    let mut anchored = true;
+
+    for (index, element) in elements.iter().enumerate() {
+        match element {
+            PatternElement::Wildcard => anchored = false,
+            PatternElement::Literal(literal) => {
+                if anchored {
+                    let Some(remaining) = rest.strip_prefix(&**literal) else {
+                        return false;
+                    };
+
+                    rest = remaining;
+                } else if index + 1 == elements.len() {
+                    // The final literal must match the end of the text.
+                    return rest.ends_with(&**literal);
+                } else {
+                    let Some(position) = rest.find(&**literal) else {
+                        return false;
+                    };
+
+                    rest = &rest[position + literal.len()..];
+                }
+
+                anchored = true;
+            }
+        }
+    }
+
+    !anchored || rest.is_empty()
+}
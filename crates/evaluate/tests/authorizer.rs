@@ -0,0 +1,268 @@
+#![expect(clippy::unwrap_used, reason = "Tests")]
+
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write as _;
+
+use duramen_ast::{EntityReference, Identifier, Name, PolicyId, PolicySet, SlotKind};
+use duramen_diagnostic::Diagnostic;
+use duramen_evaluate::{Authorizer, Response};
+use duramen_lowerer::PolicyLowerer;
+use duramen_parser::PolicyParser;
+use duramen_runtime::{Entities, EntitiesParser, Request};
+use duramen_test::anstream::adapter::strip_str;
+use duramen_test::insta::assert_snapshot;
+use duramen_test::uid;
+
+extern crate alloc;
+
+const ENTITIES: &str = r#"[
+    {
+        "uid": { "type": "User", "id": "alice" },
+        "attrs": { "age": 30 },
+        "parents": [{ "type": "Group", "id": "admins" }]
+    },
+    { "uid": { "type": "User", "id": "bob" }, "attrs": {} },
+    { "uid": { "type": "Group", "id": "admins" } },
+    {
+        "uid": { "type": "Action", "id": "view" },
+        "parents": [{ "type": "Action", "id": "read" }]
+    },
+    { "uid": { "type": "Action", "id": "read" } },
+    { "uid": { "type": "Photo", "id": "cat.jpg" } }
+]"#;
+
+fn request(principal: &str) -> Request {
+    Request::new(
+        uid("User", principal),
+        uid("Action", "view"),
+        uid("Photo", "cat.jpg"),
+    )
+}
+
+fn authorize(principal: &str, source: &str) -> String {
+    let (tree, diagnostics) = PolicyParser::parse(source);
+    let (policies, diagnostics) = PolicyLowerer::lower(&tree, diagnostics);
+    assert!(diagnostics.is_empty(), "{source}");
+
+    let entities: Entities = EntitiesParser::parse(ENTITIES).unwrap();
+    let response = Authorizer::is_authorized(&request(principal), &policies, &entities);
+    display(&response)
+}
+
+fn authorize_set(principal: &str, policies: &PolicySet<'_>) -> String {
+    let entities: Entities = EntitiesParser::parse(ENTITIES).unwrap();
+    let response = Authorizer::is_authorized_set(&request(principal), policies, &entities);
+    display(&response)
+}
+
+fn display(response: &Response<'_>) -> String {
+    let reasons = response
+        .reasons()
+        .iter()
+        .map(PolicyId::as_str)
+        .collect::<Vec<_>>();
+
+    let mut output = format!("{} [{}]", response.decision(), reasons.join(", "));
+    for error in response.errors() {
+        write!(output, "\n{error}").unwrap();
+    }
+
+    output
+}
+
+#[test]
+fn default_deny() {
+    let output = authorize("alice", "");
+    assert_snapshot!(output, @"deny []");
+
+    let output = authorize(
+        "alice",
+        r#"permit(principal == User::"bob", action, resource);"#,
+    );
+    assert_snapshot!(output, @"deny []");
+}
+
+#[test]
+fn permit() {
+    let source = r#"
+        permit(principal == User::"bob", action, resource);
+        permit(principal in Group::"admins", action in [Action::"read"], resource is Photo);
+        permit(principal, action == Action::"view", resource) when { principal.age > 18 };
+    "#;
+
+    let output = authorize("alice", source);
+    assert_snapshot!(output, @"allow [policy1, policy2]");
+}
+
+#[test]
+fn forbid_overrides_permit() {
+    let source = r#"
+        permit(principal, action, resource);
+        forbid(principal is User in Group::"admins", action, resource) unless { principal.age > 40 };
+    "#;
+
+    let output = authorize("alice", source);
+    assert_snapshot!(output, @"deny [policy1]");
+
+    let output = authorize("bob", source);
+    assert_snapshot!(output, @"allow [policy0]");
+}
+
+#[test]
+fn errors_are_skipped() {
+    let source = r#"
+        permit(principal, action, resource) when { principal.age > 18 };
+        forbid(principal, action, resource) when { principal.age + "1" > 0 };
+    "#;
+
+    let output = authorize("alice", source);
+    assert_snapshot!(output, @r"
+    allow [policy0]
+    error while evaluating policy `policy1`: type error: expected Long, found String
+    ");
+
+    let output = authorize("bob", source);
+    assert_snapshot!(output, @r#"
+    deny []
+    error while evaluating policy `policy0`: `User::"bob"` does not have the attribute `age`
+    error while evaluating policy `policy1`: `User::"bob"` does not have the attribute `age`
+    "#);
+}
+
+#[test]
+fn errors_render_spans() {
+    let source = r#"
+        forbid(principal, action, resource) when { principal.age + "1" > 0 };
+        permit(principal, action, resource) when { context.missing };
+        permit(principal, action, resource) when { 1 };
+    "#;
+
+    let (tree, diagnostics) = PolicyParser::parse(source);
+    let (policies, diagnostics) = PolicyLowerer::lower(&tree, diagnostics);
+    assert!(diagnostics.is_empty());
+
+    let entities: Entities = EntitiesParser::parse(ENTITIES).unwrap();
+    let response = Authorizer::is_authorized(&request("alice"), &policies, &entities);
+
+    let rendered = response
+        .errors()
+        .iter()
+        .map(|error| Diagnostic::from(error.clone()).render("test.cedar", source))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    assert_snapshot!(strip_str(&rendered).to_string(), @r#"
+    error: type error: expected Long, found String
+      ╭▸ test.cedar:2:52
+      │
+    2 │         forbid(principal, action, resource) when { principal.age + "1" > 0 };
+      │                                                    ━━━━━━━━━━━━━━━━━━━ while evaluating this expression
+      ╰╴
+    note: error while evaluating policy `policy0`
+    error: record does not have the attribute `missing`
+      ╭▸ test.cedar:3:52
+      │
+    3 │         permit(principal, action, resource) when { context.missing };
+      │                                                    ━━━━━━━━━━━━━━━ while evaluating this expression
+      ╰╴
+    note: use `has` to check for optional attributes before accessing them
+    note: error while evaluating policy `policy1`
+    error: type error: expected Bool, found Long
+      ╭▸ test.cedar:4:52
+      │
+    4 │         permit(principal, action, resource) when { 1 };
+      │                                                    ━ while evaluating this expression
+      ╰╴
+    note: error while evaluating policy `policy2`
+    "#);
+}
+
+#[test]
+fn conditions_short_circuit() {
+    let source = r#"
+        permit(principal == User::"bob", action, resource) when { principal.age > 18 };
+        permit(principal, action, resource) when { false } when { 1 };
+        permit(principal, action, resource) when { 1 };
+    "#;
+
+    let output = authorize("alice", source);
+    assert_snapshot!(output, @r"
+    deny []
+    error while evaluating policy `policy2`: type error: expected Bool, found Long
+    ");
+}
+
+#[test]
+fn templates() {
+    let source = r#"
+        @id("share")
+        permit(principal == ?principal, action, resource);
+    "#;
+
+    let (tree, diagnostics) = PolicyParser::parse(source);
+    let (mut policies, diagnostics) = PolicyLowerer::lower_set(&tree, diagnostics);
+    assert!(diagnostics.is_empty());
+
+    let output = authorize_set("alice", &policies);
+    assert_snapshot!(output, @"deny []");
+
+    let alice = EntityReference::new(
+        Name::new(Vec::new(), Identifier::new("User").unwrap()),
+        Cow::Borrowed("alice"),
+    );
+
+    policies
+        .link(
+            "share",
+            PolicyId::new(Cow::Borrowed("share-alice")),
+            vec![(SlotKind::Principal, alice)],
+        )
+        .unwrap();
+
+    let output = authorize_set("alice", &policies);
+    assert_snapshot!(output, @"allow [share-alice]");
+
+    let output = authorize_set("bob", &policies);
+    assert_snapshot!(output, @"deny []");
+}
+
+#[test]
+fn reasons_use_policy_ids() {
+    let source = r#"
+        @id("allow-admins")
+        permit(principal in Group::"admins", action, resource);
+        permit(principal, action, resource) when { principal.age > 18 };
+        @id("broken")
+        forbid(principal, action, resource) when { principal.missing };
+    "#;
+
+    let output = authorize("alice", source);
+    assert_snapshot!(output, @r#"
+    allow [allow-admins, policy1]
+    error while evaluating policy `broken`: `User::"alice"` does not have the attribute `missing`
+    "#);
+}
+
+#[test]
+fn policies_and_sets() {
+    let source = r#"
+        @id("allow-admins")
+        permit(principal in Group::"admins", action, resource);
+        forbid(principal == User::"bob", action, resource);
+        @id("share")
+        permit(principal == ?principal, action, resource);
+    "#;
+
+    let output = authorize("alice", source);
+    assert_snapshot!(output, @"allow [allow-admins]");
+
+    let (tree, diagnostics) = PolicyParser::parse(source);
+    let (policies, diagnostics) = PolicyLowerer::lower_set(&tree, diagnostics);
+    assert!(diagnostics.is_empty());
+
+    let output = authorize_set("alice", &policies);
+    assert_snapshot!(output, @"allow [allow-admins]");
+}
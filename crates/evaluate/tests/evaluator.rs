@@ -0,0 +1,210 @@
+#![expect(clippy::unwrap_used, reason = "Tests")]
+
+use alloc::format;
+use alloc::string::{String, ToString as _};
+
+use duramen_ast::SlotKind;
+use duramen_evaluate::{EvaluationError, Evaluator};
+use duramen_lowerer::PolicyLowerer;
+use duramen_parser::PolicyParser;
+use duramen_runtime::{Entities, EntitiesParser, Request, Value};
+use duramen_test::insta::assert_snapshot;
+use duramen_test::uid;
+
+extern crate alloc;
+
+const ENTITIES: &str = r#"[
+    {
+        "uid": { "type": "User", "id": "alice" },
+        "attrs": { "age": 30, "name": "Alice", "address": { "city": "Dublin" } },
+        "parents": [{ "type": "Group", "id": "admins" }],
+        "tags": { "team": "core" }
+    },
+    {
+        "uid": { "type": "Group", "id": "admins" },
+        "parents": [{ "type": "Group", "id": "staff" }]
+    },
+    { "uid": { "type": "Group", "id": "staff" } },
+    { "uid": { "type": "Action", "id": "view" } },
+    { "uid": { "type": "Photo", "id": "cat.jpg" }, "attrs": { "owner": { "__entity": { "type": "User", "id": "alice" } } } }
+]"#;
+
+fn evaluate(expression: &str) -> Result<Value, EvaluationError> {
+    let source = format!("permit(principal, action, resource) when {{ {expression} }};");
+    let (tree, diagnostics) = PolicyParser::parse(&source);
+    let (policies, diagnostics) = PolicyLowerer::lower(&tree, diagnostics);
+    assert!(diagnostics.is_empty(), "{expression}");
+
+    let entities: Entities = EntitiesParser::parse(ENTITIES).unwrap();
+    let request = Request::new(
+        uid("User", "alice"),
+        uid("Action", "view"),
+        uid("Photo", "cat.jpg"),
+    );
+
+    let policy = &policies.policies()[0];
+    Evaluator::new(&request, &entities).evaluate(policy.conditions()[0].body())
+}
+
+fn display(expression: &str) -> String {
+    match evaluate(expression) {
+        Ok(value) => value.to_string(),
+        Err(error) => format!("error: {error}"),
+    }
+}
+
+// -- logic --
+
+#[test]
+fn short_circuit() {
+    assert_snapshot!(display("false && (1 + true)"), @"false");
+    assert_snapshot!(display("true || (1 + true)"), @"true");
+    assert_snapshot!(display("true && 1"), @"error: type error: expected Bool, found Long");
+}
+
+#[test]
+fn if_then_else() {
+    assert_snapshot!(display(r#"if 1 < 2 then "yes" else 1 + true"#), @r#""yes""#);
+    assert_snapshot!(display(r#"if "no" then 1 else 2"#), @"error: type error: expected Bool, found String");
+}
+
+// -- arithmetic --
+
+#[test]
+fn arithmetic() {
+    assert_snapshot!(display("1 + 2 * 3 - -4"), @"11");
+    assert_snapshot!(display("9223372036854775807 + 1"), @"error: integer overflow while evaluating `+`");
+    assert_snapshot!(display("-9223372036854775807 - 2"), @"error: integer overflow while evaluating `-`");
+    assert_snapshot!(display("4611686018427387904 * 2"), @"error: integer overflow while evaluating `*`");
+    assert_snapshot!(display(r#""a" + 1"#), @"error: type error: expected Long, found String");
+}
+
+#[test]
+fn comparison() {
+    assert_snapshot!(display("1 <= 1 && 2 > 1 && !(1 >= 2)"), @"true");
+    assert_snapshot!(display(r#"datetime("2024-01-01") < datetime("2024-01-02")"#), @"true");
+    assert_snapshot!(display(r#"duration("1h") > duration("59m")"#), @"true");
+    assert_snapshot!(display(r#"1 < "2""#), @"error: type error: expected Long, found String");
+    assert_snapshot!(display(r#""a" < "b""#), @"error: type error: expected Long or datetime or duration, found String");
+}
+
+#[test]
+fn equality() {
+    assert_snapshot!(display("[1, 2] == [2, 1] && {a: 1, b: 2} == {b: 2, a: 1}"), @"true");
+    assert_snapshot!(display(r#"1 == "1""#), @"false");
+    assert_snapshot!(display(r#"decimal("1.0") == decimal("1.0000")"#), @"true");
+    assert_snapshot!(display("principal != resource"), @"true");
+}
+
+// -- attributes --
+
+#[test]
+fn attributes() {
+    assert_snapshot!(display("principal.age"), @"30");
+    assert_snapshot!(display("resource.owner.address.city"), @r#""Dublin""#);
+    assert_snapshot!(display("context has foo || principal has address"), @"true");
+    assert_snapshot!(display(r#"User::"bob" has name"#), @"false");
+}
+
+#[test]
+fn missing_attributes() {
+    assert_snapshot!(display("principal.email"), @r#"error: `User::"alice"` does not have the attribute `email`"#);
+    assert_snapshot!(display("context.foo"), @"error: record does not have the attribute `foo`");
+    assert_snapshot!(display(r#"User::"bob".name"#), @r#"error: entity `User::"bob"` does not exist"#);
+    assert_snapshot!(display("1.foo"), @"error: type error: expected Entity or Record, found Long");
+}
+
+#[test]
+fn tags() {
+    assert_snapshot!(display(r#"principal.hasTag("team") && principal.getTag("team") == "core""#), @"true");
+    assert_snapshot!(display(r#"principal.getTag("role")"#), @r#"error: `User::"alice"` does not have the tag `role`"#);
+    assert_snapshot!(display(r#"User::"bob".hasTag("team")"#), @"false");
+}
+
+// -- hierarchy --
+
+#[test]
+fn hierarchy() {
+    assert_snapshot!(display(r#"principal in Group::"staff""#), @"true");
+    assert_snapshot!(display(r#"principal in [Group::"other", Group::"admins"]"#), @"true");
+    assert_snapshot!(display(r#"Group::"staff" in principal"#), @"false");
+    assert_snapshot!(display(r#"User::"bob" in User::"bob""#), @"true");
+    assert_snapshot!(display(r#"principal in [Group::"admins", 1]"#), @"error: type error: expected Entity, found Long");
+    assert_snapshot!(display(r#"principal in "admins""#), @"error: type error: expected Entity or Set, found String");
+}
+
+#[test]
+fn is() {
+    assert_snapshot!(display("principal is User && !(resource is User)"), @"true");
+    assert_snapshot!(display(r#"principal is User in Group::"staff""#), @"true");
+    assert_snapshot!(display("resource is User in 1"), @"false");
+}
+
+// -- sets --
+
+#[test]
+fn sets() {
+    assert_snapshot!(display("[1, 2, 3].contains(2)"), @"true");
+    assert_snapshot!(display("[1, 2, 3].containsAll([1, 3])"), @"true");
+    assert_snapshot!(display("[1, 2, 3].containsAny([4, 5])"), @"false");
+    assert_snapshot!(display("[].isEmpty() && ![1].isEmpty()"), @"true");
+    assert_snapshot!(display("1.contains(1)"), @"error: type error: expected Set, found Long");
+}
+
+// -- like --
+
+#[test]
+fn like() {
+    assert_snapshot!(display(r#""cat.jpg" like "*.jpg""#), @"true");
+    assert_snapshot!(display(r#""cat.jpg" like "c*t*""#), @"true");
+    assert_snapshot!(display(r#""cat.jpg" like "*.png""#), @"false");
+    assert_snapshot!(display(r#""a*b" like "a\*b""#), @"true");
+    assert_snapshot!(display(r#""axb" like "a\*b""#), @"false");
+    assert_snapshot!(display(r#""" like "*""#), @"true");
+    assert_snapshot!(display(r#""aXbXb" like "a*b""#), @"true");
+    assert_snapshot!(display(r#"1 like "*""#), @"error: type error: expected String, found Long");
+}
+
+// -- extensions --
+
+#[test]
+fn decimal() {
+    assert_snapshot!(display(r#"decimal("1.5").lessThan(decimal("2.25"))"#), @"true");
+    assert_snapshot!(display(r#"decimal("1.5").greaterThanOrEqual(decimal("1.5"))"#), @"true");
+    assert_snapshot!(display(r#"decimal("1.23456")"#), @"error: `1.23456` is not a valid decimal");
+    assert_snapshot!(display(r#"decimal("1.5").lessThan(1)"#), @"error: type error: expected decimal, found Long");
+}
+
+#[test]
+fn ip() {
+    assert_snapshot!(display(r#"ip("10.0.0.1").isInRange(ip("10.0.0.0/24"))"#), @"true");
+    assert_snapshot!(display(r#"ip("::1").isLoopback() && ip("::1").isIpv6()"#), @"true");
+    assert_snapshot!(display(r#"ip("224.0.0.1").isMulticast() && ip("1.2.3.4").isIpv4()"#), @"true");
+}
+
+#[test]
+fn datetime() {
+    assert_snapshot!(display(r#"datetime("2024-10-15T11:35:00Z").offset(duration("1d"))"#), @r#"datetime("2024-10-16T11:35:00.000Z")"#);
+    assert_snapshot!(display(r#"datetime("2024-10-15").durationSince(datetime("2024-10-14"))"#), @r#"duration("1d")"#);
+    assert_snapshot!(display(r#"datetime("2024-10-15T11:35:00Z").toDate()"#), @r#"datetime("2024-10-15T00:00:00.000Z")"#);
+    assert_snapshot!(display(r#"datetime("2024-10-15T11:35:00Z").toTime()"#), @r#"duration("11h35m")"#);
+    assert_snapshot!(display(r#"duration("1d2h").toHours()"#), @"26");
+    assert_snapshot!(display(r#"duration("90s").toMinutes() + duration("1500ms").toSeconds()"#), @"2");
+}
+
+#[test]
+fn extension_errors() {
+    assert_snapshot!(display("ip(1)"), @"error: type error: expected String, found Long");
+    assert_snapshot!(display(r#"decimal("1.0", "2.0")"#), @"error: `decimal` expects 1 argument(s), found 2");
+}
+
+// -- slots --
+
+#[test]
+fn unlinked_slot() {
+    let error = EvaluationError::UnlinkedSlot {
+        slot: SlotKind::Principal,
+    };
+
+    assert_snapshot!(error, @"template slot `?principal` is not linked");
+}
@@ -0,0 +1,232 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem;
+
+use duramen_syntax::{Group, Node, Syntax, Token};
+
+use crate::doc::Doc;
+use crate::trivia::{Comment, Comments};
+
+/// Emits tokens together with the comments attached to them.
+pub struct FormatContext<'a> {
+    comments: Comments<'a>,
+    cursor: usize,
+}
+
+impl<'a> FormatContext<'a> {
+    pub fn new(root: Node<'a>) -> Self {
+        Self {
+            comments: Comments::collect(root),
+            cursor: 0,
+        }
+    }
+
+    /// Returns the children of a node, skipping whitespace, newlines and comments.
+    pub fn children(node: Node<'a>) -> Vec<Node<'a>> {
+        node.children()
+            .filter(|child| {
+                let kind = child.kind();
+                !kind.is_whitespace() && !kind.is_newline() && !kind.is_comment()
+            })
+            .collect()
+    }
+
+    /// Returns whether a node is the given token.
+    pub fn is(node: Node<'_>, token: Token) -> bool {
+        node.kind() == Syntax::Token(token)
+    }
+
+    /// Returns whether a blank line precedes the next token or its leading comments.
+    pub fn blank(&self) -> bool {
+        self.comments.tokens.get(self.cursor).is_some_and(|trivia| {
+            trivia
+                .leading
+                .first()
+                .map_or(trivia.blank, |comment| comment.blank)
+        })
+    }
+
+    /// Takes the comments on the lines before the next token, leaving the line after them open.
+    pub fn leading(&mut self) -> Vec<Doc<'a>> {
+        let Some(trivia) = self.comments.tokens.get_mut(self.cursor) else {
+            return Vec::new();
+        };
+
+        let comments = mem::take(&mut trivia.leading);
+        let mut docs = Self::comments(&comments);
+        docs.pop();
+        docs
+    }
+
+    /// Emits the next token, which must be visited in source order.
+    pub fn token(&mut self, node: Node<'a>) -> Doc<'a> {
+        match self.split_token(node) {
+            (doc, Some(trailing)) => Doc::Concat(vec![doc, trailing]),
+            (doc, None) => doc,
+        }
+    }
+
+    /// Emits the next token like [`Self::token`], returning its trailing comment separately.
+    pub fn split_token(&mut self, node: Node<'a>) -> (Doc<'a>, Option<Doc<'a>>) {
+        let Some(trivia) = self.comments.tokens.get(self.cursor) else {
+            return (Doc::Text(node.text()), None);
+        };
+
+        self.cursor += 1;
+
+        let trailing = trivia.trailing.map(Doc::Suffix);
+        if trivia.leading.is_empty() {
+            return (Doc::Text(node.text()), trailing);
+        }
+
+        let mut docs = Self::comments(&trivia.leading);
+        if trivia.blank {
+            docs.push(Doc::Blank);
+        }
+
+        docs.push(Doc::Text(node.text()));
+        (Doc::Concat(docs), trailing)
+    }
+
+    /// Emits comments after the last token, ending the output with a newline.
+    pub fn finish(&self, mut docs: Vec<Doc<'a>>) -> Doc<'a> {
+        debug_assert_eq!(
+            self.cursor,
+            self.comments.tokens.len(),
+            "every token should be emitted"
+        );
+
+        docs.extend(Self::comments(&self.comments.dangling));
+        if !docs.is_empty() {
+            docs.push(Doc::Fresh);
+        }
+
+        Doc::Concat(docs)
+    }
+
+    /// Lays out comments on their own lines.
+    fn comments(comments: &[Comment<'a>]) -> Vec<Doc<'a>> {
+        let mut docs = Vec::with_capacity(comments.len() * 3);
+        for comment in comments {
+            docs.extend(vec![
+                if comment.blank {
+                    Doc::Blank
+                } else {
+                    Doc::Fresh
+                },
+                Doc::Text(comment.text),
+                Doc::HardLine,
+            ]);
+        }
+
+        docs
+    }
+}
+
+/// Shared layout rules, implemented by each formatter.
+pub trait Layout<'a> {
+    fn ctx(&mut self) -> &mut FormatContext<'a>;
+
+    /// Formats any node.
+    fn node(&mut self, node: Node<'a>) -> Doc<'a>;
+
+    /// Concatenates children without spacing.
+    fn tight(&mut self, node: Node<'a>) -> Doc<'a> {
+        let docs = FormatContext::children(node)
+            .into_iter()
+            .map(|child| self.node(child))
+            .collect();
+
+        Doc::Concat(docs)
+    }
+
+    /// Separates children by single spaces, except before `:`, `,` and `;`.
+    fn spaced(&mut self, children: &[Node<'a>]) -> Doc<'a> {
+        let mut docs = Vec::new();
+
+        for &child in children {
+            let tight = FormatContext::is(child, Token::Colon)
+                || FormatContext::is(child, Token::Comma)
+                || FormatContext::is(child, Token::Semicolon);
+            if !docs.is_empty() && !tight {
+                docs.push(Doc::Text(" "));
+            }
+
+            docs.push(self.node(child));
+        }
+
+        Doc::Concat(docs)
+    }
+
+    /// Lays out a node ending in a bracketed list, such as a call or a record.
+    ///
+    /// Children before the opening bracket are concatenated, and argument lists are spliced into the list.
+    fn bracketed(&mut self, node: Node<'a>, line: &Doc<'a>) -> Doc<'a> {
+        let mut prefix = Vec::new();
+        let mut items = Vec::new();
+
+        for child in FormatContext::children(node) {
+            let open = FormatContext::is(child, Token::OpenParenthesis)
+                || FormatContext::is(child, Token::OpenBracket)
+                || FormatContext::is(child, Token::OpenBrace);
+
+            if items.is_empty() && !open {
+                prefix.push(self.node(child));
+            } else if child.kind() == Syntax::Group(Group::Arguments) {
+                items.extend(FormatContext::children(child));
+            } else {
+                items.push(child);
+            }
+        }
+
+        prefix.push(self.delimited(&items, line));
+        Doc::Concat(prefix)
+    }
+
+    /// Lays out a bracketed, comma separated list of items.
+    ///
+    /// Items are placed on their own indented lines if the list does not fit,
+    /// with `line` separating the items from the brackets.
+    fn delimited(&mut self, children: &[Node<'a>], line: &Doc<'a>) -> Doc<'a> {
+        let [open, inner @ .., close] = children else {
+            let docs = children.iter().map(|child| self.node(*child)).collect();
+            return Doc::Concat(docs);
+        };
+
+        let open = self.node(*open);
+        if inner.is_empty() {
+            let close = self.node(*close);
+            return Doc::Concat(vec![open, close]);
+        }
+
+        let mut docs = vec![line.clone()];
+        for (index, child) in inner.iter().enumerate() {
+            if index > 0 && self.ctx().blank() {
+                docs.push(Doc::Blank);
+            }
+
+            docs.push(self.node(*child));
+
+            if FormatContext::is(*child, Token::Comma) && index + 1 < inner.len() {
+                docs.push(Doc::Line);
+            }
+        }
+
+        // Comments before the closing bracket belong with the items.
+        docs.extend(self.ctx().leading());
+
+        // A comment after the closing bracket is written at the end of the line,
+        // so it must not force the list to break.
+        let (close, trailing) = if close.kind().is_token() {
+            self.ctx().split_token(*close)
+        } else {
+            (self.node(*close), None)
+        };
+
+        let group = Doc::group(vec![open, Doc::Indent(docs), line.clone(), close]);
+        match trailing {
+            Some(trailing) => Doc::Concat(vec![group, trailing]),
+            None => group,
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Layout document, rendered by [`Doc::render`].
+#[derive(Clone, Debug)]
+pub enum Doc<'a> {
+    /// Verbatim text, never containing a newline.
+    Text(&'a str),
+    /// A space when flat, a newline when broken.
+    Line,
+    /// Nothing when flat, a newline when broken.
+    SoftLine,
+    /// Always a newline.
+    HardLine,
+    /// A newline, unless already at the start of a line.
+    Fresh,
+    /// A blank line, unless one was just written.
+    Blank,
+    /// A number of spaces, used for alignment.
+    Pad(usize),
+    /// Text deferred to the end of the current line, such as a trailing comment.
+    Suffix(&'a str),
+    /// Documents laid out in sequence.
+    Concat(Vec<Self>),
+    /// Documents indented one level when broken onto new lines.
+    Indent(Vec<Self>),
+    /// Documents laid out flat if they fit, or broken otherwise.
+    Group { docs: Vec<Self>, broken: bool },
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Mode {
+    Flat,
+    Break,
+}
+
+impl Doc<'_> {
+    /// Creates a group, which is always broken if it contains a forced newline.
+    pub fn group(docs: Vec<Self>) -> Self {
+        let broken = docs.iter().any(Self::forces_break);
+        Self::Group { docs, broken }
+    }
+
+    fn forces_break(&self) -> bool {
+        match self {
+            Self::HardLine | Self::Fresh | Self::Blank | Self::Suffix(_) => true,
+            Self::Text(_) | Self::Pad(_) | Self::Line | Self::SoftLine => false,
+            Self::Concat(docs) | Self::Indent(docs) => docs.iter().any(Self::forces_break),
+            Self::Group { broken, .. } => *broken,
+        }
+    }
+
+    /// Renders the document within `width` columns, indenting by `indent` spaces per level.
+    pub fn render(&self, width: usize, indent: usize) -> String {
+        let mut renderer = Renderer {
+            output: String::new(),
+            width,
+            column: 0,
+            line_start: true,
+            blank: true,
+            suffixes: Vec::new(),
+        };
+
+        let mut stack = alloc::vec![(0, Mode::Break, self)];
+        while let Some((level, mode, doc)) = stack.pop() {
+            match doc {
+                Self::Text(text) => renderer.text(text),
+                Self::Pad(width) => {
+                    for _ in 0..*width {
+                        renderer.text(" ");
+                    }
+                }
+                Self::Line => match mode {
+                    Mode::Flat => renderer.text(" "),
+                    Mode::Break => renderer.newline(level),
+                },
+                Self::SoftLine => {
+                    if mode == Mode::Break {
+                        renderer.newline(level);
+                    }
+                }
+                Self::HardLine => renderer.newline(level),
+                Self::Fresh => {
+                    if !renderer.line_start {
+                        renderer.newline(level);
+                    }
+                }
+                Self::Blank => {
+                    if !renderer.line_start {
+                        renderer.newline(level);
+                    }
+
+                    if !renderer.blank {
+                        renderer.newline(level);
+                    }
+                }
+                Self::Suffix(text) => renderer.suffixes.push(text),
+                Self::Concat(docs) => {
+                    stack.extend(docs.iter().rev().map(|doc| (level, mode, doc)));
+                }
+                Self::Indent(docs) => {
+                    stack.extend(docs.iter().rev().map(|doc| (level + indent, mode, doc)));
+                }
+                Self::Group { docs, broken } => {
+                    let mode = if !broken && (mode == Mode::Flat || renderer.fits(docs, &stack)) {
+                        Mode::Flat
+                    } else {
+                        Mode::Break
+                    };
+
+                    stack.extend(docs.iter().rev().map(|doc| (level, mode, doc)));
+                }
+            }
+        }
+
+        renderer.flush(0);
+        renderer.output
+    }
+}
+
+struct Renderer<'a> {
+    output: String,
+    width: usize,
+    column: usize,
+    line_start: bool,
+    blank: bool,
+    suffixes: Vec<&'a str>,
+}
+
+impl Renderer<'_> {
+    fn text(&mut self, text: &str) {
+        if self.line_start && text == " " {
+            return;
+        }
+
+        self.output.push_str(text);
+        self.column += text.chars().count();
+        self.line_start = false;
+    }
+
+    fn newline(&mut self, level: usize) {
+        self.blank = self.line_start && self.suffixes.is_empty();
+        self.flush(level);
+
+        self.output.push('\n');
+        for _ in 0..level {
+            self.output.push(' ');
+        }
+
+        self.column = level;
+        self.line_start = true;
+    }
+
+    /// Writes pending suffixes and trims trailing whitespace from the current line.
+    ///
+    /// Only the first suffix shares the line, later ones get their own lines at `level`.
+    fn flush(&mut self, level: usize) {
+        for (index, suffix) in self.suffixes.drain(..).enumerate() {
+            if index > 0 {
+                self.output.push('\n');
+                for _ in 0..level {
+                    self.output.push(' ');
+                }
+            } else if !self.line_start {
+                self.output.push(' ');
+            }
+
+            self.output.push_str(suffix);
+            self.line_start = false;
+        }
+
+        let trimmed = self.output.trim_end_matches(' ').len();
+        self.output.truncate(trimmed);
+    }
+
+    /// Returns whether `docs` fit flat on the rest of the current line.
+    fn fits(&self, docs: &[Doc<'_>], rest: &[(usize, Mode, &Doc<'_>)]) -> bool {
+        let mut remaining = self.width.saturating_sub(self.column);
+        let mut stack = docs
+            .iter()
+            .rev()
+            .map(|doc| (Mode::Flat, doc))
+            .collect::<Vec<_>>();
+        let mut rest = rest.iter().rev();
+
+        loop {
+            let Some((mode, doc)) = stack.pop() else {
+                match rest.next() {
+                    Some(&(_, mode, doc)) => {
+                        stack.push((mode, doc));
+                        continue;
+                    }
+                    None => return true,
+                }
+            };
+
+            match doc {
+                Doc::Text(text) => {
+                    let width = text.chars().count();
+                    if width > remaining {
+                        return false;
+                    }
+
+                    remaining -= width;
+                }
+                Doc::Pad(width) => {
+                    if *width > remaining {
+                        return false;
+                    }
+
+                    remaining -= width;
+                }
+                Doc::Line => {
+                    if mode == Mode::Break {
+                        return true;
+                    }
+
+                    if remaining == 0 {
+                        return false;
+                    }
+
+                    remaining -= 1;
+                }
+                Doc::SoftLine => {
+                    if mode == Mode::Break {
+                        return true;
+                    }
+                }
+                Doc::HardLine | Doc::Fresh | Doc::Blank => return true,
+                Doc::Suffix(_) => {}
+                Doc::Concat(docs) | Doc::Indent(docs) => {
+                    stack.extend(docs.iter().rev().map(|doc| (mode, doc)));
+                }
+                Doc::Group { docs, broken } => {
+                    let mode = if *broken { Mode::Break } else { mode };
+                    stack.extend(docs.iter().rev().map(|doc| (mode, doc)));
+                }
+            }
+        }
+    }
+}
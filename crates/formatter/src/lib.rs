@@ -0,0 +1,20 @@
+#![cfg_attr(doc, doc = include_str!("../README.md"))]
+#![no_std]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+mod context;
+mod doc;
+
+mod options;
+pub use options::FormatOptions;
+
+mod policy;
+pub use policy::PolicyFormatter;
+
+mod schema;
+pub use schema::SchemaFormatter;
+
+mod trivia;
@@ -0,0 +1,49 @@
+/// Layout options for formatters.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FormatOptions {
+    width: usize,
+    indent: usize,
+}
+
+impl FormatOptions {
+    /// Creates options with a line width of 80 and an indent of 2 spaces.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            width: 80,
+            indent: 2,
+        }
+    }
+
+    /// Sets the line width that groups are broken at.
+    #[must_use]
+    pub const fn with_width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Sets the number of spaces per indentation level.
+    #[must_use]
+    pub const fn with_indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Returns the line width.
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of spaces per indentation level.
+    #[must_use]
+    pub const fn indent(&self) -> usize {
+        self.indent
+    }
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,186 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use duramen_diagnostic::Diagnostics;
+use duramen_parser::PolicyParser;
+use duramen_syntax::{Group, Node, Syntax, Token};
+
+use crate::context::{FormatContext, Layout};
+use crate::doc::Doc;
+use crate::options::FormatOptions;
+
+/// Comment-preserving formatter for Cedar policy files.
+///
+/// ```cedar
+/// @id("view")
+/// permit (
+///   principal == User::"alice",
+///   action == Action::"view",
+///   resource in Album::"trip"
+/// )
+/// when { context.authenticated && resource.public };
+/// ```
+pub struct PolicyFormatter<'a> {
+    ctx: FormatContext<'a>,
+}
+
+impl<'a> PolicyFormatter<'a> {
+    /// Formats a policy file.
+    ///
+    /// # Errors
+    ///
+    /// Returns the parser diagnostics if the source has syntax errors.
+    pub fn format(source: &str, options: &FormatOptions) -> Result<String, Diagnostics> {
+        let (tree, diagnostics) = PolicyParser::parse(source);
+        if !diagnostics.is_empty() {
+            return Err(diagnostics);
+        }
+
+        let Some(root) = tree.root() else {
+            return Ok(String::new());
+        };
+
+        let mut this = PolicyFormatter {
+            ctx: FormatContext::new(root),
+        };
+
+        let doc = this.policies(root);
+        Ok(doc.render(options.width(), options.indent()))
+    }
+
+    /// Separates policies by a blank line.
+    fn policies(&mut self, root: Node<'a>) -> Doc<'a> {
+        let mut docs = Vec::new();
+
+        for child in FormatContext::children(root) {
+            if !docs.is_empty() {
+                docs.push(Doc::Blank);
+            }
+
+            docs.push(self.node(child));
+        }
+
+        self.ctx.finish(docs)
+    }
+
+    /// Places annotations and conditions on their own lines.
+    fn policy(&mut self, node: Node<'a>) -> Doc<'a> {
+        let mut docs = Vec::new();
+
+        for child in FormatContext::children(node) {
+            match child.kind() {
+                Syntax::Group(Group::Annotation) => {
+                    docs.push(self.node(child));
+                    docs.push(Doc::HardLine);
+                }
+                Syntax::Group(Group::Scope) => {
+                    docs.push(Doc::Text(" "));
+                    docs.push(self.node(child));
+                }
+                Syntax::Group(Group::Condition) => {
+                    docs.push(Doc::HardLine);
+                    docs.push(self.node(child));
+                }
+                _ => docs.push(self.node(child)),
+            }
+        }
+
+        Doc::Concat(docs)
+    }
+
+    /// Formats `when { ... }`, indenting the body when it does not fit.
+    fn condition(&mut self, node: Node<'a>) -> Doc<'a> {
+        let children = FormatContext::children(node);
+        let Some((keyword, body)) = children.split_first() else {
+            return Doc::Concat(Vec::new());
+        };
+
+        let keyword = self.node(*keyword);
+        let body = self.delimited(body, &Doc::Line);
+
+        Doc::Concat(vec![keyword, Doc::Text(" "), body])
+    }
+
+    /// Flattens a chain of `&&` or `||` operands, breaking after each operator.
+    fn chain(&mut self, node: Node<'a>, group: Group, docs: &mut Vec<Doc<'a>>) {
+        for child in FormatContext::children(node) {
+            if child.kind() == Syntax::Group(group) {
+                self.chain(child, group, docs);
+            } else if FormatContext::is(child, Token::And) || FormatContext::is(child, Token::Or) {
+                docs.push(Doc::Text(" "));
+                docs.push(self.node(child));
+                docs.push(Doc::Line);
+            } else {
+                docs.push(self.node(child));
+            }
+        }
+    }
+
+    /// Formats `if a then b else c`, breaking before `then` and `else`.
+    fn if_expression(&mut self, node: Node<'a>) -> Doc<'a> {
+        let mut head = Vec::new();
+        let mut tail = Vec::new();
+
+        for child in FormatContext::children(node) {
+            let branch = FormatContext::is(child, Token::ThenKeyword)
+                || FormatContext::is(child, Token::ElseKeyword);
+
+            if branch {
+                tail.push(Doc::Line);
+            } else if !head.is_empty() {
+                if tail.is_empty() {
+                    head.push(Doc::Text(" "));
+                } else {
+                    tail.push(Doc::Text(" "));
+                }
+            }
+
+            let doc = self.node(child);
+            if tail.is_empty() {
+                head.push(doc);
+            } else {
+                tail.push(doc);
+            }
+        }
+
+        Doc::group(vec![Doc::Concat(head), Doc::Indent(tail)])
+    }
+}
+
+impl<'a> Layout<'a> for PolicyFormatter<'a> {
+    fn ctx(&mut self) -> &mut FormatContext<'a> {
+        &mut self.ctx
+    }
+
+    fn node(&mut self, node: Node<'a>) -> Doc<'a> {
+        let Syntax::Group(group) = node.kind() else {
+            return self.ctx.token(node);
+        };
+
+        match group {
+            Group::Policy => self.policy(node),
+            Group::Condition => self.condition(node),
+            Group::Scope | Group::List | Group::Call | Group::Parenthesized => {
+                self.bracketed(node, &Doc::SoftLine)
+            }
+            Group::Record => self.bracketed(node, &Doc::Line),
+            Group::OrExpression | Group::AndExpression => {
+                let mut docs = Vec::new();
+                self.chain(node, group, &mut docs);
+                Doc::group(docs)
+            }
+            Group::IfExpression => self.if_expression(node),
+            Group::Annotation
+            | Group::Name
+            | Group::EntityReference
+            | Group::Slot
+            | Group::Literal
+            | Group::UnaryExpression
+            | Group::MemberExpression
+            | Group::Field
+            | Group::Index => self.tight(node),
+            _ => self.spaced(&FormatContext::children(node)),
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem;
+
+use duramen_diagnostic::Diagnostics;
+use duramen_parser::SchemaParser;
+use duramen_syntax::{Group, Node, Syntax, Token};
+
+use crate::context::{FormatContext, Layout};
+use crate::doc::Doc;
+use crate::options::FormatOptions;
+
+/// Comment-preserving formatter for Cedar schema files.
+///
+/// ```cedarschema
+/// namespace Acme {
+///   entity User in [Group] {
+///     name:    String,
+///     manager: User
+///   };
+///
+///   action view appliesTo {
+///     principal: [User],
+///     resource: Document
+///   };
+/// }
+/// ```
+pub struct SchemaFormatter<'a> {
+    ctx: FormatContext<'a>,
+    /// Width of the longest attribute name in the enclosing record.
+    align: usize,
+}
+
+impl<'a> SchemaFormatter<'a> {
+    /// Formats a schema file.
+    ///
+    /// # Errors
+    ///
+    /// Returns the parser diagnostics if the source has syntax errors.
+    pub fn format(source: &str, options: &FormatOptions) -> Result<String, Diagnostics> {
+        let (tree, diagnostics) = SchemaParser::parse(source);
+        if !diagnostics.is_empty() {
+            return Err(diagnostics);
+        }
+
+        let Some(root) = tree.root() else {
+            return Ok(String::new());
+        };
+
+        let mut this = SchemaFormatter {
+            ctx: FormatContext::new(root),
+            align: 0,
+        };
+
+        let docs = this.declarations(&FormatContext::children(root));
+        let doc = this.ctx.finish(docs);
+        Ok(doc.render(options.width(), options.indent()))
+    }
+
+    /// Places declarations on their own lines, keeping blank lines between them.
+    fn declarations(&mut self, children: &[Node<'a>]) -> Vec<Doc<'a>> {
+        let mut docs = Vec::new();
+
+        for &child in children {
+            if !docs.is_empty() {
+                docs.push(if self.ctx.blank() {
+                    Doc::Blank
+                } else {
+                    Doc::HardLine
+                });
+            }
+
+            docs.push(self.node(child));
+        }
+
+        docs
+    }
+
+    /// Places annotations on their own lines, followed by the spaced remaining children.
+    fn declaration(&mut self, children: &[Node<'a>]) -> Vec<Doc<'a>> {
+        let count = children
+            .iter()
+            .take_while(|child| child.kind() == Syntax::Group(Group::Annotation))
+            .count();
+
+        let (annotations, rest) = children.split_at(count);
+
+        let mut docs = Vec::new();
+        for &annotation in annotations {
+            docs.push(self.node(annotation));
+            docs.push(Doc::HardLine);
+        }
+
+        docs.push(self.spaced(rest));
+        docs
+    }
+
+    /// Formats `namespace Name { ... }`, indenting its declarations.
+    fn namespace(&mut self, node: Node<'a>) -> Doc<'a> {
+        let children = FormatContext::children(node);
+        let Some(open) = children
+            .iter()
+            .position(|child| FormatContext::is(*child, Token::OpenBrace))
+        else {
+            return Doc::Concat(self.declaration(&children));
+        };
+
+        let (head, rest) = children.split_at(open);
+        let (close, body) = match rest[1..].split_last() {
+            Some((close, body)) if FormatContext::is(*close, Token::CloseBrace) => {
+                (Some(*close), body)
+            }
+            _ => (None, &rest[1..]),
+        };
+
+        let mut docs = self.declaration(head);
+        docs.push(Doc::Text(" "));
+        docs.push(self.node(rest[0]));
+
+        let mut inner = self.declarations(body);
+        inner.extend(self.ctx.leading());
+        if !inner.is_empty() {
+            inner.insert(0, Doc::HardLine);
+            docs.push(Doc::Indent(inner));
+            docs.push(Doc::HardLine);
+        }
+
+        if let Some(close) = close {
+            docs.push(self.node(close));
+        }
+
+        Doc::Concat(docs)
+    }
+
+    /// Formats a node ending in braced attributes, such as a record type.
+    ///
+    /// Attributes are placed on their own lines, with their types aligned.
+    fn record(&mut self, node: Node<'a>) -> Doc<'a> {
+        let children = FormatContext::children(node);
+        let open = children
+            .iter()
+            .position(|child| FormatContext::is(*child, Token::OpenBrace))
+            .unwrap_or(children.len());
+
+        let (head, body) = children.split_at(open);
+
+        let align = body
+            .iter()
+            .filter(|child| child.kind() == Syntax::Group(Group::AttributeDeclaration))
+            .map(|child| Self::key_width(*child))
+            .max()
+            .unwrap_or(0);
+
+        let mut docs = Vec::new();
+        if !head.is_empty() {
+            docs.push(self.spaced(head));
+            docs.push(Doc::Text(" "));
+        }
+
+        let align = mem::replace(&mut self.align, align);
+        docs.push(self.delimited(body, &Doc::HardLine));
+        self.align = align;
+
+        Doc::Concat(docs)
+    }
+
+    /// Returns the width of an attribute name, including the `?` of optional attributes.
+    fn key_width(node: Node<'_>) -> usize {
+        FormatContext::children(node)
+            .into_iter()
+            .filter(|child| child.kind() != Syntax::Group(Group::Annotation))
+            .take_while(|child| !FormatContext::is(*child, Token::Colon))
+            .map(|child| child.text().chars().count())
+            .sum()
+    }
+
+    /// Formats `name: Type`, padding after the colon to align the type.
+    fn attribute(&mut self, node: Node<'a>) -> Doc<'a> {
+        let width = Self::key_width(node);
+        let mut docs = Vec::new();
+
+        for child in FormatContext::children(node) {
+            docs.push(self.node(child));
+
+            if child.kind() == Syntax::Group(Group::Annotation) {
+                docs.push(Doc::HardLine);
+            } else if FormatContext::is(child, Token::Colon) {
+                docs.push(Doc::Pad(self.align.saturating_sub(width) + 1));
+            }
+        }
+
+        Doc::Concat(docs)
+    }
+
+    /// Formats `appliesTo { ... }` with each entry on its own line.
+    fn applies_to(&mut self, node: Node<'a>) -> Doc<'a> {
+        let children = FormatContext::children(node);
+        let Some((keyword, body)) = children.split_first() else {
+            return Doc::Concat(Vec::new());
+        };
+
+        let keyword = self.node(*keyword);
+        let body = self.delimited(body, &Doc::HardLine);
+
+        Doc::Concat(vec![keyword, Doc::Text(" "), body])
+    }
+
+    /// Formats `in [a, b]` or `in a`.
+    fn parents(&mut self, node: Node<'a>) -> Doc<'a> {
+        let children = FormatContext::children(node);
+        let Some((keyword, rest)) = children.split_first() else {
+            return Doc::Concat(Vec::new());
+        };
+
+        let keyword = self.node(*keyword);
+        let rest = match rest.first() {
+            Some(open) if FormatContext::is(*open, Token::OpenBracket) => {
+                self.delimited(rest, &Doc::SoftLine)
+            }
+            _ => self.spaced(rest),
+        };
+
+        Doc::Concat(vec![keyword, Doc::Text(" "), rest])
+    }
+}
+
+impl<'a> Layout<'a> for SchemaFormatter<'a> {
+    fn ctx(&mut self) -> &mut FormatContext<'a> {
+        &mut self.ctx
+    }
+
+    fn node(&mut self, node: Node<'a>) -> Doc<'a> {
+        let Syntax::Group(group) = node.kind() else {
+            return self.ctx.token(node);
+        };
+
+        match group {
+            Group::NamespaceDeclaration => self.namespace(node),
+            Group::EntityDeclaration | Group::ActionDeclaration | Group::TypeDeclaration => {
+                Doc::Concat(self.declaration(&FormatContext::children(node)))
+            }
+            Group::EntityAttributes | Group::ActionAttributes | Group::RecordType => {
+                self.record(node)
+            }
+            Group::AttributeDeclaration => self.attribute(node),
+            Group::AppliesToClause => self.applies_to(node),
+            Group::ActionParents => self.parents(node),
+            Group::Types | Group::EnumType => {
+                self.delimited(&FormatContext::children(node), &Doc::SoftLine)
+            }
+            Group::Annotation
+            | Group::Name
+            | Group::EntityReference
+            | Group::SetType
+            | Group::EntityType => self.tight(node),
+            _ => self.spaced(&FormatContext::children(node)),
+        }
+    }
+}
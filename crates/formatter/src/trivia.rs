@@ -0,0 +1,88 @@
+use alloc::vec::Vec;
+use core::mem;
+
+use duramen_syntax::Node;
+
+/// A comment on its own line.
+#[derive(Clone, Copy, Debug)]
+pub struct Comment<'a> {
+    pub text: &'a str,
+    /// Whether a blank line precedes the comment.
+    pub blank: bool,
+}
+
+/// Comments attached to a significant token.
+#[derive(Clone, Debug, Default)]
+pub struct Trivia<'a> {
+    /// Comments on the lines before the token.
+    pub leading: Vec<Comment<'a>>,
+    /// Comment after the token on the same line.
+    pub trailing: Option<&'a str>,
+    /// Whether a blank line precedes the token.
+    pub blank: bool,
+}
+
+/// Comments of every significant token in source order, and those after the last token.
+#[derive(Clone, Debug)]
+pub struct Comments<'a> {
+    pub tokens: Vec<Trivia<'a>>,
+    pub dangling: Vec<Comment<'a>>,
+}
+
+impl<'a> Comments<'a> {
+    /// Attaches each comment to the token it belongs to.
+    ///
+    /// A comment sharing a line with the preceding token trails that token,
+    /// while any other comment leads the next token.
+    pub fn collect(root: Node<'a>) -> Self {
+        let mut tokens: Vec<Trivia<'a>> = Vec::new();
+        let mut leading = Vec::new();
+        let mut newlines = 0;
+
+        for node in root.descendants() {
+            let kind = node.kind();
+            if !kind.is_token() {
+                continue;
+            }
+
+            if kind.is_whitespace() {
+                continue;
+            }
+
+            if kind.is_newline() {
+                newlines += node.text().matches('\n').count();
+                continue;
+            }
+
+            if kind.is_comment() {
+                match tokens.last_mut() {
+                    Some(last)
+                        if newlines == 0 && leading.is_empty() && last.trailing.is_none() =>
+                    {
+                        last.trailing = Some(node.text());
+                    }
+                    _ => leading.push(Comment {
+                        text: node.text(),
+                        blank: newlines > 1,
+                    }),
+                }
+
+                newlines = 0;
+                continue;
+            }
+
+            tokens.push(Trivia {
+                leading: mem::take(&mut leading),
+                trailing: None,
+                blank: newlines > 1,
+            });
+
+            newlines = 0;
+        }
+
+        Self {
+            tokens,
+            dangling: leading,
+        }
+    }
+}
@@ -0,0 +1,158 @@
+#![expect(clippy::unwrap_used, reason = "Tests")]
+
+use alloc::string::String;
+
+use duramen_formatter::{FormatOptions, PolicyFormatter};
+use duramen_test::insta::assert_snapshot;
+
+extern crate alloc;
+
+/// Formats a policy file, asserting that formatting again changes nothing.
+fn format_with(source: &str, options: &FormatOptions) -> String {
+    let output = PolicyFormatter::format(source, options).unwrap();
+    let again = PolicyFormatter::format(&output, options).unwrap();
+    assert_eq!(again, output, "{source}");
+
+    output
+}
+
+fn format(source: &str) -> String {
+    format_with(source, &FormatOptions::new())
+}
+
+#[test]
+fn layout() {
+    let output = format(
+        r#"
+        @id("short")   permit(principal,action,resource)when{a.f(1,2)&&{a:1,"b":2}&&x[1]};
+        forbid ( principal == User::"alice", action in [Action::"read", Action::"write"], resource is Photo in Album::"vacation" )
+        unless { context.authenticated && context.ip.isInRange(ip("10.0.0.0/8")) && resource.owner == principal };
+        permit(principal in ?principal,action,resource)when{if a then b else c};
+        "#,
+    );
+
+    assert_snapshot!(output, @r#"
+    @id("short")
+    permit (principal, action, resource)
+    when { a.f(1, 2) && { a: 1, "b": 2 } && x[1] };
+
+    forbid (
+      principal == User::"alice",
+      action in [Action::"read", Action::"write"],
+      resource is Photo in Album::"vacation"
+    )
+    unless {
+      context.authenticated &&
+      context.ip.isInRange(ip("10.0.0.0/8")) &&
+      resource.owner == principal
+    };
+
+    permit (principal in ?principal, action, resource)
+    when { if a then b else c };
+    "#);
+}
+
+#[test]
+fn comments() {
+    let output = format(
+        r#"
+        // Leading comment.
+        @id("a") // trailing annotation
+        permit ( // open
+            principal == User::"a", // first
+            action,
+
+            // before resource
+            resource
+        )
+        when {
+            a && // after a
+            b
+            // before close
+        }; // end
+
+        // Dangling comment.
+        "#,
+    );
+
+    assert_snapshot!(output, @r#"
+    // Leading comment.
+    @id("a") // trailing annotation
+    permit ( // open
+      principal == User::"a", // first
+      action,
+
+      // before resource
+      resource
+    )
+    when {
+      a && // after a
+      b
+      // before close
+    }; // end
+
+    // Dangling comment.
+    "#);
+}
+
+#[test]
+fn stacked_comments() {
+    let output = format(
+        r#"
+        permit (principal == // eq
+            User::"a" // user
+            , action, resource);
+        "#,
+    );
+
+    assert_snapshot!(output, @r#"
+    permit (
+      principal == User::"a", // eq
+      // user
+      action,
+      resource
+    );
+    "#);
+}
+
+#[test]
+fn close_comments() {
+    assert_snapshot!(format("permit(principal,action,resource) // c\n;"), @"permit (principal, action, resource); // c");
+    assert_snapshot!(format("permit(principal,action,resource) when { true } // trailing\n;"), @r#"
+    permit (principal, action, resource)
+    when { true }; // trailing
+    "#);
+}
+
+#[test]
+fn options() {
+    let source = "permit (principal, action, resource) when { [1, 2, 3].contains(resource.size) && (if context.flag then true else false) };";
+
+    let output = format_with(source, &FormatOptions::new().with_width(40).with_indent(4));
+    assert_snapshot!(output, @r#"
+    permit (principal, action, resource)
+    when {
+        [1, 2, 3].contains(resource.size) &&
+        (
+            if context.flag
+                then true
+                else false
+        )
+    };
+    "#);
+}
+
+#[test]
+fn empty() {
+    assert_snapshot!(format(""), @"");
+    assert_snapshot!(format("  // only a comment\n"), @"// only a comment");
+}
+
+#[test]
+fn syntax_errors() {
+    let result = PolicyFormatter::format(
+        "permit (principal, action resource);",
+        &FormatOptions::new(),
+    );
+    assert!(result.is_err());
+}
@@ -0,0 +1,144 @@
+#![expect(clippy::unwrap_used, reason = "Tests")]
+
+use alloc::string::String;
+
+use duramen_formatter::{FormatOptions, SchemaFormatter};
+use duramen_test::insta::assert_snapshot;
+
+extern crate alloc;
+
+/// Formats a schema file, asserting that formatting again changes nothing.
+fn format(source: &str) -> String {
+    let options = FormatOptions::new();
+    let output = SchemaFormatter::format(source, &options).unwrap();
+    let again = SchemaFormatter::format(&output, &options).unwrap();
+    assert_eq!(again, output, "{source}");
+
+    output
+}
+
+#[test]
+fn layout() {
+    let output = format(
+        r#"
+        @doc("shared")   namespace Acme{
+        type Person={name:String,"e-mail"?:Email,aliases:Set<String>};
+        type Email=String;
+
+        entity User,Robot in[Group]{owner:Person,active:Bool}tags Set<Long>;
+        entity Group;
+        entity Color enum["red","blue"];
+        action read,"list all";
+        action view in[read,"list all"]appliesTo{principal:[User,Robot],resource:Group,context:{ip:ipaddr}};
+        action audit in read appliesTo { principal: User, resource: [VeryLongResourceTypeNameNumberOne, VeryLongResourceTypeNameNumberTwo] };
+        }
+        entity Top = { a: Long };
+        namespace Empty {}
+        "#,
+    );
+
+    assert_snapshot!(output, @r#"
+    @doc("shared")
+    namespace Acme {
+      type Person = {
+        name:      String,
+        "e-mail"?: Email,
+        aliases:   Set<String>
+      };
+      type Email = String;
+
+      entity User, Robot in [Group] {
+        owner:  Person,
+        active: Bool
+      } tags Set<Long>;
+      entity Group;
+      entity Color enum ["red", "blue"];
+      action read, "list all";
+      action view in [read, "list all"] appliesTo {
+        principal: [User, Robot],
+        resource: Group,
+        context: {
+          ip: ipaddr
+        }
+      };
+      action audit in read appliesTo {
+        principal: User,
+        resource: [
+          VeryLongResourceTypeNameNumberOne,
+          VeryLongResourceTypeNameNumberTwo
+        ]
+      };
+    }
+    entity Top = {
+      a: Long
+    };
+    namespace Empty {}
+    "#);
+}
+
+#[test]
+fn comments() {
+    let output = format(
+        r#"
+        // Header.
+        namespace Acme {
+          entity A in [ // parents
+            B, // b
+            C
+          ] {
+            // leading
+            x: Long, // trailing
+
+            @doc("y")
+            yy?: String
+            // before close
+          };
+
+          action a appliesTo {
+            // who
+            principal: [A], // principals
+            resource: [B],
+          };
+          // dangling
+        }
+        // end
+        "#,
+    );
+
+    assert_snapshot!(output, @r#"
+    // Header.
+    namespace Acme {
+      entity A in [ // parents
+        B, // b
+        C
+      ] {
+        // leading
+        x:   Long, // trailing
+
+        @doc("y")
+        yy?: String
+        // before close
+      };
+
+      action a appliesTo {
+        // who
+        principal: [A], // principals
+        resource: [B],
+      };
+      // dangling
+    }
+    // end
+    "#);
+}
+
+#[test]
+fn empty() {
+    assert_snapshot!(format(""), @"");
+    assert_snapshot!(format("// only a comment\n"), @"// only a comment");
+}
+
+#[test]
+fn syntax_errors() {
+    let result = SchemaFormatter::format("entity User {", &FormatOptions::new());
+    assert!(result.is_err());
+}
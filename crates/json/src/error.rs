@@ -0,0 +1,95 @@
+use alloc::format;
+use alloc::string::String;
+use core::error::Error;
+use core::fmt;
+use core::ops::Range;
+
+use duramen_diagnostic::Diagnostic;
+
+/// An error produced while parsing JSON text.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum JsonError {
+    /// A character that cannot start or continue the current construct.
+    UnexpectedCharacter {
+        span: Range<usize>,
+        expected: &'static str,
+    },
+    /// The input ended before the current construct was complete.
+    UnexpectedEnd {
+        span: Range<usize>,
+        expected: &'static str,
+    },
+    /// Input remains after the top-level value.
+    TrailingCharacters { span: Range<usize> },
+    /// A malformed number.
+    InvalidNumber { span: Range<usize> },
+    /// A malformed escape sequence in a string.
+    InvalidEscape { span: Range<usize> },
+    /// An unescaped control character in a string.
+    ControlCharacter { span: Range<usize> },
+    /// An object contains the same key twice.
+    DuplicateKey {
+        key: String,
+        span: Range<usize>,
+        first: Range<usize>,
+    },
+    /// Arrays and objects are nested too deeply.
+    NestingTooDeep { span: Range<usize> },
+}
+
+impl JsonError {
+    #[must_use]
+    pub const fn span(&self) -> &Range<usize> {
+        match self {
+            Self::UnexpectedCharacter { span, .. }
+            | Self::UnexpectedEnd { span, .. }
+            | Self::TrailingCharacters { span }
+            | Self::InvalidNumber { span }
+            | Self::InvalidEscape { span }
+            | Self::ControlCharacter { span }
+            | Self::DuplicateKey { span, .. }
+            | Self::NestingTooDeep { span } => span,
+        }
+    }
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedCharacter { expected, .. } => {
+                write!(f, "unexpected character, expected {expected}")
+            }
+            Self::UnexpectedEnd { expected, .. } => {
+                write!(f, "unexpected end of input, expected {expected}")
+            }
+            Self::TrailingCharacters { .. } => f.write_str("unexpected trailing characters"),
+            Self::InvalidNumber { .. } => f.write_str("invalid number"),
+            Self::InvalidEscape { .. } => f.write_str("invalid escape sequence"),
+            Self::ControlCharacter { .. } => f.write_str("control character in string"),
+            Self::DuplicateKey { key, .. } => write!(f, "duplicate key `{key}`"),
+            Self::NestingTooDeep { .. } => f.write_str("nesting too deep"),
+        }
+    }
+}
+
+impl Error for JsonError {}
+
+impl From<JsonError> for Diagnostic {
+    fn from(value: JsonError) -> Self {
+        let span = value.span().clone();
+
+        match &value {
+            JsonError::DuplicateKey { first, .. } => Self::error(format!("{value}"))
+                .with_label(span, "duplicate key")
+                .with_context(first.clone(), "first defined here"),
+            JsonError::UnexpectedCharacter { expected, .. }
+            | JsonError::UnexpectedEnd { expected, .. } => {
+                Self::error(format!("{value}")).with_label(span, format!("expected {expected}"))
+            }
+            JsonError::ControlCharacter { .. } => Self::error(format!("{value}"))
+                .with_label(span, "invalid character")
+                .with_note("control characters must be escaped, like `\\n`"),
+            _ => Self::error(format!("{value}")).with_label(span, "invalid JSON"),
+        }
+    }
+}
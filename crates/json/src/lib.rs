@@ -0,0 +1,18 @@
+#![cfg_attr(doc, doc = include_str!("../README.md"))]
+#![no_std]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+mod error;
+pub use error::JsonError;
+
+mod parser;
+pub use parser::JsonParser;
+
+mod value;
+pub use value::*;
+
+mod writer;
+pub use writer::JsonWriter;
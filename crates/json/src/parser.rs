@@ -0,0 +1,385 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::error::JsonError;
+use crate::value::{JsonKind, JsonMember, JsonObject, JsonValue};
+
+/// Maximum nesting depth of arrays and objects.
+const MAX_DEPTH: usize = 128;
+
+/// Parses JSON text into values with byte spans.
+pub struct JsonParser<'src> {
+    source: &'src str,
+    position: usize,
+    depth: usize,
+}
+
+impl<'src> JsonParser<'src> {
+    /// Parses a single JSON value, surrounded by optional whitespace.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text is not valid JSON, or an object contains duplicate keys.
+    pub fn parse(source: &'src str) -> Result<JsonValue<'src>, JsonError> {
+        let mut this = Self {
+            source,
+            position: 0,
+            depth: 0,
+        };
+
+        let value = this.value()?;
+
+        this.whitespace();
+        if this.position < source.len() {
+            return Err(JsonError::TrailingCharacters {
+                span: this.position..source.len(),
+            });
+        }
+
+        Ok(value)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.source.as_bytes().get(self.position).copied()
+    }
+
+    fn whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.position += 1;
+        }
+    }
+
+    /// Returns an error for the character at the current position.
+    fn unexpected(&self, expected: &'static str) -> JsonError {
+        match self.source[self.position..].chars().next() {
+            Some(character) => JsonError::UnexpectedCharacter {
+                span: self.position..self.position + character.len_utf8(),
+                expected,
+            },
+            None => JsonError::UnexpectedEnd {
+                span: self.position..self.position,
+                expected,
+            },
+        }
+    }
+
+    fn expect(&mut self, byte: u8, expected: &'static str) -> Result<(), JsonError> {
+        if self.peek() == Some(byte) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.unexpected(expected))
+        }
+    }
+
+    fn value(&mut self) -> Result<JsonValue<'src>, JsonError> {
+        self.whitespace();
+        let start = self.position;
+
+        let kind = match self.peek() {
+            Some(b'{') => self.object()?,
+            Some(b'[') => self.array()?,
+            Some(b'"') => JsonKind::String(self.string()?),
+            Some(b'-' | b'0'..=b'9') => self.number()?,
+            Some(b't') => self.keyword("true", JsonKind::Bool(true))?,
+            Some(b'f') => self.keyword("false", JsonKind::Bool(false))?,
+            Some(b'n') => self.keyword("null", JsonKind::Null)?,
+            _ => return Err(self.unexpected("a value")),
+        };
+
+        Ok(JsonValue::new(kind, start..self.position))
+    }
+
+    fn keyword(
+        &mut self,
+        keyword: &'static str,
+        kind: JsonKind<'src>,
+    ) -> Result<JsonKind<'src>, JsonError> {
+        if self.source[self.position..].starts_with(keyword) {
+            self.position += keyword.len();
+            Ok(kind)
+        } else {
+            Err(self.unexpected("a value"))
+        }
+    }
+
+    const fn enter(&mut self) -> Result<(), JsonError> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(JsonError::NestingTooDeep {
+                span: self.position..self.position + 1,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn array(&mut self) -> Result<JsonKind<'src>, JsonError> {
+        self.enter()?;
+        self.position += 1;
+
+        let mut values = Vec::new();
+
+        self.whitespace();
+        if self.peek() == Some(b']') {
+            self.position += 1;
+        } else {
+            loop {
+                values.push(self.value()?);
+
+                self.whitespace();
+                match self.peek() {
+                    Some(b',') => self.position += 1,
+                    Some(b']') => {
+                        self.position += 1;
+                        break;
+                    }
+                    _ => return Err(self.unexpected("`,` or `]`")),
+                }
+            }
+        }
+
+        self.depth -= 1;
+        Ok(JsonKind::Array(values))
+    }
+
+    fn object(&mut self) -> Result<JsonKind<'src>, JsonError> {
+        self.enter()?;
+        self.position += 1;
+
+        let mut members = Vec::new();
+
+        self.whitespace();
+        if self.peek() == Some(b'}') {
+            self.position += 1;
+        } else {
+            loop {
+                self.whitespace();
+                if self.peek() != Some(b'"') {
+                    return Err(self.unexpected("a string key"));
+                }
+
+                let start = self.position;
+                let key = self.string()?;
+                let key_span = start..self.position;
+
+                self.whitespace();
+                self.expect(b':', "`:`")?;
+
+                let value = self.value()?;
+                members.push(JsonMember::new(key, key_span, value));
+
+                self.whitespace();
+                match self.peek() {
+                    Some(b',') => self.position += 1,
+                    Some(b'}') => {
+                        self.position += 1;
+                        break;
+                    }
+                    _ => return Err(self.unexpected("`,` or `}`")),
+                }
+            }
+        }
+
+        Self::check_duplicates(&members)?;
+
+        self.depth -= 1;
+        Ok(JsonKind::Object(JsonObject::new(members)))
+    }
+
+    fn check_duplicates(members: &[JsonMember<'src>]) -> Result<(), JsonError> {
+        let mut sorted: Vec<&JsonMember<'src>> = members.iter().collect();
+        sorted.sort_by(|left, right| {
+            left.key()
+                .cmp(right.key())
+                .then_with(|| left.key_span().start.cmp(&right.key_span().start))
+        });
+
+        let mut duplicate: Option<(&JsonMember<'src>, &JsonMember<'src>)> = None;
+        for pair in sorted.windows(2) {
+            if let [first, second] = pair
+                && first.key() == second.key()
+                && duplicate
+                    .is_none_or(|(_, current)| second.key_span().start < current.key_span().start)
+            {
+                duplicate = Some((first, second));
+            }
+        }
+
+        match duplicate {
+            Some((first, second)) => Err(JsonError::DuplicateKey {
+                key: String::from(second.key()),
+                span: second.key_span(),
+                first: first.key_span(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn number(&mut self) -> Result<JsonKind<'src>, JsonError> {
+        let start = self.position;
+        let bytes = self.source.as_bytes();
+        let digits = |position: usize| {
+            bytes[position..]
+                .iter()
+                .take_while(|byte| byte.is_ascii_digit())
+                .count()
+        };
+
+        let mut position = start;
+        if bytes.get(position) == Some(&b'-') {
+            position += 1;
+        }
+
+        let integer = digits(position);
+        let valid_integer = integer == 1 || (integer > 1 && bytes[position] != b'0');
+        position += integer;
+
+        let mut valid = valid_integer;
+
+        if bytes.get(position) == Some(&b'.') {
+            let fraction = digits(position + 1);
+            valid &= fraction > 0;
+            position += 1 + fraction;
+        }
+
+        if let Some(b'e' | b'E') = bytes.get(position) {
+            position += 1;
+            if let Some(b'+' | b'-') = bytes.get(position) {
+                position += 1;
+            }
+
+            let exponent = digits(position);
+            valid &= exponent > 0;
+            position += exponent;
+        }
+
+        self.position = position;
+
+        if valid {
+            Ok(JsonKind::Number(&self.source[start..position]))
+        } else {
+            Err(JsonError::InvalidNumber {
+                span: start..position.max(start + 1),
+            })
+        }
+    }
+
+    /// Parses a string starting at the opening quote, borrowing when there are no escapes.
+    fn string(&mut self) -> Result<Cow<'src, str>, JsonError> {
+        self.position += 1;
+
+        let bytes = self.source.as_bytes();
+        let mut segment = self.position;
+        let mut owned: Option<String> = None;
+
+        loop {
+            let Some(&byte) = bytes.get(self.position) else {
+                return Err(JsonError::UnexpectedEnd {
+                    span: self.position..self.position,
+                    expected: "`\"`",
+                });
+            };
+
+            match byte {
+                b'"' => {
+                    let tail = &self.source[segment..self.position];
+                    self.position += 1;
+
+                    return Ok(match owned {
+                        Some(mut owned) => {
+                            owned.push_str(tail);
+                            Cow::Owned(owned)
+                        }
+                        None => Cow::Borrowed(tail),
+                    });
+                }
+                b'\\' => {
+                    let owned = owned.get_or_insert_with(String::new);
+                    owned.push_str(&self.source[segment..self.position]);
+                    owned.push(self.escape()?);
+                    segment = self.position;
+                }
+                0x00..=0x1F => {
+                    return Err(JsonError::ControlCharacter {
+                        span: self.position..self.position + 1,
+                    });
+                }
+                _ => self.position += 1,
+            }
+        }
+    }
+
+    /// Parses an escape sequence starting at the backslash.
+    fn escape(&mut self) -> Result<char, JsonError> {
+        let start = self.position;
+        let bytes = self.source.as_bytes();
+
+        let invalid = |end: usize| JsonError::InvalidEscape { span: start..end };
+
+        let Some(&byte) = bytes.get(start + 1) else {
+            return Err(invalid(start + 1));
+        };
+
+        self.position = start + 2;
+
+        let character = match byte {
+            b'"' => '"',
+            b'\\' => '\\',
+            b'/' => '/',
+            b'b' => '\u{8}',
+            b'f' => '\u{c}',
+            b'n' => '\n',
+            b'r' => '\r',
+            b't' => '\t',
+            b'u' => {
+                let high = self.hex().ok_or_else(|| invalid(self.position))?;
+
+                if (0xDC00..0xE000).contains(&high) {
+                    return Err(invalid(self.position));
+                }
+
+                let code = if (0xD800..0xDC00).contains(&high) {
+                    if !self.source[self.position..].starts_with("\\u") {
+                        return Err(invalid(self.position));
+                    }
+
+                    self.position += 2;
+                    let low = self.hex().ok_or_else(|| invalid(self.position))?;
+
+                    if !(0xDC00..0xE000).contains(&low) {
+                        return Err(invalid(self.position));
+                    }
+
+                    0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+                } else {
+                    high
+                };
+
+                char::from_u32(code).ok_or_else(|| invalid(self.position))?
+            }
+            _ => {
+                let width = self.source[start + 1..]
+                    .chars()
+                    .next()
+                    .map_or(1, char::len_utf8);
+
+                return Err(invalid(start + 1 + width));
+            }
+        };
+
+        Ok(character)
+    }
+
+    /// Parses four hex digits.
+    fn hex(&mut self) -> Option<u32> {
+        let digits = self.source.get(self.position..self.position + 4)?;
+        if !digits.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        self.position += 4;
+        u32::from_str_radix(digits, 16).ok()
+    }
+}
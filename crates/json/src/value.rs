@@ -0,0 +1,102 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+mod object;
+pub use object::{JsonMember, JsonObject};
+
+/// The kind of a JSON value.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum JsonKind<'src> {
+    Null,
+    Bool(bool),
+    /// A number, kept as its source text.
+    Number(&'src str),
+    String(Cow<'src, str>),
+    Array(Vec<JsonValue<'src>>),
+    Object(JsonObject<'src>),
+}
+
+/// A JSON value with its byte span in the source text.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct JsonValue<'src> {
+    kind: JsonKind<'src>,
+    span: Range<usize>,
+}
+
+impl<'src> JsonValue<'src> {
+    #[must_use]
+    pub const fn new(kind: JsonKind<'src>, span: Range<usize>) -> Self {
+        Self { kind, span }
+    }
+
+    #[must_use]
+    pub const fn kind(&self) -> &JsonKind<'src> {
+        &self.kind
+    }
+
+    #[must_use]
+    pub fn into_kind(self) -> JsonKind<'src> {
+        self.kind
+    }
+
+    /// Returns the byte span of this value in the source text.
+    #[must_use]
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Returns a human readable name for the kind of this value.
+    #[must_use]
+    pub const fn describe(&self) -> &'static str {
+        match self.kind {
+            JsonKind::Null => "null",
+            JsonKind::Bool(_) => "a boolean",
+            JsonKind::Number(_) => "a number",
+            JsonKind::String(_) => "a string",
+            JsonKind::Array(_) => "an array",
+            JsonKind::Object(_) => "an object",
+        }
+    }
+
+    #[must_use]
+    pub const fn as_bool(&self) -> Option<bool> {
+        match self.kind {
+            JsonKind::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as an integer, if it is a number without a fraction or exponent that fits in `i64`.
+    #[must_use]
+    pub fn as_i64(&self) -> Option<i64> {
+        match self.kind {
+            JsonKind::Number(text) => text.parse().ok(),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.kind {
+            JsonKind::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_array(&self) -> Option<&[Self]> {
+        match &self.kind {
+            JsonKind::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn as_object(&self) -> Option<&JsonObject<'src>> {
+        match &self.kind {
+            JsonKind::Object(object) => Some(object),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::ops::Range;
+use core::slice::Iter;
+
+use crate::value::JsonValue;
+
+/// A key-value pair in a JSON object.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct JsonMember<'src> {
+    key: Cow<'src, str>,
+    key_span: Range<usize>,
+    value: JsonValue<'src>,
+}
+
+impl<'src> JsonMember<'src> {
+    #[must_use]
+    pub const fn new(key: Cow<'src, str>, key_span: Range<usize>, value: JsonValue<'src>) -> Self {
+        Self {
+            key,
+            key_span,
+            value,
+        }
+    }
+
+    #[must_use]
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Returns the byte span of the key, including its quotes.
+    #[must_use]
+    pub fn key_span(&self) -> Range<usize> {
+        self.key_span.clone()
+    }
+
+    #[must_use]
+    pub const fn value(&self) -> &JsonValue<'src> {
+        &self.value
+    }
+
+    /// Returns the byte span of the whole member, from its key to the end of its value.
+    #[must_use]
+    pub const fn span(&self) -> Range<usize> {
+        self.key_span.start..self.value.span.end
+    }
+}
+
+/// A JSON object, with members in source order.
+///
+/// Keys are unique: the parser rejects duplicates.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct JsonObject<'src> {
+    members: Vec<JsonMember<'src>>,
+}
+
+impl<'src> JsonObject<'src> {
+    #[must_use]
+    pub const fn new(members: Vec<JsonMember<'src>>) -> Self {
+        Self { members }
+    }
+
+    /// Returns the member with the given key.
+    #[must_use]
+    pub fn member(&self, key: &str) -> Option<&JsonMember<'src>> {
+        self.members.iter().find(|member| member.key() == key)
+    }
+
+    /// Returns the value for the given key.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&JsonValue<'src>> {
+        self.member(key).map(JsonMember::value)
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    pub fn iter(&self) -> Iter<'_, JsonMember<'src>> {
+        self.members.iter()
+    }
+}
+
+impl<'a, 'src> IntoIterator for &'a JsonObject<'src> {
+    type IntoIter = Iter<'a, JsonMember<'src>>;
+    type Item = &'a JsonMember<'src>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.members.iter()
+    }
+}
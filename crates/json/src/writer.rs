@@ -0,0 +1,162 @@
+use alloc::string::{String, ToString as _};
+use alloc::vec::Vec;
+
+/// Streaming JSON writer.
+///
+/// Containers are opened and closed explicitly, and separators are inserted automatically.
+///
+/// ```
+/// use duramen_json::JsonWriter;
+///
+/// let mut writer = JsonWriter::new();
+/// writer.begin_object();
+/// writer.key("effect");
+/// writer.string("permit");
+/// writer.end_object();
+///
+/// assert_eq!(writer.finish(), r#"{"effect":"permit"}"#);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct JsonWriter {
+    output: String,
+    indent: Option<usize>,
+    /// Whether each open container has at least one element.
+    containers: Vec<bool>,
+    after_key: bool,
+}
+
+impl JsonWriter {
+    /// Creates a writer with compact output.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a writer that puts each element on its own line, indented by `indent` spaces.
+    #[must_use]
+    pub fn pretty(indent: usize) -> Self {
+        Self {
+            indent: Some(indent),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the written JSON.
+    #[must_use]
+    pub fn finish(self) -> String {
+        self.output
+    }
+
+    pub fn begin_object(&mut self) {
+        self.begin_value();
+        self.output.push('{');
+        self.containers.push(false);
+    }
+
+    pub fn end_object(&mut self) {
+        self.end_container();
+        self.output.push('}');
+    }
+
+    pub fn begin_array(&mut self) {
+        self.begin_value();
+        self.output.push('[');
+        self.containers.push(false);
+    }
+
+    pub fn end_array(&mut self) {
+        self.end_container();
+        self.output.push(']');
+    }
+
+    /// Writes an object key; the next call writes its value.
+    pub fn key(&mut self, key: &str) {
+        self.begin_value();
+        self.quote(key);
+        self.output.push(':');
+        if self.indent.is_some() {
+            self.output.push(' ');
+        }
+
+        self.after_key = true;
+    }
+
+    pub fn null(&mut self) {
+        self.begin_value();
+        self.output.push_str("null");
+    }
+
+    pub fn bool(&mut self, value: bool) {
+        self.begin_value();
+        self.output.push_str(if value { "true" } else { "false" });
+    }
+
+    pub fn number(&mut self, value: i64) {
+        self.begin_value();
+        self.output.push_str(&value.to_string());
+    }
+
+    pub fn string(&mut self, value: &str) {
+        self.begin_value();
+        self.quote(value);
+    }
+
+    fn begin_value(&mut self) {
+        if self.after_key {
+            self.after_key = false;
+            return;
+        }
+
+        let Some(has_elements) = self.containers.last_mut() else {
+            return;
+        };
+
+        if *has_elements {
+            self.output.push(',');
+        }
+
+        *has_elements = true;
+        self.newline(self.containers.len());
+    }
+
+    fn end_container(&mut self) {
+        if self.containers.pop() == Some(true) {
+            self.newline(self.containers.len());
+        }
+    }
+
+    fn newline(&mut self, depth: usize) {
+        if let Some(indent) = self.indent {
+            self.output.push('\n');
+            for _ in 0..depth * indent {
+                self.output.push(' ');
+            }
+        }
+    }
+
+    /// Writes a quoted string, escaping quotes, backslashes and control characters.
+    fn quote(&mut self, value: &str) {
+        self.output.push('"');
+
+        for char in value.chars() {
+            match char {
+                '"' => self.output.push_str("\\\""),
+                '\\' => self.output.push_str("\\\\"),
+                '\n' => self.output.push_str("\\n"),
+                '\r' => self.output.push_str("\\r"),
+                '\t' => self.output.push_str("\\t"),
+                '\u{8}' => self.output.push_str("\\b"),
+                '\u{c}' => self.output.push_str("\\f"),
+                char if char < ' ' => {
+                    let code = u32::from(char);
+                    self.output.push_str("\\u00");
+                    self.output.extend(char::from_digit(code >> 4, 16));
+                    self.output.extend(char::from_digit(code & 0xf, 16));
+                }
+                char => self.output.push(char),
+            }
+        }
+
+        self.output.push('"');
+    }
+}
@@ -0,0 +1,151 @@
+#![expect(clippy::unwrap_used, reason = "Tests")]
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+
+use duramen_diagnostic::Diagnostic;
+use duramen_json::{JsonError, JsonKind, JsonMember, JsonParser};
+use duramen_test::insta::assert_snapshot;
+
+extern crate alloc;
+
+fn render(source: &str) -> String {
+    let error = JsonParser::parse(source).unwrap_err();
+    let rendered = Diagnostic::from(error).render("input.json", source);
+    duramen_test::anstream::adapter::strip_str(&rendered).to_string()
+}
+
+// -- values --
+
+#[test]
+fn scalars() {
+    assert_eq!(JsonParser::parse("null").unwrap().kind(), &JsonKind::Null);
+    assert_eq!(JsonParser::parse(" true ").unwrap().as_bool(), Some(true));
+    assert_eq!(JsonParser::parse("-12").unwrap().as_i64(), Some(-12));
+    assert_eq!(JsonParser::parse("1.5e3").unwrap().as_i64(), None);
+    assert_eq!(JsonParser::parse(r#""hi""#).unwrap().as_str(), Some("hi"));
+}
+
+#[test]
+fn spans() {
+    let value = JsonParser::parse(r#"{ "a": [1, "b"] }"#).unwrap();
+    assert_eq!(value.span(), 0..17);
+
+    let object = value.as_object().unwrap();
+    let member = object.member("a").unwrap();
+    assert_eq!(member.key_span(), 2..5);
+    assert_eq!(member.value().span(), 7..15);
+
+    let array = member.value().as_array().unwrap();
+    assert_eq!(array[1].span(), 11..14);
+}
+
+#[test]
+fn string_borrowed() {
+    let value = JsonParser::parse(r#""plain""#).unwrap();
+    assert_eq!(value.into_kind(), JsonKind::String(Cow::Borrowed("plain")));
+}
+
+#[test]
+fn string_escapes() {
+    let value = JsonParser::parse(r#""a\"b\\c\/\né😀""#).unwrap();
+    assert_eq!(value.as_str(), Some("a\"b\\c/\né\u{1F600}"));
+}
+
+#[test]
+fn member_order() {
+    let value = JsonParser::parse(r#"{"b": 1, "a": 2}"#).unwrap();
+    let keys: alloc::vec::Vec<_> = value
+        .as_object()
+        .unwrap()
+        .iter()
+        .map(JsonMember::key)
+        .collect();
+    assert_eq!(keys, ["b", "a"]);
+}
+
+// -- errors --
+
+#[test]
+fn invalid_numbers() {
+    for source in ["01", "-", "1.", "1e", "-.5"] {
+        assert!(
+            matches!(
+                JsonParser::parse(source),
+                Err(JsonError::InvalidNumber { .. })
+            ),
+            "{source}"
+        );
+    }
+}
+
+#[test]
+fn lone_surrogate() {
+    assert_eq!(
+        JsonParser::parse(r#""\ud83d""#),
+        Err(JsonError::InvalidEscape { span: 1..7 })
+    );
+}
+
+#[test]
+fn nesting_too_deep() {
+    let source = "[".repeat(200);
+    assert!(matches!(
+        JsonParser::parse(&source),
+        Err(JsonError::NestingTooDeep { span }) if span == (128..129)
+    ));
+}
+
+#[test]
+fn trailing_comma() {
+    assert_snapshot!(render(r#"{"a": 1,}"#), @r#"
+    error: unexpected character, expected a string key
+      ╭▸ input.json:1:9
+      │
+    1 │ {"a": 1,}
+      ╰╴        ━ expected a string key
+    "#);
+}
+
+#[test]
+fn unterminated_string() {
+    assert_snapshot!(render(r#"["abc"#), @r#"
+    error: unexpected end of input, expected `"`
+      ╭▸ input.json:1:6
+      │
+    1 │ ["abc
+      ╰╴     ━ expected `"`
+    "#);
+}
+
+#[test]
+fn duplicate_key() {
+    assert_snapshot!(render(r#"{"a": 1, "b": 2, "a": 3}"#), @r#"
+    error: duplicate key `a`
+      ╭▸ input.json:1:18
+      │
+    1 │ {"a": 1, "b": 2, "a": 3}
+      │  ┬──             ━━━ duplicate key
+      │  │
+      ╰╴ first defined here
+    "#);
+}
+
+#[test]
+fn trailing_characters() {
+    assert_snapshot!(render("{} {}"), @r#"
+    error: unexpected trailing characters
+      ╭▸ input.json:1:4
+      │
+    1 │ {} {}
+      ╰╴   ━━ invalid JSON
+    "#);
+}
+
+#[test]
+fn control_character() {
+    assert_eq!(
+        JsonParser::parse("\"a\tb\""),
+        Err(JsonError::ControlCharacter { span: 2..3 })
+    );
+}
@@ -0,0 +1,62 @@
+use duramen_json::{JsonParser, JsonWriter};
+use duramen_test::insta::assert_snapshot;
+
+fn document(writer: &mut JsonWriter) {
+    writer.begin_object();
+    writer.key("name");
+    writer.string("line\nbreak \"quoted\" \\ \u{1}\u{1b} é");
+    writer.key("values");
+    writer.begin_array();
+    writer.number(-1);
+    writer.bool(true);
+    writer.null();
+    writer.begin_object();
+    writer.end_object();
+    writer.end_array();
+    writer.key("empty");
+    writer.begin_array();
+    writer.end_array();
+    writer.end_object();
+}
+
+#[test]
+fn compact() {
+    let mut writer = JsonWriter::new();
+    document(&mut writer);
+
+    assert_snapshot!(writer.finish(), @r#"{"name":"line\nbreak \"quoted\" \\ \u0001\u001b é","values":[-1,true,null,{}],"empty":[]}"#);
+}
+
+#[test]
+fn pretty() {
+    let mut writer = JsonWriter::pretty(2);
+    document(&mut writer);
+
+    assert_snapshot!(writer.finish(), @r#"
+    {
+      "name": "line\nbreak \"quoted\" \\ \u0001\u001b é",
+      "values": [
+        -1,
+        true,
+        null,
+        {}
+      ],
+      "empty": []
+    }
+    "#);
+}
+
+#[test]
+fn round_trip() {
+    let mut writer = JsonWriter::new();
+    document(&mut writer);
+
+    let output = writer.finish();
+    let value = JsonParser::parse(&output).unwrap();
+    let object = value.as_object().unwrap();
+
+    assert_eq!(
+        object.get("name").and_then(|name| name.as_str()),
+        Some("line\nbreak \"quoted\" \\ \u{1}\u{1b} é")
+    );
+}
@@ -2,6 +2,7 @@ use alloc::format;
 use alloc::string::String;
 use core::ops::Range;
 
+use duramen_ast::SlotKind;
 use duramen_diagnostic::{Diagnostic, Suggestion};
 
 pub enum LowerError {
@@ -60,10 +61,46 @@ pub enum LowerError {
     UnexpectedTypeExpression {
         span: Range<usize>,
     },
+    InvalidEntityShape {
+        span: Range<usize>,
+    },
+    AdditionalAttributes {
+        span: Range<usize>,
+    },
+    UnknownExtensionType {
+        span: Range<usize>,
+        name: String,
+    },
 
     InvalidEquals {
         span: Range<usize>,
     },
+
+    UnexpectedTemplate {
+        span: Range<usize>,
+        id: String,
+    },
+
+    UnexpectedJsonType {
+        span: Range<usize>,
+        expected: &'static str,
+        found: &'static str,
+    },
+    MissingJsonField {
+        span: Range<usize>,
+        field: &'static str,
+    },
+    UnknownJsonField {
+        span: Range<usize>,
+        field: String,
+    },
+    InvalidJsonExpression {
+        span: Range<usize>,
+    },
+    UnexpectedSlot {
+        span: Range<usize>,
+        expected: SlotKind,
+    },
 }
 
 impl From<LowerError> for Diagnostic {
@@ -135,6 +172,17 @@ impl From<LowerError> for Diagnostic {
             LowerError::UnexpectedTypeExpression { span } => {
                 Self::error("unexpected type expression").with_label(span, "expected a type")
             }
+            LowerError::InvalidEntityShape { span } => Self::error("invalid entity shape")
+                .with_label(span, "expected a record type or a common record type"),
+            LowerError::AdditionalAttributes { span } => {
+                Self::error("records with additional attributes are not supported")
+                    .with_label(span, "expected `false`")
+            }
+            LowerError::UnknownExtensionType { span, name } => {
+                Self::error(format!("`{name}` is not an extension type"))
+                    .with_label(span, "unknown extension type")
+                    .with_note("expected `ipaddr`, `decimal`, `datetime`, or `duration`")
+            }
 
             LowerError::InvalidEquals { span } => {
                 let suggestion =
@@ -144,6 +192,32 @@ impl From<LowerError> for Diagnostic {
                     .with_label(span, "not a valid operator")
                     .with_suggestion(suggestion)
             }
+
+            LowerError::UnexpectedTemplate { span, id } => {
+                Self::error(format!("static policy `{id}` contains template slots"))
+                    .with_label(span, "expected a static policy")
+                    .with_note("templates belong in `templates`")
+            }
+
+            LowerError::UnexpectedJsonType {
+                span,
+                expected,
+                found,
+            } => Self::error(format!("expected {expected}, found {found}"))
+                .with_label(span, format!("expected {expected}")),
+            LowerError::MissingJsonField { span, field } => {
+                Self::error(format!("missing field `{field}`"))
+                    .with_label(span, format!("missing `{field}`"))
+            }
+            LowerError::UnknownJsonField { span, field } => {
+                Self::error(format!("unknown field `{field}`")).with_label(span, "unknown field")
+            }
+            LowerError::InvalidJsonExpression { span } => Self::error("invalid expression")
+                .with_label(span, "expected an object with a single key"),
+            LowerError::UnexpectedSlot { span, expected } => {
+                Self::error(format!("expected slot `{expected}`"))
+                    .with_label(span, "wrong slot for this constraint")
+            }
         }
     }
 }
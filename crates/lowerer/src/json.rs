@@ -0,0 +1,7 @@
+mod context;
+
+mod policy;
+pub use policy::PolicyJsonLowerer;
+
+mod schema;
+pub use schema::SchemaJsonLowerer;
@@ -0,0 +1,176 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use duramen_ast as ast;
+use duramen_diagnostic::{Diagnostic, Diagnostics};
+use duramen_json::{JsonKind, JsonObject, JsonValue};
+
+use crate::error::LowerError;
+
+/// Shared context for lowering JSON to AST.
+pub struct JsonContext {
+    pub diagnostics: Diagnostics,
+}
+
+impl JsonContext {
+    pub const fn new(diagnostics: Diagnostics) -> Self {
+        Self { diagnostics }
+    }
+
+    /// Returns a required field, reporting it if missing.
+    pub fn field<'a, 'src>(
+        &mut self,
+        json: &JsonValue<'src>,
+        object: &'a JsonObject<'src>,
+        field: &'static str,
+    ) -> Option<&'a JsonValue<'src>> {
+        let value = object.get(field);
+        if value.is_none() {
+            self.diagnostics.push(LowerError::MissingJsonField {
+                span: json.span(),
+                field,
+            });
+        }
+
+        value
+    }
+
+    /// Reports every member whose key is not one of `fields`.
+    pub fn check_fields(&mut self, object: &JsonObject<'_>, fields: &[&str]) {
+        for member in object {
+            if !fields.contains(&member.key()) {
+                self.diagnostics.push(LowerError::UnknownJsonField {
+                    span: member.key_span(),
+                    field: String::from(member.key()),
+                });
+            }
+        }
+    }
+
+    pub fn expect_object<'a, 'src>(
+        &mut self,
+        json: &'a JsonValue<'src>,
+    ) -> Option<&'a JsonObject<'src>> {
+        let object = json.as_object();
+        if object.is_none() {
+            self.unexpected("an object", json);
+        }
+
+        object
+    }
+
+    pub fn expect_array<'a, 'src>(
+        &mut self,
+        json: &'a JsonValue<'src>,
+    ) -> Option<&'a [JsonValue<'src>]> {
+        let array = json.as_array();
+        if array.is_none() {
+            self.unexpected("an array", json);
+        }
+
+        array
+    }
+
+    pub fn expect_str<'a>(&mut self, json: &'a JsonValue<'_>) -> Option<&'a str> {
+        let value = json.as_str();
+        if value.is_none() {
+            self.unexpected("a string", json);
+        }
+
+        value
+    }
+
+    pub fn unexpected(&mut self, expected: &'static str, json: &JsonValue<'_>) {
+        self.diagnostics.push(LowerError::UnexpectedJsonType {
+            span: json.span(),
+            expected,
+            found: json.describe(),
+        });
+    }
+
+    /// Lowers a `::` separated name string.
+    pub fn name<'src>(&mut self, json: &'src JsonValue<'_>) -> Option<ast::Name<'src>> {
+        let text = self.expect_str(json)?;
+
+        match ast::Name::parse(text) {
+            Ok(name) => Some(name),
+            Err(error) => {
+                self.diagnostics
+                    .push(Diagnostic::from(error).with_label(json.span(), "invalid name"));
+
+                None
+            }
+        }
+    }
+
+    /// Lowers an entity reference, either `{ "type", "id" }` or the `__entity` escape.
+    pub fn entity_reference<'src>(
+        &mut self,
+        json: &'src JsonValue<'_>,
+    ) -> Option<ast::EntityReference<'src>> {
+        let object = self.expect_object(json)?;
+
+        if let Some(inner) = object.get("__entity")
+            && object.len() == 1
+        {
+            return self.entity_reference(inner);
+        }
+
+        self.check_fields(object, &["type", "id"]);
+
+        let kind = self
+            .field(json, object, "type")
+            .and_then(|kind| self.name(kind));
+
+        let id = self
+            .field(json, object, "id")
+            .and_then(|id| self.expect_str(id));
+
+        Some(ast::EntityReference::new(kind?, Cow::Borrowed(id?)))
+    }
+
+    /// Lowers annotations: `{ "id": "value", "flag": null }`.
+    pub fn annotations<'src>(
+        &mut self,
+        json: &'src JsonValue<'_>,
+    ) -> Option<ast::Annotations<'src>> {
+        let object = self.expect_object(json)?;
+
+        let mut entries = Vec::with_capacity(object.len());
+        for member in object {
+            let key = match ast::Identifier::new(member.key()) {
+                Ok(key) => key,
+                Err(error) => {
+                    self.diagnostics.push(
+                        Diagnostic::from(error)
+                            .with_label(member.key_span(), "invalid annotation name"),
+                    );
+
+                    continue;
+                }
+            };
+
+            let value = match member.value().kind() {
+                JsonKind::Null => ast::AnnotationValue::Empty,
+                JsonKind::String(value) => ast::AnnotationValue::String(Cow::Borrowed(value)),
+                _ => {
+                    self.unexpected("a string or null", member.value());
+                    continue;
+                }
+            };
+
+            entries.push((key, value));
+        }
+
+        match ast::Annotations::new(entries) {
+            Ok(annotations) => Some(annotations),
+            Err(error) => {
+                self.diagnostics
+                    .push(Diagnostic::from(error).with_label(json.span(), "in these annotations"));
+
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,918 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use duramen_ast as ast;
+use duramen_diagnostic::{Diagnostic, Diagnostics};
+use duramen_json::{JsonKind, JsonObject, JsonValue};
+
+use crate::error::LowerError;
+use crate::json::context::JsonContext;
+use crate::policy::EXTENSION_FUNCTIONS;
+
+/// Policy lowerer for the Cedar policy JSON format.
+///
+/// ```json
+/// {
+///   "effect": "permit",
+///   "principal": { "op": "==", "entity": { "type": "User", "id": "alice" } },
+///   "action": { "op": "All" },
+///   "resource": { "op": "in", "slot": "?resource" },
+///   "conditions": [{ "kind": "when", "body": { "Var": "context" } }],
+///   "annotations": { "id": "example" }
+/// }
+/// ```
+pub struct PolicyJsonLowerer {
+    ctx: JsonContext,
+}
+
+impl PolicyJsonLowerer {
+    /// Lowers an array of policies and its diagnostics to an AST.
+    ///
+    /// Policies with errors are reported and skipped.
+    #[must_use]
+    pub fn lower<'src>(
+        json: &'src JsonValue<'_>,
+        diagnostics: Diagnostics,
+    ) -> (ast::Policies<'src>, Diagnostics) {
+        let mut this = Self {
+            ctx: JsonContext::new(diagnostics),
+        };
+
+        let mut result = Vec::new();
+        if let Some(values) = this.ctx.expect_array(json) {
+            for value in values {
+                if let Some(policy) = this.policy(value) {
+                    result.push(policy);
+                }
+            }
+        }
+
+        (ast::Policies::new(result), this.ctx.diagnostics)
+    }
+
+    /// Lowers a single policy or template.
+    #[must_use]
+    pub fn lower_policy<'src>(
+        json: &'src JsonValue<'_>,
+        diagnostics: Diagnostics,
+    ) -> (Option<ast::Policy<'src>>, Diagnostics) {
+        let mut this = Self {
+            ctx: JsonContext::new(diagnostics),
+        };
+
+        let policy = this.policy(json);
+        (policy, this.ctx.diagnostics)
+    }
+
+    /// Lowers a policy set with `staticPolicies`, `templates` and `templateLinks`.
+    ///
+    /// ```json
+    /// {
+    ///   "staticPolicies": { "policy0": { ... } },
+    ///   "templates": { "share": { ... } },
+    ///   "templateLinks": [
+    ///     {
+    ///       "templateId": "share",
+    ///       "newId": "share-alice",
+    ///       "values": { "?principal": { "type": "User", "id": "alice" } }
+    ///     }
+    ///   ]
+    /// }
+    /// ```
+    #[must_use]
+    pub fn lower_set<'src>(
+        json: &'src JsonValue<'_>,
+        diagnostics: Diagnostics,
+    ) -> (ast::PolicySet<'src>, Diagnostics) {
+        let mut this = Self {
+            ctx: JsonContext::new(diagnostics),
+        };
+
+        let set = this.set(json);
+        (set, this.ctx.diagnostics)
+    }
+
+    /// Lowers a `templateLinks` array, linking each entry into `set`.
+    ///
+    /// Links with errors are reported and skipped.
+    #[must_use]
+    pub fn lower_links<'src>(
+        json: &'src JsonValue<'_>,
+        mut set: ast::PolicySet<'src>,
+        diagnostics: Diagnostics,
+    ) -> (ast::PolicySet<'src>, Diagnostics) {
+        let mut this = Self {
+            ctx: JsonContext::new(diagnostics),
+        };
+
+        if let Some(links) = this.ctx.expect_array(json) {
+            for link in links {
+                this.link(&mut set, link);
+            }
+        }
+
+        (set, this.ctx.diagnostics)
+    }
+
+    fn set<'src>(&mut self, json: &'src JsonValue<'_>) -> ast::PolicySet<'src> {
+        let mut set = ast::PolicySet::empty();
+
+        let Some(object) = self.ctx.expect_object(json) else {
+            return set;
+        };
+
+        self.ctx
+            .check_fields(object, &["staticPolicies", "templates", "templateLinks"]);
+
+        for field in ["staticPolicies", "templates"] {
+            let Some(policies) = object.get(field) else {
+                continue;
+            };
+
+            let Some(policies) = self.ctx.expect_object(policies) else {
+                continue;
+            };
+
+            for member in policies {
+                let Some(policy) = self.policy(member.value()) else {
+                    continue;
+                };
+
+                if field == "staticPolicies" && policy.is_template() {
+                    self.ctx.diagnostics.push(LowerError::UnexpectedTemplate {
+                        span: member.key_span(),
+                        id: String::from(member.key()),
+                    });
+
+                    continue;
+                }
+
+                let id = ast::PolicyId::new(Cow::Borrowed(member.key()));
+                if let Err(error) = set.insert(id, policy.with_id_span(member.key_span())) {
+                    self.ctx.diagnostics.push(error);
+                }
+            }
+        }
+
+        if let Some(links) = object.get("templateLinks")
+            && let Some(links) = self.ctx.expect_array(links)
+        {
+            for link in links {
+                self.link(&mut set, link);
+            }
+        }
+
+        set
+    }
+
+    /// Lowers a template link and adds the linked policy to the set.
+    fn link<'src>(&mut self, set: &mut ast::PolicySet<'src>, json: &'src JsonValue<'_>) {
+        let Some(object) = self.ctx.expect_object(json) else {
+            return;
+        };
+
+        self.ctx
+            .check_fields(object, &["templateId", "newId", "values"]);
+
+        let template = self.ctx.field(json, object, "templateId");
+        let template_id = template.and_then(|template| self.ctx.expect_str(template));
+
+        let id = self.ctx.field(json, object, "newId");
+        let new_id = id.and_then(|id| self.ctx.expect_str(id));
+
+        let values = self
+            .ctx
+            .field(json, object, "values")
+            .and_then(|values| self.link_values(values));
+
+        let (Some(template), Some(template_id), Some(id), Some(new_id), Some(values)) =
+            (template, template_id, id, new_id, values)
+        else {
+            return;
+        };
+
+        let new_id = ast::PolicyId::new(Cow::Borrowed(new_id));
+        let template_id = ast::PolicyId::new(Cow::Borrowed(template_id));
+        let link = ast::TemplateLink::new(template_id, values).with_span(id.span());
+
+        let Err(error) = set.insert_link(new_id, link) else {
+            return;
+        };
+
+        match error {
+            ast::Error::UnknownTemplate { .. } => {
+                self.ctx
+                    .diagnostics
+                    .push(Diagnostic::from(error).with_label(template.span(), "unknown template"));
+            }
+            ast::Error::DuplicatePolicyId { .. } => self.ctx.diagnostics.push(error),
+            error => {
+                self.ctx
+                    .diagnostics
+                    .push(Diagnostic::from(error).with_label(json.span(), "in this link"));
+            }
+        }
+    }
+
+    /// Lowers link values: `{ "?principal": { "type": "User", "id": "alice" } }`.
+    fn link_values<'src>(
+        &mut self,
+        json: &'src JsonValue<'_>,
+    ) -> Option<Vec<(ast::SlotKind, ast::EntityReference<'src>)>> {
+        let object = self.ctx.expect_object(json)?;
+
+        let mut values = Vec::with_capacity(object.len());
+        let mut valid = true;
+
+        for member in object {
+            let slot = self.slot_kind(member.key(), member.key_span());
+            let entity = self.ctx.entity_reference(member.value());
+
+            if let (Some(slot), Some(entity)) = (slot, entity) {
+                values.push((slot, entity));
+            } else {
+                valid = false;
+            }
+        }
+
+        valid.then_some(values)
+    }
+
+    fn policy<'src>(&mut self, json: &'src JsonValue<'_>) -> Option<ast::Policy<'src>> {
+        let object = self.ctx.expect_object(json)?;
+        self.ctx.check_fields(
+            object,
+            &[
+                "effect",
+                "principal",
+                "action",
+                "resource",
+                "conditions",
+                "annotations",
+            ],
+        );
+
+        let annotations = match object.get("annotations") {
+            Some(annotations) => self.ctx.annotations(annotations),
+            None => Some(ast::Annotations::empty()),
+        };
+
+        let effect = self
+            .ctx
+            .field(json, object, "effect")
+            .and_then(|effect| self.effect(effect));
+
+        let principal = self
+            .ctx
+            .field(json, object, "principal")
+            .and_then(|principal| {
+                let constraint = self.scope(principal, ast::SlotKind::Principal)?;
+                Some(ast::PrincipalConstraint::new(constraint).with_span(principal.span()))
+            });
+
+        let action = self.ctx.field(json, object, "action");
+        let action_span = action.map(JsonValue::span);
+        let action = action.and_then(|action| self.action(action));
+
+        let resource = self
+            .ctx
+            .field(json, object, "resource")
+            .and_then(|resource| {
+                let constraint = self.scope(resource, ast::SlotKind::Resource)?;
+                Some(ast::ResourceConstraint::new(constraint).with_span(resource.span()))
+            });
+
+        let conditions = match object.get("conditions") {
+            Some(conditions) => self.conditions(conditions),
+            None => Some(Vec::new()),
+        };
+
+        let policy = ast::Policy::new(
+            annotations?,
+            effect?,
+            principal?,
+            action?,
+            resource?,
+            conditions?,
+        );
+
+        let mut policy = policy.with_span(json.span());
+        if let Some(span) = action_span {
+            policy = policy.with_action_span(span);
+        }
+
+        Some(policy)
+    }
+
+    fn effect(&mut self, json: &JsonValue<'_>) -> Option<ast::Effect> {
+        match self.ctx.expect_str(json)? {
+            "permit" => Some(ast::Effect::Permit),
+            "forbid" => Some(ast::Effect::Forbid),
+            _ => {
+                self.ctx
+                    .diagnostics
+                    .push(LowerError::MissingEffect { span: json.span() });
+                None
+            }
+        }
+    }
+
+    /// Lowers a principal or resource constraint.
+    fn scope<'src>(
+        &mut self,
+        json: &'src JsonValue<'_>,
+        slot: ast::SlotKind,
+    ) -> Option<ast::ScopeConstraint<'src>> {
+        let object = self.ctx.expect_object(json)?;
+        let operator = self.ctx.field(json, object, "op")?;
+
+        match self.ctx.expect_str(operator)? {
+            "All" => {
+                self.ctx.check_fields(object, &["op"]);
+                Some(ast::ScopeConstraint::Any)
+            }
+            "==" => {
+                self.ctx.check_fields(object, &["op", "entity", "slot"]);
+                self.entity_or_slot(json, object, slot)
+                    .map(ast::ScopeConstraint::Equal)
+            }
+            "in" => {
+                self.ctx.check_fields(object, &["op", "entity", "slot"]);
+                self.entity_or_slot(json, object, slot)
+                    .map(ast::ScopeConstraint::In)
+            }
+            "is" => {
+                self.ctx.check_fields(object, &["op", "entity_type", "in"]);
+
+                let kind = self
+                    .ctx
+                    .field(json, object, "entity_type")
+                    .and_then(|kind| self.ctx.name(kind));
+
+                let Some(target) = object.get("in") else {
+                    return kind.map(ast::ScopeConstraint::Is);
+                };
+
+                let target = self.ctx.expect_object(target).and_then(|object| {
+                    self.ctx.check_fields(object, &["entity", "slot"]);
+                    self.entity_or_slot(target, object, slot)
+                });
+
+                Some(ast::ScopeConstraint::IsIn(kind?, target?))
+            }
+            _ => {
+                self.ctx.diagnostics.push(LowerError::InvalidScopeOperator {
+                    span: operator.span(),
+                    variable: match slot {
+                        ast::SlotKind::Principal => String::from("principal"),
+                        ast::SlotKind::Resource => String::from("resource"),
+                    },
+                });
+
+                None
+            }
+        }
+    }
+
+    /// Lowers the `entity` or `slot` field of a scope constraint.
+    fn entity_or_slot<'src>(
+        &mut self,
+        json: &'src JsonValue<'_>,
+        object: &'src JsonObject<'_>,
+        expected: ast::SlotKind,
+    ) -> Option<ast::EntityOrSlot<'src>> {
+        if let Some(slot) = object.get("slot") {
+            let text = self.ctx.expect_str(slot)?;
+            if self.slot_kind(text, slot.span())? != expected {
+                self.ctx.diagnostics.push(LowerError::UnexpectedSlot {
+                    span: slot.span(),
+                    expected,
+                });
+
+                return None;
+            }
+
+            return Some(ast::EntityOrSlot::Slot);
+        }
+
+        let entity = self.ctx.field(json, object, "entity")?;
+        self.ctx
+            .entity_reference(entity)
+            .map(ast::EntityOrSlot::Entity)
+    }
+
+    /// Parses a slot name like `?principal`.
+    fn slot_kind(&mut self, text: &str, span: Range<usize>) -> Option<ast::SlotKind> {
+        let result = text.strip_prefix('?').map_or_else(
+            || {
+                Err(ast::Error::InvalidSlot {
+                    name: String::from(text),
+                })
+            },
+            ast::SlotKind::new,
+        );
+
+        match result {
+            Ok(kind) => Some(kind),
+            Err(error) => {
+                self.ctx
+                    .diagnostics
+                    .push(Diagnostic::from(error).with_label(span, "invalid slot"));
+
+                None
+            }
+        }
+    }
+
+    fn action<'src>(&mut self, json: &'src JsonValue<'_>) -> Option<ast::ActionConstraint<'src>> {
+        let object = self.ctx.expect_object(json)?;
+        let operator = self.ctx.field(json, object, "op")?;
+
+        match self.ctx.expect_str(operator)? {
+            "All" => {
+                self.ctx.check_fields(object, &["op"]);
+                Some(ast::ActionConstraint::Any)
+            }
+            "==" => {
+                self.ctx.check_fields(object, &["op", "entity"]);
+                let entity = self.ctx.field(json, object, "entity")?;
+                self.ctx
+                    .entity_reference(entity)
+                    .map(ast::ActionConstraint::Equal)
+            }
+            "in" => {
+                self.ctx.check_fields(object, &["op", "entity", "entities"]);
+
+                let actions = if let Some(entities) = object.get("entities") {
+                    let entities = self.ctx.expect_array(entities)?;
+                    let mut actions = Vec::with_capacity(entities.len());
+                    for entity in entities {
+                        actions.push(self.ctx.entity_reference(entity));
+                    }
+
+                    actions.into_iter().collect::<Option<Vec<_>>>()?
+                } else {
+                    let entity = self.ctx.field(json, object, "entity")?;
+                    alloc::vec![self.ctx.entity_reference(entity)?]
+                };
+
+                match ast::ActionList::new(actions) {
+                    Ok(list) => Some(ast::ActionConstraint::In(list)),
+                    Err(error) => {
+                        self.ctx
+                            .diagnostics
+                            .push(Diagnostic::from(error).with_label(json.span(), "in this list"));
+
+                        None
+                    }
+                }
+            }
+            _ => {
+                self.ctx.diagnostics.push(LowerError::InvalidScopeOperator {
+                    span: operator.span(),
+                    variable: String::from("action"),
+                });
+
+                None
+            }
+        }
+    }
+
+    fn conditions<'src>(&mut self, json: &'src JsonValue<'_>) -> Option<Vec<ast::Condition<'src>>> {
+        let values = self.ctx.expect_array(json)?;
+
+        let mut conditions = Vec::with_capacity(values.len());
+        for value in values {
+            conditions.push(self.condition(value));
+        }
+
+        conditions.into_iter().collect()
+    }
+
+    fn condition<'src>(&mut self, json: &'src JsonValue<'_>) -> Option<ast::Condition<'src>> {
+        let object = self.ctx.expect_object(json)?;
+        self.ctx.check_fields(object, &["kind", "body"]);
+
+        let kind = self.ctx.field(json, object, "kind").and_then(|kind| {
+            match self.ctx.expect_str(kind)? {
+                "when" => Some(ast::ConditionKind::When),
+                "unless" => Some(ast::ConditionKind::Unless),
+                _ => {
+                    self.ctx.unexpected("`when` or `unless`", kind);
+                    None
+                }
+            }
+        });
+
+        let body = self
+            .ctx
+            .field(json, object, "body")
+            .and_then(|body| self.expression(body));
+
+        Some(ast::Condition::new(kind?, body?))
+    }
+
+    /// Lowers an expression, keeping its span in the JSON source.
+    fn expression<'src>(&mut self, json: &'src JsonValue<'_>) -> Option<ast::Expression<'src>> {
+        self.operation(json)
+            .map(|expression| expression.with_span(json.span()))
+    }
+
+    /// Lowers an object with a single key naming the operator.
+    fn operation<'src>(&mut self, json: &'src JsonValue<'_>) -> Option<ast::Expression<'src>> {
+        let object = self.ctx.expect_object(json)?;
+
+        let mut members = object.iter();
+        let (Some(member), None) = (members.next(), members.next()) else {
+            self.ctx
+                .diagnostics
+                .push(LowerError::InvalidJsonExpression { span: json.span() });
+
+            return None;
+        };
+
+        let value = member.value();
+        match member.key() {
+            "Value" => self.value(value),
+            "Var" => {
+                let name = self.ctx.expect_str(value)?;
+                let variable = match name {
+                    "principal" => ast::Variable::Principal,
+                    "action" => ast::Variable::Action,
+                    "resource" => ast::Variable::Resource,
+                    "context" => ast::Variable::Context,
+                    _ => {
+                        self.ctx.diagnostics.push(LowerError::UnknownVariable {
+                            span: value.span(),
+                            name: String::from(name),
+                        });
+
+                        return None;
+                    }
+                };
+
+                Some(ast::Expression::variable(variable))
+            }
+            "Slot" => {
+                let text = self.ctx.expect_str(value)?;
+                self.slot_kind(text, value.span())
+                    .map(ast::Expression::slot)
+            }
+            "!" => self.unary(value, ast::UnaryOperator::Not),
+            "neg" => self.unary(value, ast::UnaryOperator::Negate),
+            "isEmpty" => self.unary(value, ast::UnaryOperator::IsEmpty),
+            "&&" => {
+                let (left, right) = self.operands(value)?;
+                Some(ast::Expression::and(left, right))
+            }
+            "||" => {
+                let (left, right) = self.operands(value)?;
+                Some(ast::Expression::or(left, right))
+            }
+            "." | "has" => {
+                let object = self.ctx.expect_object(value)?;
+                self.ctx.check_fields(object, &["left", "attr"]);
+
+                let left = self
+                    .ctx
+                    .field(value, object, "left")
+                    .and_then(|left| self.expression(left));
+
+                let attribute = self
+                    .ctx
+                    .field(value, object, "attr")
+                    .and_then(|attribute| self.ctx.expect_str(attribute));
+
+                let (left, attribute) = (left?, Cow::Borrowed(attribute?));
+                if member.key() == "." {
+                    Some(ast::Expression::get_attribute(left, attribute))
+                } else {
+                    Some(ast::Expression::has_attribute(left, attribute))
+                }
+            }
+            "like" => {
+                let object = self.ctx.expect_object(value)?;
+                self.ctx.check_fields(object, &["left", "pattern"]);
+
+                let left = self
+                    .ctx
+                    .field(value, object, "left")
+                    .and_then(|left| self.expression(left));
+
+                let pattern = self
+                    .ctx
+                    .field(value, object, "pattern")
+                    .and_then(|pattern| self.pattern(pattern));
+
+                Some(ast::Expression::like(left?, pattern?))
+            }
+            "is" => {
+                let object = self.ctx.expect_object(value)?;
+                self.ctx
+                    .check_fields(object, &["left", "entity_type", "in"]);
+
+                let left = self
+                    .ctx
+                    .field(value, object, "left")
+                    .and_then(|left| self.expression(left));
+
+                let kind = self
+                    .ctx
+                    .field(value, object, "entity_type")
+                    .and_then(|kind| self.ctx.name(kind));
+
+                match object.get("in") {
+                    Some(target) => {
+                        let target = self.expression(target);
+                        Some(ast::Expression::is_in(left?, kind?, target?))
+                    }
+                    None => Some(ast::Expression::is(left?, kind?)),
+                }
+            }
+            "if-then-else" => {
+                let object = self.ctx.expect_object(value)?;
+                self.ctx.check_fields(object, &["if", "then", "else"]);
+
+                let test = self
+                    .ctx
+                    .field(value, object, "if")
+                    .and_then(|test| self.expression(test));
+
+                let consequent = self
+                    .ctx
+                    .field(value, object, "then")
+                    .and_then(|consequent| self.expression(consequent));
+
+                let alternate = self
+                    .ctx
+                    .field(value, object, "else")
+                    .and_then(|alternate| self.expression(alternate));
+
+                Some(ast::Expression::if_then_else(
+                    test?,
+                    consequent?,
+                    alternate?,
+                ))
+            }
+            "Set" => {
+                let values = self.ctx.expect_array(value)?;
+                self.expressions(values).map(ast::Expression::set)
+            }
+            "Record" => {
+                let object = self.ctx.expect_object(value)?;
+
+                let mut entries = Vec::with_capacity(object.len());
+                for member in object {
+                    let value = self.expression(member.value());
+                    entries.push(value.map(|value| (Cow::Borrowed(member.key()), value)));
+                }
+
+                let entries = entries.into_iter().collect::<Option<Vec<_>>>()?;
+                self.record(entries, value.span())
+            }
+            key => {
+                if let Some(operator) = binary_operator(key) {
+                    let (left, right) = self.operands(value)?;
+                    return Some(ast::Expression::binary(operator, left, right));
+                }
+
+                self.extension_call(key, member.key_span(), value)
+            }
+        }
+    }
+
+    fn expressions<'src>(
+        &mut self,
+        values: &'src [JsonValue<'_>],
+    ) -> Option<Vec<ast::Expression<'src>>> {
+        let mut expressions = Vec::with_capacity(values.len());
+        for value in values {
+            expressions.push(self.expression(value));
+        }
+
+        expressions.into_iter().collect()
+    }
+
+    /// Lowers `{ "arg": ... }`.
+    fn unary<'src>(
+        &mut self,
+        json: &'src JsonValue<'_>,
+        operator: ast::UnaryOperator,
+    ) -> Option<ast::Expression<'src>> {
+        let object = self.ctx.expect_object(json)?;
+        self.ctx.check_fields(object, &["arg"]);
+
+        let operand = self.ctx.field(json, object, "arg")?;
+        let operand = self.expression(operand)?;
+
+        Some(ast::Expression::unary(operator, operand))
+    }
+
+    /// Lowers `{ "left": ..., "right": ... }`.
+    fn operands<'src>(
+        &mut self,
+        json: &'src JsonValue<'_>,
+    ) -> Option<(ast::Expression<'src>, ast::Expression<'src>)> {
+        let object = self.ctx.expect_object(json)?;
+        self.ctx.check_fields(object, &["left", "right"]);
+
+        let left = self
+            .ctx
+            .field(json, object, "left")
+            .and_then(|left| self.expression(left));
+
+        let right = self
+            .ctx
+            .field(json, object, "right")
+            .and_then(|right| self.expression(right));
+
+        Some((left?, right?))
+    }
+
+    /// Lowers an extension function call: `{ "decimal": [{ "Value": "1.5" }] }`.
+    fn extension_call<'src>(
+        &mut self,
+        name: &'src str,
+        span: Range<usize>,
+        json: &'src JsonValue<'_>,
+    ) -> Option<ast::Expression<'src>> {
+        let function = self.extension_function(name, span);
+        let values = self.ctx.expect_array(json)?;
+        let arguments = self.expressions(values);
+
+        Some(ast::Expression::extension_call(function?, arguments?))
+    }
+
+    /// Resolves a known extension function name.
+    fn extension_function<'src>(
+        &mut self,
+        name: &'src str,
+        span: Range<usize>,
+    ) -> Option<ast::Name<'src>> {
+        let identifier = ast::Identifier::new(name)
+            .ok()
+            .filter(|_| EXTENSION_FUNCTIONS.contains(&name));
+
+        if identifier.is_none() {
+            self.ctx.diagnostics.push(LowerError::UnknownFunction {
+                span,
+                name: String::from(name),
+            });
+        }
+
+        identifier.map(ast::Name::unqualified)
+    }
+
+    /// Lowers a `like` pattern: `["Wildcard", { "Literal": "abc" }]`.
+    fn pattern<'src>(&mut self, json: &'src JsonValue<'_>) -> Option<ast::Pattern<'src>> {
+        let values = self.ctx.expect_array(json)?;
+
+        let mut elements = Vec::with_capacity(values.len());
+        let mut valid = true;
+
+        for value in values {
+            if value.as_str() == Some("Wildcard") {
+                elements.push(ast::PatternElement::Wildcard);
+                continue;
+            }
+
+            if let Some(object) = value.as_object()
+                && let Some(literal) = object.get("Literal")
+                && object.len() == 1
+                && let Some(literal) = self.ctx.expect_str(literal)
+            {
+                elements.push(ast::PatternElement::Literal(Cow::Borrowed(literal)));
+                continue;
+            }
+
+            self.ctx
+                .unexpected("`\"Wildcard\"` or `{ \"Literal\": ... }`", value);
+            valid = false;
+        }
+
+        valid.then(|| ast::Pattern::new(elements))
+    }
+
+    /// Lowers a `Value` literal, which may be any Cedar value in JSON form.
+    fn value<'src>(&mut self, json: &'src JsonValue<'_>) -> Option<ast::Expression<'src>> {
+        match json.kind() {
+            JsonKind::Bool(value) => Some(ast::Expression::bool(*value)),
+            JsonKind::Number(text) => match ast::IntegerLiteral::new(text) {
+                Ok(literal) => Some(ast::Expression::integer(literal)),
+                Err(error) => {
+                    self.ctx.diagnostics.push(
+                        Diagnostic::from(error).with_label(json.span(), "expected an integer"),
+                    );
+
+                    None
+                }
+            },
+            JsonKind::String(value) => Some(ast::Expression::string(Cow::Borrowed(value))),
+            JsonKind::Array(values) => {
+                let mut elements = Vec::with_capacity(values.len());
+                for value in values {
+                    elements.push(self.value(value));
+                }
+
+                elements
+                    .into_iter()
+                    .collect::<Option<Vec<_>>>()
+                    .map(ast::Expression::set)
+            }
+            JsonKind::Object(object) => {
+                if object.len() == 1 {
+                    if let Some(inner) = object.get("__entity") {
+                        return self
+                            .ctx
+                            .entity_reference(inner)
+                            .map(ast::Expression::entity);
+                    }
+
+                    if let Some(inner) = object.get("__extn") {
+                        return self.extension_value(inner);
+                    }
+                }
+
+                let mut entries = Vec::with_capacity(object.len());
+                for member in object {
+                    let value = self.value(member.value());
+                    entries.push(value.map(|value| (Cow::Borrowed(member.key()), value)));
+                }
+
+                let entries = entries.into_iter().collect::<Option<Vec<_>>>()?;
+                self.record(entries, json.span())
+            }
+            JsonKind::Null => {
+                self.ctx.unexpected("a value", json);
+                None
+            }
+        }
+    }
+
+    /// Lowers the body of an `__extn` escape: `{ "fn": "ip", "arg": "10.0.0.1" }`.
+    fn extension_value<'src>(
+        &mut self,
+        json: &'src JsonValue<'_>,
+    ) -> Option<ast::Expression<'src>> {
+        let object = self.ctx.expect_object(json)?;
+        self.ctx.check_fields(object, &["fn", "arg"]);
+
+        let function = self.ctx.field(json, object, "fn").and_then(|function| {
+            let name = self.ctx.expect_str(function)?;
+            self.extension_function(name, function.span())
+        });
+
+        let argument = self
+            .ctx
+            .field(json, object, "arg")
+            .and_then(|argument| self.value(argument));
+
+        Some(ast::Expression::extension_call(
+            function?,
+            alloc::vec![argument?],
+        ))
+    }
+
+    fn record<'src>(
+        &mut self,
+        entries: Vec<(Cow<'src, str>, ast::Expression<'src>)>,
+        span: Range<usize>,
+    ) -> Option<ast::Expression<'src>> {
+        match ast::RecordExpression::new(entries) {
+            Ok(record) => Some(ast::Expression::record(record)),
+            Err(error) => {
+                self.ctx
+                    .diagnostics
+                    .push(Diagnostic::from(error).with_label(span, "in this record"));
+
+                None
+            }
+        }
+    }
+}
+
+/// Returns the binary operator for an expression key.
+fn binary_operator(key: &str) -> Option<ast::BinaryOperator> {
+    let operator = match key {
+        "==" => ast::BinaryOperator::Equal,
+        "!=" => ast::BinaryOperator::NotEqual,
+        "<" => ast::BinaryOperator::Less,
+        "<=" => ast::BinaryOperator::LessEqual,
+        ">" => ast::BinaryOperator::Greater,
+        ">=" => ast::BinaryOperator::GreaterEqual,
+        "in" => ast::BinaryOperator::In,
+        "+" => ast::BinaryOperator::Add,
+        "-" => ast::BinaryOperator::Subtract,
+        "*" => ast::BinaryOperator::Multiply,
+        "contains" => ast::BinaryOperator::Contains,
+        "containsAll" => ast::BinaryOperator::ContainsAll,
+        "containsAny" => ast::BinaryOperator::ContainsAny,
+        "getTag" => ast::BinaryOperator::GetTag,
+        "hasTag" => ast::BinaryOperator::HasTag,
+        _ => return None,
+    };
+
+    Some(operator)
+}
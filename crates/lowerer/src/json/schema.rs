@@ -0,0 +1,802 @@
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::mem;
+use core::ops::Range;
+
+use duramen_ast as ast;
+use duramen_diagnostic::{Diagnostic, Diagnostics};
+use duramen_json::{JsonMember, JsonObject, JsonValue};
+
+use crate::error::LowerError;
+use crate::json::context::JsonContext;
+
+/// Names of the extension types.
+const EXTENSION_TYPES: &[&str] = &["ipaddr", "decimal", "datetime", "duration"];
+
+/// Schema lowerer for the Cedar JSON schema format.
+///
+/// ```json
+/// {
+///   "Acme": {
+///     "commonTypes": { "Name": { "type": "String" } },
+///     "entityTypes": { "User": { "memberOfTypes": ["Group"] }, "Group": {} },
+///     "actions": {
+///       "view": { "appliesTo": { "principalTypes": ["User"], "resourceTypes": ["Group"] } }
+///     }
+///   }
+/// }
+/// ```
+pub struct SchemaJsonLowerer {
+    ctx: JsonContext,
+    /// Name of the namespace being lowered.
+    namespace: Option<String>,
+    /// Common types of every namespace, as `(namespace, name, definition)`.
+    common_types: Vec<(Option<String>, String, ast::TypeExpression<'static>)>,
+    /// Entity types of every namespace, as `(namespace, name)`.
+    entity_types: Vec<(Option<String>, String)>,
+}
+
+impl SchemaJsonLowerer {
+    /// Lowers a JSON schema and its diagnostics to an AST.
+    ///
+    /// The empty namespace name `""` lowers to a namespace without a name.
+    #[must_use]
+    pub fn lower<'src>(
+        json: &'src JsonValue<'_>,
+        diagnostics: Diagnostics,
+    ) -> (ast::Schema<'src>, Diagnostics) {
+        let mut this = Self {
+            ctx: JsonContext::new(diagnostics),
+            namespace: None,
+            common_types: Vec::new(),
+            entity_types: Vec::new(),
+        };
+
+        let mut namespaces = Vec::new();
+
+        if let Some(object) = this.ctx.expect_object(json) {
+            this.collect_declarations(object);
+
+            for member in object {
+                let name = if member.key().is_empty() {
+                    None
+                } else {
+                    match ast::Name::parse(member.key()) {
+                        Ok(name) => Some(name),
+                        Err(error) => {
+                            this.ctx.diagnostics.push(
+                                Diagnostic::from(error)
+                                    .with_label(member.key_span(), "invalid namespace name"),
+                            );
+
+                            continue;
+                        }
+                    }
+                };
+
+                if let Some(namespace) = this.namespace(name, member.value()) {
+                    namespaces.push(namespace);
+                }
+            }
+        }
+
+        (ast::Schema::new(namespaces), this.ctx.diagnostics)
+    }
+
+    /// Lowers every common type and collects every entity type name up front, so entity shapes
+    /// can refer to common types from any namespace.
+    ///
+    /// Diagnostics are discarded here and reported when each namespace is lowered.
+    fn collect_declarations(&mut self, object: &JsonObject<'_>) {
+        let diagnostics = mem::replace(&mut self.ctx.diagnostics, Diagnostics::new());
+
+        for namespace in object {
+            let Some(declarations) = namespace.value().as_object() else {
+                continue;
+            };
+
+            let name = (!namespace.key().is_empty()).then(|| String::from(namespace.key()));
+
+            if let Some(entities) = declarations
+                .get("entityTypes")
+                .and_then(JsonValue::as_object)
+            {
+                for member in entities {
+                    self.entity_types
+                        .push((name.clone(), String::from(member.key())));
+                }
+            }
+
+            let Some(types) = declarations
+                .get("commonTypes")
+                .and_then(JsonValue::as_object)
+            else {
+                continue;
+            };
+
+            for member in types {
+                if let Some(definition) = self.type_expression(member.value()) {
+                    self.common_types.push((
+                        name.clone(),
+                        String::from(member.key()),
+                        definition.into_owned(),
+                    ));
+                }
+            }
+        }
+
+        self.ctx.diagnostics = diagnostics;
+    }
+
+    fn namespace<'src>(
+        &mut self,
+        name: Option<ast::Name<'src>>,
+        json: &'src JsonValue<'_>,
+    ) -> Option<ast::Namespace<'src>> {
+        let object = self.ctx.expect_object(json)?;
+        self.ctx.check_fields(
+            object,
+            &["commonTypes", "entityTypes", "actions", "annotations"],
+        );
+
+        let annotations = self.annotations(object);
+        self.namespace = name.as_ref().map(ToString::to_string);
+
+        // Both sections are required, even when empty.
+        for field in ["entityTypes", "actions"] {
+            self.ctx.field(json, object, field);
+        }
+
+        let mut declarations = Vec::new();
+
+        for member in object {
+            match member.key() {
+                "commonTypes" => {
+                    let Some(types) = self.ctx.expect_object(member.value()) else {
+                        continue;
+                    };
+
+                    for member in types {
+                        if let Some(declaration) = self.type_declaration(member) {
+                            declarations.push(ast::Declaration::Type(declaration));
+                        }
+                    }
+                }
+                "entityTypes" => {
+                    let Some(entities) = self.ctx.expect_object(member.value()) else {
+                        continue;
+                    };
+
+                    for member in entities {
+                        if let Some(declaration) = self.entity_declaration(member) {
+                            declarations.push(ast::Declaration::Entity(declaration));
+                        }
+                    }
+                }
+                "actions" => {
+                    let Some(actions) = self.ctx.expect_object(member.value()) else {
+                        continue;
+                    };
+
+                    for member in actions {
+                        if let Some(declaration) = self.action_declaration(member) {
+                            declarations.push(ast::Declaration::Action(declaration));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        match ast::Namespace::new(annotations?, name, declarations) {
+            Ok(namespace) => Some(namespace),
+            Err(error) => {
+                self.ctx
+                    .diagnostics
+                    .push(Diagnostic::from(error).with_label(json.span(), "in this namespace"));
+
+                None
+            }
+        }
+    }
+
+    /// Lowers the optional `annotations` member of an object.
+    fn annotations<'src>(
+        &mut self,
+        object: &'src JsonObject<'_>,
+    ) -> Option<ast::Annotations<'src>> {
+        match object.get("annotations") {
+            Some(annotations) => self.ctx.annotations(annotations),
+            None => Some(ast::Annotations::empty()),
+        }
+    }
+
+    /// Lowers a common type: `"Name": { "type": "String" }`.
+    fn type_declaration<'src>(
+        &mut self,
+        member: &'src JsonMember<'_>,
+    ) -> Option<ast::TypeDeclaration<'src>> {
+        let identifier = self.identifier(member.key(), member.key_span());
+
+        let object = self.ctx.expect_object(member.value())?;
+        let annotations = self.annotations(object);
+        let definition = self.type_expression(member.value());
+
+        match ast::TypeDeclaration::new(annotations?, identifier?, definition?) {
+            Ok(declaration) => Some(declaration.with_span(member.span())),
+            Err(error) => {
+                self.ctx.diagnostics.push(
+                    Diagnostic::from(error)
+                        .with_label(member.key_span(), "in this type declaration"),
+                );
+
+                None
+            }
+        }
+    }
+
+    /// Lowers an entity type: `"User": { "memberOfTypes": [...], "shape": {...}, "tags": {...} }`.
+    fn entity_declaration<'src>(
+        &mut self,
+        member: &'src JsonMember<'_>,
+    ) -> Option<ast::EntityDeclaration<'src>> {
+        let json = member.value();
+        let identifier = self.identifier(member.key(), member.key_span());
+
+        let object = self.ctx.expect_object(json)?;
+        let annotations = self.annotations(object);
+
+        let kind = if let Some(variants) = object.get("enum") {
+            self.ctx.check_fields(object, &["enum", "annotations"]);
+            self.enum_type(variants).map(ast::EntityKind::Enum)
+        } else {
+            self.ctx
+                .check_fields(object, &["memberOfTypes", "shape", "tags", "annotations"]);
+
+            self.standard_entity(json, object)
+                .map(ast::EntityKind::Standard)
+        };
+
+        let result = ast::EntityDeclaration::new(annotations?, alloc::vec![identifier?], kind?);
+        match result {
+            Ok(declaration) => Some(declaration.with_span(member.span())),
+            Err(error) => {
+                self.ctx.diagnostics.push(
+                    Diagnostic::from(error)
+                        .with_label(member.key_span(), "in this entity declaration"),
+                );
+
+                None
+            }
+        }
+    }
+
+    fn standard_entity<'src>(
+        &mut self,
+        json: &'src JsonValue<'_>,
+        object: &'src JsonObject<'_>,
+    ) -> Option<ast::StandardEntity<'src>> {
+        let parents = match object.get("memberOfTypes") {
+            Some(parents) => self.names(parents),
+            None => Some(Vec::new()),
+        };
+
+        let attributes = match object.get("shape") {
+            Some(shape) => self.shape(shape),
+            None => Some(Vec::new()),
+        };
+
+        let tags = match object.get("tags") {
+            Some(tags) => self.type_expression(tags).map(Some),
+            None => Some(None),
+        };
+
+        let (parents, attributes, tags) = (parents?, attributes?, tags?);
+        match ast::StandardEntity::new(parents, attributes, tags) {
+            Ok(standard) => Some(standard),
+            Err(error) => {
+                self.ctx.diagnostics.push(
+                    Diagnostic::from(error).with_label(json.span(), "in this entity declaration"),
+                );
+
+                None
+            }
+        }
+    }
+
+    /// Lowers an entity shape, which must be a record type or a common type defined as one.
+    ///
+    /// Common types are inlined, since entity declarations hold their attributes directly. Names
+    /// in a common type from another namespace are qualified, so they still refer to that
+    /// namespace.
+    fn shape<'src>(
+        &mut self,
+        json: &'src JsonValue<'_>,
+    ) -> Option<Vec<(Cow<'src, str>, ast::AttributeDeclaration<'src>)>> {
+        let object = self.ctx.expect_object(json)?;
+        let kind = self.ctx.field(json, object, "type")?;
+
+        if self.ctx.expect_str(kind)? == "Record" {
+            return self.attributes(json, object);
+        }
+
+        let ast::TypeExpression::Reference(name) = self.type_expression(json)? else {
+            self.ctx.unexpected("a record type", kind);
+            return None;
+        };
+
+        let Some((namespace, record)) = self.common_record(&name) else {
+            self.ctx
+                .diagnostics
+                .push(LowerError::InvalidEntityShape { span: json.span() });
+
+            return None;
+        };
+
+        let foreign = namespace.filter(|namespace| self.namespace.as_deref() != Some(*namespace));
+
+        let attributes = record
+            .attributes()
+            .map(|(key, attribute)| {
+                let mut attribute = attribute.clone();
+                if let Some(namespace) = foreign {
+                    self.qualify(namespace, attribute.definition_mut());
+                }
+
+                (Cow::Owned(String::from(key)), attribute)
+            })
+            .collect();
+
+        Some(attributes)
+    }
+
+    /// Qualifies the unqualified names in a type that refer to declarations in `namespace`.
+    fn qualify(&self, namespace: &str, expression: &mut ast::TypeExpression<'static>) {
+        match expression {
+            ast::TypeExpression::Reference(name) => {
+                if let Some(qualified) = self.qualified(namespace, name) {
+                    *name = qualified;
+                }
+            }
+            ast::TypeExpression::Set(element) => self.qualify(namespace, element),
+            ast::TypeExpression::Record(record) => {
+                for (_, attribute) in record.attributes_mut() {
+                    self.qualify(namespace, attribute.definition_mut());
+                }
+            }
+            ast::TypeExpression::Entity(types) => {
+                let names = types
+                    .iter()
+                    .map(|name| {
+                        self.qualified(namespace, name)
+                            .unwrap_or_else(|| name.clone())
+                    })
+                    .collect();
+
+                if let Ok(qualified) = ast::EntityTypeSet::new(names) {
+                    *types = qualified;
+                }
+            }
+            ast::TypeExpression::Enum(_) => {}
+        }
+    }
+
+    /// Returns `namespace::name` for an unqualified name declared in `namespace`.
+    ///
+    /// Other names, such as built-in types, resolve the same way from every namespace.
+    fn qualified(&self, namespace: &str, name: &ast::Name<'_>) -> Option<ast::Name<'static>> {
+        if !name.path().is_empty() {
+            return None;
+        }
+
+        let basename = name.basename().as_str();
+        let declared = self
+            .common_types
+            .iter()
+            .map(|(candidate, name, _)| (candidate, name))
+            .chain(
+                self.entity_types
+                    .iter()
+                    .map(|(candidate, name)| (candidate, name)),
+            )
+            .any(|(candidate, name)| candidate.as_deref() == Some(namespace) && name == basename);
+
+        if !declared {
+            return None;
+        }
+
+        ast::Name::parse(&format!("{namespace}::{basename}"))
+            .ok()
+            .map(ast::Name::into_owned)
+    }
+
+    /// Follows common type references from the current namespace until a record type.
+    ///
+    /// Returns the namespace the record type was declared in along with the record type.
+    fn common_record(
+        &self,
+        name: &ast::Name<'_>,
+    ) -> Option<(Option<&str>, &ast::RecordType<'static>)> {
+        let mut namespace = self.namespace.as_deref();
+        let mut name = name.to_string();
+
+        // Each step visits a different common type, unless the references form a cycle.
+        for _ in 0..=self.common_types.len() {
+            let (found, definition) = self.common_type(namespace, &name)?;
+
+            match definition {
+                ast::TypeExpression::Record(record) => return Some((found, record)),
+                ast::TypeExpression::Reference(next) => {
+                    namespace = found;
+                    name = next.to_string();
+                }
+                ast::TypeExpression::Set(_)
+                | ast::TypeExpression::Entity(_)
+                | ast::TypeExpression::Enum(_) => return None,
+            }
+        }
+
+        None
+    }
+
+    /// Looks up a common type, first in `namespace` and then in the unnamed namespace.
+    ///
+    /// Returns the namespace it was found in along with its definition.
+    fn common_type<'a>(
+        &'a self,
+        namespace: Option<&'a str>,
+        name: &str,
+    ) -> Option<(Option<&'a str>, &'a ast::TypeExpression<'static>)> {
+        let find = |namespace: Option<&str>, name: &str| {
+            self.common_types
+                .iter()
+                .find(|(candidate, basename, _)| {
+                    candidate.as_deref() == namespace && basename == name
+                })
+                .map(|(candidate, _, definition)| (candidate.as_deref(), definition))
+        };
+
+        if let Some((path, basename)) = name.rsplit_once("::") {
+            return find(Some(path), basename);
+        }
+
+        namespace
+            .and_then(|namespace| find(Some(namespace), name))
+            .or_else(|| find(None, name))
+    }
+
+    /// Lowers an action: `"view": { "memberOf": [...], "appliesTo": {...} }`.
+    fn action_declaration<'src>(
+        &mut self,
+        member: &'src JsonMember<'_>,
+    ) -> Option<ast::ActionDeclaration<'src>> {
+        let json = member.value();
+        let object = self.ctx.expect_object(json)?;
+        self.ctx
+            .check_fields(object, &["memberOf", "appliesTo", "annotations"]);
+
+        let annotations = self.annotations(object);
+
+        let parents = match object.get("memberOf") {
+            Some(parents) => self.action_references(parents),
+            None => Some(Vec::new()),
+        };
+
+        let applies_to = match object.get("appliesTo") {
+            Some(applies_to) => self.applies_to(applies_to).map(Some),
+            None => Some(None),
+        };
+
+        let result = ast::ActionDeclaration::new(
+            annotations?,
+            alloc::vec![Cow::Borrowed(member.key())],
+            parents?,
+            applies_to?,
+            Vec::new(),
+        );
+
+        match result {
+            Ok(declaration) => Some(declaration.with_span(member.span())),
+            Err(error) => {
+                self.ctx.diagnostics.push(
+                    Diagnostic::from(error)
+                        .with_label(member.key_span(), "in this action declaration"),
+                );
+
+                None
+            }
+        }
+    }
+
+    /// Lowers action parents: `[{ "id": "read", "type": "Acme::Action" }]`.
+    fn action_references<'src>(
+        &mut self,
+        json: &'src JsonValue<'_>,
+    ) -> Option<Vec<ast::ActionReference<'src>>> {
+        let values = self.ctx.expect_array(json)?;
+
+        let mut references = Vec::with_capacity(values.len());
+        for value in values {
+            references.push(self.action_reference(value));
+        }
+
+        references.into_iter().collect()
+    }
+
+    fn action_reference<'src>(
+        &mut self,
+        json: &'src JsonValue<'_>,
+    ) -> Option<ast::ActionReference<'src>> {
+        let object = self.ctx.expect_object(json)?;
+        self.ctx.check_fields(object, &["id", "type"]);
+
+        let kind = match object.get("type") {
+            Some(kind) => self.ctx.name(kind).map(Some),
+            None => Some(None),
+        };
+
+        let id = self
+            .ctx
+            .field(json, object, "id")
+            .and_then(|id| self.ctx.expect_str(id));
+
+        Some(ast::ActionReference::new(kind?, Cow::Borrowed(id?)))
+    }
+
+    /// Lowers `{ "principalTypes": [...], "resourceTypes": [...], "context": {...} }`.
+    fn applies_to<'src>(&mut self, json: &'src JsonValue<'_>) -> Option<ast::AppliesTo<'src>> {
+        let object = self.ctx.expect_object(json)?;
+        self.ctx
+            .check_fields(object, &["principalTypes", "resourceTypes", "context"]);
+
+        let principals = match object.get("principalTypes") {
+            Some(principals) => self.names(principals),
+            None => Some(Vec::new()),
+        };
+
+        let resources = match object.get("resourceTypes") {
+            Some(resources) => self.names(resources),
+            None => Some(Vec::new()),
+        };
+
+        let context = match object.get("context") {
+            Some(context) => self.context_type(context).map(Some),
+            None => Some(None),
+        };
+
+        match ast::AppliesTo::new(principals?, resources?, context?) {
+            Ok(applies_to) => Some(applies_to),
+            Err(error) => {
+                self.ctx.diagnostics.push(
+                    Diagnostic::from(error).with_label(json.span(), "in this `appliesTo` clause"),
+                );
+
+                None
+            }
+        }
+    }
+
+    /// Lowers a context type, which must be a record or a type reference.
+    fn context_type<'src>(&mut self, json: &'src JsonValue<'_>) -> Option<ast::ContextType<'src>> {
+        match self.type_expression(json)? {
+            ast::TypeExpression::Record(record) => Some(ast::ContextType::Record(record)),
+            ast::TypeExpression::Reference(name) => Some(ast::ContextType::Reference(name)),
+            ast::TypeExpression::Set(_)
+            | ast::TypeExpression::Entity(_)
+            | ast::TypeExpression::Enum(_) => {
+                self.ctx
+                    .diagnostics
+                    .push(LowerError::InvalidContextType { span: json.span() });
+
+                None
+            }
+        }
+    }
+
+    /// Lowers a type: `{ "type": "Set", "element": { "type": "Long" } }`.
+    fn type_expression<'src>(
+        &mut self,
+        json: &'src JsonValue<'_>,
+    ) -> Option<ast::TypeExpression<'src>> {
+        let object = self.ctx.expect_object(json)?;
+        let kind = self.ctx.field(json, object, "type")?;
+
+        match self.ctx.expect_str(kind)? {
+            "Set" => {
+                self.ctx
+                    .check_fields(object, &["type", "element", "annotations", "required"]);
+
+                let element = self.ctx.field(json, object, "element")?;
+                let element = self.type_expression(element)?;
+                Some(ast::TypeExpression::Set(Box::new(element)))
+            }
+            "Record" => {
+                let attributes = self.attributes(json, object)?;
+                match ast::RecordType::new(attributes) {
+                    Ok(record) => Some(ast::TypeExpression::Record(record)),
+                    Err(error) => {
+                        self.ctx.diagnostics.push(
+                            Diagnostic::from(error).with_label(json.span(), "in this record type"),
+                        );
+
+                        None
+                    }
+                }
+            }
+            kind @ ("Entity" | "Extension" | "EntityOrCommon") => {
+                self.ctx
+                    .check_fields(object, &["type", "name", "annotations", "required"]);
+
+                let json = self.ctx.field(json, object, "name")?;
+                let name = self.ctx.name(json)?;
+
+                match kind {
+                    "Entity" => match ast::EntityTypeSet::new(alloc::vec![name]) {
+                        Ok(types) => Some(ast::TypeExpression::Entity(types)),
+                        Err(error) => {
+                            self.ctx.diagnostics.push(
+                                Diagnostic::from(error).with_label(json.span(), "in this type"),
+                            );
+
+                            None
+                        }
+                    },
+                    "Extension" if !EXTENSION_TYPES.contains(&name.to_string().as_str()) => {
+                        self.ctx.diagnostics.push(LowerError::UnknownExtensionType {
+                            span: json.span(),
+                            name: name.to_string(),
+                        });
+
+                        None
+                    }
+                    _ => Some(ast::TypeExpression::Reference(name)),
+                }
+            }
+            "Boolean" => {
+                self.ctx
+                    .check_fields(object, &["type", "annotations", "required"]);
+
+                let name = ast::Identifier::new("Bool").ok()?;
+                Some(ast::TypeExpression::Reference(ast::Name::unqualified(name)))
+            }
+            _ => {
+                self.ctx
+                    .check_fields(object, &["type", "annotations", "required"]);
+
+                self.ctx.name(kind).map(ast::TypeExpression::Reference)
+            }
+        }
+    }
+
+    /// Lowers record attributes: `{ "attributes": { "name": { "type": "String", "required": false } } }`.
+    fn attributes<'src>(
+        &mut self,
+        json: &'src JsonValue<'_>,
+        object: &'src JsonObject<'_>,
+    ) -> Option<Vec<(Cow<'src, str>, ast::AttributeDeclaration<'src>)>> {
+        self.ctx.check_fields(
+            object,
+            &[
+                "type",
+                "attributes",
+                "additionalAttributes",
+                "annotations",
+                "required",
+            ],
+        );
+
+        if let Some(additional) = object.get("additionalAttributes") {
+            match additional.as_bool() {
+                Some(false) => {}
+                Some(true) => {
+                    self.ctx.diagnostics.push(LowerError::AdditionalAttributes {
+                        span: additional.span(),
+                    });
+
+                    return None;
+                }
+                None => {
+                    self.ctx.unexpected("a boolean", additional);
+                    return None;
+                }
+            }
+        }
+
+        let attributes = self.ctx.field(json, object, "attributes")?;
+        let attributes = self.ctx.expect_object(attributes)?;
+
+        let mut result = Vec::with_capacity(attributes.len());
+        let mut valid = true;
+
+        for member in attributes {
+            match self.attribute(member.value()) {
+                Some(attribute) => result.push((Cow::Borrowed(member.key()), attribute)),
+                None => valid = false,
+            }
+        }
+
+        valid.then_some(result)
+    }
+
+    fn attribute<'src>(
+        &mut self,
+        json: &'src JsonValue<'_>,
+    ) -> Option<ast::AttributeDeclaration<'src>> {
+        let object = self.ctx.expect_object(json)?;
+        let annotations = self.annotations(object);
+
+        let optionality = match object.get("required") {
+            Some(required) => match required.as_bool() {
+                Some(true) => Some(ast::Optionality::Required),
+                Some(false) => Some(ast::Optionality::Optional),
+                None => {
+                    self.ctx.unexpected("a boolean", required);
+                    None
+                }
+            },
+            None => Some(ast::Optionality::Required),
+        };
+
+        let definition = self.type_expression(json);
+
+        Some(ast::AttributeDeclaration::new(
+            annotations?,
+            optionality?,
+            definition?,
+        ))
+    }
+
+    /// Lowers enum entity variants: `["a", "b"]`.
+    fn enum_type<'src>(&mut self, json: &'src JsonValue<'_>) -> Option<ast::EnumType<'src>> {
+        let values = self.ctx.expect_array(json)?;
+
+        let mut variants = Vec::with_capacity(values.len());
+        for value in values {
+            variants.push(self.ctx.expect_str(value).map(Cow::Borrowed));
+        }
+
+        let variants = variants.into_iter().collect::<Option<Vec<_>>>()?;
+        match ast::EnumType::new(variants) {
+            Ok(enum_type) => Some(enum_type),
+            Err(error) => {
+                self.ctx
+                    .diagnostics
+                    .push(Diagnostic::from(error).with_label(json.span(), "in this enum type"));
+
+                None
+            }
+        }
+    }
+
+    /// Lowers an array of `::` separated names.
+    fn names<'src>(&mut self, json: &'src JsonValue<'_>) -> Option<Vec<ast::Name<'src>>> {
+        let values = self.ctx.expect_array(json)?;
+
+        let mut names = Vec::with_capacity(values.len());
+        for value in values {
+            names.push(self.ctx.name(value));
+        }
+
+        names.into_iter().collect()
+    }
+
+    fn identifier<'src>(
+        &mut self,
+        name: &'src str,
+        span: Range<usize>,
+    ) -> Option<ast::Identifier<'src>> {
+        match ast::Identifier::new(name) {
+            Ok(identifier) => Some(identifier),
+            Err(error) => {
+                self.ctx
+                    .diagnostics
+                    .push(Diagnostic::from(error).with_label(span, "invalid identifier"));
+
+                None
+            }
+        }
+    }
+}
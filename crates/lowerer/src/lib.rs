@@ -9,6 +9,9 @@ pub(crate) mod common;
 
 mod error;
 
+mod json;
+pub use json::{PolicyJsonLowerer, SchemaJsonLowerer};
+
 mod policy;
 pub use policy::PolicyLowerer;
 
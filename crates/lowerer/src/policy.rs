@@ -2,6 +2,7 @@ use alloc::borrow::Cow;
 use alloc::string::String;
 use alloc::vec;
 use alloc::vec::Vec;
+use core::ops::Range;
 
 use duramen_ast as ast;
 use duramen_cst::{self as cst, CstNode as _};
@@ -21,6 +22,7 @@ pub const EXTENSION_FUNCTIONS: &[&str] = &[
     "date",
     "time",
     "offset",
+    "durationSince",
     "toDate",
     "toTime",
     "toDuration",
@@ -71,6 +73,48 @@ impl PolicyLowerer {
         (ast::Policies::new(result), this.ctx.diagnostics)
     }
 
+    /// Lowers a parsed tree to a policy set, assigning each policy an ID.
+    ///
+    /// IDs come from the `@id` annotation, or are generated from the position of the policy.
+    #[must_use]
+    pub fn lower_set<'src>(
+        tree: &'src Tree<'_>,
+        diagnostics: Diagnostics,
+    ) -> (ast::PolicySet<'src>, Diagnostics) {
+        let mut this = Self {
+            ctx: LowerContext::new(diagnostics),
+        };
+
+        let mut set = ast::PolicySet::empty();
+
+        if let Some(root) = tree.root()
+            && let Some(policies) = cst::Policies::cast(root)
+        {
+            for (index, policy) in policies.policies().enumerate() {
+                let Some(lowered) = this.lower_policy(&policy) else {
+                    continue;
+                };
+
+                let id = ast::PolicySet::assign_id(&lowered, index);
+                if let Err(error) = set.insert(id, lowered) {
+                    this.ctx.diagnostics.push(error);
+                }
+            }
+        }
+
+        (set, this.ctx.diagnostics)
+    }
+
+    /// Returns the span of the `@id` annotation value, or of the policy effect for generated IDs.
+    fn id_span(policy: &cst::Policy<'_>) -> Range<usize> {
+        policy
+            .annotations()
+            .find(|annotation| annotation.name().is_some_and(|name| name.text() == "id"))
+            .and_then(|annotation| annotation.value().or_else(|| annotation.name()))
+            .or_else(|| policy.effect_token())
+            .map_or_else(|| policy.range(), |node| node.range())
+    }
+
     /// Lowers a single policy.
     fn lower_policy<'src>(&mut self, policy: &cst::Policy<'src>) -> Option<ast::Policy<'src>> {
         let annotations = self.ctx.lower_annotations(policy.annotations())?;
@@ -90,6 +134,7 @@ impl PolicyLowerer {
 
         let mut principal = None;
         let mut action = None;
+        let mut action_span = None;
         let mut resource = None;
 
         for variable_definition in policy.variable_definitions() {
@@ -101,16 +146,23 @@ impl PolicyLowerer {
                 cst::Variable::Principal => {
                     principal = Some(
                         self.lower_scope_constraint(&variable_definition, "principal")
-                            .map(ast::PrincipalConstraint::new),
+                            .map(|constraint| {
+                                ast::PrincipalConstraint::new(constraint)
+                                    .with_span(variable_definition.trimmed_range())
+                            }),
                     );
                 }
                 cst::Variable::Action => {
                     action = Some(self.lower_action_constraint(&variable_definition));
+                    action_span = Some(variable_definition.trimmed_range());
                 }
                 cst::Variable::Resource => {
                     resource = Some(
                         self.lower_scope_constraint(&variable_definition, "resource")
-                            .map(ast::ResourceConstraint::new),
+                            .map(|constraint| {
+                                ast::ResourceConstraint::new(constraint)
+                                    .with_span(variable_definition.trimmed_range())
+                            }),
                     );
                 }
                 cst::Variable::Context => {
@@ -146,14 +198,16 @@ impl PolicyLowerer {
             }
         }
 
-        Some(ast::Policy::new(
-            annotations,
-            effect,
-            principal,
-            action,
-            resource,
-            conditions,
-        ))
+        let mut lowered =
+            ast::Policy::new(annotations, effect, principal, action, resource, conditions)
+                .with_span(policy.trimmed_range())
+                .with_id_span(Self::id_span(policy));
+
+        if let Some(span) = action_span {
+            lowered = lowered.with_action_span(span);
+        }
+
+        Some(lowered)
     }
 
     /// Lowers a principal or resource scope constraint.
@@ -382,7 +436,7 @@ impl PolicyLowerer {
         &mut self,
         expression: &cst::Expression<'src>,
     ) -> Option<ast::Expression<'src>> {
-        match expression {
+        let lowered = match expression {
             cst::Expression::If(if_expression) => self.lower_if(if_expression),
             cst::Expression::Or(or_expression) => self.lower_or(or_expression),
             cst::Expression::And(and_expression) => self.lower_and(and_expression),
@@ -407,7 +461,9 @@ impl PolicyLowerer {
             cst::Expression::List(list) => Some(self.lower_list(list)),
             cst::Expression::Record(record) => self.lower_record(record),
             cst::Expression::Name(name) => self.lower_name_expression(name),
-        }
+        };
+
+        lowered.map(|lowered| lowered.with_span(expression.trimmed_range()))
     }
 
     /// Lowers an if expression.
@@ -584,8 +640,11 @@ impl PolicyLowerer {
         let left = expression.expression()?;
         let left = self.lower_expression(&left)?;
 
-        let pattern = expression.pattern()?;
-        let pattern = pattern.syntax();
+        // Use the string token itself so trailing trivia is not part of the pattern.
+        let pattern = match expression.pattern()? {
+            cst::Expression::Literal(literal) => literal.token()?,
+            pattern => pattern.syntax(),
+        };
 
         let raw = pattern.text();
         let offset = pattern.range().start;
@@ -672,9 +731,14 @@ impl PolicyLowerer {
             cst::UnaryOperator::Negate => ast::UnaryOperator::Negate,
         };
 
-        let mut result = ast::Expression::unary(ast_operator, operand);
-        for _ in 1..operator_count {
-            result = ast::Expression::unary(ast_operator, result);
+        // Each nested operator spans from its own token to the end of the operand.
+        let end = expression.trimmed_range().end;
+        let mut tokens: Vec<_> = expression.operator_tokens().collect();
+
+        let mut result = operand;
+        while let Some(token) = tokens.pop() {
+            result =
+                ast::Expression::unary(ast_operator, result).with_span(token.range().start..end);
         }
 
         Some(result)
@@ -687,15 +751,18 @@ impl PolicyLowerer {
     ) -> Option<ast::Expression<'src>> {
         let base = expression.expression()?;
         let accesses: Vec<_> = expression.accesses().collect();
+        let start = expression.trimmed_range().start;
 
         if let cst::Expression::Name(name) = &base
             && let Some(cst::MemberAccess::Call(call)) = accesses.first()
             && call.dot().is_none()
         {
-            let mut result = self.lower_function_call(name, call)?;
+            let span = start..call.trimmed_range().end;
+            let mut result = self.lower_function_call(name, call)?.with_span(span);
 
             for access in &accesses[1..] {
-                result = self.lower_member_access(result, access)?;
+                let span = start..access.trimmed_range().end;
+                result = self.lower_member_access(result, access)?.with_span(span);
             }
 
             return Some(result);
@@ -703,8 +770,10 @@ impl PolicyLowerer {
 
         let mut result = self.lower_expression(&base)?;
 
+        // Each access in the chain spans from the start of the base to its own end.
         for access in &accesses {
-            result = self.lower_member_access(result, access)?;
+            let span = start..access.trimmed_range().end;
+            result = self.lower_member_access(result, access)?.with_span(span);
         }
 
         Some(result)
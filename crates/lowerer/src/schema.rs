@@ -170,7 +170,7 @@ impl SchemaLowerer {
             let kind = ast::EntityKind::Enum(enum_type);
 
             return match ast::EntityDeclaration::new(annotations, names, kind) {
-                Ok(declaration) => Some(declaration),
+                Ok(declaration) => Some(declaration.with_span(entity.trimmed_range())),
                 Err(error) => {
                     self.ctx.diagnostics.push(
                         Diagnostic::from(error)
@@ -214,7 +214,7 @@ impl SchemaLowerer {
         let kind = ast::EntityKind::Standard(standard);
 
         match ast::EntityDeclaration::new(annotations, names, kind) {
-            Ok(declaration) => Some(declaration),
+            Ok(declaration) => Some(declaration.with_span(entity.trimmed_range())),
             Err(error) => {
                 self.ctx.diagnostics.push(
                     Diagnostic::from(error)
@@ -235,8 +235,11 @@ impl SchemaLowerer {
 
         let mut names: Vec<Cow<'src, str>> = Vec::new();
         for name in action.names() {
-            let text = name.basename()?;
-            names.push(Cow::Borrowed(text));
+            if let Some(string) = name.syntax().child(Token::String) {
+                names.push(self.ctx.lower_string(string)?);
+            } else {
+                names.push(Cow::Borrowed(name.basename()?));
+            }
         }
 
         if names.is_empty() {
@@ -260,7 +263,7 @@ impl SchemaLowerer {
         };
 
         match ast::ActionDeclaration::new(annotations, names, parents, applies_to, attributes) {
-            Ok(declaration) => Some(declaration),
+            Ok(declaration) => Some(declaration.with_span(action.trimmed_range())),
             Err(error) => {
                 self.ctx.diagnostics.push(
                     Diagnostic::from(error)
@@ -279,27 +282,28 @@ impl SchemaLowerer {
     ) -> Vec<ast::ActionReference<'src>> {
         let mut result = Vec::new();
 
-        for entity_reference in parents.entity_references() {
-            let kind = entity_reference
-                .kind()
-                .and_then(|n| self.ctx.lower_name(&n));
-            if let Some(id_node) = entity_reference.id()
-                && let Some(id) = self.ctx.lower_string(id_node)
+        // Action parents can be entity references or bare names, kept in source order
+        for child in parents.syntax().children() {
+            if let Some(entity_reference) = cst::EntityReference::cast(child) {
+                let kind = entity_reference
+                    .kind()
+                    .and_then(|n| self.ctx.lower_name(&n));
+                if let Some(id_node) = entity_reference.id()
+                    && let Some(id) = self.ctx.lower_string(id_node)
+                {
+                    result.push(ast::ActionReference::new(kind, id));
+                }
+            } else if let Some(name) = cst::Name::cast(child)
+                && let Some(text) = name.basename()
             {
-                result.push(ast::ActionReference::new(kind, id));
+                result.push(ast::ActionReference::new(None, Cow::Borrowed(text)));
+            } else if child.kind() == Token::String
+                && let Some(id) = self.ctx.lower_string(child)
+            {
+                result.push(ast::ActionReference::new(None, id));
             }
         }
 
-        // Action parents can also appear as bare names without entity reference syntax
-        for name in parents.names() {
-            let Some(text) = name.basename() else {
-                continue;
-            };
-
-            let kind_name = self.ctx.lower_name(&name);
-            result.push(ast::ActionReference::new(kind_name, Cow::Borrowed(text)));
-        }
-
         result
     }
 
@@ -386,7 +390,7 @@ impl SchemaLowerer {
         let definition = self.lower_type_expression(&definition)?;
 
         match ast::TypeDeclaration::new(annotations, identifier, definition) {
-            Ok(declaration) => Some(declaration),
+            Ok(declaration) => Some(declaration.with_span(type_declaration.trimmed_range())),
             Err(error) => {
                 self.ctx.diagnostics.push(
                     Diagnostic::from(error)
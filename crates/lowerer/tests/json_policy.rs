@@ -0,0 +1,293 @@
+#![expect(clippy::unwrap_used, reason = "Tests")]
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use duramen_diagnostic::Diagnostics;
+use duramen_json::JsonParser;
+use duramen_lowerer::{PolicyJsonLowerer, PolicyLowerer};
+use duramen_parser::PolicyParser;
+use duramen_test::anstream::adapter::strip_str;
+use duramen_test::insta::assert_snapshot;
+
+extern crate alloc;
+
+fn render(diagnostics: &Diagnostics, source: &str) -> String {
+    let rendered = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.render("test.json", source))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    strip_str(&rendered).to_string()
+}
+
+/// Asserts that a JSON policy lowers to the same AST as its textual form.
+fn assert_same(text: &str, json: &str) {
+    let (tree, diagnostics) = PolicyParser::parse(text);
+    let (expected, diagnostics) = PolicyLowerer::lower(&tree, diagnostics);
+    assert!(diagnostics.is_empty(), "{text}");
+
+    let value = JsonParser::parse(json).unwrap();
+    let (actual, diagnostics) = PolicyJsonLowerer::lower_policy(&value, Diagnostics::new());
+    assert!(diagnostics.is_empty(), "{}", render(&diagnostics, json));
+
+    assert_eq!(
+        format!("{:?}", actual.unwrap()),
+        format!("{:?}", expected.policies()[0]),
+        "{text}"
+    );
+}
+
+/// Asserts that a JSON condition body lowers to the same AST as a textual expression.
+fn assert_expression(text: &str, body: &str) {
+    assert_same(
+        &format!("permit(principal, action, resource) when {{ {text} }};"),
+        &format!(
+            r#"{{
+                "effect": "permit",
+                "principal": {{ "op": "All" }},
+                "action": {{ "op": "All" }},
+                "resource": {{ "op": "All" }},
+                "conditions": [{{ "kind": "when", "body": {body} }}]
+            }}"#
+        ),
+    );
+}
+
+fn lower(source: &str) -> (usize, String) {
+    let json = JsonParser::parse(source).unwrap();
+    let (policies, diagnostics) = PolicyJsonLowerer::lower(&json, Diagnostics::new());
+    (policies.policies().len(), render(&diagnostics, source))
+}
+
+#[test]
+fn scope() {
+    assert_same(
+        r#"
+        @id("scoped")
+        @flag
+        forbid(principal == User::"alice", action in [Action::"view", Action::"edit"], resource is Photo in Album::"trip")
+        unless { false };
+        "#,
+        r#"{
+            "effect": "forbid",
+            "principal": { "op": "==", "entity": { "type": "User", "id": "alice" } },
+            "action": { "op": "in", "entities": [{ "type": "Action", "id": "view" }, { "type": "Action", "id": "edit" }] },
+            "resource": { "op": "is", "entity_type": "Photo", "in": { "entity": { "type": "Album", "id": "trip" } } },
+            "conditions": [{ "kind": "unless", "body": { "Value": false } }],
+            "annotations": { "id": "scoped", "flag": null }
+        }"#,
+    );
+
+    assert_same(
+        r#"permit(principal in Org::Group::"admins", action == Action::"view", resource is Photo);"#,
+        r#"{
+            "effect": "permit",
+            "principal": { "op": "in", "entity": { "__entity": { "type": "Org::Group", "id": "admins" } } },
+            "action": { "op": "==", "entity": { "type": "Action", "id": "view" } },
+            "resource": { "op": "is", "entity_type": "Photo" }
+        }"#,
+    );
+}
+
+#[test]
+fn templates() {
+    assert_same(
+        "permit(principal == ?principal, action in Action::\"view\", resource is Photo in ?resource) when { ?principal has name };",
+        r#"{
+            "effect": "permit",
+            "principal": { "op": "==", "slot": "?principal" },
+            "action": { "op": "in", "entity": { "type": "Action", "id": "view" } },
+            "resource": { "op": "is", "entity_type": "Photo", "in": { "slot": "?resource" } },
+            "conditions": [{ "kind": "when", "body": { "has": { "left": { "Slot": "?principal" }, "attr": "name" } } }]
+        }"#,
+    );
+}
+
+#[test]
+fn literals() {
+    assert_expression("true", r#"{ "Value": true }"#);
+    assert_expression("-42", r#"{ "Value": -42 }"#);
+    assert_expression(r#""a\"b""#, r#"{ "Value": "a\"b" }"#);
+    assert_expression(
+        r#"User::"alice""#,
+        r#"{ "Value": { "__entity": { "type": "User", "id": "alice" } } }"#,
+    );
+    assert_expression(r#"[1, "two"]"#, r#"{ "Value": [1, "two"] }"#);
+    assert_expression(
+        r#"{a: 1, "b c": true}"#,
+        r#"{ "Value": { "a": 1, "b c": true } }"#,
+    );
+    assert_expression(
+        r#"ip("10.0.0.1")"#,
+        r#"{ "Value": { "__extn": { "fn": "ip", "arg": "10.0.0.1" } } }"#,
+    );
+}
+
+#[test]
+fn operators() {
+    assert_expression(
+        "!context.a",
+        r#"{ "!": { "arg": { ".": { "left": { "Var": "context" }, "attr": "a" } } } }"#,
+    );
+    assert_expression(
+        "-(principal.age)",
+        r#"{ "neg": { "arg": { ".": { "left": { "Var": "principal" }, "attr": "age" } } } }"#,
+    );
+    assert_expression(
+        "context.a && context.b || false",
+        r#"{ "||": { "left": { "&&": { "left": { ".": { "left": { "Var": "context" }, "attr": "a" } }, "right": { ".": { "left": { "Var": "context" }, "attr": "b" } } } }, "right": { "Value": false } } }"#,
+    );
+
+    for operator in ["==", "!=", "<", "<=", ">", ">=", "+", "-", "*"] {
+        assert_expression(
+            &format!("1 {operator} 2"),
+            &format!(
+                r#"{{ "{operator}": {{ "left": {{ "Value": 1 }}, "right": {{ "Value": 2 }} }} }}"#
+            ),
+        );
+    }
+
+    assert_expression(
+        r#"principal in Group::"admins""#,
+        r#"{ "in": { "left": { "Var": "principal" }, "right": { "Value": { "__entity": { "type": "Group", "id": "admins" } } } } }"#,
+    );
+    assert_expression(
+        "if context.a then 1 else 2",
+        r#"{ "if-then-else": { "if": { ".": { "left": { "Var": "context" }, "attr": "a" } }, "then": { "Value": 1 }, "else": { "Value": 2 } } }"#,
+    );
+}
+
+#[test]
+fn methods() {
+    for method in ["contains", "containsAll", "containsAny"] {
+        assert_expression(
+            &format!("[1].{method}([2])"),
+            &format!(
+                r#"{{ "{method}": {{ "left": {{ "Set": [{{ "Value": 1 }}] }}, "right": {{ "Set": [{{ "Value": 2 }}] }} }} }}"#
+            ),
+        );
+    }
+
+    assert_expression("[].isEmpty()", r#"{ "isEmpty": { "arg": { "Set": [] } } }"#);
+    assert_expression(
+        r#"principal.getTag("a")"#,
+        r#"{ "getTag": { "left": { "Var": "principal" }, "right": { "Value": "a" } } }"#,
+    );
+    assert_expression(
+        r#"principal.hasTag("a")"#,
+        r#"{ "hasTag": { "left": { "Var": "principal" }, "right": { "Value": "a" } } }"#,
+    );
+    assert_expression(
+        r#"decimal("1.5").lessThan(decimal("2.5"))"#,
+        r#"{ "lessThan": [{ "decimal": [{ "Value": "1.5" }] }, { "decimal": [{ "Value": "2.5" }] }] }"#,
+    );
+}
+
+#[test]
+fn structure() {
+    assert_expression(
+        r#"{a: principal, "b": [action, resource]}"#,
+        r#"{ "Record": { "a": { "Var": "principal" }, "b": { "Set": [{ "Var": "action" }, { "Var": "resource" }] } } }"#,
+    );
+    assert_expression(
+        r#"resource like "*.jpg\*""#,
+        r#"{ "like": { "left": { "Var": "resource" }, "pattern": ["Wildcard", { "Literal": ".jpg*" }] } }"#,
+    );
+    assert_expression(
+        "principal is User",
+        r#"{ "is": { "left": { "Var": "principal" }, "entity_type": "User" } }"#,
+    );
+    assert_expression(
+        r#"principal is User in Group::"a""#,
+        r#"{ "is": { "left": { "Var": "principal" }, "entity_type": "User", "in": { "Value": { "__entity": { "type": "Group", "id": "a" } } } } }"#,
+    );
+}
+
+#[test]
+fn policies() {
+    let source = r#"[
+        { "effect": "permit", "principal": { "op": "All" }, "action": { "op": "All" }, "resource": { "op": "All" } },
+        { "effect": "forbid", "principal": { "op": "All" }, "action": { "op": "All" }, "resource": { "op": "==", "slot": "?principal" } },
+        { "effect": "forbid", "principal": { "op": "All" }, "action": { "op": "All" }, "resource": { "op": "All" } }
+    ]"#;
+
+    let (count, diagnostics) = lower(source);
+    assert_eq!(count, 2);
+    assert_snapshot!(diagnostics, @r#"
+    error: expected slot `?resource`
+      ╭▸ test.json:3:122
+      │
+    3 │ …: "All" }, "resource": { "op": "==", "slot": "?principal" } },
+      ╰╴                                              ━━━━━━━━━━━━ wrong slot for this constraint
+    "#);
+}
+
+#[test]
+fn expression_errors() {
+    let source = r#"[{
+        "effect": "permit",
+        "principal": { "op": "All" },
+        "action": { "op": "All" },
+        "resource": { "op": "All" },
+        "conditions": [
+            { "kind": "when", "body": { "Var": "user" } },
+            { "kind": "if", "body": { "Value": null } },
+            { "kind": "when", "body": { "like": { "left": { "Var": "context" }, "pattern": ["Any"] } } },
+            { "kind": "when", "body": { "Record": { "a": { "Value": 1 } }, "Set": [] } },
+            { "kind": "when", "body": { "Value": 9223372036854775808 } }
+        ]
+    }]"#;
+
+    let (count, diagnostics) = lower(source);
+    assert_eq!(count, 0);
+    assert_snapshot!(diagnostics, @r#"
+    error: unknown variable `user`
+      ╭▸ test.json:7:48
+      │
+    7 │             { "kind": "when", "body": { "Var": "user" } },
+      │                                                ━━━━━━ not a valid variable
+      ╰╴
+    note: `principal`, `action`, `resource`, and `context` are the only variables
+    error: expected `when` or `unless`, found a string
+      ╭▸ test.json:8:23
+      │
+    8 │             { "kind": "if", "body": { "Value": null } },
+      ╰╴                      ━━━━ expected `when` or `unless`
+    error: expected a value, found null
+      ╭▸ test.json:8:48
+      │
+    8 │             { "kind": "if", "body": { "Value": null } },
+      ╰╴                                               ━━━━ expected a value
+    error: expected `"Wildcard"` or `{ "Literal": ... }`, found a string
+      ╭▸ test.json:9:93
+      │
+    9 │             { "kind": "when", "body": { "like": { "left": { "Var": "context" }, "pattern": ["Any"] } } },
+      ╰╴                                                                                            ━━━━━ expected `"Wildcard"` or `{ "Literal": ... }`
+    error: invalid expression
+       ╭▸ test.json:10:39
+       │
+    10 │             { "kind": "when", "body": { "Record": { "a": { "Value": 1 } }, "Set": [] } },
+       ╰╴                                      ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ expected an object with a single key
+    error: integer literal `9223372036854775808` is out of range
+       ╭▸ test.json:11:50
+       │
+    11 │             { "kind": "when", "body": { "Value": 9223372036854775808 } }
+       ╰╴                                                 ━━━━━━━━━━━━━━━━━━━ expected an integer
+    "#);
+}
+
+#[test]
+fn shared_diagnostics() {
+    let (_, diagnostics) = PolicyParser::parse("permit(principal, action");
+    let parsed = diagnostics.len();
+
+    let json = JsonParser::parse(r#"[{ "effect": "permit" }]"#).unwrap();
+    let (policies, diagnostics) = PolicyJsonLowerer::lower(&json, diagnostics);
+
+    assert!(policies.policies().is_empty());
+    assert!(parsed > 0);
+    assert_eq!(diagnostics.len(), parsed + 3);
+}
@@ -0,0 +1,352 @@
+#![expect(clippy::unwrap_used, reason = "Tests")]
+
+use alloc::format;
+use alloc::string::{String, ToString as _};
+use alloc::vec::Vec;
+
+use duramen_diagnostic::Diagnostics;
+use duramen_json::JsonParser;
+use duramen_lowerer::{PolicyJsonLowerer, PolicyLowerer};
+use duramen_parser::PolicyParser;
+use duramen_test::anstream::adapter::strip_str;
+use duramen_test::insta::assert_snapshot;
+
+extern crate alloc;
+
+const POLICIES: &str = r#"
+    @id("all")
+    permit(principal, action, resource);
+
+    @id("share")
+    permit(principal == ?principal, action, resource is Photo in ?resource);
+
+    @id("view")
+    permit(principal == ?principal, action, resource);
+"#;
+
+fn lower(source: &str) -> (Vec<String>, String) {
+    let json = JsonParser::parse(source).unwrap();
+    let (policies, diagnostics) = PolicyJsonLowerer::lower_set(&json, Diagnostics::new());
+
+    let ids = policies.iter().map(|(id, _)| id.to_string()).collect();
+    let rendered = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.render("test.json", source))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    (ids, strip_str(&rendered).to_string())
+}
+
+fn lower_links(source: &str) -> (Vec<String>, String) {
+    let (tree, diagnostics) = PolicyParser::parse(POLICIES);
+    let (set, diagnostics) = PolicyLowerer::lower_set(&tree, diagnostics);
+    assert!(diagnostics.is_empty(), "{POLICIES}");
+
+    let json = JsonParser::parse(source).unwrap();
+    let (set, diagnostics) = PolicyJsonLowerer::lower_links(&json, set, diagnostics);
+
+    let ids = set
+        .links()
+        .map(|(id, link)| format!("{id} -> {}", link.template()))
+        .collect();
+
+    let rendered = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.render("test.json", source))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    (ids, strip_str(&rendered).to_string())
+}
+
+#[test]
+fn links() {
+    let source = r#"[
+        {
+            "templateId": "share",
+            "newId": "share-alice",
+            "values": {
+                "?principal": { "type": "User", "id": "alice" },
+                "?resource": { "__entity": { "type": "Album", "id": "trip" } }
+            }
+        },
+        {
+            "templateId": "view",
+            "newId": "view-bob",
+            "values": { "?principal": { "type": "Acme::User", "id": "bob" } }
+        }
+    ]"#;
+
+    let (ids, diagnostics) = lower_links(source);
+    assert_snapshot!(ids.join(", "), @"share-alice -> share, view-bob -> view");
+    assert_snapshot!(diagnostics, @"");
+}
+
+#[test]
+fn link_errors() {
+    let source = r#"[
+        { "templateId": "missing", "newId": "a", "values": {} },
+        { "templateId": "view", "newId": "b", "values": {} },
+        { "templateId": "view", "newId": "c", "values": { "?other": { "type": "User", "id": "alice" } } },
+        { "templateId": "view", "newId": "d", "values": { "?principal": { "type": "User", "id": "alice" } } },
+        { "templateId": "view", "newId": "d", "values": { "?principal": { "type": "User", "id": "bob" } } }
+    ]"#;
+
+    let (ids, diagnostics) = lower_links(source);
+    assert_snapshot!(ids.join(", "), @"d -> view");
+    assert_snapshot!(diagnostics, @r#"
+    error: template `missing` does not exist
+      ╭▸ test.json:2:25
+      │
+    2 │         { "templateId": "missing", "newId": "a", "values": {} },
+      ╰╴                        ━━━━━━━━━ unknown template
+    error: no value provided for slot `?principal`
+      ╭▸ test.json:3:9
+      │
+    3 │         { "templateId": "view", "newId": "b", "values": {} },
+      ╰╴        ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ in this link
+    error: invalid slot `?other`
+      ╭▸ test.json:4:59
+      │
+    4 │         { "templateId": "view", "newId": "c", "values": { "?other": { "type": "User", "id": "alice" } } },
+      │                                                           ━━━━━━━━ invalid slot
+      ╰╴
+    note: only `?principal` and `?resource` are allowed
+    error: duplicate policy id `d`
+      ╭▸ test.json:6:42
+      │
+    5 │         { "templateId": "view", "newId": "d", "values": { "?principal": { "type": "User", "id": "alice" } } },
+      │                                          ─── first used here
+    6 │         { "templateId": "view", "newId": "d", "values": { "?principal": { "type": "User", "id": "bob" } } }
+      ╰╴                                         ━━━ duplicate policy id
+    "#);
+}
+
+#[test]
+fn shape_errors() {
+    let source = r#"[
+        { "templateId": "view", "newId": "a", "values": {}, "extra": 1 },
+        { "templateId": "view", "values": {} },
+        { "templateId": "view", "newId": "c", "values": { "?principal": { "type": "User" } } },
+        { "templateId": 1, "newId": "d", "values": [] },
+        "e"
+    ]"#;
+
+    let (ids, diagnostics) = lower_links(source);
+    assert_snapshot!(ids.join(", "), @"");
+    assert_snapshot!(diagnostics, @r#"
+    error: unknown field `extra`
+      ╭▸ test.json:2:61
+      │
+    2 │         { "templateId": "view", "newId": "a", "values": {}, "extra": 1 },
+      ╰╴                                                            ━━━━━━━ unknown field
+    error: no value provided for slot `?principal`
+      ╭▸ test.json:2:9
+      │
+    2 │         { "templateId": "view", "newId": "a", "values": {}, "extra": 1 },
+      ╰╴        ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ in this link
+    error: missing field `newId`
+      ╭▸ test.json:3:9
+      │
+    3 │         { "templateId": "view", "values": {} },
+      ╰╴        ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ missing `newId`
+    error: missing field `id`
+      ╭▸ test.json:4:73
+      │
+    4 │         { "templateId": "view", "newId": "c", "values": { "?principal": { "type": "User" } } },
+      ╰╴                                                                        ━━━━━━━━━━━━━━━━━━ missing `id`
+    error: expected a string, found a number
+      ╭▸ test.json:5:25
+      │
+    5 │         { "templateId": 1, "newId": "d", "values": [] },
+      ╰╴                        ━ expected a string
+    error: expected an object, found an array
+      ╭▸ test.json:5:52
+      │
+    5 │         { "templateId": 1, "newId": "d", "values": [] },
+      ╰╴                                                   ━━ expected an object
+    error: expected an object, found a string
+      ╭▸ test.json:6:9
+      │
+    6 │         "e"
+      ╰╴        ━━━ expected an object
+    "#);
+}
+
+#[test]
+fn set_links() {
+    let source = r#"{
+        "staticPolicies": {
+            "all": { "effect": "permit", "principal": { "op": "All" }, "action": { "op": "All" }, "resource": { "op": "All" } }
+        },
+        "templates": {
+            "share": {
+                "effect": "permit",
+                "principal": { "op": "==", "slot": "?principal" },
+                "action": { "op": "All" },
+                "resource": { "op": "is", "entity_type": "Photo", "in": { "slot": "?resource" } }
+            }
+        },
+        "templateLinks": [
+            {
+                "templateId": "share",
+                "newId": "share-alice",
+                "values": {
+                    "?principal": { "type": "User", "id": "alice" },
+                    "?resource": { "__entity": { "type": "Album", "id": "trip" } }
+                }
+            }
+        ]
+    }"#;
+
+    let (ids, diagnostics) = lower(source);
+    assert_snapshot!(ids.join(", "), @"all, share-alice");
+    assert_snapshot!(diagnostics, @"");
+}
+
+#[test]
+fn duplicate_ids() {
+    let source = r#"{
+        "staticPolicies": {
+            "share": { "effect": "permit", "principal": { "op": "All" }, "action": { "op": "All" }, "resource": { "op": "All" } }
+        },
+        "templates": {
+            "share": { "effect": "permit", "principal": { "op": "==", "slot": "?principal" }, "action": { "op": "All" }, "resource": { "op": "All" } }
+        }
+    }"#;
+
+    let (ids, diagnostics) = lower(source);
+    assert_snapshot!(ids.join(", "), @"share");
+    assert_snapshot!(diagnostics, @r#"
+    error: duplicate policy id `share`
+      ╭▸ test.json:6:13
+      │
+    3 │ …     "share": { "effect": "permit", "principal": { "op": "All" }, "action": { "op": "All" }, "resource": { "op": "All" } }
+      │       ─────── first used here
+      ‡
+    6 │ …     "share": { "effect": "permit", "principal": { "op": "==", "slot": "?principal" }, "action": { "op": "All" }, "resource": { "op": …
+      ╰╴      ━━━━━━━ duplicate policy id
+    "#);
+}
+
+#[test]
+fn set_link_errors() {
+    let source = r#"{
+        "staticPolicies": {
+            "slotted": { "effect": "permit", "principal": { "op": "==", "slot": "?principal" }, "action": { "op": "All" }, "resource": { "op": "All" } }
+        },
+        "templates": {
+            "share": { "effect": "permit", "principal": { "op": "in", "slot": "?resource" }, "action": { "op": "All" }, "resource": { "op": "All" } },
+            "view": { "effect": "permit", "principal": { "op": "==", "slot": "?principal" }, "action": { "op": "All" }, "resource": { "op": "All" } }
+        },
+        "templateLinks": [
+            { "templateId": "missing", "newId": "a", "values": {} },
+            { "templateId": "view", "newId": "b", "values": {} },
+            { "templateId": "view", "newId": "c", "values": { "?other": { "type": "User", "id": "alice" } } },
+            { "templateId": "view", "newId": "view", "values": { "?principal": { "type": "User", "id": "alice" } } }
+        ]
+    }"#;
+
+    let (ids, diagnostics) = lower(source);
+    assert_snapshot!(ids.join(", "), @"");
+    assert_snapshot!(diagnostics, @r#"
+    error: static policy `slotted` contains template slots
+      ╭▸ test.json:3:13
+      │
+    3 │ …     "slotted": { "effect": "permit", "principal": { "op": "==", "slot": "?principal" }, "action": { "op": "All" }, "resource": { "op"…
+      │       ━━━━━━━━━ expected a static policy
+      ╰╴
+    note: templates belong in `templates`
+    error: expected slot `?principal`
+      ╭▸ test.json:6:79
+      │
+    6 │ …     "share": { "effect": "permit", "principal": { "op": "in", "slot": "?resource" }, "action": { "op": "All" }, "resource": { "op": "…
+      ╰╴                                                                        ━━━━━━━━━━━ wrong slot for this constraint
+    error: template `missing` does not exist
+       ╭▸ test.json:10:29
+       │
+    10 │             { "templateId": "missing", "newId": "a", "values": {} },
+       ╰╴                            ━━━━━━━━━ unknown template
+    error: no value provided for slot `?principal`
+       ╭▸ test.json:11:13
+       │
+    11 │             { "templateId": "view", "newId": "b", "values": {} },
+       ╰╴            ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ in this link
+    error: invalid slot `?other`
+       ╭▸ test.json:12:63
+       │
+    12 │             { "templateId": "view", "newId": "c", "values": { "?other": { "type": "User", "id": "alice" } } },
+       │                                                               ━━━━━━━━ invalid slot
+       ╰╴
+    note: only `?principal` and `?resource` are allowed
+    error: duplicate policy id `view`
+       ╭▸ test.json:13:46
+       │
+     7 │ …     "view": { "effect": "permit", "principal": { "op": "==", "slot": "?principal" }, "action": { "op": "All" }, "resource": { "op": …
+       │       ────── first used here
+       ‡
+    13 │ …     { "templateId": "view", "newId": "view", "values": { "?principal": { "type": "User", "id": "alice" } } }
+       ╰╴                                       ━━━━━━ duplicate policy id
+    "#);
+}
+
+#[test]
+fn policy_shape_errors() {
+    let source = r#"{
+        "staticPolicies": {
+            "a": { "effect": "allow", "principal": { "op": "All" }, "action": { "op": "All" }, "resource": { "op": "All" }, "extra": 1 },
+            "b": { "effect": "permit", "principal": { "op": "All" }, "action": { "op": "All" } },
+            "c": {
+                "effect": "permit",
+                "principal": { "op": "All" },
+                "action": { "op": "All" },
+                "resource": { "op": "All" },
+                "conditions": [{ "kind": "when", "body": { "Var": "context", "Value": true } }]
+            },
+            "d": {
+                "effect": "permit",
+                "principal": { "op": "All" },
+                "action": { "op": "All" },
+                "resource": { "op": "All" },
+                "conditions": [{ "kind": "when", "body": { "frobnicate": [] } }],
+                "annotations": { "id": 1 }
+            }
+        }
+    }"#;
+
+    let (ids, diagnostics) = lower(source);
+    assert_snapshot!(ids.join(", "), @"");
+    assert_snapshot!(diagnostics, @r#"
+    error: unknown field `extra`
+      ╭▸ test.json:3:125
+      │
+    3 │ …"action": { "op": "All" }, "resource": { "op": "All" }, "extra": 1 },
+      ╰╴                                                         ━━━━━━━ unknown field
+    error: missing policy effect
+      ╭▸ test.json:3:30
+      │
+    3 │ …     "a": { "effect": "allow", "principal": { "op": "All" }, "action": { "op": "All" }, "resource": { "op": "All" }, "extra": 1 },
+      ╰╴                       ━━━━━━━ expected `permit` or `forbid`
+    error: missing field `resource`
+      ╭▸ test.json:4:18
+      │
+    4 │             "b": { "effect": "permit", "principal": { "op": "All" }, "action": { "op": "All" } },
+      ╰╴                 ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ missing `resource`
+    error: invalid expression
+       ╭▸ test.json:10:58
+       │
+    10 │                 "conditions": [{ "kind": "when", "body": { "Var": "context", "Value": true } }]
+       ╰╴                                                         ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ expected an object with a single key
+    error: expected a string or null, found a number
+       ╭▸ test.json:18:40
+       │
+    18 │                 "annotations": { "id": 1 }
+       ╰╴                                       ━ expected a string or null
+    error: `frobnicate` is not a known function
+       ╭▸ test.json:17:60
+       │
+    17 │                 "conditions": [{ "kind": "when", "body": { "frobnicate": [] } }],
+       ╰╴                                                           ━━━━━━━━━━━━ unknown function
+    "#);
+}
@@ -0,0 +1,450 @@
+#![expect(clippy::unwrap_used, reason = "Tests")]
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use duramen_ast::{Declaration, EntityKind, TypeExpression};
+use duramen_diagnostic::Diagnostics;
+use duramen_json::JsonParser;
+use duramen_lowerer::{SchemaJsonLowerer, SchemaLowerer};
+use duramen_parser::SchemaParser;
+use duramen_test::anstream::adapter::strip_str;
+use duramen_test::insta::assert_snapshot;
+
+extern crate alloc;
+
+fn render(diagnostics: &Diagnostics, source: &str) -> String {
+    let rendered = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.render("test.json", source))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    strip_str(&rendered).to_string()
+}
+
+fn lower(source: &str) -> String {
+    let json = JsonParser::parse(source).unwrap();
+    let (_, diagnostics) = SchemaJsonLowerer::lower(&json, Diagnostics::new());
+    render(&diagnostics, source)
+}
+
+/// Asserts that a JSON schema lowers to the same AST as its textual form.
+fn assert_same(text: &str, json: &str) {
+    let (tree, diagnostics) = SchemaParser::parse(text);
+    let (expected, diagnostics) = SchemaLowerer::lower(&tree, diagnostics);
+    assert!(diagnostics.is_empty(), "{}", render(&diagnostics, text));
+
+    let value = JsonParser::parse(json).unwrap();
+    let (actual, diagnostics) = SchemaJsonLowerer::lower(&value, Diagnostics::new());
+    assert!(diagnostics.is_empty(), "{}", render(&diagnostics, json));
+
+    assert_eq!(format!("{actual:?}"), format!("{expected:?}"), "{text}");
+}
+
+#[test]
+fn entities() {
+    assert_same(
+        r#"
+        namespace Acme {
+            @doc("a user")
+            entity User in [Group, Acme::Team] {
+                name: String,
+                "home address"?: Address,
+                active: Bool,
+                roles: Set<Long>,
+            } tags String;
+            entity Group;
+            entity Team;
+            entity Color enum ["red", "green"];
+        }
+        "#,
+        r#"{
+            "Acme": {
+                "entityTypes": {
+                    "User": {
+                        "annotations": { "doc": "a user" },
+                        "memberOfTypes": ["Group", "Acme::Team"],
+                        "shape": {
+                            "type": "Record",
+                            "attributes": {
+                                "name": { "type": "String" },
+                                "home address": { "type": "EntityOrCommon", "name": "Address", "required": false },
+                                "active": { "type": "Boolean" },
+                                "roles": { "type": "Set", "element": { "type": "Long" } }
+                            }
+                        },
+                        "tags": { "type": "String" }
+                    },
+                    "Group": {},
+                    "Team": {},
+                    "Color": { "enum": ["red", "green"] }
+                },
+                "actions": {}
+            }
+        }"#,
+    );
+}
+
+#[test]
+fn common_types() {
+    assert_same(
+        r#"
+        @doc("address")
+        type Address = { street: String, @doc("zip") zip?: Long };
+        type Network = ipaddr;
+        type Owner = User;
+        entity User;
+        "#,
+        r#"{
+            "": {
+                "commonTypes": {
+                    "Address": {
+                        "type": "Record",
+                        "annotations": { "doc": "address" },
+                        "attributes": {
+                            "street": { "type": "String" },
+                            "zip": { "type": "Long", "required": false, "annotations": { "doc": "zip" } }
+                        }
+                    },
+                    "Network": { "type": "Extension", "name": "ipaddr" },
+                    "Owner": { "type": "EntityOrCommon", "name": "User" }
+                },
+                "entityTypes": { "User": {} },
+                "actions": {}
+            }
+        }"#,
+    );
+}
+
+#[test]
+fn type_kinds() {
+    let source = r#"{
+        "NS": {
+            "commonTypes": { "Foo": { "type": "String" } },
+            "entityTypes": {
+                "Foo": {},
+                "Doc": {
+                    "shape": {
+                        "type": "Record",
+                        "attributes": {
+                            "owner": { "type": "Entity", "name": "Foo" },
+                            "label": { "type": "EntityOrCommon", "name": "Foo" },
+                            "network": { "type": "Extension", "name": "ipaddr" }
+                        }
+                    }
+                }
+            },
+            "actions": {}
+        }
+    }"#;
+
+    let json = JsonParser::parse(source).unwrap();
+    let (schema, diagnostics) = SchemaJsonLowerer::lower(&json, Diagnostics::new());
+    assert!(diagnostics.is_empty(), "{}", render(&diagnostics, source));
+
+    let mut kinds = Vec::new();
+    for declaration in schema.namespaces()[0].declarations() {
+        if let Declaration::Entity(entity) = declaration
+            && let EntityKind::Standard(standard) = entity.kind()
+        {
+            for (name, attribute) in standard.attributes() {
+                kinds.push(match attribute.definition() {
+                    TypeExpression::Entity(types) => {
+                        let types = types.iter().map(ToString::to_string).collect::<Vec<_>>();
+                        format!("{name}: entity {}", types.join(", "))
+                    }
+                    TypeExpression::Reference(reference) => format!("{name}: {reference}"),
+                    _ => format!("{name}: other"),
+                });
+            }
+        }
+    }
+
+    assert_snapshot!(kinds.join("\n"), @r#"
+    owner: entity Foo
+    label: Foo
+    network: ipaddr
+    "#);
+
+    let source = r#"{ "": { "commonTypes": { "Ip": { "type": "Extension", "name": "ip" } }, "entityTypes": {}, "actions": {} } }"#;
+    assert_snapshot!(lower(source), @r#"
+    error: `ip` is not an extension type
+      ╭▸ test.json:1:63
+      │
+    1 │ { "": { "commonTypes": { "Ip": { "type": "Extension", "name": "ip" } }, "entityTypes": {}, "actions": {} } }
+      │                                                               ━━━━ unknown extension type
+      ╰╴
+    note: expected `ipaddr`, `decimal`, `datetime`, or `duration`
+    "#);
+}
+
+#[test]
+fn actions() {
+    assert_same(
+        r#"
+        namespace Photos {
+            entity User;
+            entity Photo;
+            action read;
+            @doc("view")
+            action view in [read, Photos::Action::"read"] appliesTo {
+                principal: User,
+                resource: [Photo, User],
+                context: { ip: ipaddr },
+            };
+            action share appliesTo { principal: User, resource: Photo, context: Context };
+        }
+        "#,
+        r#"{
+            "Photos": {
+                "entityTypes": { "User": {}, "Photo": {} },
+                "actions": {
+                    "read": {},
+                    "view": {
+                        "annotations": { "doc": "view" },
+                        "memberOf": [{ "id": "read" }, { "type": "Photos::Action", "id": "read" }],
+                        "appliesTo": {
+                            "principalTypes": ["User"],
+                            "resourceTypes": ["Photo", "User"],
+                            "context": { "type": "Record", "attributes": { "ip": { "type": "Extension", "name": "ipaddr" } } }
+                        }
+                    },
+                    "share": {
+                        "appliesTo": {
+                            "principalTypes": ["User"],
+                            "resourceTypes": ["Photo"],
+                            "context": { "type": "Context" }
+                        }
+                    }
+                }
+            }
+        }"#,
+    );
+}
+
+#[test]
+fn reserved_names() {
+    let source = r#"{
+        "": {
+            "commonTypes": { "Long": { "type": "String" } },
+            "entityTypes": { "Bool": {} },
+            "actions": { "__cedar_view": {} }
+        }
+    }"#;
+
+    assert_snapshot!(lower(source), @r#"
+    error: `Long` is a reserved type name
+      ╭▸ test.json:3:30
+      │
+    3 │             "commonTypes": { "Long": { "type": "String" } },
+      ╰╴                             ━━━━━━ in this type declaration
+    error: `Bool` is a reserved type name
+      ╭▸ test.json:4:30
+      │
+    4 │             "entityTypes": { "Bool": {} },
+      ╰╴                             ━━━━━━ in this entity declaration
+    error: identifier `__cedar_view` uses reserved `__cedar` prefix
+      ╭▸ test.json:5:26
+      │
+    5 │             "actions": { "__cedar_view": {} }
+      ╰╴                         ━━━━━━━━━━━━━━ in this action declaration
+    "#);
+}
+
+#[test]
+fn shape_errors() {
+    let source = r#"{
+        "Acme::": {
+            "entityTypes": {},
+            "actions": {}
+        },
+        "Photos": {
+            "entityTypes": {
+                "User": { "shape": { "type": "Set", "element": { "type": "Long" } }, "parents": [] },
+                "Group": { "enum": [] }
+            },
+            "actions": {
+                "view": { "appliesTo": { "context": { "type": "Set", "element": { "type": "Long" } } } },
+                "edit": { "appliesTo": { "principalTypes": ["User", "User"] } }
+            }
+        },
+        "Empty": {}
+    }"#;
+
+    assert_snapshot!(lower(source), @r#"
+    error: expected at least one element
+      ╭▸ test.json:2:9
+      │
+    2 │         "Acme::": {
+      ╰╴        ━━━━━━━━ invalid namespace name
+    error: unknown field `parents`
+      ╭▸ test.json:8:86
+      │
+    8 │                 "User": { "shape": { "type": "Set", "element": { "type": "Long" } }, "parents": [] },
+      ╰╴                                                                                     ━━━━━━━━━ unknown field
+    error: expected a record type, found a string
+      ╭▸ test.json:8:46
+      │
+    8 │                 "User": { "shape": { "type": "Set", "element": { "type": "Long" } }, "parents": [] },
+      ╰╴                                             ━━━━━ expected a record type
+    error: expected at least one element
+      ╭▸ test.json:9:36
+      │
+    9 │                 "Group": { "enum": [] }
+      ╰╴                                   ━━ in this enum type
+    error: invalid context type
+       ╭▸ test.json:12:53
+       │
+    12 │                 "view": { "appliesTo": { "context": { "type": "Set", "element": { "type": "Long" } } } },
+       ╰╴                                                    ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ expected a record type or type reference
+    error: duplicate key `User`
+       ╭▸ test.json:13:40
+       │
+    13 │                 "edit": { "appliesTo": { "principalTypes": ["User", "User"] } }
+       ╰╴                                       ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ in this `appliesTo` clause
+    error: missing field `entityTypes`
+       ╭▸ test.json:16:18
+       │
+    16 │         "Empty": {}
+       ╰╴                 ━━ missing `entityTypes`
+    error: missing field `actions`
+       ╭▸ test.json:16:18
+       │
+    16 │         "Empty": {}
+       ╰╴                 ━━ missing `actions`
+    "#);
+}
+
+#[test]
+fn shape_references() {
+    assert_same(
+        "
+        type Base = { name: String };
+        type Shape = Base;
+        namespace Acme {
+            type Local = { level: Long };
+            entity User { name: String };
+            entity Admin { level: Long };
+        }
+        ",
+        r#"{
+            "": {
+                "commonTypes": {
+                    "Base": { "type": "Record", "attributes": { "name": { "type": "String" } } },
+                    "Shape": { "type": "Base" }
+                },
+                "entityTypes": {},
+                "actions": {}
+            },
+            "Acme": {
+                "commonTypes": {
+                    "Local": {
+                        "type": "Record",
+                        "attributes": { "level": { "type": "Long" } },
+                        "additionalAttributes": false
+                    }
+                },
+                "entityTypes": {
+                    "User": { "shape": { "type": "Shape" } },
+                    "Admin": { "shape": { "type": "EntityOrCommon", "name": "Acme::Local" } }
+                },
+                "actions": {}
+            }
+        }"#,
+    );
+}
+
+#[test]
+fn shape_references_across_namespaces() {
+    assert_same(
+        "
+        namespace Other {
+            type Rec = { owner: User, friends: Set<User>, address: Address, age: Long };
+            type Address = { city: String };
+            entity User;
+        }
+        namespace NS {
+            entity User;
+            entity Address;
+            entity Doc { owner: Other::User, friends: Set<Other::User>, address: Other::Address, age: Long };
+        }
+        ",
+        r#"{
+            "Other": {
+                "commonTypes": {
+                    "Rec": {
+                        "type": "Record",
+                        "attributes": {
+                            "owner": { "type": "EntityOrCommon", "name": "User" },
+                            "friends": { "type": "Set", "element": { "type": "EntityOrCommon", "name": "User" } },
+                            "address": { "type": "EntityOrCommon", "name": "Address" },
+                            "age": { "type": "Long" }
+                        }
+                    },
+                    "Address": { "type": "Record", "attributes": { "city": { "type": "String" } } }
+                },
+                "entityTypes": { "User": {} },
+                "actions": {}
+            },
+            "NS": {
+                "entityTypes": {
+                    "User": {},
+                    "Address": {},
+                    "Doc": { "shape": { "type": "EntityOrCommon", "name": "Other::Rec" } }
+                },
+                "actions": {}
+            }
+        }"#,
+    );
+}
+
+#[test]
+fn shape_reference_errors() {
+    let source = r#"{
+        "": {
+            "commonTypes": {
+                "Count": { "type": "Long" },
+                "Loop": { "type": "Cycle" },
+                "Cycle": { "type": "Loop" },
+                "Open": { "type": "Record", "attributes": {}, "additionalAttributes": true }
+            },
+            "entityTypes": {
+                "A": { "shape": { "type": "Missing" } },
+                "B": { "shape": { "type": "Count" } },
+                "C": { "shape": { "type": "Loop" } },
+                "D": { "shape": { "type": "Record", "attributes": {}, "additionalAttributes": "yes" } }
+            },
+            "actions": {}
+        }
+    }"#;
+
+    assert_snapshot!(lower(source), @r#"
+    error: records with additional attributes are not supported
+      ╭▸ test.json:7:87
+      │
+    7 │                 "Open": { "type": "Record", "attributes": {}, "additionalAttributes": true }
+      ╰╴                                                                                      ━━━━ expected `false`
+    error: invalid entity shape
+       ╭▸ test.json:10:33
+       │
+    10 │                 "A": { "shape": { "type": "Missing" } },
+       ╰╴                                ━━━━━━━━━━━━━━━━━━━━━ expected a record type or a common record type
+    error: invalid entity shape
+       ╭▸ test.json:11:33
+       │
+    11 │                 "B": { "shape": { "type": "Count" } },
+       ╰╴                                ━━━━━━━━━━━━━━━━━━━ expected a record type or a common record type
+    error: invalid entity shape
+       ╭▸ test.json:12:33
+       │
+    12 │                 "C": { "shape": { "type": "Loop" } },
+       ╰╴                                ━━━━━━━━━━━━━━━━━━ expected a record type or a common record type
+    error: expected a boolean, found a string
+       ╭▸ test.json:13:95
+       │
+    13 │                 "D": { "shape": { "type": "Record", "attributes": {}, "additionalAttributes": "yes" } }
+       ╰╴                                                                                              ━━━━━ expected a boolean
+    "#);
+}
@@ -0,0 +1,248 @@
+#![expect(clippy::unwrap_used, reason = "Tests")]
+
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString as _};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+
+use duramen_ast::{EntityReference, Identifier, Name, PolicyId, PolicySet, SlotKind};
+use duramen_diagnostic::Diagnostic;
+use duramen_lowerer::PolicyLowerer;
+use duramen_parser::PolicyParser;
+use duramen_test::anstream::adapter::strip_str;
+use duramen_test::insta::assert_snapshot;
+
+extern crate alloc;
+
+fn lower(source: &str) -> (Vec<String>, String) {
+    let (tree, diagnostics) = PolicyParser::parse(source);
+    let (policies, diagnostics) = PolicyLowerer::lower_set(&tree, diagnostics);
+
+    let ids = policies.iter().map(|(id, _)| id.to_string()).collect();
+    let rendered = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.render("test.cedar", source))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    (ids, strip_str(&rendered).to_string())
+}
+
+#[test]
+fn generated_ids() {
+    let source = r#"
+        permit(principal, action, resource);
+        @id("admin")
+        permit(principal, action, resource);
+        forbid(principal, action, resource);
+    "#;
+
+    let (ids, diagnostics) = lower(source);
+    assert_snapshot!(ids.join(", "), @"policy0, admin, policy2");
+    assert_snapshot!(diagnostics, @"");
+}
+
+#[test]
+fn duplicate_ids() {
+    let source = r#"
+        @id("admin")
+        permit(principal, action, resource);
+        @id("admin")
+        forbid(principal, action, resource);
+    "#;
+
+    let (ids, diagnostics) = lower(source);
+    assert_snapshot!(ids.join(", "), @"admin");
+    assert_snapshot!(diagnostics, @r#"
+    error: duplicate policy id `admin`
+      ╭▸ test.cedar:4:13
+      │
+    2 │         @id("admin")
+      │             ─────── first used here
+    3 │         permit(principal, action, resource);
+    4 │         @id("admin")
+      ╰╴            ━━━━━━━ duplicate policy id
+    "#);
+}
+
+#[test]
+fn duplicate_generated_id() {
+    let source = r#"
+        @id("policy1")
+        permit(principal, action, resource);
+        permit(principal, action, resource);
+    "#;
+
+    let (ids, diagnostics) = lower(source);
+    assert_snapshot!(ids.join(", "), @"policy1");
+    assert_snapshot!(diagnostics, @r#"
+    error: duplicate policy id `policy1`
+      ╭▸ test.cedar:4:9
+      │
+    2 │         @id("policy1")
+      │             ───────── first used here
+    3 │         permit(principal, action, resource);
+    4 │         permit(principal, action, resource);
+      ╰╴        ━━━━━━ duplicate policy id
+    "#);
+}
+
+#[test]
+fn lookup_insert_remove() {
+    let source = r#"
+        @id("first")
+        permit(principal, action, resource);
+        forbid(principal, action, resource);
+    "#;
+
+    let (tree, diagnostics) = PolicyParser::parse(source);
+    let (mut policies, diagnostics) = PolicyLowerer::lower_set(&tree, diagnostics);
+    assert!(diagnostics.is_empty());
+
+    assert!(policies.contains("first"));
+    assert!(policies.contains("policy1"));
+
+    let removed = policies.remove("first").unwrap();
+    assert!(policies.get("first").is_none());
+
+    let error = policies
+        .insert(PolicyId::new(Cow::Borrowed("policy1")), removed.clone())
+        .unwrap_err();
+    assert_snapshot!(error, @"duplicate policy id `policy1`");
+
+    policies
+        .insert(PolicyId::new(Cow::Borrowed("renamed")), removed)
+        .unwrap();
+
+    let ids = policies
+        .iter()
+        .map(|(id, _)| id.as_str())
+        .collect::<Vec<_>>();
+    assert_snapshot!(ids.join(", "), @"policy1, renamed");
+}
+
+fn entity<'a>(kind: &'a str, id: &'a str) -> EntityReference<'a> {
+    EntityReference::new(
+        Name::new(Vec::new(), Identifier::new(kind).unwrap()),
+        Cow::Borrowed(id),
+    )
+}
+
+#[test]
+fn link_template() {
+    let source = r#"
+        @id("share")
+        permit(principal == ?principal, action, resource in ?resource);
+        permit(principal, action, resource);
+    "#;
+
+    let (tree, diagnostics) = PolicyParser::parse(source);
+    let (mut policies, diagnostics) = PolicyLowerer::lower_set(&tree, diagnostics);
+    assert!(diagnostics.is_empty());
+
+    let templates = policies
+        .templates()
+        .map(|(id, _)| id.as_str())
+        .collect::<Vec<_>>();
+    assert_snapshot!(templates.join(", "), @"share");
+    assert_eq!(policies.len(), 1);
+
+    policies
+        .link(
+            "share",
+            PolicyId::new(Cow::Borrowed("share-alice")),
+            vec![
+                (SlotKind::Principal, entity("User", "alice")),
+                (SlotKind::Resource, entity("Folder", "docs")),
+            ],
+        )
+        .unwrap();
+
+    let linked = policies.get("share-alice").unwrap();
+    assert!(!linked.is_template());
+    assert_snapshot!(format!("{:?}", linked.principal().constraint()), @r#"Equal(Entity(EntityReference { kind: Name { path: [], basename: Identifier("User") }, id: "alice" }))"#);
+    assert_snapshot!(format!("{:?}", linked.resource().constraint()), @r#"In(Entity(EntityReference { kind: Name { path: [], basename: Identifier("Folder") }, id: "docs" }))"#);
+    assert_snapshot!(policies.template_of("share-alice").unwrap(), @"share");
+
+    let ids = policies
+        .iter()
+        .map(|(id, _)| id.as_str())
+        .collect::<Vec<_>>();
+    assert_snapshot!(ids.join(", "), @"policy1, share-alice");
+
+    let error = policies.remove("share").unwrap_err();
+    assert_snapshot!(error, @"template `share` still has linked policies");
+
+    policies.remove("share-alice").unwrap();
+    assert!(policies.template_of("share-alice").is_none());
+    policies.remove("share").unwrap();
+
+    let error = policies.remove("share").unwrap_err();
+    assert_snapshot!(error, @"policy `share` does not exist");
+}
+
+#[test]
+fn link_errors() {
+    let source = r#"
+        @id("share")
+        permit(principal == ?principal, action, resource);
+        @id("static")
+        permit(principal, action, resource);
+    "#;
+
+    let (tree, diagnostics) = PolicyParser::parse(source);
+    let (mut policies, diagnostics) = PolicyLowerer::lower_set(&tree, diagnostics);
+    assert!(diagnostics.is_empty());
+
+    let mut link =
+        |template: &str, id: &'static str, values: Vec<(SlotKind, EntityReference<'static>)>| {
+            policies
+                .link(template, PolicyId::new(Cow::Borrowed(id)), values)
+                .unwrap_err()
+                .to_string()
+        };
+
+    assert_snapshot!(link("share", "a", vec![]), @"no value provided for slot `?principal`");
+    assert_snapshot!(link("share", "b", vec![(SlotKind::Resource, entity("Photo", "cat.jpg"))]), @"template does not have the slot `?resource`");
+    assert_snapshot!(
+        link(
+            "share",
+            "c",
+            vec![
+                (SlotKind::Principal, entity("User", "alice")),
+                (SlotKind::Principal, entity("User", "bob")),
+            ],
+        ),
+        @"duplicate key `?principal`"
+    );
+    assert_snapshot!(link("share", "static", vec![(SlotKind::Principal, entity("User", "alice"))]), @"duplicate policy id `static`");
+    assert_snapshot!(link("static", "d", vec![]), @"template `static` does not exist");
+    assert_snapshot!(link("missing", "e", vec![]), @"template `missing` does not exist");
+}
+
+#[test]
+fn new_reports_duplicate_spans() {
+    let source = r#"
+        @id("a")
+        permit(principal, action, resource);
+        @id("a")
+        forbid(principal, action, resource);
+    "#;
+
+    let (tree, diagnostics) = PolicyParser::parse(source);
+    let (policies, diagnostics) = PolicyLowerer::lower(&tree, diagnostics);
+    assert!(diagnostics.is_empty());
+
+    let error = PolicySet::new(policies).unwrap_err();
+    let rendered = Diagnostic::from(error).render("test.cedar", source);
+    assert_snapshot!(strip_str(&rendered), @r#"
+    error: duplicate policy id `a`
+      ╭▸ test.cedar:4:13
+      │
+    2 │         @id("a")
+      │             ─── first used here
+    3 │         permit(principal, action, resource);
+    4 │         @id("a")
+      ╰╴            ━━━ duplicate policy id
+    "#);
+}
@@ -0,0 +1,141 @@
+use alloc::format;
+use alloc::string::{String, ToString as _};
+use alloc::vec::Vec;
+
+use duramen_ast::{Declaration, EntityKind, Schema, TypeExpression};
+use duramen_lowerer::SchemaLowerer;
+use duramen_parser::SchemaParser;
+use duramen_test::anstream::adapter::strip_str;
+use duramen_test::insta::assert_snapshot;
+
+extern crate alloc;
+
+fn lower(source: &str) -> (String, String) {
+    let (tree, diagnostics) = SchemaParser::parse(source);
+    let (schema, diagnostics) = SchemaLowerer::lower(&tree, diagnostics);
+
+    let rendered = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.render("test.cedarschema", source))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    (describe(&schema), strip_str(&rendered).to_string())
+}
+
+/// Lists each declaration on its own line.
+fn describe(schema: &Schema<'_>) -> String {
+    let mut lines = Vec::new();
+
+    for namespace in schema.namespaces() {
+        for declaration in namespace.declarations() {
+            lines.push(match declaration {
+                Declaration::Entity(entity) => {
+                    let names = entity.names().map(ToString::to_string).collect::<Vec<_>>();
+                    let attributes = match entity.kind() {
+                        EntityKind::Standard(standard) => standard
+                            .attributes()
+                            .map(|(name, _)| name)
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                        EntityKind::Enum(_) => String::from("enum"),
+                    };
+
+                    format!("entity {} ({attributes})", names.join(", "))
+                }
+                Declaration::Action(action) => {
+                    let parents = action
+                        .parents()
+                        .map(|parent| match parent.kind() {
+                            Some(kind) => format!("{kind}::{:?}", parent.id()),
+                            None => format!("{:?}", parent.id()),
+                        })
+                        .collect::<Vec<_>>();
+
+                    let names = action
+                        .names()
+                        .map(|name| format!("{name:?}"))
+                        .collect::<Vec<_>>();
+                    format!("action {} in [{}]", names.join(", "), parents.join(", "))
+                }
+                Declaration::Type(declaration) => {
+                    let definition = match declaration.definition() {
+                        TypeExpression::Reference(name) => name.to_string(),
+                        TypeExpression::Set(_) => String::from("Set"),
+                        TypeExpression::Record(_) => String::from("Record"),
+                        TypeExpression::Entity(_) => String::from("Entity"),
+                        TypeExpression::Enum(_) => String::from("Enum"),
+                    };
+
+                    format!("type {} = {definition}", declaration.name())
+                }
+            });
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[test]
+fn entity_without_equals() {
+    let source = "
+        entity User { name: String };
+        entity Admin = { name: String, level: Long };
+        entity Group;
+    ";
+
+    let (schema, diagnostics) = lower(source);
+    assert_snapshot!(schema, @r#"
+    entity User (name)
+    entity Admin (name, level)
+    entity Group ()
+    "#);
+    assert_snapshot!(diagnostics, @"");
+}
+
+#[test]
+fn type_definition() {
+    let source = "
+        type Email = String;
+        type Emails = Set<Email>;
+    ";
+
+    let (schema, diagnostics) = lower(source);
+    assert_snapshot!(schema, @r#"
+    type Email = String
+    type Emails = Set
+    "#);
+    assert_snapshot!(diagnostics, @"");
+}
+
+#[test]
+fn bare_action_parents() {
+    let source = r#"
+        action read;
+        action write;
+        action edit in [read, Action::"view", write];
+    "#;
+
+    let (schema, diagnostics) = lower(source);
+    assert_snapshot!(schema, @r#"
+    action "read" in []
+    action "write" in []
+    action "edit" in ["read", Action::"view", "write"]
+    "#);
+    assert_snapshot!(diagnostics, @"");
+}
+
+#[test]
+fn quoted_action_names() {
+    let source = r#"
+        action "view photo", "edit\tphoto";
+        action "share" in ["view photo", Action::"edit\tphoto", read];
+    "#;
+
+    let (schema, diagnostics) = lower(source);
+    assert_snapshot!(schema, @r#"
+    action "view photo", "edit\tphoto" in []
+    action "share" in ["view photo", Action::"edit\tphoto", "read"]
+    "#);
+    assert_snapshot!(diagnostics, @"");
+}
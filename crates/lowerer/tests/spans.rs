@@ -0,0 +1,213 @@
+#![expect(clippy::unwrap_used, reason = "Tests")]
+
+use alloc::string::{String, ToString as _};
+use alloc::vec::Vec;
+
+use duramen_ast::{Declaration, Expression, ExpressionKind};
+use duramen_diagnostic::{Diagnostic, Diagnostics};
+use duramen_json::JsonParser;
+use duramen_lowerer::{PolicyJsonLowerer, PolicyLowerer, SchemaLowerer};
+use duramen_parser::{PolicyParser, SchemaParser};
+use duramen_test::anstream::adapter::strip_str;
+use duramen_test::insta::assert_snapshot;
+
+extern crate alloc;
+
+/// Lists the source text of an expression and its operands, in preorder.
+fn texts(expression: &Expression<'_>, source: &str, output: &mut Vec<String>) {
+    let span = expression.span().unwrap();
+    output.push(source[span].to_string());
+
+    match expression.kind() {
+        ExpressionKind::And { left, right }
+        | ExpressionKind::Or { left, right }
+        | ExpressionKind::BinaryApp { left, right, .. } => {
+            texts(left, source, output);
+            texts(right, source, output);
+        }
+        ExpressionKind::UnaryApp { operand, .. } => texts(operand, source, output),
+        ExpressionKind::GetAttribute { expression, .. }
+        | ExpressionKind::HasAttribute { expression, .. }
+        | ExpressionKind::Like { expression, .. }
+        | ExpressionKind::Is { expression, .. } => texts(expression, source, output),
+        ExpressionKind::ExtensionCall { arguments, .. } | ExpressionKind::Set(arguments) => {
+            for argument in arguments {
+                texts(argument, source, output);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn condition_texts(source: &str) -> String {
+    let (tree, diagnostics) = PolicyParser::parse(source);
+    let (policies, diagnostics) = PolicyLowerer::lower(&tree, diagnostics);
+    assert!(diagnostics.is_empty(), "{source}");
+
+    let mut output = Vec::new();
+    for policy in policies.policies() {
+        for condition in policy.conditions() {
+            texts(condition.body(), source, &mut output);
+        }
+    }
+
+    output.join("\n")
+}
+
+#[test]
+fn policy() {
+    let source = r#"
+        @id("a") // comment
+        permit (
+            principal == User::"alice" // alice
+            ,
+            action,
+            resource in Folder::"docs"
+        ) when { true }; // trailing
+    "#;
+
+    let (tree, diagnostics) = PolicyParser::parse(source);
+    let (policies, _) = PolicyLowerer::lower(&tree, diagnostics);
+    let policy = &policies.policies()[0];
+
+    assert_snapshot!(&source[policy.span().unwrap()], @r#"
+    @id("a") // comment
+            permit (
+                principal == User::"alice" // alice
+                ,
+                action,
+                resource in Folder::"docs"
+            ) when { true };
+    "#);
+    assert_snapshot!(&source[policy.principal().span().unwrap()], @r#"principal == User::"alice""#);
+    assert_snapshot!(&source[policy.action_span().unwrap()], @"action");
+    assert_snapshot!(&source[policy.resource().span().unwrap()], @r#"resource in Folder::"docs""#);
+}
+
+#[test]
+fn expressions() {
+    let output = condition_texts(
+        r#"permit (principal, action, resource) when {
+            context.a.b &&   // comment
+            (1 + 2) * -3 < 4 ||
+            !!resource.tags.contains("x") && ip("10.0.0.1").isLoopback()
+        };"#,
+    );
+
+    assert_snapshot!(output, @r#"
+    context.a.b &&   // comment
+                (1 + 2) * -3 < 4 ||
+                !!resource.tags.contains("x") && ip("10.0.0.1").isLoopback()
+    context.a.b &&   // comment
+                (1 + 2) * -3 < 4
+    context.a.b
+    context.a
+    context
+    (1 + 2) * -3 < 4
+    (1 + 2) * -3
+    (1 + 2)
+    1
+    2
+    -3
+    4
+    !!resource.tags.contains("x") && ip("10.0.0.1").isLoopback()
+    !!resource.tags.contains("x")
+    !resource.tags.contains("x")
+    resource.tags.contains("x")
+    resource.tags
+    resource
+    "x"
+    ip("10.0.0.1").isLoopback()
+    ip("10.0.0.1")
+    "10.0.0.1"
+    "#);
+}
+
+#[test]
+fn json() {
+    let source = r#"{
+        "effect": "permit",
+        "principal": { "op": "All" },
+        "action": { "op": "All" },
+        "resource": { "op": "All" },
+        "conditions": [
+            { "kind": "when", "body": { "!": { "arg": { "Var": "context" } } } }
+        ]
+    }"#;
+
+    let json = JsonParser::parse(source).unwrap();
+    let (policy, _) = PolicyJsonLowerer::lower_policy(&json, Diagnostics::new());
+    let policy = policy.unwrap();
+
+    assert_eq!(policy.span(), Some(0..source.len()));
+    assert_snapshot!(&source[policy.principal().span().unwrap()], @r#"{ "op": "All" }"#);
+    assert_snapshot!(&source[policy.action_span().unwrap()], @r#"{ "op": "All" }"#);
+
+    let mut output = Vec::new();
+    texts(policy.conditions()[0].body(), source, &mut output);
+    assert_snapshot!(output.join("\n"), @r#"
+    { "!": { "arg": { "Var": "context" } } }
+    { "Var": "context" }
+    "#);
+}
+
+#[test]
+fn schema() {
+    let source = "
+        namespace Acme {
+            @doc(\"user\")
+            entity User in [Group] { name: String }; // user
+            entity Group;
+            type Name = String;
+            action view appliesTo { principal: User, resource: User };
+        }
+    ";
+
+    let (tree, diagnostics) = SchemaParser::parse(source);
+    let (schema, _) = SchemaLowerer::lower(&tree, diagnostics);
+
+    let spans: Vec<_> = schema.namespaces()[0]
+        .declarations()
+        .iter()
+        .map(|declaration| match declaration {
+            Declaration::Entity(entity) => entity.span(),
+            Declaration::Action(action) => action.span(),
+            Declaration::Type(type_declaration) => type_declaration.span(),
+        })
+        .map(|span| &source[span.unwrap()])
+        .collect();
+
+    assert_snapshot!(spans.join("\n"), @r#"
+    @doc("user")
+                entity User in [Group] { name: String };
+    entity Group;
+    type Name = String;
+    action view appliesTo { principal: User, resource: User };
+    "#);
+}
+
+#[test]
+fn render() {
+    let source = "permit (principal, action, resource) when { context.a + 1 > 2 };";
+
+    let (tree, diagnostics) = PolicyParser::parse(source);
+    let (policies, _) = PolicyLowerer::lower(&tree, diagnostics);
+
+    let ExpressionKind::BinaryApp { left, .. } =
+        policies.policies()[0].conditions()[0].body().kind()
+    else {
+        panic!("expected a binary expression");
+    };
+
+    let diagnostic = Diagnostic::error("type mismatch")
+        .with_label(left.span().unwrap(), "expected `Long`, found `String`");
+
+    let rendered = diagnostic.render("test.cedar", source);
+    assert_snapshot!(strip_str(&rendered).to_string(), @r#"
+    error: type mismatch
+      ╭▸ test.cedar:1:45
+      │
+    1 │ permit (principal, action, resource) when { context.a + 1 > 2 };
+      ╰╴                                            ━━━━━━━━━━━━━ expected `Long`, found `String`
+    "#);
+}
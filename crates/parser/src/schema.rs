@@ -251,7 +251,7 @@ impl<'src> SchemaParser<'src> {
                 self.parser.builder.close(&branch);
             }
         } else {
-            self.parser.expect(TokenKind::Equals);
+            self.parser.eat(TokenKind::Equals);
             if self.parser.at(&[TokenKind::OpenBrace]) {
                 self.entity_attributes();
             }
@@ -0,0 +1,32 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use duramen_parser::SchemaParser;
+use duramen_test::anstream::adapter::strip_str;
+use duramen_test::insta::assert_snapshot;
+
+extern crate alloc;
+
+fn parse(source: &str) -> String {
+    let (_tree, diagnostics) = SchemaParser::parse(source);
+
+    let rendered = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.render("test.cedarschema", source))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    strip_str(&rendered).to_string()
+}
+
+#[test]
+fn entity_equals_optional() {
+    let source = "
+        entity User { name: String };
+        entity Admin = { name: String } tags String;
+        entity Photo tags String;
+        entity Group in [User];
+    ";
+
+    assert_snapshot!(parse(source), @"");
+}
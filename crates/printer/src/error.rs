@@ -0,0 +1,49 @@
+use alloc::format;
+use core::fmt;
+use core::ops::Range;
+
+use duramen_diagnostic::Diagnostic;
+
+/// An error produced while printing.
+///
+/// Spans are those of the enclosing declaration, when known.
+#[derive(Clone, Debug)]
+pub enum PrintError {
+    /// An inline enum type has no Cedar JSON form.
+    InlineEnum { span: Option<Range<usize>> },
+    /// An entity type set has no Cedar JSON form.
+    EntityTypeSet { span: Option<Range<usize>> },
+    /// Action attributes have no Cedar JSON form.
+    ActionAttributes { span: Option<Range<usize>> },
+}
+
+impl fmt::Display for PrintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InlineEnum { .. } => f.write_str("inline enum types have no JSON form"),
+            Self::EntityTypeSet { .. } => f.write_str("entity type sets have no JSON form"),
+            Self::ActionAttributes { .. } => f.write_str("action attributes have no JSON form"),
+        }
+    }
+}
+
+impl core::error::Error for PrintError {}
+
+impl From<PrintError> for Diagnostic {
+    fn from(value: PrintError) -> Self {
+        let (span, note) = match &value {
+            PrintError::InlineEnum { span } => (span, "declare an enumerated entity type instead"),
+            PrintError::EntityTypeSet { span } => (span, "use a single entity type instead"),
+            PrintError::ActionAttributes { span } => {
+                (span, "move the attributes to the request context instead")
+            }
+        };
+
+        let mut diagnostic = Self::error(format!("{value}")).with_note(note);
+        if let Some(span) = span {
+            diagnostic = diagnostic.with_label(span.clone(), "in this declaration");
+        }
+
+        diagnostic
+    }
+}
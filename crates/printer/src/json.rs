@@ -0,0 +1,5 @@
+mod policy;
+pub use policy::PolicyJsonPrinter;
+
+mod schema;
+pub use schema::SchemaJsonPrinter;
@@ -0,0 +1,414 @@
+use alloc::string::{String, ToString as _};
+
+use duramen_ast as ast;
+use duramen_json::JsonWriter;
+
+/// Policy printer for the Cedar policy JSON format.
+///
+/// Output can be read back by `PolicyJsonLowerer` and is accepted by upstream Cedar.
+pub struct PolicyJsonPrinter {
+    writer: JsonWriter,
+}
+
+impl PolicyJsonPrinter {
+    /// Prints policies as an array.
+    #[must_use]
+    pub fn print(policies: &ast::Policies<'_>) -> String {
+        let mut this = Self::new();
+
+        this.writer.begin_array();
+        for policy in policies.policies() {
+            this.policy(policy);
+        }
+        this.writer.end_array();
+
+        this.writer.finish()
+    }
+
+    /// Prints a single policy or template.
+    #[must_use]
+    pub fn print_policy(policy: &ast::Policy<'_>) -> String {
+        let mut this = Self::new();
+        this.policy(policy);
+        this.writer.finish()
+    }
+
+    /// Prints a policy set with `staticPolicies`, `templates` and `templateLinks`.
+    ///
+    /// Linked policies are written as links rather than static policies.
+    #[must_use]
+    pub fn print_set(set: &ast::PolicySet<'_>) -> String {
+        let mut this = Self::new();
+        this.writer.begin_object();
+
+        this.writer.key("staticPolicies");
+        this.writer.begin_object();
+        for (id, policy) in set.iter() {
+            if set.template_of(id.as_str()).is_none() {
+                this.writer.key(id.as_str());
+                this.policy(policy);
+            }
+        }
+        this.writer.end_object();
+
+        this.writer.key("templates");
+        this.writer.begin_object();
+        for (id, template) in set.templates() {
+            this.writer.key(id.as_str());
+            this.policy(template);
+        }
+        this.writer.end_object();
+
+        this.writer.key("templateLinks");
+        this.writer.begin_array();
+        for (id, link) in set.links() {
+            this.link(id, link);
+        }
+        this.writer.end_array();
+
+        this.writer.end_object();
+        this.writer.finish()
+    }
+
+    /// Prints the `templateLinks` array of a policy set.
+    #[must_use]
+    pub fn print_links(set: &ast::PolicySet<'_>) -> String {
+        let mut this = Self::new();
+
+        this.writer.begin_array();
+        for (id, link) in set.links() {
+            this.link(id, link);
+        }
+        this.writer.end_array();
+
+        this.writer.finish()
+    }
+
+    fn new() -> Self {
+        Self {
+            writer: JsonWriter::pretty(2),
+        }
+    }
+
+    fn link(&mut self, id: &ast::PolicyId<'_>, link: &ast::TemplateLink<'_>) {
+        self.writer.begin_object();
+        self.writer.key("templateId");
+        self.writer.string(link.template().as_str());
+        self.writer.key("newId");
+        self.writer.string(id.as_str());
+
+        self.writer.key("values");
+        self.writer.begin_object();
+        for (slot, entity) in link.values() {
+            self.writer.key(&slot.to_string());
+            self.entity(entity);
+        }
+        self.writer.end_object();
+
+        self.writer.end_object();
+    }
+
+    fn policy(&mut self, policy: &ast::Policy<'_>) {
+        self.writer.begin_object();
+
+        self.writer.key("effect");
+        self.writer.string(&policy.effect().to_string());
+
+        self.writer.key("principal");
+        self.scope(policy.principal().constraint(), ast::SlotKind::Principal);
+
+        self.writer.key("action");
+        self.action(policy.action());
+
+        self.writer.key("resource");
+        self.scope(policy.resource().constraint(), ast::SlotKind::Resource);
+
+        self.writer.key("conditions");
+        self.writer.begin_array();
+        for condition in policy.conditions() {
+            self.writer.begin_object();
+            self.writer.key("kind");
+            self.writer.string(&condition.kind().to_string());
+            self.writer.key("body");
+            self.expression(condition.body());
+            self.writer.end_object();
+        }
+        self.writer.end_array();
+
+        self.writer.key("annotations");
+        self.writer.begin_object();
+        for (key, value) in policy.annotations().iter() {
+            self.writer.key(key.as_str());
+            match value {
+                ast::AnnotationValue::Empty => self.writer.null(),
+                ast::AnnotationValue::String(value) => self.writer.string(value),
+            }
+        }
+        self.writer.end_object();
+
+        self.writer.end_object();
+    }
+
+    fn scope(&mut self, constraint: &ast::ScopeConstraint<'_>, slot: ast::SlotKind) {
+        self.writer.begin_object();
+        self.writer.key("op");
+
+        match constraint {
+            ast::ScopeConstraint::Any => self.writer.string("All"),
+            ast::ScopeConstraint::Equal(target) => {
+                self.writer.string("==");
+                self.entity_or_slot(target, slot);
+            }
+            ast::ScopeConstraint::In(target) => {
+                self.writer.string("in");
+                self.entity_or_slot(target, slot);
+            }
+            ast::ScopeConstraint::Is(kind) => {
+                self.writer.string("is");
+                self.writer.key("entity_type");
+                self.writer.string(&kind.to_string());
+            }
+            ast::ScopeConstraint::IsIn(kind, target) => {
+                self.writer.string("is");
+                self.writer.key("entity_type");
+                self.writer.string(&kind.to_string());
+                self.writer.key("in");
+                self.writer.begin_object();
+                self.entity_or_slot(target, slot);
+                self.writer.end_object();
+            }
+        }
+
+        self.writer.end_object();
+    }
+
+    /// Writes the `entity` or `slot` member of a scope constraint.
+    fn entity_or_slot(&mut self, target: &ast::EntityOrSlot<'_>, slot: ast::SlotKind) {
+        match target {
+            ast::EntityOrSlot::Entity(entity) => {
+                self.writer.key("entity");
+                self.entity(entity);
+            }
+            ast::EntityOrSlot::Slot => {
+                self.writer.key("slot");
+                self.writer.string(&slot.to_string());
+            }
+        }
+    }
+
+    fn action(&mut self, constraint: &ast::ActionConstraint<'_>) {
+        self.writer.begin_object();
+        self.writer.key("op");
+
+        match constraint {
+            ast::ActionConstraint::Any => self.writer.string("All"),
+            ast::ActionConstraint::Equal(entity) => {
+                self.writer.string("==");
+                self.writer.key("entity");
+                self.entity(entity);
+            }
+            ast::ActionConstraint::In(list) => {
+                self.writer.string("in");
+                self.writer.key("entities");
+                self.writer.begin_array();
+                for entity in list.iter() {
+                    self.entity(entity);
+                }
+                self.writer.end_array();
+            }
+        }
+
+        self.writer.end_object();
+    }
+
+    /// Writes `{ "type": ..., "id": ... }`.
+    fn entity(&mut self, entity: &ast::EntityReference<'_>) {
+        self.writer.begin_object();
+        self.writer.key("type");
+        self.writer.string(&entity.kind().to_string());
+        self.writer.key("id");
+        self.writer.string(entity.id());
+        self.writer.end_object();
+    }
+
+    /// Writes an expression as an object with a single key naming the operator.
+    fn expression(&mut self, expression: &ast::Expression<'_>) {
+        self.writer.begin_object();
+
+        match expression.kind() {
+            ast::ExpressionKind::Literal(literal) => {
+                self.writer.key("Value");
+                self.literal(literal);
+            }
+            ast::ExpressionKind::Variable(variable) => {
+                self.writer.key("Var");
+                self.writer.string(match variable {
+                    ast::Variable::Principal => "principal",
+                    ast::Variable::Action => "action",
+                    ast::Variable::Resource => "resource",
+                    ast::Variable::Context => "context",
+                });
+            }
+            ast::ExpressionKind::Slot(slot) => {
+                self.writer.key("Slot");
+                self.writer.string(&slot.to_string());
+            }
+            ast::ExpressionKind::If {
+                test,
+                consequent,
+                alternate,
+            } => {
+                self.writer.key("if-then-else");
+                self.writer.begin_object();
+                self.writer.key("if");
+                self.expression(test);
+                self.writer.key("then");
+                self.expression(consequent);
+                self.writer.key("else");
+                self.expression(alternate);
+                self.writer.end_object();
+            }
+            ast::ExpressionKind::And { left, right } => self.operands("&&", left, right),
+            ast::ExpressionKind::Or { left, right } => self.operands("||", left, right),
+            ast::ExpressionKind::UnaryApp { operator, operand } => {
+                self.writer.key(match operator {
+                    ast::UnaryOperator::Not => "!",
+                    ast::UnaryOperator::Negate => "neg",
+                    ast::UnaryOperator::IsEmpty => "isEmpty",
+                });
+
+                self.writer.begin_object();
+                self.writer.key("arg");
+                self.expression(operand);
+                self.writer.end_object();
+            }
+            ast::ExpressionKind::BinaryApp {
+                operator,
+                left,
+                right,
+            } => self.operands(&operator.to_string(), left, right),
+            ast::ExpressionKind::GetAttribute {
+                expression,
+                attribute,
+            } => self.attribute(".", expression, attribute),
+            ast::ExpressionKind::HasAttribute {
+                expression,
+                attribute,
+            } => self.attribute("has", expression, attribute),
+            ast::ExpressionKind::Like {
+                expression,
+                pattern,
+            } => {
+                self.writer.key("like");
+                self.writer.begin_object();
+                self.writer.key("left");
+                self.expression(expression);
+                self.writer.key("pattern");
+                self.writer.begin_array();
+                for element in pattern.elements() {
+                    match element {
+                        ast::PatternElement::Wildcard => self.writer.string("Wildcard"),
+                        ast::PatternElement::Literal(literal) => {
+                            self.writer.begin_object();
+                            self.writer.key("Literal");
+                            self.writer.string(literal);
+                            self.writer.end_object();
+                        }
+                    }
+                }
+                self.writer.end_array();
+                self.writer.end_object();
+            }
+            ast::ExpressionKind::Is { expression, kind } => {
+                self.writer.key("is");
+                self.writer.begin_object();
+                self.writer.key("left");
+                self.expression(expression);
+                self.writer.key("entity_type");
+                self.writer.string(&kind.to_string());
+                self.writer.end_object();
+            }
+            ast::ExpressionKind::IsIn {
+                expression,
+                kind,
+                target,
+            } => {
+                self.writer.key("is");
+                self.writer.begin_object();
+                self.writer.key("left");
+                self.expression(expression);
+                self.writer.key("entity_type");
+                self.writer.string(&kind.to_string());
+                self.writer.key("in");
+                self.expression(target);
+                self.writer.end_object();
+            }
+            ast::ExpressionKind::ExtensionCall {
+                function,
+                arguments,
+            } => {
+                self.writer.key(&function.to_string());
+                self.writer.begin_array();
+                for argument in arguments {
+                    self.expression(argument);
+                }
+                self.writer.end_array();
+            }
+            ast::ExpressionKind::Set(elements) => {
+                self.writer.key("Set");
+                self.writer.begin_array();
+                for element in elements {
+                    self.expression(element);
+                }
+                self.writer.end_array();
+            }
+            ast::ExpressionKind::Record(record) => {
+                self.writer.key("Record");
+                self.writer.begin_object();
+                for (key, value) in record.iter() {
+                    self.writer.key(key);
+                    self.expression(value);
+                }
+                self.writer.end_object();
+            }
+        }
+
+        self.writer.end_object();
+    }
+
+    fn literal(&mut self, literal: &ast::Literal<'_>) {
+        match literal {
+            ast::Literal::Bool(value) => self.writer.bool(value.value()),
+            ast::Literal::Integer(value) => self.writer.number(value.value()),
+            ast::Literal::String(value) => self.writer.string(value.value()),
+            ast::Literal::Entity(entity) => {
+                self.writer.begin_object();
+                self.writer.key("__entity");
+                self.entity(entity);
+                self.writer.end_object();
+            }
+        }
+    }
+
+    /// Writes `"key": { "left": ..., "right": ... }`.
+    fn operands(&mut self, key: &str, left: &ast::Expression<'_>, right: &ast::Expression<'_>) {
+        self.writer.key(key);
+        self.writer.begin_object();
+        self.writer.key("left");
+        self.expression(left);
+        self.writer.key("right");
+        self.expression(right);
+        self.writer.end_object();
+    }
+
+    /// Writes `"key": { "left": ..., "attr": ... }`.
+    fn attribute(&mut self, key: &str, expression: &ast::Expression<'_>, attribute: &str) {
+        self.writer.key(key);
+        self.writer.begin_object();
+        self.writer.key("left");
+        self.expression(expression);
+        self.writer.key("attr");
+        self.writer.string(attribute);
+        self.writer.end_object();
+    }
+}
@@ -0,0 +1,329 @@
+use alloc::string::{String, ToString as _};
+use core::ops::Range;
+
+use duramen_ast as ast;
+use duramen_json::JsonWriter;
+
+use crate::PrintError;
+
+/// Schema printer for the Cedar JSON schema format.
+///
+/// Output can be read back by `SchemaJsonLowerer`.
+/// Within a namespace, declarations are grouped into `commonTypes`, `entityTypes` and `actions`.
+pub struct SchemaJsonPrinter {
+    writer: JsonWriter,
+    span: Option<Range<usize>>,
+    error: Option<PrintError>,
+}
+
+impl SchemaJsonPrinter {
+    /// Prints a schema, using `""` as the key of the unnamed namespace.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the schema uses an inline enum, an entity type set, or action
+    /// attributes, which have no JSON form.
+    pub fn print(schema: &ast::Schema<'_>) -> Result<String, PrintError> {
+        let mut this = Self {
+            writer: JsonWriter::pretty(2),
+            span: None,
+            error: None,
+        };
+
+        this.writer.begin_object();
+        for namespace in schema.namespaces() {
+            match namespace.name() {
+                Some(name) => this.writer.key(&name.to_string()),
+                None => this.writer.key(""),
+            }
+
+            this.namespace(namespace);
+        }
+        this.writer.end_object();
+
+        match this.error {
+            Some(error) => Err(error),
+            None => Ok(this.writer.finish()),
+        }
+    }
+
+    fn namespace(&mut self, namespace: &ast::Namespace<'_>) {
+        self.writer.begin_object();
+
+        let declarations = namespace.declarations();
+        if declarations
+            .iter()
+            .any(|declaration| matches!(declaration, ast::Declaration::Type(_)))
+        {
+            self.writer.key("commonTypes");
+            self.writer.begin_object();
+            for declaration in declarations {
+                if let ast::Declaration::Type(declaration) = declaration {
+                    self.span = declaration.span();
+                    self.writer.key(declaration.name().as_str());
+                    self.writer.begin_object();
+                    self.type_fields(declaration.definition());
+                    self.annotations(declaration.annotations());
+                    self.writer.end_object();
+                }
+            }
+            self.writer.end_object();
+        }
+
+        self.writer.key("entityTypes");
+        self.writer.begin_object();
+        for declaration in declarations {
+            if let ast::Declaration::Entity(entity) = declaration {
+                self.span = entity.span();
+                for name in entity.names() {
+                    self.writer.key(name.as_str());
+                    self.entity(entity);
+                }
+            }
+        }
+        self.writer.end_object();
+
+        self.writer.key("actions");
+        self.writer.begin_object();
+        for declaration in declarations {
+            if let ast::Declaration::Action(action) = declaration {
+                self.span = action.span();
+                for name in action.names() {
+                    self.writer.key(name);
+                    self.action(action);
+                }
+            }
+        }
+        self.writer.end_object();
+
+        self.annotations(namespace.annotations());
+        self.writer.end_object();
+    }
+
+    fn entity(&mut self, entity: &ast::EntityDeclaration<'_>) {
+        self.writer.begin_object();
+
+        match entity.kind() {
+            ast::EntityKind::Standard(standard) => {
+                let mut parents = standard.parents().peekable();
+                if parents.peek().is_some() {
+                    self.writer.key("memberOfTypes");
+                    self.names(parents);
+                }
+
+                let mut attributes = standard.attributes().peekable();
+                if attributes.peek().is_some() {
+                    self.writer.key("shape");
+                    self.writer.begin_object();
+                    self.writer.key("type");
+                    self.writer.string("Record");
+                    self.attributes(attributes);
+                    self.writer.end_object();
+                }
+
+                if let Some(tags) = standard.tags() {
+                    self.writer.key("tags");
+                    self.type_expression(tags);
+                }
+            }
+            ast::EntityKind::Enum(variants) => {
+                self.writer.key("enum");
+                self.variants(variants);
+            }
+        }
+
+        self.annotations(entity.annotations());
+        self.writer.end_object();
+    }
+
+    fn action(&mut self, action: &ast::ActionDeclaration<'_>) {
+        if action.attributes().next().is_some() {
+            self.unsupported(|span| PrintError::ActionAttributes { span });
+        }
+
+        self.writer.begin_object();
+
+        let mut parents = action.parents().peekable();
+        if parents.peek().is_some() {
+            self.writer.key("memberOf");
+            self.writer.begin_array();
+            for parent in parents {
+                self.writer.begin_object();
+                self.writer.key("id");
+                self.writer.string(parent.id());
+                if let Some(kind) = parent.kind() {
+                    self.writer.key("type");
+                    self.writer.string(&kind.to_string());
+                }
+                self.writer.end_object();
+            }
+            self.writer.end_array();
+        }
+
+        if let Some(applies_to) = action.applies_to() {
+            self.writer.key("appliesTo");
+            self.writer.begin_object();
+
+            self.writer.key("principalTypes");
+            self.names(applies_to.principals());
+
+            self.writer.key("resourceTypes");
+            self.names(applies_to.resources());
+
+            if let Some(context) = applies_to.context() {
+                self.writer.key("context");
+                self.writer.begin_object();
+                match context {
+                    ast::ContextType::Reference(name) => self.reference(name),
+                    ast::ContextType::Record(record) => {
+                        self.writer.key("type");
+                        self.writer.string("Record");
+                        self.attributes(record.attributes());
+                    }
+                }
+                self.writer.end_object();
+            }
+
+            self.writer.end_object();
+        }
+
+        self.annotations(action.annotations());
+        self.writer.end_object();
+    }
+
+    fn type_expression(&mut self, expression: &ast::TypeExpression<'_>) {
+        self.writer.begin_object();
+        self.type_fields(expression);
+        self.writer.end_object();
+    }
+
+    /// Writes the members describing a type into the current object.
+    ///
+    /// Inline enums and sets of several entity types have no JSON form, and are
+    /// recorded as an error against the enclosing declaration.
+    fn type_fields(&mut self, expression: &ast::TypeExpression<'_>) {
+        match expression {
+            ast::TypeExpression::Reference(name) => self.reference(name),
+            ast::TypeExpression::Set(element) => {
+                self.writer.key("type");
+                self.writer.string("Set");
+                self.writer.key("element");
+                self.type_expression(element);
+            }
+            ast::TypeExpression::Record(record) => {
+                self.writer.key("type");
+                self.writer.string("Record");
+                self.attributes(record.attributes());
+            }
+            ast::TypeExpression::Entity(types) => {
+                let mut names = types.iter();
+                if let (Some(name), None) = (names.next(), names.next()) {
+                    self.writer.key("type");
+                    self.writer.string("Entity");
+                    self.writer.key("name");
+                    self.writer.string(&name.to_string());
+                } else {
+                    self.unsupported(|span| PrintError::EntityTypeSet { span });
+                }
+            }
+            ast::TypeExpression::Enum(_) => {
+                self.unsupported(|span| PrintError::InlineEnum { span });
+            }
+        }
+    }
+
+    /// Records the first type without a JSON form.
+    fn unsupported(&mut self, error: fn(Option<Range<usize>>) -> PrintError) {
+        if self.error.is_none() {
+            self.error = Some(error(self.span.clone()));
+        }
+    }
+
+    /// Writes a type reference, spelling out primitive and extension types.
+    fn reference(&mut self, name: &ast::Name<'_>) {
+        if name.path().is_empty() {
+            match name.basename().as_str() {
+                primitive @ ("Long" | "String") => {
+                    self.writer.key("type");
+                    self.writer.string(primitive);
+                    return;
+                }
+                "Bool" => {
+                    self.writer.key("type");
+                    self.writer.string("Boolean");
+                    return;
+                }
+                extension @ ("ipaddr" | "decimal" | "datetime" | "duration") => {
+                    self.writer.key("type");
+                    self.writer.string("Extension");
+                    self.writer.key("name");
+                    self.writer.string(extension);
+                    return;
+                }
+                _ => {}
+            }
+        }
+
+        self.writer.key("type");
+        self.writer.string("EntityOrCommon");
+        self.writer.key("name");
+        self.writer.string(&name.to_string());
+    }
+
+    fn attributes<'a, I: Iterator<Item = (&'a str, &'a ast::AttributeDeclaration<'a>)>>(
+        &mut self,
+        attributes: I,
+    ) {
+        self.writer.key("attributes");
+        self.writer.begin_object();
+        for (name, attribute) in attributes {
+            self.writer.key(name);
+            self.writer.begin_object();
+            self.type_fields(attribute.definition());
+
+            if attribute.optionality() == ast::Optionality::Optional {
+                self.writer.key("required");
+                self.writer.bool(false);
+            }
+
+            self.annotations(attribute.annotations());
+            self.writer.end_object();
+        }
+        self.writer.end_object();
+    }
+
+    fn variants(&mut self, variants: &ast::EnumType<'_>) {
+        self.writer.begin_array();
+        for variant in variants.variants() {
+            self.writer.string(variant);
+        }
+        self.writer.end_array();
+    }
+
+    fn names<'a, I: Iterator<Item = &'a ast::Name<'a>>>(&mut self, names: I) {
+        self.writer.begin_array();
+        for name in names {
+            self.writer.string(&name.to_string());
+        }
+        self.writer.end_array();
+    }
+
+    /// Writes an `annotations` member, if there are any.
+    fn annotations(&mut self, annotations: &ast::Annotations<'_>) {
+        let mut annotations = annotations.iter().peekable();
+        if annotations.peek().is_none() {
+            return;
+        }
+
+        self.writer.key("annotations");
+        self.writer.begin_object();
+        for (key, value) in annotations {
+            self.writer.key(key.as_str());
+            match value {
+                ast::AnnotationValue::Empty => self.writer.null(),
+                ast::AnnotationValue::String(value) => self.writer.string(value),
+            }
+        }
+        self.writer.end_object();
+    }
+}
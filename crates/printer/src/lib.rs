@@ -0,0 +1,15 @@
+#![cfg_attr(doc, doc = include_str!("../README.md"))]
+#![no_std]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+mod error;
+pub use error::PrintError;
+
+mod json;
+pub use json::{PolicyJsonPrinter, SchemaJsonPrinter};
+
+mod schema;
+pub use schema::SchemaPrinter;
@@ -0,0 +1,330 @@
+use alloc::string::{String, ToString as _};
+use alloc::vec::Vec;
+
+use duramen_ast as ast;
+use duramen_escape::Escaper;
+
+/// Schema printer for the `.cedarschema` text format.
+///
+/// Output can be read back by `SchemaLowerer`.
+/// Annotations on the unnamed namespace have no textual form and are omitted.
+pub struct SchemaPrinter {
+    output: String,
+    depth: usize,
+}
+
+impl SchemaPrinter {
+    /// Prints a schema, separating namespaces with a blank line.
+    #[must_use]
+    pub fn print(schema: &ast::Schema<'_>) -> String {
+        let mut this = Self {
+            output: String::new(),
+            depth: 0,
+        };
+
+        for (index, namespace) in schema.namespaces().iter().enumerate() {
+            if index > 0 {
+                this.output.push('\n');
+            }
+
+            this.namespace(namespace);
+        }
+
+        this.output
+    }
+
+    fn namespace(&mut self, namespace: &ast::Namespace<'_>) {
+        let Some(name) = namespace.name() else {
+            for declaration in namespace.declarations() {
+                self.declaration(declaration);
+            }
+
+            return;
+        };
+
+        self.annotations(namespace.annotations());
+        self.output.push_str("namespace ");
+        self.output.push_str(&name.to_string());
+        self.output.push_str(" {\n");
+
+        self.depth += 1;
+        for declaration in namespace.declarations() {
+            self.declaration(declaration);
+        }
+        self.depth -= 1;
+
+        self.output.push_str("}\n");
+    }
+
+    fn declaration(&mut self, declaration: &ast::Declaration<'_>) {
+        match declaration {
+            ast::Declaration::Entity(entity) => self.entity(entity),
+            ast::Declaration::Action(action) => self.action(action),
+            ast::Declaration::Type(declaration) => {
+                self.annotations(declaration.annotations());
+                self.indent();
+                self.output.push_str("type ");
+                self.output.push_str(declaration.name().as_str());
+                self.output.push_str(" = ");
+                self.type_expression(declaration.definition());
+                self.output.push_str(";\n");
+            }
+        }
+    }
+
+    fn entity(&mut self, entity: &ast::EntityDeclaration<'_>) {
+        self.annotations(entity.annotations());
+        self.indent();
+        self.output.push_str("entity ");
+
+        for (index, name) in entity.names().enumerate() {
+            if index > 0 {
+                self.output.push_str(", ");
+            }
+
+            self.output.push_str(name.as_str());
+        }
+
+        match entity.kind() {
+            ast::EntityKind::Standard(standard) => {
+                let mut parents = standard.parents().peekable();
+                if parents.peek().is_some() {
+                    self.output.push_str(" in ");
+                    self.names(parents);
+                }
+
+                let attributes = standard.attributes().collect::<Vec<_>>();
+                if !attributes.is_empty() {
+                    self.output.push(' ');
+                    self.record(&attributes);
+                }
+
+                if let Some(tags) = standard.tags() {
+                    self.output.push_str(" tags ");
+                    self.type_expression(tags);
+                }
+            }
+            ast::EntityKind::Enum(variants) => {
+                self.output.push(' ');
+                self.enum_type(variants);
+            }
+        }
+
+        self.output.push_str(";\n");
+    }
+
+    fn action(&mut self, action: &ast::ActionDeclaration<'_>) {
+        self.annotations(action.annotations());
+        self.indent();
+        self.output.push_str("action ");
+
+        for (index, name) in action.names().enumerate() {
+            if index > 0 {
+                self.output.push_str(", ");
+            }
+
+            self.key(name);
+        }
+
+        let mut parents = action.parents().peekable();
+        if parents.peek().is_some() {
+            self.output.push_str(" in [");
+            for (index, parent) in parents.enumerate() {
+                if index > 0 {
+                    self.output.push_str(", ");
+                }
+
+                match parent.kind() {
+                    Some(kind) => {
+                        self.output.push_str(&kind.to_string());
+                        self.output.push_str("::");
+                        self.string(parent.id());
+                    }
+                    None => self.key(parent.id()),
+                }
+            }
+            self.output.push(']');
+        }
+
+        if let Some(applies_to) = action.applies_to() {
+            self.output.push_str(" appliesTo ");
+            self.applies_to(applies_to);
+        }
+
+        let attributes = action.attributes().collect::<Vec<_>>();
+        if !attributes.is_empty() {
+            self.output.push_str(" attributes ");
+            self.record(&attributes);
+        }
+
+        self.output.push_str(";\n");
+    }
+
+    fn applies_to(&mut self, applies_to: &ast::AppliesTo<'_>) {
+        let mut principals = applies_to.principals().peekable();
+        let mut resources = applies_to.resources().peekable();
+
+        let mut entries = 0;
+        self.output.push('{');
+        self.depth += 1;
+
+        if principals.peek().is_some() {
+            self.entry(&mut entries, "principal");
+            self.names(principals);
+        }
+
+        if resources.peek().is_some() {
+            self.entry(&mut entries, "resource");
+            self.names(resources);
+        }
+
+        if let Some(context) = applies_to.context() {
+            self.entry(&mut entries, "context");
+            match context {
+                ast::ContextType::Reference(name) => {
+                    self.output.push_str(&name.to_string());
+                }
+                ast::ContextType::Record(record) => {
+                    self.record(&record.attributes().collect::<Vec<_>>());
+                }
+            }
+        }
+
+        self.depth -= 1;
+        if entries > 0 {
+            self.output.push('\n');
+            self.indent();
+        }
+        self.output.push('}');
+    }
+
+    /// Starts an `appliesTo` entry on a new line.
+    fn entry(&mut self, entries: &mut usize, key: &str) {
+        if *entries > 0 {
+            self.output.push(',');
+        }
+
+        *entries += 1;
+        self.output.push('\n');
+        self.indent();
+        self.output.push_str(key);
+        self.output.push_str(": ");
+    }
+
+    fn type_expression(&mut self, expression: &ast::TypeExpression<'_>) {
+        match expression {
+            ast::TypeExpression::Reference(name) => {
+                self.output.push_str(&name.to_string());
+            }
+            ast::TypeExpression::Set(element) => {
+                self.output.push_str("Set<");
+                self.type_expression(element);
+                self.output.push('>');
+            }
+            ast::TypeExpression::Record(record) => {
+                self.record(&record.attributes().collect::<Vec<_>>());
+            }
+            ast::TypeExpression::Entity(types) => self.names(types.iter()),
+            ast::TypeExpression::Enum(variants) => self.enum_type(variants),
+        }
+    }
+
+    fn record(&mut self, attributes: &[(&str, &ast::AttributeDeclaration<'_>)]) {
+        if attributes.is_empty() {
+            self.output.push_str("{}");
+            return;
+        }
+
+        self.output.push_str("{\n");
+        self.depth += 1;
+
+        for (index, (name, attribute)) in attributes.iter().enumerate() {
+            self.annotations(attribute.annotations());
+            self.indent();
+            self.key(name);
+
+            if attribute.optionality() == ast::Optionality::Optional {
+                self.output.push('?');
+            }
+
+            self.output.push_str(": ");
+            self.type_expression(attribute.definition());
+
+            if index + 1 < attributes.len() {
+                self.output.push(',');
+            }
+
+            self.output.push('\n');
+        }
+
+        self.depth -= 1;
+        self.indent();
+        self.output.push('}');
+    }
+
+    fn enum_type(&mut self, variants: &ast::EnumType<'_>) {
+        self.output.push_str("enum [");
+        for (index, variant) in variants.variants().enumerate() {
+            if index > 0 {
+                self.output.push_str(", ");
+            }
+
+            self.string(variant);
+        }
+        self.output.push(']');
+    }
+
+    /// Prints a single name, or a bracketed list for several.
+    fn names<'a, I: Iterator<Item = &'a ast::Name<'a>>>(&mut self, names: I) {
+        let names = names.collect::<Vec<_>>();
+        if let [name] = names.as_slice() {
+            self.output.push_str(&name.to_string());
+            return;
+        }
+
+        self.output.push('[');
+        for (index, name) in names.iter().enumerate() {
+            if index > 0 {
+                self.output.push_str(", ");
+            }
+
+            self.output.push_str(&name.to_string());
+        }
+        self.output.push(']');
+    }
+
+    fn annotations(&mut self, annotations: &ast::Annotations<'_>) {
+        for (key, value) in annotations.iter() {
+            self.indent();
+            self.output.push('@');
+            self.output.push_str(key.as_str());
+
+            if let ast::AnnotationValue::String(value) = value {
+                self.output.push('(');
+                self.string(value);
+                self.output.push(')');
+            }
+
+            self.output.push('\n');
+        }
+    }
+
+    /// Prints an identifier as-is, quoting anything else.
+    fn key(&mut self, key: &str) {
+        if ast::Identifier::new(key).is_ok() {
+            self.output.push_str(key);
+        } else {
+            self.string(key);
+        }
+    }
+
+    fn string(&mut self, value: &str) {
+        self.output.push_str(&Escaper::escape_str(value));
+    }
+
+    fn indent(&mut self) {
+        for _ in 0..self.depth {
+            self.output.push_str("  ");
+        }
+    }
+}
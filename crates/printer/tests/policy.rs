@@ -0,0 +1,205 @@
+#![expect(clippy::unwrap_used, reason = "Tests")]
+
+use alloc::format;
+use alloc::string::String;
+
+use duramen_diagnostic::Diagnostics;
+use duramen_json::JsonParser;
+use duramen_lowerer::{PolicyJsonLowerer, PolicyLowerer};
+use duramen_parser::PolicyParser;
+use duramen_printer::PolicyJsonPrinter;
+use duramen_test::insta::assert_snapshot;
+
+extern crate alloc;
+
+fn print(source: &str) -> String {
+    let (tree, diagnostics) = PolicyParser::parse(source);
+    let (policies, diagnostics) = PolicyLowerer::lower(&tree, diagnostics);
+    assert!(diagnostics.is_empty(), "{source}");
+
+    PolicyJsonPrinter::print_policy(&policies.policies()[0])
+}
+
+/// Asserts that printing and lowering again produces the same AST.
+fn assert_round_trip(source: &str) {
+    let (tree, diagnostics) = PolicyParser::parse(source);
+    let (policies, diagnostics) = PolicyLowerer::lower(&tree, diagnostics);
+    assert!(diagnostics.is_empty(), "{source}");
+
+    let printed = PolicyJsonPrinter::print(&policies);
+    let json = JsonParser::parse(&printed).unwrap();
+    let (lowered, diagnostics) = PolicyJsonLowerer::lower(&json, Diagnostics::new());
+    assert!(diagnostics.is_empty(), "{printed}");
+
+    assert_eq!(format!("{lowered:?}"), format!("{policies:?}"), "{source}");
+}
+
+#[test]
+fn annotations() {
+    let output = print(
+        r#"
+        @id("example")
+        @reviewed
+        permit(principal, action, resource);
+        "#,
+    );
+
+    assert_snapshot!(output, @r#"
+    {
+      "effect": "permit",
+      "principal": {
+        "op": "All"
+      },
+      "action": {
+        "op": "All"
+      },
+      "resource": {
+        "op": "All"
+      },
+      "conditions": [],
+      "annotations": {
+        "id": "example",
+        "reviewed": null
+      }
+    }
+    "#);
+}
+
+#[test]
+fn slots() {
+    let output = print("permit(principal in ?principal, action, resource == ?resource);");
+    assert_snapshot!(output, @r#"
+    {
+      "effect": "permit",
+      "principal": {
+        "op": "in",
+        "slot": "?principal"
+      },
+      "action": {
+        "op": "All"
+      },
+      "resource": {
+        "op": "==",
+        "slot": "?resource"
+      },
+      "conditions": [],
+      "annotations": {}
+    }
+    "#);
+}
+
+#[test]
+fn like() {
+    let output =
+        print(r#"permit(principal, action, resource) when { resource.path like "*/a\*b/*" };"#);
+    assert_snapshot!(output, @r#"
+    {
+      "effect": "permit",
+      "principal": {
+        "op": "All"
+      },
+      "action": {
+        "op": "All"
+      },
+      "resource": {
+        "op": "All"
+      },
+      "conditions": [
+        {
+          "kind": "when",
+          "body": {
+            "like": {
+              "left": {
+                ".": {
+                  "left": {
+                    "Var": "resource"
+                  },
+                  "attr": "path"
+                }
+              },
+              "pattern": [
+                "Wildcard",
+                {
+                  "Literal": "/a*b/"
+                },
+                "Wildcard"
+              ]
+            }
+          }
+        }
+      ],
+      "annotations": {}
+    }
+    "#);
+}
+
+#[test]
+fn extension_calls() {
+    let output = print(
+        r#"permit(principal, action, resource) when { ip(context.ip).isInRange(ip("10.0.0.0/8")) };"#,
+    );
+
+    assert_snapshot!(output, @r#"
+    {
+      "effect": "permit",
+      "principal": {
+        "op": "All"
+      },
+      "action": {
+        "op": "All"
+      },
+      "resource": {
+        "op": "All"
+      },
+      "conditions": [
+        {
+          "kind": "when",
+          "body": {
+            "isInRange": [
+              {
+                "ip": [
+                  {
+                    ".": {
+                      "left": {
+                        "Var": "context"
+                      },
+                      "attr": "ip"
+                    }
+                  }
+                ]
+              },
+              {
+                "ip": [
+                  {
+                    "Value": "10.0.0.0/8"
+                  }
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "annotations": {}
+    }
+    "#);
+}
+
+#[test]
+fn round_trip() {
+    assert_round_trip(
+        r#"
+        @id("a")
+        permit(principal is User in Group::"admins", action in [Action::"view", Action::"edit"], resource)
+        when { context.level >= -2 && [1, "two", User::"alice"].containsAny(resource.tags) }
+        unless { principal.hasTag("blocked") || !(resource has owner) };
+
+        forbid(principal == ?principal, action == Action::"delete", resource is Photo in ?resource)
+        when { if context.a then {b: 1, "c d": decimal("1.5")} else {} }
+        when { principal is Admin in resource.org && resource.name like "*.jpg\*" };
+
+        permit(principal, action in Action::"read", resource)
+        when { -(principal.age * 2 + 1 - 3) < 0 && principal.getTag("team") != "core" }
+        when { datetime("2024-01-01").offset(duration("1d")) > context.now && [].isEmpty() };
+        "#,
+    );
+}
@@ -0,0 +1,224 @@
+use alloc::borrow::Cow;
+use alloc::{format, vec};
+
+use duramen_ast::{EntityReference, Name, PolicyId, SlotKind};
+use duramen_diagnostic::Diagnostics;
+use duramen_json::JsonParser;
+use duramen_lowerer::{PolicyJsonLowerer, PolicyLowerer};
+use duramen_parser::PolicyParser;
+use duramen_printer::PolicyJsonPrinter;
+use duramen_test::insta::assert_snapshot;
+
+extern crate alloc;
+
+const SOURCE: &str = r#"
+    @id("share")
+    permit(principal == ?principal, action, resource in ?resource);
+    @id("all")
+    forbid(principal, action, resource) unless { context.safe };
+"#;
+
+const POLICIES: &str = r#"
+    @id("share")
+    permit(principal == ?principal, action in [Action::"view", Action::"edit"], resource in ?resource)
+    when { context.level >= 2 && resource has owner }
+    unless { principal is Admin in Org::"acme" };
+
+    @id("audit")
+    @internal
+    forbid(principal, action == Action::"delete", resource is Photo)
+    when { !(resource.tags.contains("keep")) || (if ip("10.0.0.1").isLoopback() then -1 < 0 else false) }
+    when { context.path like "/home/*/docs\*" && {a: [1, "two", User::"alice"]}.a.isEmpty() };
+"#;
+
+#[test]
+fn print_links() {
+    let (tree, diagnostics) = PolicyParser::parse(SOURCE);
+    let (mut policies, diagnostics) = PolicyLowerer::lower_set(&tree, diagnostics);
+    assert!(diagnostics.is_empty());
+
+    let alice = EntityReference::new(Name::parse("Acme::User").unwrap(), Cow::Borrowed("alice"));
+    let trip = EntityReference::new(
+        Name::parse("Album").unwrap(),
+        Cow::Borrowed("trip \"2024\""),
+    );
+    policies
+        .link(
+            "share",
+            PolicyId::new(Cow::Borrowed("share-alice")),
+            vec![(SlotKind::Principal, alice), (SlotKind::Resource, trip)],
+        )
+        .unwrap();
+
+    assert_snapshot!(PolicyJsonPrinter::print_links(&policies), @r#"
+    [
+      {
+        "templateId": "share",
+        "newId": "share-alice",
+        "values": {
+          "?principal": {
+            "type": "Acme::User",
+            "id": "alice"
+          },
+          "?resource": {
+            "type": "Album",
+            "id": "trip \"2024\""
+          }
+        }
+      }
+    ]
+    "#);
+}
+
+#[test]
+fn links_round_trip() {
+    let (tree, diagnostics) = PolicyParser::parse(SOURCE);
+    let (mut policies, diagnostics) = PolicyLowerer::lower_set(&tree, diagnostics);
+    assert!(diagnostics.is_empty());
+
+    for name in ["alice", "bob"] {
+        let user = EntityReference::new(Name::parse("User").unwrap(), Cow::Borrowed(name));
+        let album = EntityReference::new(Name::parse("Album").unwrap(), Cow::Borrowed(name));
+        policies
+            .link(
+                "share",
+                PolicyId::new(Cow::Owned(format!("share-{name}"))),
+                vec![(SlotKind::Principal, user), (SlotKind::Resource, album)],
+            )
+            .unwrap();
+    }
+
+    let printed = PolicyJsonPrinter::print_links(&policies);
+
+    let (tree, diagnostics) = PolicyParser::parse(SOURCE);
+    let (unlinked, diagnostics) = PolicyLowerer::lower_set(&tree, diagnostics);
+    let json = JsonParser::parse(&printed).unwrap();
+    let (relinked, diagnostics) = PolicyJsonLowerer::lower_links(&json, unlinked, diagnostics);
+    assert!(diagnostics.is_empty());
+
+    assert_eq!(PolicyJsonPrinter::print_links(&relinked), printed);
+    assert_eq!(relinked.len(), policies.len());
+}
+
+#[test]
+fn print_set() {
+    let source = r#"
+        @id("share")
+        permit(principal == ?principal, action, resource);
+        @id("all")
+        forbid(principal, action, resource) unless { context.safe };
+    "#;
+
+    let (tree, diagnostics) = PolicyParser::parse(source);
+    let (mut policies, diagnostics) = PolicyLowerer::lower_set(&tree, diagnostics);
+    assert!(diagnostics.is_empty());
+
+    let alice = EntityReference::new(Name::parse("Acme::User").unwrap(), Cow::Borrowed("alice"));
+    policies
+        .link(
+            "share",
+            PolicyId::new(Cow::Borrowed("share-alice")),
+            vec![(SlotKind::Principal, alice)],
+        )
+        .unwrap();
+
+    assert_snapshot!(PolicyJsonPrinter::print_set(&policies), @r#"
+    {
+      "staticPolicies": {
+        "all": {
+          "effect": "forbid",
+          "principal": {
+            "op": "All"
+          },
+          "action": {
+            "op": "All"
+          },
+          "resource": {
+            "op": "All"
+          },
+          "conditions": [
+            {
+              "kind": "unless",
+              "body": {
+                ".": {
+                  "left": {
+                    "Var": "context"
+                  },
+                  "attr": "safe"
+                }
+              }
+            }
+          ],
+          "annotations": {
+            "id": "all"
+          }
+        }
+      },
+      "templates": {
+        "share": {
+          "effect": "permit",
+          "principal": {
+            "op": "==",
+            "slot": "?principal"
+          },
+          "action": {
+            "op": "All"
+          },
+          "resource": {
+            "op": "All"
+          },
+          "conditions": [],
+          "annotations": {
+            "id": "share"
+          }
+        }
+      },
+      "templateLinks": [
+        {
+          "templateId": "share",
+          "newId": "share-alice",
+          "values": {
+            "?principal": {
+              "type": "Acme::User",
+              "id": "alice"
+            }
+          }
+        }
+      ]
+    }
+    "#);
+}
+
+#[test]
+fn set_round_trip() {
+    let (tree, diagnostics) = PolicyParser::parse(POLICIES);
+    let (mut policies, diagnostics) = PolicyLowerer::lower_set(&tree, diagnostics);
+    assert!(diagnostics.is_empty());
+
+    policies
+        .link(
+            "share",
+            PolicyId::new(Cow::Borrowed("share-bob")),
+            vec![
+                (
+                    SlotKind::Principal,
+                    EntityReference::new(Name::parse("User").unwrap(), Cow::Borrowed("bob")),
+                ),
+                (
+                    SlotKind::Resource,
+                    EntityReference::new(Name::parse("Folder").unwrap(), Cow::Borrowed("home")),
+                ),
+            ],
+        )
+        .unwrap();
+
+    let printed = PolicyJsonPrinter::print_set(&policies);
+
+    let json = JsonParser::parse(&printed).unwrap();
+    let (lowered, diagnostics) = PolicyJsonLowerer::lower_set(&json, Diagnostics::new());
+    assert!(diagnostics.is_empty());
+
+    assert_eq!(lowered.len(), policies.len());
+    assert_eq!(lowered.template_of("share-bob").unwrap().as_str(), "share");
+    assert_eq!(PolicyJsonPrinter::print_set(&lowered), printed);
+}
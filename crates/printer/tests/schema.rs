@@ -0,0 +1,393 @@
+use duramen_diagnostic::{Diagnostic, Diagnostics};
+use duramen_json::JsonParser;
+use duramen_lowerer::{SchemaJsonLowerer, SchemaLowerer};
+use duramen_parser::SchemaParser;
+use duramen_printer::{SchemaJsonPrinter, SchemaPrinter};
+use duramen_test::anstream::adapter::strip_str;
+use duramen_test::insta::assert_snapshot;
+
+const SOURCE: &str = r#"
+    @doc("shared types")
+    namespace Acme {
+        @doc("a person")
+        type Person = {
+            name: String,
+            @doc("optional")
+            "e-mail"?: Email,
+            aliases: Set<String>,
+        };
+        type Email = String;
+
+        entity User, Robot in [Group] {
+            owner: Person,
+            active: Bool,
+            home: ipaddr,
+        } tags Set<Long>;
+        entity Group;
+        @deprecated
+        entity Color enum ["red", "say \"hi\"\n"];
+
+        action read, "list all";
+        @doc("view")
+        action view in [read, "list all", Acme::Action::"read"] appliesTo {
+            principal: [User, Robot],
+            resource: Group,
+            context: { ip: ipaddr, level?: Long },
+        };
+        action audit appliesTo { principal: User, resource: Group, context: Person };
+    }
+
+    namespace Other::Nested {
+        entity Item;
+    }
+"#;
+
+#[test]
+fn print_text() {
+    let source = r#"{
+        "": {
+            "commonTypes": {
+                "Address": {
+                    "type": "Record",
+                    "attributes": {
+                        "street": { "type": "String", "annotations": { "doc": "street" } },
+                        "zip code": { "type": "Long", "required": false }
+                    }
+                }
+            },
+            "entityTypes": {
+                "User": {
+                    "memberOfTypes": ["Team", "Org::Team"],
+                    "shape": { "type": "Record", "attributes": { "address": { "type": "EntityOrCommon", "name": "Address" } } },
+                    "tags": { "type": "Boolean" },
+                    "annotations": { "doc": "user" }
+                },
+                "Team": {},
+                "Status": { "enum": ["on", "off"] }
+            },
+            "actions": {
+                "read": {},
+                "read all": {
+                    "memberOf": [{ "id": "read" }, { "type": "Org::Action", "id": "read" }],
+                    "appliesTo": {
+                        "principalTypes": ["User"],
+                        "resourceTypes": ["Team", "User"],
+                        "context": { "type": "Record", "attributes": {} }
+                    }
+                }
+            }
+        }
+    }"#;
+
+    let json = JsonParser::parse(source).unwrap();
+    let (schema, diagnostics) = SchemaJsonLowerer::lower(&json, Diagnostics::new());
+    assert!(diagnostics.is_empty());
+
+    assert_snapshot!(SchemaPrinter::print(&schema), @r#"
+    type Address = {
+      @doc("street")
+      street: String,
+      "zip code"?: Long
+    };
+    @doc("user")
+    entity User in [Team, Org::Team] {
+      address: Address
+    } tags Bool;
+    entity Team;
+    entity Status enum ["on", "off"];
+    action read;
+    action "read all" in [read, Org::Action::"read"] appliesTo {
+      principal: User,
+      resource: [Team, User],
+      context: {}
+    };
+    "#);
+}
+
+#[test]
+fn print_json() {
+    let (tree, diagnostics) = SchemaParser::parse(SOURCE);
+    let (schema, diagnostics) = SchemaLowerer::lower(&tree, diagnostics);
+    assert!(diagnostics.is_empty());
+
+    assert_snapshot!(SchemaJsonPrinter::print(&schema).unwrap(), @r#"
+    {
+      "Acme": {
+        "commonTypes": {
+          "Person": {
+            "type": "Record",
+            "attributes": {
+              "name": {
+                "type": "String"
+              },
+              "e-mail": {
+                "type": "EntityOrCommon",
+                "name": "Email",
+                "required": false,
+                "annotations": {
+                  "doc": "optional"
+                }
+              },
+              "aliases": {
+                "type": "Set",
+                "element": {
+                  "type": "String"
+                }
+              }
+            },
+            "annotations": {
+              "doc": "a person"
+            }
+          },
+          "Email": {
+            "type": "String"
+          }
+        },
+        "entityTypes": {
+          "User": {
+            "memberOfTypes": [
+              "Group"
+            ],
+            "shape": {
+              "type": "Record",
+              "attributes": {
+                "owner": {
+                  "type": "EntityOrCommon",
+                  "name": "Person"
+                },
+                "active": {
+                  "type": "Boolean"
+                },
+                "home": {
+                  "type": "Extension",
+                  "name": "ipaddr"
+                }
+              }
+            },
+            "tags": {
+              "type": "Set",
+              "element": {
+                "type": "Long"
+              }
+            }
+          },
+          "Robot": {
+            "memberOfTypes": [
+              "Group"
+            ],
+            "shape": {
+              "type": "Record",
+              "attributes": {
+                "owner": {
+                  "type": "EntityOrCommon",
+                  "name": "Person"
+                },
+                "active": {
+                  "type": "Boolean"
+                },
+                "home": {
+                  "type": "Extension",
+                  "name": "ipaddr"
+                }
+              }
+            },
+            "tags": {
+              "type": "Set",
+              "element": {
+                "type": "Long"
+              }
+            }
+          },
+          "Group": {},
+          "Color": {
+            "enum": [
+              "red",
+              "say \"hi\"\n"
+            ],
+            "annotations": {
+              "deprecated": null
+            }
+          }
+        },
+        "actions": {
+          "read": {},
+          "list all": {},
+          "view": {
+            "memberOf": [
+              {
+                "id": "read"
+              },
+              {
+                "id": "list all"
+              },
+              {
+                "id": "read",
+                "type": "Acme::Action"
+              }
+            ],
+            "appliesTo": {
+              "principalTypes": [
+                "User",
+                "Robot"
+              ],
+              "resourceTypes": [
+                "Group"
+              ],
+              "context": {
+                "type": "Record",
+                "attributes": {
+                  "ip": {
+                    "type": "Extension",
+                    "name": "ipaddr"
+                  },
+                  "level": {
+                    "type": "Long",
+                    "required": false
+                  }
+                }
+              }
+            },
+            "annotations": {
+              "doc": "view"
+            }
+          },
+          "audit": {
+            "appliesTo": {
+              "principalTypes": [
+                "User"
+              ],
+              "resourceTypes": [
+                "Group"
+              ],
+              "context": {
+                "type": "EntityOrCommon",
+                "name": "Person"
+              }
+            }
+          }
+        },
+        "annotations": {
+          "doc": "shared types"
+        }
+      },
+      "Other::Nested": {
+        "entityTypes": {
+          "Item": {}
+        },
+        "actions": {}
+      }
+    }
+    "#);
+}
+
+#[test]
+fn round_trip() {
+    let (tree, diagnostics) = SchemaParser::parse(SOURCE);
+    let (schema, diagnostics) = SchemaLowerer::lower(&tree, diagnostics);
+    assert!(diagnostics.is_empty());
+
+    let printed = SchemaJsonPrinter::print(&schema).unwrap();
+
+    let json = JsonParser::parse(&printed).unwrap();
+    let (lowered, diagnostics) = SchemaJsonLowerer::lower(&json, Diagnostics::new());
+    assert!(diagnostics.is_empty());
+    assert_eq!(SchemaJsonPrinter::print(&lowered).unwrap(), printed);
+
+    let text = SchemaPrinter::print(&lowered);
+
+    let (tree, diagnostics) = SchemaParser::parse(&text);
+    let (reparsed, diagnostics) = SchemaLowerer::lower(&tree, diagnostics);
+    assert!(diagnostics.is_empty());
+    assert_eq!(SchemaPrinter::print(&reparsed), text);
+    assert_eq!(SchemaJsonPrinter::print(&reparsed).unwrap(), printed);
+}
+
+#[test]
+fn entity_types() {
+    let source = r#"{
+        "": {
+            "entityTypes": {
+                "User": {},
+                "Doc": {
+                    "shape": {
+                        "type": "Record",
+                        "attributes": { "owner": { "type": "Entity", "name": "User" } }
+                    }
+                }
+            },
+            "actions": {}
+        }
+    }"#;
+
+    let json = JsonParser::parse(source).unwrap();
+    let (schema, diagnostics) = SchemaJsonLowerer::lower(&json, Diagnostics::new());
+    assert!(diagnostics.is_empty());
+
+    assert_snapshot!(SchemaJsonPrinter::print(&schema).unwrap(), @r#"
+    {
+      "": {
+        "entityTypes": {
+          "User": {},
+          "Doc": {
+            "shape": {
+              "type": "Record",
+              "attributes": {
+                "owner": {
+                  "type": "Entity",
+                  "name": "User"
+                }
+              }
+            }
+          }
+        },
+        "actions": {}
+      }
+    }
+    "#);
+    assert_snapshot!(SchemaPrinter::print(&schema), @r#"
+    entity User;
+    entity Doc {
+      owner: User
+    };
+    "#);
+}
+
+#[test]
+fn unsupported_types() {
+    let unsupported = |source: &str| {
+        let (tree, diagnostics) = SchemaParser::parse(source);
+        let (schema, diagnostics) = SchemaLowerer::lower(&tree, diagnostics);
+        assert!(diagnostics.is_empty(), "{source}");
+
+        let error = SchemaJsonPrinter::print(&schema).unwrap_err();
+        strip_str(&Diagnostic::from(error).render("test.cedarschema", source)).to_string()
+    };
+
+    assert_snapshot!(unsupported("entity Doc { status: enum [\"draft\", \"final\"] };\n"), @r#"
+    error: inline enum types have no JSON form
+      ╭▸ test.cedarschema:1:1
+      │
+    1 │ entity Doc { status: enum ["draft", "final"] };
+      │ ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ in this declaration
+      ╰╴
+    note: declare an enumerated entity type instead
+    "#);
+    assert_snapshot!(unsupported("type Color = enum [\"red\", \"blue\"];\n"), @r#"
+    error: inline enum types have no JSON form
+      ╭▸ test.cedarschema:1:1
+      │
+    1 │ type Color = enum ["red", "blue"];
+      │ ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ in this declaration
+      ╰╴
+    note: declare an enumerated entity type instead
+    "#);
+    assert_snapshot!(unsupported("action view attributes { level: Long };\n"), @r#"
+    error: action attributes have no JSON form
+      ╭▸ test.cedarschema:1:1
+      │
+    1 │ action view attributes { level: Long };
+      │ ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ in this declaration
+      ╰╴
+    note: move the attributes to the request context instead
+    "#);
+}
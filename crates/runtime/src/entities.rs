@@ -0,0 +1,209 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use duramen_diagnostic::Diagnostics;
+
+use crate::error::EntityError;
+use crate::value::EntityUid;
+use crate::{IndexMap, IndexSet};
+
+mod entity;
+pub use entity::Entity;
+
+mod parser;
+pub use parser::{EntitiesParser, Hint, TypeHints};
+
+/// Traversal state of an entity while computing the hierarchy closure.
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Visit {
+    Pending,
+    Active,
+    Done,
+}
+
+/// An immutable store of entities.
+///
+/// The transitive closure of the hierarchy is computed up front, so ancestor queries are
+/// constant time lookups.
+#[derive(Clone, Debug, Default)]
+pub struct Entities {
+    entities: IndexMap<EntityUid, Entity>,
+    ancestors: Vec<IndexSet<EntityUid>>,
+}
+
+impl Entities {
+    /// Creates an empty store.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Builds a store from entities, computing the transitive closure of the hierarchy.
+    ///
+    /// # Errors
+    ///
+    /// Returns diagnostics for duplicate entities, unknown parents, and hierarchy cycles.
+    pub fn new<I: IntoIterator<Item = Entity>>(entities: I) -> Result<Self, Diagnostics> {
+        let mut diagnostics = Diagnostics::new();
+
+        let mut store: IndexMap<EntityUid, Entity> = IndexMap::default();
+        for entity in entities {
+            if store.contains_key(entity.uid()) {
+                diagnostics.push(EntityError::DuplicateEntity {
+                    uid: entity.uid().clone(),
+                });
+
+                continue;
+            }
+
+            store.insert(entity.uid().clone(), entity);
+        }
+
+        let mut parents: Vec<Vec<usize>> = Vec::with_capacity(store.len());
+        for entity in store.values() {
+            let mut indices = Vec::new();
+
+            for parent in entity.parents() {
+                match store.get_index_of(parent) {
+                    Some(index) => indices.push(index),
+                    None => diagnostics.push(EntityError::UnknownParent {
+                        uid: entity.uid().clone(),
+                        parent: parent.clone(),
+                    }),
+                }
+            }
+
+            parents.push(indices);
+        }
+
+        let ancestors = closure(&store, &parents, &mut diagnostics);
+
+        if diagnostics.is_empty() {
+            Ok(Self {
+                entities: store,
+                ancestors,
+            })
+        } else {
+            Err(diagnostics)
+        }
+    }
+
+    /// Returns the entity with the given identifier.
+    #[must_use]
+    pub fn get(&self, uid: &EntityUid) -> Option<&Entity> {
+        self.entities.get(uid)
+    }
+
+    /// Returns whether the store contains the given entity.
+    #[must_use]
+    pub fn contains(&self, uid: &EntityUid) -> bool {
+        self.entities.contains_key(uid)
+    }
+
+    /// Returns whether `ancestor` is a direct or transitive parent of `descendant`.
+    ///
+    /// An entity is not its own ancestor.
+    #[must_use]
+    pub fn is_ancestor(&self, ancestor: &EntityUid, descendant: &EntityUid) -> bool {
+        self.entities
+            .get_index_of(descendant)
+            .is_some_and(|index| self.ancestors[index].contains(ancestor))
+    }
+
+    /// Returns all direct and transitive parents of the given entity.
+    ///
+    /// Unknown entities have no ancestors.
+    pub fn ancestors(&self, uid: &EntityUid) -> impl Iterator<Item = &EntityUid> {
+        self.entities
+            .get_index_of(uid)
+            .map(|index| &self.ancestors[index])
+            .into_iter()
+            .flatten()
+    }
+
+    /// Returns the number of entities.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns whether the store has no entities.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Returns an iterator over the entities, in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = &Entity> {
+        self.entities.values()
+    }
+}
+
+/// Computes the ancestors of every entity, reporting any cycles.
+///
+/// Uses an iterative depth-first search so deep hierarchies cannot overflow the stack.
+fn closure(
+    store: &IndexMap<EntityUid, Entity>,
+    parents: &[Vec<usize>],
+    diagnostics: &mut Diagnostics,
+) -> Vec<IndexSet<EntityUid>> {
+    let mut visits = vec![Visit::Pending; parents.len()];
+    let mut ancestors: Vec<IndexSet<EntityUid>> = vec![IndexSet::default(); parents.len()];
+
+    for root in 0..parents.len() {
+        if visits[root] != Visit::Pending {
+            continue;
+        }
+
+        visits[root] = Visit::Active;
+        let mut stack: Vec<(usize, usize)> = vec![(root, 0)];
+
+        while let Some((node, position)) = stack.last_mut() {
+            let node = *node;
+
+            if let Some(&parent) = parents[node].get(*position) {
+                *position += 1;
+
+                match visits[parent] {
+                    Visit::Pending => {
+                        visits[parent] = Visit::Active;
+                        stack.push((parent, 0));
+                    }
+                    Visit::Active => {
+                        let start = stack
+                            .iter()
+                            .position(|(index, _)| *index == parent)
+                            .unwrap_or_default();
+
+                        let path = stack[start..]
+                            .iter()
+                            .chain(core::iter::once(&(parent, 0)))
+                            .filter_map(|(index, _)| store.get_index(*index))
+                            .map(|(uid, _)| uid.clone())
+                            .collect();
+
+                        diagnostics.push(EntityError::Cycle { path });
+                    }
+                    Visit::Done => {}
+                }
+
+                continue;
+            }
+
+            let mut closure = IndexSet::default();
+            for &parent in &parents[node] {
+                if let Some((uid, _)) = store.get_index(parent) {
+                    closure.insert(uid.clone());
+                }
+
+                closure.extend(ancestors[parent].iter().cloned());
+            }
+
+            ancestors[node] = closure;
+            visits[node] = Visit::Done;
+            stack.pop();
+        }
+    }
+
+    ancestors
+}
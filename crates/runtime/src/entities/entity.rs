@@ -0,0 +1,68 @@
+use crate::IndexSet;
+use crate::value::{EntityUid, Record};
+
+/// An entity with its attributes, tags and direct parents.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Entity {
+    uid: EntityUid,
+    attributes: Record,
+    tags: Record,
+    parents: IndexSet<EntityUid>,
+}
+
+impl Entity {
+    /// Creates an entity with no attributes, tags or parents.
+    #[must_use]
+    pub fn new(uid: EntityUid) -> Self {
+        Self {
+            uid,
+            attributes: Record::empty(),
+            tags: Record::empty(),
+            parents: IndexSet::default(),
+        }
+    }
+
+    /// Sets the attributes of this entity.
+    #[must_use]
+    pub fn with_attributes(mut self, attributes: Record) -> Self {
+        self.attributes = attributes;
+        self
+    }
+
+    /// Sets the tags of this entity.
+    #[must_use]
+    pub fn with_tags(mut self, tags: Record) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Adds direct parents to this entity.
+    #[must_use]
+    pub fn with_parents<I: IntoIterator<Item = EntityUid>>(mut self, parents: I) -> Self {
+        self.parents.extend(parents);
+        self
+    }
+
+    /// Returns the unique identifier of this entity.
+    #[must_use]
+    pub const fn uid(&self) -> &EntityUid {
+        &self.uid
+    }
+
+    /// Returns the attributes of this entity.
+    #[must_use]
+    pub const fn attributes(&self) -> &Record {
+        &self.attributes
+    }
+
+    /// Returns the tags of this entity.
+    #[must_use]
+    pub const fn tags(&self) -> &Record {
+        &self.tags
+    }
+
+    /// Returns the direct parents of this entity, in declaration order.
+    pub fn parents(&self) -> impl Iterator<Item = &EntityUid> {
+        self.parents.iter()
+    }
+}
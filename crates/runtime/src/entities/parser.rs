@@ -0,0 +1,467 @@
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use duramen_diagnostic::Diagnostics;
+use duramen_json::{JsonKind, JsonObject, JsonParser, JsonValue};
+
+use crate::IndexMap;
+use crate::entities::{Entities, Entity};
+use crate::error::DecodeError;
+use crate::value::{EntityType, EntityUid, Extension, Record, Set, Value};
+
+mod hint;
+pub use hint::Hint;
+
+mod type_hints;
+pub use type_hints::TypeHints;
+
+/// Entity references paired with their spans.
+type References = Vec<(EntityUid, Range<usize>)>;
+
+/// Parses the Cedar entities JSON format into an entity store.
+///
+/// ```json
+/// [
+///   {
+///     "uid": { "type": "User", "id": "alice" },
+///     "attrs": { "manager": { "__entity": { "type": "User", "id": "bob" } } },
+///     "parents": [{ "type": "Group", "id": "admins" }],
+///     "tags": {}
+///   }
+/// ]
+/// ```
+pub struct EntitiesParser {
+    diagnostics: Diagnostics,
+}
+
+/// Hints for plain JSON, where every value is decoded from its JSON form alone.
+struct Untyped;
+
+impl TypeHints for Untyped {
+    type Record = ();
+    type Type = ();
+
+    fn attribute_types(&self, _: &EntityType) -> Option<&Self::Record> {
+        None
+    }
+
+    fn tag_type(&self, _: &EntityType) -> Option<&Self::Type> {
+        None
+    }
+
+    fn context_type(&self, _: &EntityUid) -> Option<&Self::Record> {
+        None
+    }
+
+    fn attribute_type<'a>(&'a self, &(): &'a Self::Record, _: &str) -> Option<&'a Self::Type> {
+        None
+    }
+
+    fn hint<'a>(&'a self, &(): &'a Self::Type) -> Hint<'a, Self::Type, Self::Record> {
+        Hint::Other
+    }
+}
+
+impl EntitiesParser {
+    /// Parses the source text and builds the entity store.
+    ///
+    /// Missing `attrs`, `parents` and `tags` fields default to empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns diagnostics for malformed JSON, malformed entities, and invalid hierarchies.
+    pub fn parse(source: &str) -> Result<Entities, Diagnostics> {
+        Self::parse_with_hints(source, &Untyped)
+    }
+
+    /// Parses the source text, using `hints` to decode attributes and tags.
+    ///
+    /// Values without a hint, such as attributes of undeclared entity types, are decoded as
+    /// plain JSON. Hints do not check conformance.
+    ///
+    /// # Errors
+    ///
+    /// Returns diagnostics for malformed JSON, malformed entities, and invalid hierarchies.
+    pub fn parse_with_hints<H: TypeHints>(
+        source: &str,
+        hints: &H,
+    ) -> Result<Entities, Diagnostics> {
+        let mut this = Self {
+            diagnostics: Diagnostics::new(),
+        };
+
+        let json = match JsonParser::parse(source) {
+            Ok(json) => json,
+            Err(error) => {
+                this.diagnostics.push(error);
+                return Err(this.diagnostics);
+            }
+        };
+
+        let entities = this.entities(&json, hints);
+        if !this.diagnostics.is_empty() {
+            return Err(this.diagnostics);
+        }
+
+        Entities::new(entities)
+    }
+
+    /// Parses the JSON context of a request for `action`, using `hints` to decode attributes.
+    ///
+    /// # Errors
+    ///
+    /// Returns diagnostics for malformed JSON and malformed values.
+    pub fn parse_context<H: TypeHints>(
+        source: &str,
+        hints: &H,
+        action: &EntityUid,
+    ) -> Result<Record, Diagnostics> {
+        let mut this = Self {
+            diagnostics: Diagnostics::new(),
+        };
+
+        let json = match JsonParser::parse(source) {
+            Ok(json) => json,
+            Err(error) => {
+                this.diagnostics.push(error);
+                return Err(this.diagnostics);
+            }
+        };
+
+        let context = hints.context_type(action);
+        let record = this.record(&json, hints, |name| {
+            let context = context?;
+            hints.attribute_type(context, name)
+        });
+
+        match record {
+            Some(record) if this.diagnostics.is_empty() => Ok(record),
+            _ => Err(this.diagnostics),
+        }
+    }
+
+    fn entities<H: TypeHints>(&mut self, json: &JsonValue<'_>, hints: &H) -> Vec<Entity> {
+        let Some(values) = self.expect_array(json) else {
+            return Vec::new();
+        };
+
+        let mut declared: IndexMap<EntityUid, Range<usize>> = IndexMap::default();
+        let mut references: References = Vec::new();
+        let mut entities = Vec::with_capacity(values.len());
+
+        for value in values {
+            let Some((entity, span, parents)) = self.entity(value, hints) else {
+                continue;
+            };
+
+            if let Some(first) = declared.get(entity.uid()) {
+                self.diagnostics.push(DecodeError::DuplicateEntity {
+                    uid: entity.uid().clone(),
+                    span,
+                    first: first.clone(),
+                });
+
+                continue;
+            }
+
+            declared.insert(entity.uid().clone(), span);
+            references.extend(parents);
+            entities.push(entity);
+        }
+
+        for (parent, span) in references {
+            if !declared.contains_key(&parent) {
+                self.diagnostics
+                    .push(DecodeError::UnknownParent { parent, span });
+            }
+        }
+
+        entities
+    }
+
+    /// Decodes a single entity, along with the span of its `uid` and the spans of its parents.
+    fn entity<H: TypeHints>(
+        &mut self,
+        json: &JsonValue<'_>,
+        hints: &H,
+    ) -> Option<(Entity, Range<usize>, References)> {
+        let object = self.expect_object(json)?;
+        self.check_fields(object, &["uid", "attrs", "parents", "tags"]);
+
+        let Some(uid_json) = object.get("uid") else {
+            self.diagnostics.push(DecodeError::MissingField {
+                field: "uid",
+                span: json.span(),
+            });
+
+            return None;
+        };
+
+        let uid = self.entity_uid(uid_json);
+        let kind = uid.as_ref().map(EntityUid::kind);
+
+        let attributes = match object.get("attrs") {
+            Some(attrs) => {
+                let record = kind.and_then(|kind| hints.attribute_types(kind));
+                self.record(attrs, hints, |name| {
+                    let record = record?;
+                    hints.attribute_type(record, name)
+                })
+            }
+            None => Some(Record::empty()),
+        };
+
+        let tags = match object.get("tags") {
+            Some(tags) => {
+                let ty = kind.and_then(|kind| hints.tag_type(kind));
+                self.record(tags, hints, |_| ty)
+            }
+            None => Some(Record::empty()),
+        };
+
+        let mut parents = Vec::new();
+        if let Some(parents_json) = object.get("parents")
+            && let Some(values) = self.expect_array(parents_json)
+        {
+            for value in values {
+                if let Some(parent) = self.entity_uid(value) {
+                    parents.push((parent, value.span()));
+                }
+            }
+        }
+
+        let entity = Entity::new(uid?)
+            .with_attributes(attributes?)
+            .with_tags(tags?)
+            .with_parents(parents.iter().map(|(parent, _)| parent.clone()));
+
+        Some((entity, uid_json.span(), parents))
+    }
+
+    /// Decodes an entity reference, either `{ "type", "id" }` or the `__entity` escape.
+    fn entity_uid(&mut self, json: &JsonValue<'_>) -> Option<EntityUid> {
+        let object = self.expect_object(json)?;
+
+        if let Some(inner) = object.get("__entity")
+            && object.len() == 1
+        {
+            return self.entity_uid(inner);
+        }
+
+        self.check_fields(object, &["type", "id"]);
+
+        let kind = self.field(json, object, "type").and_then(|kind| {
+            let name = self.expect_str(kind)?;
+            let entity_type = EntityType::new(name).ok();
+            if entity_type.is_none() {
+                self.diagnostics.push(DecodeError::InvalidEntityType {
+                    name: String::from(name),
+                    span: kind.span(),
+                });
+            }
+
+            entity_type
+        });
+
+        let id = self
+            .field(json, object, "id")
+            .and_then(|id| self.expect_str(id));
+
+        Some(EntityUid::new(kind?, Arc::from(id?)))
+    }
+
+    /// Decodes a value, using the hint for `ty` if there is one.
+    fn value<H: TypeHints>(
+        &mut self,
+        json: &JsonValue<'_>,
+        hints: &H,
+        ty: Option<&H::Type>,
+    ) -> Option<Value> {
+        let hint = ty.map_or(Hint::Other, |ty| hints.hint(ty));
+
+        match json.kind() {
+            JsonKind::Bool(value) => Some(Value::Bool(*value)),
+            JsonKind::Number(text) => {
+                let value = json.as_i64();
+                if value.is_none() {
+                    self.diagnostics.push(DecodeError::InvalidInteger {
+                        text: String::from(*text),
+                        span: json.span(),
+                    });
+                }
+
+                value.map(Value::Long)
+            }
+            JsonKind::String(value) => match hint {
+                Hint::Extension(function) => self
+                    .construct(function, value, json.span())
+                    .map(Value::Extension),
+                _ => Some(Value::String(Arc::from(&**value))),
+            },
+            JsonKind::Array(values) => {
+                let element = match hint {
+                    Hint::Set(element) => Some(element),
+                    _ => None,
+                };
+
+                let mut set = Vec::with_capacity(values.len());
+                for value in values {
+                    set.push(self.value(value, hints, element));
+                }
+
+                set.into_iter().collect::<Option<Set>>().map(Value::Set)
+            }
+            JsonKind::Object(object) => {
+                if object.len() == 1 {
+                    if let Some(inner) = object.get("__entity") {
+                        return self.entity_uid(inner).map(Value::Entity);
+                    }
+
+                    if let Some(inner) = object.get("__extn") {
+                        return self.extension(inner).map(Value::Extension);
+                    }
+                }
+
+                match hint {
+                    Hint::Entity => self.entity_uid(json).map(Value::Entity),
+                    Hint::Record(record) => self
+                        .record(json, hints, |name| hints.attribute_type(record, name))
+                        .map(Value::Record),
+                    _ => self.record(json, hints, |_| None).map(Value::Record),
+                }
+            }
+            JsonKind::Null => {
+                self.diagnostics.push(DecodeError::UnexpectedType {
+                    expected: "a value",
+                    found: json.describe(),
+                    span: json.span(),
+                });
+
+                None
+            }
+        }
+    }
+
+    /// Decodes an object into a record, using `attribute` to find the type of each member.
+    fn record<'h, H, F>(&mut self, json: &JsonValue<'_>, hints: &H, attribute: F) -> Option<Record>
+    where
+        H: TypeHints,
+        H::Type: 'h,
+        F: Fn(&str) -> Option<&'h H::Type>,
+    {
+        let object = self.expect_object(json)?;
+
+        let mut entries = Vec::with_capacity(object.len());
+        for member in object {
+            let value = self.value(member.value(), hints, attribute(member.key()));
+            entries.push(value.map(|value| (Arc::from(member.key()), value)));
+        }
+
+        entries.into_iter().collect()
+    }
+
+    /// Decodes the body of an `__extn` escape: `{ "fn": "ip", "arg": "10.0.0.1" }`.
+    fn extension(&mut self, json: &JsonValue<'_>) -> Option<Extension> {
+        let object = self.expect_object(json)?;
+        self.check_fields(object, &["fn", "arg"]);
+
+        let function = self
+            .field(json, object, "fn")
+            .and_then(|function| self.expect_str(function));
+
+        let argument = self
+            .field(json, object, "arg")
+            .and_then(|argument| self.expect_str(argument));
+
+        self.construct(function?, argument?, json.span())
+    }
+
+    /// Calls an extension constructor, reporting invalid arguments at `span`.
+    fn construct(
+        &mut self,
+        function: &str,
+        argument: &str,
+        span: Range<usize>,
+    ) -> Option<Extension> {
+        match Extension::new(function, argument) {
+            Ok(extension) => Some(extension),
+            Err(error) => {
+                self.diagnostics
+                    .push(DecodeError::InvalidExtension { error, span });
+
+                None
+            }
+        }
+    }
+
+    fn field<'a, 'src>(
+        &mut self,
+        json: &JsonValue<'src>,
+        object: &'a JsonObject<'src>,
+        field: &'static str,
+    ) -> Option<&'a JsonValue<'src>> {
+        let value = object.get(field);
+        if value.is_none() {
+            self.diagnostics.push(DecodeError::MissingField {
+                field,
+                span: json.span(),
+            });
+        }
+
+        value
+    }
+
+    fn check_fields(&mut self, object: &JsonObject<'_>, fields: &[&str]) {
+        for member in object {
+            if !fields.contains(&member.key()) {
+                self.diagnostics.push(DecodeError::UnknownField {
+                    field: String::from(member.key()),
+                    span: member.key_span(),
+                });
+            }
+        }
+    }
+
+    fn expect_object<'a, 'src>(
+        &mut self,
+        json: &'a JsonValue<'src>,
+    ) -> Option<&'a JsonObject<'src>> {
+        let object = json.as_object();
+        if object.is_none() {
+            self.unexpected("an object", json);
+        }
+
+        object
+    }
+
+    fn expect_array<'a, 'src>(
+        &mut self,
+        json: &'a JsonValue<'src>,
+    ) -> Option<&'a [JsonValue<'src>]> {
+        let array = json.as_array();
+        if array.is_none() {
+            self.unexpected("an array", json);
+        }
+
+        array
+    }
+
+    fn expect_str<'a>(&mut self, json: &'a JsonValue<'_>) -> Option<&'a str> {
+        let value = json.as_str();
+        if value.is_none() {
+            self.unexpected("a string", json);
+        }
+
+        value
+    }
+
+    fn unexpected(&mut self, expected: &'static str, json: &JsonValue<'_>) {
+        self.diagnostics.push(DecodeError::UnexpectedType {
+            expected,
+            found: json.describe(),
+            span: json.span(),
+        });
+    }
+}
@@ -0,0 +1,14 @@
+/// How a value of a hinted type is decoded.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Hint<'a, T, R> {
+    /// An entity reference.
+    Entity,
+    /// An extension value, built by the named constructor.
+    Extension(&'static str),
+    /// A set with elements of the given type.
+    Set(&'a T),
+    /// A record with the given attribute types.
+    Record(&'a R),
+    /// A value that is decoded as plain JSON.
+    Other,
+}
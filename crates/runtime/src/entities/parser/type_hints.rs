@@ -0,0 +1,31 @@
+use crate::entities::parser::Hint;
+use crate::value::{EntityType, EntityUid};
+
+/// Expected types that guide decoding of JSON whose meaning is ambiguous.
+///
+/// With hints, an attribute typed as an entity may be written `{ "type": .., "id": .. }` without
+/// the `__entity` escape, and an extension value may be written as its constructor argument,
+/// like `"10.0.0.1"`, without the `__extn` escape.
+pub trait TypeHints {
+    /// The type of a single value.
+    type Type;
+
+    /// The type of a record.
+    type Record;
+
+    /// Returns the attribute types of an entity type, if known.
+    fn attribute_types(&self, kind: &EntityType) -> Option<&Self::Record>;
+
+    /// Returns the type of tag values of an entity type, if known.
+    fn tag_type(&self, kind: &EntityType) -> Option<&Self::Type>;
+
+    /// Returns the context type of an action, if known.
+    fn context_type(&self, action: &EntityUid) -> Option<&Self::Record>;
+
+    /// Returns the type of a record attribute, if known.
+    fn attribute_type<'a>(&'a self, record: &'a Self::Record, name: &str)
+    -> Option<&'a Self::Type>;
+
+    /// Returns how a value of the given type is decoded.
+    fn hint<'a>(&'a self, ty: &'a Self::Type) -> Hint<'a, Self::Type, Self::Record>;
+}
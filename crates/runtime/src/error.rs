@@ -0,0 +1,211 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+use duramen_diagnostic::Diagnostic;
+
+use crate::value::EntityUid;
+
+/// An error produced while constructing an extension value.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum ExtensionError {
+    /// The extension function is not known.
+    UnknownFunction { name: String },
+    /// A `decimal` literal is malformed or out of range.
+    InvalidDecimal { text: String },
+    /// An `ip` literal is malformed.
+    InvalidIpAddress { text: String },
+    /// A `datetime` literal is malformed or out of range.
+    InvalidDatetime { text: String },
+    /// A `duration` literal is malformed or out of range.
+    InvalidDuration { text: String },
+}
+
+impl fmt::Display for ExtensionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownFunction { name } => write!(f, "`{name}` is not a known extension"),
+            Self::InvalidDecimal { text } => write!(f, "`{text}` is not a valid decimal"),
+            Self::InvalidIpAddress { text } => write!(f, "`{text}` is not a valid IP address"),
+            Self::InvalidDatetime { text } => write!(f, "`{text}` is not a valid datetime"),
+            Self::InvalidDuration { text } => write!(f, "`{text}` is not a valid duration"),
+        }
+    }
+}
+
+impl core::error::Error for ExtensionError {}
+
+impl From<ExtensionError> for Diagnostic {
+    fn from(value: ExtensionError) -> Self {
+        match &value {
+            ExtensionError::InvalidDecimal { .. } => Self::error(format!("{value}"))
+                .with_note("decimals have at most 4 fractional digits, like `1.2345`"),
+            ExtensionError::InvalidDatetime { .. } => Self::error(format!("{value}")).with_note(
+                "expected `YYYY-MM-DD`, optionally followed by `Thh:mm:ss`, `.SSS`, and `Z` or `+hhmm`",
+            ),
+            ExtensionError::InvalidDuration { .. } => Self::error(format!("{value}"))
+                .with_note("durations are written in `d`, `h`, `m`, `s`, `ms` order, like `1h30m`"),
+            _ => Self::error(format!("{value}")),
+        }
+    }
+}
+
+/// An error produced while building an entity store.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum EntityError {
+    /// The same entity was declared more than once.
+    DuplicateEntity { uid: EntityUid },
+    /// An entity declares a parent that is not in the store.
+    UnknownParent { uid: EntityUid, parent: EntityUid },
+    /// The entity hierarchy contains a cycle.
+    Cycle { path: Vec<EntityUid> },
+}
+
+impl fmt::Display for EntityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateEntity { uid } => write!(f, "duplicate entity `{uid}`"),
+            Self::UnknownParent { uid, parent } => {
+                write!(f, "entity `{uid}` has unknown parent `{parent}`")
+            }
+            Self::Cycle { path } => {
+                f.write_str("entity hierarchy contains a cycle: ")?;
+
+                for (index, uid) in path.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(" -> ")?;
+                    }
+
+                    write!(f, "`{uid}`")?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl core::error::Error for EntityError {}
+
+impl From<EntityError> for Diagnostic {
+    fn from(value: EntityError) -> Self {
+        match &value {
+            EntityError::UnknownParent { .. } => Self::error(format!("{value}"))
+                .with_note("every parent must also be an entity in the store"),
+            EntityError::Cycle { .. } => {
+                Self::error(format!("{value}")).with_note("an entity cannot be its own ancestor")
+            }
+            EntityError::DuplicateEntity { .. } => Self::error(format!("{value}")),
+        }
+    }
+}
+
+/// An error produced while decoding Cedar JSON into runtime values.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum DecodeError {
+    /// A JSON value has the wrong type.
+    UnexpectedType {
+        expected: &'static str,
+        found: &'static str,
+        span: Range<usize>,
+    },
+    /// A required field is missing from an object.
+    MissingField {
+        field: &'static str,
+        span: Range<usize>,
+    },
+    /// An object has a field that is not allowed.
+    UnknownField { field: String, span: Range<usize> },
+    /// An entity type name is malformed.
+    InvalidEntityType { name: String, span: Range<usize> },
+    /// A number is not an integer in the `i64` range.
+    InvalidInteger { text: String, span: Range<usize> },
+    /// An extension value is malformed.
+    InvalidExtension {
+        error: ExtensionError,
+        span: Range<usize>,
+    },
+    /// The same entity was declared more than once.
+    DuplicateEntity {
+        uid: EntityUid,
+        span: Range<usize>,
+        first: Range<usize>,
+    },
+    /// An entity declares a parent that is not declared.
+    UnknownParent {
+        parent: EntityUid,
+        span: Range<usize>,
+    },
+}
+
+impl DecodeError {
+    #[must_use]
+    pub const fn span(&self) -> &Range<usize> {
+        match self {
+            Self::UnexpectedType { span, .. }
+            | Self::MissingField { span, .. }
+            | Self::UnknownField { span, .. }
+            | Self::InvalidEntityType { span, .. }
+            | Self::InvalidInteger { span, .. }
+            | Self::InvalidExtension { span, .. }
+            | Self::DuplicateEntity { span, .. }
+            | Self::UnknownParent { span, .. } => span,
+        }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedType {
+                expected, found, ..
+            } => write!(f, "expected {expected}, found {found}"),
+            Self::MissingField { field, .. } => write!(f, "missing field `{field}`"),
+            Self::UnknownField { field, .. } => write!(f, "unknown field `{field}`"),
+            Self::InvalidEntityType { name, .. } => {
+                write!(f, "`{name}` is not a valid entity type")
+            }
+            Self::InvalidInteger { text, .. } => write!(f, "`{text}` is not a valid integer"),
+            Self::InvalidExtension { error, .. } => error.fmt(f),
+            Self::DuplicateEntity { uid, .. } => write!(f, "duplicate entity `{uid}`"),
+            Self::UnknownParent { parent, .. } => write!(f, "unknown parent `{parent}`"),
+        }
+    }
+}
+
+impl core::error::Error for DecodeError {}
+
+impl From<DecodeError> for Diagnostic {
+    fn from(value: DecodeError) -> Self {
+        let span = value.span().clone();
+
+        match &value {
+            DecodeError::UnexpectedType { expected, .. } => {
+                Self::error(format!("{value}")).with_label(span, format!("expected {expected}"))
+            }
+            DecodeError::MissingField { .. } => {
+                Self::error(format!("{value}")).with_label(span, "missing field")
+            }
+            DecodeError::UnknownField { .. } => {
+                Self::error(format!("{value}")).with_label(span, "unknown field")
+            }
+            DecodeError::InvalidInteger { .. } => Self::error(format!("{value}"))
+                .with_label(span, "invalid integer")
+                .with_note("integers must be whole numbers that fit in 64 bits"),
+            DecodeError::InvalidExtension { error, .. } => {
+                Self::from(error.clone()).with_label(span, "invalid extension value")
+            }
+            DecodeError::DuplicateEntity { first, .. } => Self::error(format!("{value}"))
+                .with_label(span, "duplicate entity")
+                .with_context(first.clone(), "first declared here"),
+            DecodeError::UnknownParent { .. } => Self::error(format!("{value}"))
+                .with_label(span, "unknown parent")
+                .with_note("every parent must also be an entity in the store"),
+            DecodeError::InvalidEntityType { .. } => {
+                Self::error(format!("{value}")).with_label(span, "invalid entity type")
+            }
+        }
+    }
+}
@@ -1,5 +1,23 @@
 #![cfg_attr(doc, doc = include_str!("../README.md"))]
 #![no_std]
+extern crate alloc;
 
 #[cfg(feature = "std")]
 extern crate std;
+
+pub(crate) use rustc_hash::FxBuildHasher;
+
+pub(crate) type IndexMap<K, V> = indexmap::IndexMap<K, V, FxBuildHasher>;
+pub(crate) type IndexSet<T> = indexmap::IndexSet<T, FxBuildHasher>;
+
+mod entities;
+pub use entities::{Entities, EntitiesParser, Entity, Hint, TypeHints};
+
+mod error;
+pub use error::{DecodeError, EntityError, ExtensionError};
+
+mod request;
+pub use request::Request;
+
+mod value;
+pub use value::*;
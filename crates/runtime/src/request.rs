@@ -0,0 +1,50 @@
+use crate::value::{EntityUid, Record};
+
+/// An authorization request.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Request {
+    principal: EntityUid,
+    action: EntityUid,
+    resource: EntityUid,
+    context: Record,
+}
+
+impl Request {
+    /// Creates a request with an empty context.
+    #[must_use]
+    pub fn new(principal: EntityUid, action: EntityUid, resource: EntityUid) -> Self {
+        Self {
+            principal,
+            action,
+            resource,
+            context: Record::empty(),
+        }
+    }
+
+    /// Sets the context of this request.
+    #[must_use]
+    pub fn with_context(mut self, context: Record) -> Self {
+        self.context = context;
+        self
+    }
+
+    #[must_use]
+    pub const fn principal(&self) -> &EntityUid {
+        &self.principal
+    }
+
+    #[must_use]
+    pub const fn action(&self) -> &EntityUid {
+        &self.action
+    }
+
+    #[must_use]
+    pub const fn resource(&self) -> &EntityUid {
+        &self.resource
+    }
+
+    #[must_use]
+    pub const fn context(&self) -> &Record {
+        &self.context
+    }
+}
@@ -0,0 +1,206 @@
+use alloc::sync::Arc;
+use core::fmt;
+
+use duramen_ast as ast;
+
+mod datetime;
+pub use datetime::Datetime;
+
+mod decimal;
+pub use decimal::Decimal;
+
+mod duration;
+pub use duration::Duration;
+
+mod entity_type;
+pub use entity_type::EntityType;
+
+mod entity_uid;
+pub use entity_uid::EntityUid;
+
+mod extension;
+pub use extension::Extension;
+
+mod ip_address;
+pub use ip_address::IpAddress;
+
+mod record;
+pub use record::Record;
+
+mod set;
+pub use set::Set;
+
+mod value_kind;
+pub use value_kind::ValueKind;
+
+/// A Cedar runtime value.
+///
+/// Cloning is cheap: strings, entity identifiers, sets and records are reference counted.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
+pub enum Value {
+    Bool(bool),
+    Long(i64),
+    String(Arc<str>),
+    Entity(EntityUid),
+    Set(Set),
+    Record(Record),
+    Extension(Extension),
+}
+
+impl Value {
+    /// Returns the kind of this value.
+    #[must_use]
+    pub const fn kind(&self) -> ValueKind {
+        match self {
+            Self::Bool(_) => ValueKind::Bool,
+            Self::Long(_) => ValueKind::Long,
+            Self::String(_) => ValueKind::String,
+            Self::Entity(_) => ValueKind::Entity,
+            Self::Set(_) => ValueKind::Set,
+            Self::Record(_) => ValueKind::Record,
+            Self::Extension(extension) => extension.kind(),
+        }
+    }
+
+    /// Returns the boolean value, if this is a boolean.
+    #[must_use]
+    pub const fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the integer value, if this is a long.
+    #[must_use]
+    pub const fn as_long(&self) -> Option<i64> {
+        match self {
+            Self::Long(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the string value, if this is a string.
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the entity identifier, if this is an entity.
+    #[must_use]
+    pub const fn as_entity(&self) -> Option<&EntityUid> {
+        match self {
+            Self::Entity(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the set, if this is a set.
+    #[must_use]
+    pub const fn as_set(&self) -> Option<&Set> {
+        match self {
+            Self::Set(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the record, if this is a record.
+    #[must_use]
+    pub const fn as_record(&self) -> Option<&Record> {
+        match self {
+            Self::Record(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the extension value, if this is an extension.
+    #[must_use]
+    pub const fn as_extension(&self) -> Option<&Extension> {
+        match self {
+            Self::Extension(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Self::Long(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Self::String(Arc::from(value))
+    }
+}
+
+impl From<Arc<str>> for Value {
+    fn from(value: Arc<str>) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<EntityUid> for Value {
+    fn from(value: EntityUid) -> Self {
+        Self::Entity(value)
+    }
+}
+
+impl From<Set> for Value {
+    fn from(value: Set) -> Self {
+        Self::Set(value)
+    }
+}
+
+impl From<Record> for Value {
+    fn from(value: Record) -> Self {
+        Self::Record(value)
+    }
+}
+
+impl From<Extension> for Value {
+    fn from(value: Extension) -> Self {
+        Self::Extension(value)
+    }
+}
+
+impl From<&ast::Literal<'_>> for Value {
+    fn from(value: &ast::Literal<'_>) -> Self {
+        match value {
+            ast::Literal::Bool(literal) => Self::Bool(literal.value()),
+            ast::Literal::Integer(literal) => Self::Long(literal.value()),
+            ast::Literal::String(literal) => Self::String(Arc::from(literal.value())),
+            ast::Literal::Entity(reference) => Self::Entity(EntityUid::from(reference)),
+        }
+    }
+}
+
+impl From<&ast::EntityReference<'_>> for Value {
+    fn from(value: &ast::EntityReference<'_>) -> Self {
+        Self::Entity(EntityUid::from(value))
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool(value) => value.fmt(f),
+            Self::Long(value) => value.fmt(f),
+            Self::String(value) => ast::Quoted(value).fmt(f),
+            Self::Entity(value) => value.fmt(f),
+            Self::Set(value) => value.fmt(f),
+            Self::Record(value) => value.fmt(f),
+            Self::Extension(value) => value.fmt(f),
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use alloc::string::String;
+use core::fmt;
+
+use crate::error::ExtensionError;
+use crate::value::Duration;
+
+const MILLISECONDS_PER_DAY: i64 = 86_400_000;
+
+/// An instant in time, stored as milliseconds since the Unix epoch.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
+pub struct Datetime(i64);
+
+impl Datetime {
+    /// Parses a datetime literal like `2024-10-15T11:35:00Z`.
+    ///
+    /// Accepts `YYYY-MM-DD`, optionally followed by `Thh:mm:ss`, optional `.SSS` milliseconds,
+    /// and either `Z` or a `+hhmm`/`-hhmm` offset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `text` is malformed or out of range.
+    pub fn new(text: &str) -> Result<Self, ExtensionError> {
+        let error = || ExtensionError::InvalidDatetime {
+            text: String::from(text),
+        };
+
+        let bytes = text.as_bytes();
+        let number = |start: usize, length: usize| -> Option<i64> {
+            let digits = bytes.get(start..start + length)?;
+            digits.iter().try_fold(0_i64, |total, byte| {
+                byte.is_ascii_digit()
+                    .then(|| total * 10 + i64::from(byte - b'0'))
+            })
+        };
+        let expect = |index: usize, byte: u8| bytes.get(index) == Some(&byte);
+
+        if !expect(4, b'-') || !expect(7, b'-') {
+            return Err(error());
+        }
+
+        let year = number(0, 4).ok_or_else(error)?;
+        let month = number(5, 2).ok_or_else(error)?;
+        let day = number(8, 2).ok_or_else(error)?;
+
+        if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+            return Err(error());
+        }
+
+        let mut milliseconds = days_from_civil(year, month, day) * MILLISECONDS_PER_DAY;
+        if bytes.len() == 10 {
+            return Ok(Self(milliseconds));
+        }
+
+        if !expect(10, b'T') || !expect(13, b':') || !expect(16, b':') {
+            return Err(error());
+        }
+
+        let hour = number(11, 2).ok_or_else(error)?;
+        let minute = number(14, 2).ok_or_else(error)?;
+        let second = number(17, 2).ok_or_else(error)?;
+
+        if hour > 23 || minute > 59 || second > 59 {
+            return Err(error());
+        }
+
+        milliseconds += ((hour * 60 + minute) * 60 + second) * 1_000;
+
+        let mut index = 19;
+        if expect(index, b'.') {
+            milliseconds += number(index + 1, 3).ok_or_else(error)?;
+            index += 4;
+        }
+
+        match bytes.get(index) {
+            Some(b'Z') if bytes.len() == index + 1 => Ok(Self(milliseconds)),
+            Some(sign @ (b'+' | b'-')) if bytes.len() == index + 5 => {
+                let hours = number(index + 1, 2).ok_or_else(error)?;
+                let minutes = number(index + 3, 2).ok_or_else(error)?;
+
+                if hours > 23 || minutes > 59 {
+                    return Err(error());
+                }
+
+                // Local time is UTC plus the offset, so subtract it to get UTC.
+                let offset = (hours * 60 + minutes) * 60_000;
+                if *sign == b'+' {
+                    Ok(Self(milliseconds - offset))
+                } else {
+                    Ok(Self(milliseconds + offset))
+                }
+            }
+            _ => Err(error()),
+        }
+    }
+
+    /// Creates a datetime from milliseconds since the Unix epoch.
+    #[must_use]
+    pub const fn from_milliseconds(milliseconds: i64) -> Self {
+        Self(milliseconds)
+    }
+
+    /// Returns the number of milliseconds since the Unix epoch.
+    #[must_use]
+    pub const fn milliseconds(self) -> i64 {
+        self.0
+    }
+
+    /// Returns this datetime shifted by `duration`, or `None` on overflow.
+    #[must_use]
+    pub const fn offset(self, duration: Duration) -> Option<Self> {
+        match self.0.checked_add(duration.to_milliseconds()) {
+            Some(milliseconds) => Some(Self(milliseconds)),
+            None => None,
+        }
+    }
+
+    /// Returns the duration from `other` to this datetime, or `None` on overflow.
+    #[must_use]
+    pub const fn duration_since(self, other: Self) -> Option<Duration> {
+        match self.0.checked_sub(other.0) {
+            Some(milliseconds) => Some(Duration::from_milliseconds(milliseconds)),
+            None => None,
+        }
+    }
+
+    /// Returns the start of the day containing this datetime, or `None` on overflow.
+    #[must_use]
+    pub const fn to_date(self) -> Option<Self> {
+        match self
+            .0
+            .div_euclid(MILLISECONDS_PER_DAY)
+            .checked_mul(MILLISECONDS_PER_DAY)
+        {
+            Some(milliseconds) => Some(Self(milliseconds)),
+            None => None,
+        }
+    }
+
+    /// Returns the time elapsed since the start of the day containing this datetime.
+    #[must_use]
+    pub const fn to_time(self) -> Duration {
+        Duration::from_milliseconds(self.0.rem_euclid(MILLISECONDS_PER_DAY))
+    }
+}
+
+impl fmt::Display for Datetime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let days = self.0.div_euclid(MILLISECONDS_PER_DAY);
+        let time = self.0.rem_euclid(MILLISECONDS_PER_DAY);
+        let (year, month, day) = civil_from_days(days);
+
+        let hour = time / 3_600_000;
+        let minute = time / 60_000 % 60;
+        let second = time / 1_000 % 60;
+        let millisecond = time % 1_000;
+
+        write!(
+            f,
+            "{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}.{millisecond:03}Z"
+        )
+    }
+}
+
+const fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+const fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the number of days since the Unix epoch for a proleptic Gregorian date.
+const fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Returns the proleptic Gregorian date for a number of days since the Unix epoch.
+const fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400;
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
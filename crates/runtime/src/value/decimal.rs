@@ -0,0 +1,77 @@
+use alloc::string::String;
+use core::fmt;
+
+use crate::error::ExtensionError;
+
+/// The number of fractional digits stored by a decimal.
+const SCALE: u32 = 4;
+
+/// A fixed-point decimal with 4 fractional digits.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
+pub struct Decimal(i64);
+
+impl Decimal {
+    /// Parses a decimal literal like `-12.34`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `text` is malformed or out of range.
+    pub fn new(text: &str) -> Result<Self, ExtensionError> {
+        let error = || ExtensionError::InvalidDecimal {
+            text: String::from(text),
+        };
+
+        let (negative, unsigned) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text),
+        };
+
+        let (integer, fraction) = unsigned.split_once('.').ok_or_else(error)?;
+
+        if integer.is_empty()
+            || fraction.is_empty()
+            || fraction.len() > SCALE as usize
+            || !integer.bytes().all(|byte| byte.is_ascii_digit())
+            || !fraction.bytes().all(|byte| byte.is_ascii_digit())
+        {
+            return Err(error());
+        }
+
+        let padding = SCALE - fraction.len() as u32;
+        let integer: i128 = integer.parse().map_err(|_err| error())?;
+        let fraction: i128 = fraction.parse().map_err(|_err| error())?;
+
+        let magnitude = integer
+            .checked_mul(10_i128.pow(SCALE))
+            .and_then(|scaled| scaled.checked_add(fraction * 10_i128.pow(padding)))
+            .ok_or_else(error)?;
+        let value = if negative { -magnitude } else { magnitude };
+
+        i64::try_from(value).map(Self).map_err(|_err| error())
+    }
+
+    /// Creates a decimal from its scaled integer representation.
+    #[must_use]
+    pub const fn from_scaled(value: i64) -> Self {
+        Self(value)
+    }
+
+    /// Returns the scaled integer representation (the value multiplied by `10^4`).
+    #[must_use]
+    pub const fn scaled(self) -> i64 {
+        self.0
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let magnitude = self.0.unsigned_abs();
+        let divisor = 10_u64.pow(SCALE);
+
+        if self.0 < 0 {
+            f.write_str("-")?;
+        }
+
+        write!(f, "{}.{:04}", magnitude / divisor, magnitude % divisor)
+    }
+}
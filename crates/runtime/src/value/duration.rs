@@ -0,0 +1,133 @@
+use alloc::string::String;
+use core::fmt;
+
+use crate::error::ExtensionError;
+
+/// Duration units in the order they must appear, with their size in milliseconds.
+const UNITS: [(&str, i64); 5] = [
+    ("d", 86_400_000),
+    ("h", 3_600_000),
+    ("m", 60_000),
+    ("s", 1_000),
+    ("ms", 1),
+];
+
+/// A signed span of time, with millisecond precision.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
+pub struct Duration(i64);
+
+impl Duration {
+    /// Parses a duration literal like `1d2h3m4s5ms`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `text` is malformed or out of range.
+    pub fn new(text: &str) -> Result<Self, ExtensionError> {
+        let error = || ExtensionError::InvalidDuration {
+            text: String::from(text),
+        };
+
+        let (negative, mut rest) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text),
+        };
+
+        if rest.is_empty() {
+            return Err(error());
+        }
+
+        let mut total: i64 = 0;
+        let mut next = 0;
+
+        while !rest.is_empty() {
+            let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+            if digits == 0 {
+                return Err(error());
+            }
+
+            let (number, suffix) = rest.split_at(digits);
+            let unit = if suffix.starts_with("ms") {
+                "ms"
+            } else {
+                suffix.get(..1).ok_or_else(error)?
+            };
+
+            let offset = UNITS[next..]
+                .iter()
+                .position(|(name, _)| *name == unit)
+                .ok_or_else(error)?;
+            let (_, size) = UNITS[next + offset];
+            next += offset + 1;
+
+            let number: i64 = number.parse().map_err(|_err| error())?;
+            let amount = number.checked_mul(size).ok_or_else(error)?;
+            let amount = if negative { -amount } else { amount };
+            total = total.checked_add(amount).ok_or_else(error)?;
+
+            rest = &suffix[unit.len()..];
+        }
+
+        Ok(Self(total))
+    }
+
+    /// Creates a duration from a number of milliseconds.
+    #[must_use]
+    pub const fn from_milliseconds(milliseconds: i64) -> Self {
+        Self(milliseconds)
+    }
+
+    /// Returns the total number of milliseconds.
+    #[must_use]
+    pub const fn to_milliseconds(self) -> i64 {
+        self.0
+    }
+
+    /// Returns the total number of whole seconds.
+    #[must_use]
+    pub const fn to_seconds(self) -> i64 {
+        self.0 / 1_000
+    }
+
+    /// Returns the total number of whole minutes.
+    #[must_use]
+    pub const fn to_minutes(self) -> i64 {
+        self.0 / 60_000
+    }
+
+    /// Returns the total number of whole hours.
+    #[must_use]
+    pub const fn to_hours(self) -> i64 {
+        self.0 / 3_600_000
+    }
+
+    /// Returns the total number of whole days.
+    #[must_use]
+    pub const fn to_days(self) -> i64 {
+        self.0 / 86_400_000
+    }
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 == 0 {
+            return f.write_str("0ms");
+        }
+
+        if self.0 < 0 {
+            f.write_str("-")?;
+        }
+
+        let mut remaining = self.0.unsigned_abs();
+        for (name, size) in UNITS {
+            let size = size.unsigned_abs();
+            let amount = remaining / size;
+            remaining %= size;
+
+            if amount > 0 {
+                write!(f, "{amount}{name}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use alloc::string::ToString as _;
+use alloc::sync::Arc;
+use core::fmt;
+
+use duramen_ast as ast;
+
+/// A fully qualified entity type name like `Namespace::User`.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
+pub struct EntityType(Arc<str>);
+
+impl EntityType {
+    /// Creates an entity type from a `::` separated name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any segment of `name` is not a valid identifier.
+    pub fn new(name: &str) -> Result<Self, ast::Error> {
+        for segment in name.split("::") {
+            ast::Identifier::new(segment)?;
+        }
+
+        Ok(Self(Arc::from(name)))
+    }
+
+    /// Returns the fully qualified name.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the basename (final segment).
+    #[must_use]
+    pub fn basename(&self) -> &str {
+        self.0.rsplit("::").next().unwrap_or(&self.0)
+    }
+}
+
+impl From<&ast::Name<'_>> for EntityType {
+    fn from(value: &ast::Name<'_>) -> Self {
+        if value.path().is_empty() {
+            return Self(Arc::from(value.basename().as_str()));
+        }
+
+        Self(Arc::from(value.to_string()))
+    }
+}
+
+impl PartialEq<ast::Name<'_>> for EntityType {
+    fn eq(&self, other: &ast::Name<'_>) -> bool {
+        let mut segments = self.0.split("::");
+
+        for identifier in other.path() {
+            if segments.next() != Some(identifier.as_str()) {
+                return false;
+            }
+        }
+
+        segments.next() == Some(other.basename().as_str()) && segments.next().is_none()
+    }
+}
+
+impl fmt::Display for EntityType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
@@ -0,0 +1,48 @@
+use alloc::sync::Arc;
+use core::fmt;
+
+use duramen_ast as ast;
+
+use crate::value::EntityType;
+
+/// A unique entity identifier like `User::"alice"`.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
+pub struct EntityUid {
+    kind: EntityType,
+    id: Arc<str>,
+}
+
+impl EntityUid {
+    /// Creates a new entity identifier.
+    #[must_use]
+    pub const fn new(kind: EntityType, id: Arc<str>) -> Self {
+        Self { kind, id }
+    }
+
+    /// Returns the entity type (e.g. `User`).
+    #[must_use]
+    pub const fn kind(&self) -> &EntityType {
+        &self.kind
+    }
+
+    /// Returns the entity identifier (e.g. `"alice"`).
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl From<&ast::EntityReference<'_>> for EntityUid {
+    fn from(value: &ast::EntityReference<'_>) -> Self {
+        Self {
+            kind: EntityType::from(value.kind()),
+            id: Arc::from(value.id()),
+        }
+    }
+}
+
+impl fmt::Display for EntityUid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}::{}", self.kind, ast::Quoted(&self.id))
+    }
+}
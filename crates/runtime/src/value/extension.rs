@@ -0,0 +1,66 @@
+use alloc::string::String;
+use core::fmt;
+
+use crate::error::ExtensionError;
+use crate::value::{Datetime, Decimal, Duration, IpAddress, ValueKind};
+
+/// An extension value.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
+pub enum Extension {
+    Decimal(Decimal),
+    IpAddress(IpAddress),
+    Datetime(Datetime),
+    Duration(Duration),
+}
+
+impl Extension {
+    /// Constructs an extension value from a constructor name and its string argument.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `function` is not a constructor or `argument` is invalid.
+    pub fn new(function: &str, argument: &str) -> Result<Self, ExtensionError> {
+        match function {
+            "decimal" => Decimal::new(argument).map(Self::Decimal),
+            "ip" => IpAddress::new(argument).map(Self::IpAddress),
+            "datetime" => Datetime::new(argument).map(Self::Datetime),
+            "duration" => Duration::new(argument).map(Self::Duration),
+            _ => Err(ExtensionError::UnknownFunction {
+                name: String::from(function),
+            }),
+        }
+    }
+
+    /// Returns the kind of this extension value.
+    #[must_use]
+    pub const fn kind(&self) -> ValueKind {
+        match self {
+            Self::Decimal(_) => ValueKind::Decimal,
+            Self::IpAddress(_) => ValueKind::IpAddress,
+            Self::Datetime(_) => ValueKind::Datetime,
+            Self::Duration(_) => ValueKind::Duration,
+        }
+    }
+
+    /// Returns the name of the constructor function for this value.
+    #[must_use]
+    pub const fn constructor(&self) -> &'static str {
+        match self {
+            Self::Decimal(_) => "decimal",
+            Self::IpAddress(_) => "ip",
+            Self::Datetime(_) => "datetime",
+            Self::Duration(_) => "duration",
+        }
+    }
+}
+
+impl fmt::Display for Extension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Decimal(value) => write!(f, "decimal(\"{value}\")"),
+            Self::IpAddress(value) => write!(f, "ip(\"{value}\")"),
+            Self::Datetime(value) => write!(f, "datetime(\"{value}\")"),
+            Self::Duration(value) => write!(f, "duration(\"{value}\")"),
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use alloc::string::String;
+use core::fmt;
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use crate::error::ExtensionError;
+
+/// An IPv4 or IPv6 address with a prefix length, like `10.0.0.0/8`.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
+pub struct IpAddress {
+    address: IpAddr,
+    prefix: u8,
+}
+
+impl IpAddress {
+    /// Parses an address with an optional `/prefix` suffix.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `text` is malformed.
+    pub fn new(text: &str) -> Result<Self, ExtensionError> {
+        let error = || ExtensionError::InvalidIpAddress {
+            text: String::from(text),
+        };
+
+        // Cedar does not support IPv4-embedded IPv6 addresses.
+        if text.contains(':') && text.contains('.') {
+            return Err(error());
+        }
+
+        let (address, prefix) = match text.split_once('/') {
+            Some((address, prefix)) => (address, Some(prefix)),
+            None => (text, None),
+        };
+
+        let address: IpAddr = address.parse().map_err(|_err| error())?;
+        let width = Self::width(address);
+
+        let prefix = match prefix {
+            None => width,
+            Some(prefix) => {
+                if prefix.is_empty()
+                    || (prefix.len() > 1 && prefix.starts_with('0'))
+                    || !prefix.bytes().all(|byte| byte.is_ascii_digit())
+                {
+                    return Err(error());
+                }
+
+                let prefix: u8 = prefix.parse().map_err(|_err| error())?;
+                if prefix > width {
+                    return Err(error());
+                }
+
+                prefix
+            }
+        };
+
+        Ok(Self { address, prefix })
+    }
+
+    /// Returns the address.
+    #[must_use]
+    pub const fn address(&self) -> IpAddr {
+        self.address
+    }
+
+    /// Returns the prefix length.
+    #[must_use]
+    pub const fn prefix(&self) -> u8 {
+        self.prefix
+    }
+
+    /// Returns whether this is an IPv4 address.
+    #[must_use]
+    pub const fn is_ipv4(&self) -> bool {
+        self.address.is_ipv4()
+    }
+
+    /// Returns whether this is an IPv6 address.
+    #[must_use]
+    pub const fn is_ipv6(&self) -> bool {
+        self.address.is_ipv6()
+    }
+
+    /// Returns whether this range lies within the loopback range.
+    #[must_use]
+    pub fn is_loopback(&self) -> bool {
+        let range = match self.address {
+            IpAddr::V4(_) => Self::v4(Ipv4Addr::new(127, 0, 0, 0), 8),
+            IpAddr::V6(_) => Self::v6(Ipv6Addr::LOCALHOST, 128),
+        };
+
+        self.is_in_range(&range)
+    }
+
+    /// Returns whether this range lies within the multicast range.
+    #[must_use]
+    pub fn is_multicast(&self) -> bool {
+        let range = match self.address {
+            IpAddr::V4(_) => Self::v4(Ipv4Addr::new(224, 0, 0, 0), 4),
+            IpAddr::V6(_) => Self::v6(Ipv6Addr::new(0xff00, 0, 0, 0, 0, 0, 0, 0), 8),
+        };
+
+        self.is_in_range(&range)
+    }
+
+    /// Returns whether this range lies within `other`.
+    #[must_use]
+    pub fn is_in_range(&self, other: &Self) -> bool {
+        if self.address.is_ipv4() != other.address.is_ipv4() {
+            return false;
+        }
+
+        let (start, end) = self.bounds();
+        let (other_start, other_end) = other.bounds();
+        other_start <= start && end <= other_end
+    }
+
+    const fn v4(address: Ipv4Addr, prefix: u8) -> Self {
+        Self {
+            address: IpAddr::V4(address),
+            prefix,
+        }
+    }
+
+    const fn v6(address: Ipv6Addr, prefix: u8) -> Self {
+        Self {
+            address: IpAddr::V6(address),
+            prefix,
+        }
+    }
+
+    const fn width(address: IpAddr) -> u8 {
+        match address {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        }
+    }
+
+    /// Returns the first and last address of the range, as integers.
+    fn bounds(&self) -> (u128, u128) {
+        let (bits, width) = match self.address {
+            IpAddr::V4(address) => (u128::from(address.to_bits()), 32),
+            IpAddr::V6(address) => (address.to_bits(), 128),
+        };
+
+        let host = u32::from(width - self.prefix);
+        let mask = u128::MAX.checked_shl(host).unwrap_or(0);
+        let full = u128::MAX >> (128 - u32::from(width));
+
+        let start = bits & mask;
+        (start, start | (!mask & full))
+    }
+}
+
+impl fmt::Display for IpAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.address, self.prefix)
+    }
+}
@@ -0,0 +1,82 @@
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+
+use duramen_ast as ast;
+
+use crate::value::Value;
+
+/// A record of attribute values.
+///
+/// Attributes are kept sorted by key, so equality ignores insertion order.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
+pub struct Record(Arc<[(Arc<str>, Value)]>);
+
+impl Record {
+    /// Creates an empty record.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self(Arc::from([]))
+    }
+
+    /// Returns the number of attributes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether the record has no attributes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the value for the given key.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.0
+            .binary_search_by(|(candidate, _)| (**candidate).cmp(key))
+            .ok()
+            .map(|index| &self.0[index].1)
+    }
+
+    /// Returns whether the record has the given key.
+    #[must_use]
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns an iterator over key-value pairs, sorted by key.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.0.iter().map(|(key, value)| (&**key, value))
+    }
+}
+
+/// Collects key-value pairs into a record.
+///
+/// If a key appears more than once, the last value wins.
+impl FromIterator<(Arc<str>, Value)> for Record {
+    fn from_iter<I: IntoIterator<Item = (Arc<str>, Value)>>(iter: I) -> Self {
+        let mut entries: Vec<(Arc<str>, Value)> = iter.into_iter().collect();
+        entries.reverse();
+        entries.sort_by(|(left, _), (right, _)| left.cmp(right));
+        entries.dedup_by(|(left, _), (right, _)| left == right);
+        Self(Arc::from(entries))
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("{")?;
+
+        for (index, (key, value)) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+
+            write!(f, "{}: {value}", ast::Quoted(key))?;
+        }
+
+        f.write_str("}")
+    }
+}
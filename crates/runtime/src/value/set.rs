@@ -0,0 +1,89 @@
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+use core::slice::Iter;
+
+use crate::value::Value;
+
+/// An unordered set of values.
+///
+/// Elements are kept sorted and deduplicated, so equality ignores insertion order.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
+pub struct Set(Arc<[Value]>);
+
+impl Set {
+    /// Creates an empty set.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self(Arc::from([]))
+    }
+
+    /// Returns the number of elements.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether the set is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns whether the set contains the given value.
+    #[must_use]
+    pub fn contains(&self, value: &Value) -> bool {
+        self.0.binary_search(value).is_ok()
+    }
+
+    /// Returns whether every element of this set is in `other`.
+    #[must_use]
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.0.iter().all(|value| other.contains(value))
+    }
+
+    /// Returns whether this set shares no elements with `other`.
+    #[must_use]
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        !self.0.iter().any(|value| other.contains(value))
+    }
+
+    /// Returns an iterator over the elements in canonical order.
+    pub fn iter(&self) -> Iter<'_, Value> {
+        self.0.iter()
+    }
+}
+
+impl FromIterator<Value> for Set {
+    fn from_iter<I: IntoIterator<Item = Value>>(iter: I) -> Self {
+        let mut values: Vec<Value> = iter.into_iter().collect();
+        values.sort_unstable();
+        values.dedup();
+        Self(Arc::from(values))
+    }
+}
+
+impl<'a> IntoIterator for &'a Set {
+    type IntoIter = Iter<'a, Value>;
+    type Item = &'a Value;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl fmt::Display for Set {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+
+        for (index, value) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+
+            value.fmt(f)?;
+        }
+
+        f.write_str("]")
+    }
+}
@@ -0,0 +1,33 @@
+use core::fmt;
+
+/// The kind of a runtime value.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Hash)]
+pub enum ValueKind {
+    Bool,
+    Long,
+    String,
+    Entity,
+    Set,
+    Record,
+    Decimal,
+    IpAddress,
+    Datetime,
+    Duration,
+}
+
+impl fmt::Display for ValueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool => f.write_str("Bool"),
+            Self::Long => f.write_str("Long"),
+            Self::String => f.write_str("String"),
+            Self::Entity => f.write_str("Entity"),
+            Self::Set => f.write_str("Set"),
+            Self::Record => f.write_str("Record"),
+            Self::Decimal => f.write_str("decimal"),
+            Self::IpAddress => f.write_str("ipaddr"),
+            Self::Datetime => f.write_str("datetime"),
+            Self::Duration => f.write_str("duration"),
+        }
+    }
+}
@@ -0,0 +1,283 @@
+#![expect(clippy::unwrap_used, reason = "Tests")]
+
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use duramen_diagnostic::{Diagnostic, Diagnostics};
+use duramen_runtime::{Entities, EntitiesParser, Entity, EntityType, EntityUid, Record, Value};
+use duramen_test::insta::assert_snapshot;
+
+extern crate alloc;
+
+fn uid(id: &str) -> EntityUid {
+    EntityUid::new(EntityType::new("Group").unwrap(), Arc::from(id))
+}
+
+fn entity(id: &str, parents: &[&str]) -> Entity {
+    Entity::new(uid(id)).with_parents(parents.iter().map(|parent| uid(parent)))
+}
+
+fn messages(diagnostics: &Diagnostics) -> String {
+    diagnostics
+        .iter()
+        .map(Diagnostic::message)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn empty() {
+    let entities = Entities::empty();
+    assert!(entities.is_empty());
+    assert!(!entities.is_ancestor(&uid("a"), &uid("b")));
+    assert_eq!(entities.ancestors(&uid("a")).count(), 0);
+}
+
+#[test]
+fn transitive_closure() {
+    let entities = Entities::new([
+        entity("a", &["b"]),
+        entity("b", &["c", "d"]),
+        entity("c", &["d"]),
+        entity("d", &[]),
+    ])
+    .unwrap();
+
+    assert_eq!(entities.len(), 4);
+    assert!(entities.is_ancestor(&uid("b"), &uid("a")));
+    assert!(entities.is_ancestor(&uid("d"), &uid("a")));
+    assert!(!entities.is_ancestor(&uid("a"), &uid("d")));
+    assert!(!entities.is_ancestor(&uid("a"), &uid("a")));
+
+    let ancestors: Vec<_> = entities.ancestors(&uid("a")).cloned().collect();
+    assert_eq!(ancestors, [uid("b"), uid("c"), uid("d")]);
+}
+
+#[test]
+fn declaration_order_independent() {
+    let entities = Entities::new([
+        entity("d", &[]),
+        entity("c", &["d"]),
+        entity("a", &["b"]),
+        entity("b", &["c"]),
+    ])
+    .unwrap();
+
+    assert!(entities.is_ancestor(&uid("d"), &uid("a")));
+    assert_eq!(entities.ancestors(&uid("a")).count(), 3);
+}
+
+#[test]
+fn deep_hierarchy() {
+    let depth = 1_000;
+    let ids: Vec<String> = (0..depth).map(|index| format!("{index}")).collect();
+    let entities = Entities::new(ids.iter().enumerate().map(|(index, id)| {
+        let parents: Vec<&str> = ids.get(index + 1).map(String::as_str).into_iter().collect();
+        entity(id, &parents)
+    }))
+    .unwrap();
+
+    assert!(entities.is_ancestor(&uid("999"), &uid("0")));
+}
+
+#[test]
+fn attributes_and_tags() {
+    let attributes: Record = core::iter::once((Arc::from("level"), Value::from(3))).collect();
+    let entities = Entities::new([Entity::new(uid("a")).with_attributes(attributes)]).unwrap();
+
+    let entity = entities.get(&uid("a")).unwrap();
+    assert_eq!(entity.attributes().get("level"), Some(&Value::from(3)));
+    assert!(entity.tags().is_empty());
+    assert!(entities.get(&uid("b")).is_none());
+}
+
+#[test]
+fn unknown_parent() {
+    let diagnostics = Entities::new([entity("a", &["missing"])]).unwrap_err();
+    assert_snapshot!(messages(&diagnostics), @r#"entity `Group::"a"` has unknown parent `Group::"missing"`"#);
+}
+
+#[test]
+fn duplicate_entity() {
+    let diagnostics = Entities::new([entity("a", &[]), entity("a", &[])]).unwrap_err();
+    assert_snapshot!(messages(&diagnostics), @r#"duplicate entity `Group::"a"`"#);
+}
+
+#[test]
+fn cycle() {
+    let diagnostics = Entities::new([
+        entity("a", &["b"]),
+        entity("b", &["c"]),
+        entity("c", &["a"]),
+        entity("d", &["d"]),
+    ])
+    .unwrap_err();
+
+    assert_snapshot!(messages(&diagnostics), @r#"
+    entity hierarchy contains a cycle: `Group::"a"` -> `Group::"b"` -> `Group::"c"` -> `Group::"a"`
+    entity hierarchy contains a cycle: `Group::"d"` -> `Group::"d"`
+    "#);
+}
+
+// -- json --
+
+fn render(source: &str) -> String {
+    let diagnostics = EntitiesParser::parse(source).unwrap_err();
+    let rendered = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.render("entities.json", source))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    duramen_test::anstream::adapter::strip_str(&rendered).to_string()
+}
+
+#[test]
+fn json_entities() {
+    let entities = EntitiesParser::parse(
+        r#"[
+            {
+                "uid": { "type": "User", "id": "alice" },
+                "attrs": {
+                    "age": 42,
+                    "name": "Alice",
+                    "active": true,
+                    "roles": ["admin", "dev", "admin"],
+                    "address": { "city": "Dublin" },
+                    "manager": { "__entity": { "type": "User", "id": "bob" } },
+                    "network": { "__extn": { "fn": "ip", "arg": "10.0.0.0/8" } }
+                },
+                "parents": [{ "type": "Group", "id": "admins" }],
+                "tags": { "team": "core" }
+            },
+            {
+                "uid": { "__entity": { "type": "User", "id": "bob" } },
+                "attrs": {},
+                "parents": []
+            },
+            { "uid": { "type": "Group", "id": "admins" } }
+        ]"#,
+    )
+    .unwrap();
+
+    let alice = EntityUid::new(EntityType::new("User").unwrap(), Arc::from("alice"));
+    let admins = EntityUid::new(EntityType::new("Group").unwrap(), Arc::from("admins"));
+    assert_eq!(entities.len(), 3);
+    assert!(entities.is_ancestor(&admins, &alice));
+
+    let entity = entities.get(&alice).unwrap();
+    assert_snapshot!(
+        Value::from(entity.attributes().clone()),
+        @r#"{"active": true, "address": {"city": "Dublin"}, "age": 42, "manager": User::"bob", "name": "Alice", "network": ip("10.0.0.0/8"), "roles": ["admin", "dev"]}"#
+    );
+    assert_snapshot!(Value::from(entity.tags().clone()), @r#"{"team": "core"}"#);
+}
+
+#[test]
+fn json_syntax_error() {
+    assert_snapshot!(render(r#"[{ "uid": }]"#), @r#"
+    error: unexpected character, expected a value
+      ╭▸ entities.json:1:11
+      │
+    1 │ [{ "uid": }]
+      ╰╴          ━ expected a value
+    "#);
+}
+
+#[test]
+fn json_malformed_entity() {
+    let source = r#"[
+      { "uid": { "type": "User" }, "attr": {} },
+      { "uid": { "type": "User", "id": "a" }, "attrs": { "n": 1.5, "x": null } }
+    ]"#;
+
+    assert_snapshot!(render(source), @r#"
+    error: unknown field `attr`
+      ╭▸ entities.json:2:36
+      │
+    2 │       { "uid": { "type": "User" }, "attr": {} },
+      ╰╴                                   ━━━━━━ unknown field
+    error: missing field `id`
+      ╭▸ entities.json:2:16
+      │
+    2 │       { "uid": { "type": "User" }, "attr": {} },
+      ╰╴               ━━━━━━━━━━━━━━━━━━ missing field
+    error: `1.5` is not a valid integer
+      ╭▸ entities.json:3:63
+      │
+    3 │       { "uid": { "type": "User", "id": "a" }, "attrs": { "n": 1.5, "x": null } }
+      │                                                               ━━━ invalid integer
+      ╰╴
+    note: integers must be whole numbers that fit in 64 bits
+    error: expected a value, found null
+      ╭▸ entities.json:3:73
+      │
+    3 │       { "uid": { "type": "User", "id": "a" }, "attrs": { "n": 1.5, "x": null } }
+      ╰╴                                                                        ━━━━ expected a value
+    "#);
+}
+
+#[test]
+fn json_invalid_extension() {
+    assert_snapshot!(render(r#"[{ "uid": { "type": "User", "id": "a" }, "attrs": { "n": { "__extn": { "fn": "decimal", "arg": "1.23456" } } } }]"#), @r#"
+    error: `1.23456` is not a valid decimal
+      ╭▸ entities.json:1:70
+      │
+    1 │ [{ "uid": { "type": "User", "id": "a" }, "attrs": { "n": { "__extn": { "fn": "decimal", "arg": "1.23456" } } } }]
+      │                                                                      ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ invalid extension value
+      ╰╴
+    note: decimals have at most 4 fractional digits, like `1.2345`
+    "#);
+}
+
+#[test]
+fn json_invalid_entity_type() {
+    assert_snapshot!(render(r#"[{ "uid": { "type": "App::", "id": "a" } }]"#), @r#"
+    error: `App::` is not a valid entity type
+      ╭▸ entities.json:1:21
+      │
+    1 │ [{ "uid": { "type": "App::", "id": "a" } }]
+      ╰╴                    ━━━━━━━ invalid entity type
+    "#);
+}
+
+#[test]
+fn json_hierarchy_errors() {
+    let source = r#"[
+      { "uid": { "type": "User", "id": "a" }, "parents": [{ "type": "Group", "id": "missing" }] },
+      { "uid": { "type": "User", "id": "a" } }
+    ]"#;
+
+    assert_snapshot!(render(source), @r#"
+    error: duplicate entity `User::"a"`
+      ╭▸ entities.json:3:16
+      │
+    2 │       { "uid": { "type": "User", "id": "a" }, "parents": [{ "type": "Group", "id": "missing" }] },
+      │                ───────────────────────────── first declared here
+    3 │       { "uid": { "type": "User", "id": "a" } }
+      ╰╴               ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ duplicate entity
+    error: unknown parent `Group::"missing"`
+      ╭▸ entities.json:2:59
+      │
+    2 │       { "uid": { "type": "User", "id": "a" }, "parents": [{ "type": "Group", "id": "missing" }] },
+      │                                                           ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ unknown parent
+      ╰╴
+    note: every parent must also be an entity in the store
+    "#);
+}
+
+#[test]
+fn json_cycle() {
+    let source = r#"[
+      { "uid": { "type": "Group", "id": "a" }, "parents": [{ "type": "Group", "id": "a" }] }
+    ]"#;
+
+    assert_snapshot!(render(source), @r#"
+    error: entity hierarchy contains a cycle: `Group::"a"` -> `Group::"a"`
+      ╭▸ entities.json
+      │
+      ╰╴
+    note: an entity cannot be its own ancestor
+    "#);
+}
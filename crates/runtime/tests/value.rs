@@ -0,0 +1,355 @@
+use alloc::borrow::Cow;
+use alloc::sync::Arc;
+use alloc::vec;
+
+use duramen_ast::{BoolLiteral, EntityReference, Identifier, Literal, Name, StringLiteral};
+use duramen_runtime::{
+    Datetime, Decimal, Duration, EntityType, Extension, ExtensionError, IpAddress, Record, Set,
+    Value, ValueKind,
+};
+use duramen_test::insta::assert_snapshot;
+use duramen_test::uid;
+
+extern crate alloc;
+
+// -- set --
+
+#[test]
+fn set_ignores_order() {
+    let left: Set = [Value::from(1), Value::from(2), Value::from(3)]
+        .into_iter()
+        .collect();
+    let right: Set = [Value::from(3), Value::from(1), Value::from(2)]
+        .into_iter()
+        .collect();
+    assert_eq!(left, right);
+}
+
+#[test]
+fn set_deduplicates() {
+    let set: Set = [Value::from("a"), Value::from("b"), Value::from("a")]
+        .into_iter()
+        .collect();
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(&Value::from("a")));
+    assert!(!set.contains(&Value::from("c")));
+}
+
+#[test]
+fn set_subset_and_disjoint() {
+    let small: Set = core::iter::once(Value::from(1)).collect();
+    let large: Set = [Value::from(1), Value::from(2)].into_iter().collect();
+    let other: Set = core::iter::once(Value::from(3)).collect();
+
+    assert!(small.is_subset(&large));
+    assert!(!large.is_subset(&small));
+    assert!(Set::empty().is_subset(&small));
+    assert!(large.is_disjoint(&other));
+    assert!(!small.is_disjoint(&large));
+}
+
+#[test]
+fn set_display() {
+    let set: Set = [Value::from(2), Value::from(true), Value::from("x")]
+        .into_iter()
+        .collect();
+    assert_snapshot!(Value::from(set), @r#"[true, 2, "x"]"#);
+}
+
+// -- record --
+
+#[test]
+fn record_ignores_order() {
+    let left: Record = [
+        (Arc::from("a"), Value::from(1)),
+        (Arc::from("b"), Value::from(2)),
+    ]
+    .into_iter()
+    .collect();
+    let right: Record = [
+        (Arc::from("b"), Value::from(2)),
+        (Arc::from("a"), Value::from(1)),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(left, right);
+}
+
+#[test]
+fn record_last_key_wins() {
+    let record: Record = [
+        (Arc::from("a"), Value::from(1)),
+        (Arc::from("a"), Value::from(2)),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(record.len(), 1);
+    assert_eq!(record.get("a"), Some(&Value::from(2)));
+    assert!(!record.contains_key("b"));
+}
+
+#[test]
+fn record_display() {
+    let record: Record = [
+        (Arc::from("name"), Value::from("alice")),
+        (Arc::from("age"), Value::from(42)),
+        (Arc::from("tags"), Value::from(Set::empty())),
+    ]
+    .into_iter()
+    .collect();
+    assert_snapshot!(Value::from(record), @r#"{"age": 42, "name": "alice", "tags": []}"#);
+}
+
+#[test]
+fn string_display() {
+    assert_snapshot!(Value::from("it's\t\"quoted\""), @r#""it's\t\"quoted\"""#);
+
+    let record: Record = core::iter::once((Arc::from("it's"), Value::from(1))).collect();
+    assert_snapshot!(Value::from(record), @r#"{"it's": 1}"#);
+}
+
+// -- entity --
+
+#[test]
+fn entity_type_valid() {
+    let kind = EntityType::new("App::User").unwrap();
+    assert_eq!(kind.as_str(), "App::User");
+    assert_eq!(kind.basename(), "User");
+}
+
+#[test]
+fn entity_type_invalid() {
+    assert!(EntityType::new("App::").is_err());
+    assert!(EntityType::new("1User").is_err());
+    assert!(EntityType::new("__cedar::User").is_err());
+}
+
+#[test]
+fn entity_type_name_equality() {
+    let name = Name::new(
+        vec![Identifier::new("App").unwrap()],
+        Identifier::new("User").unwrap(),
+    );
+    assert_eq!(EntityType::new("App::User").unwrap(), name);
+    assert_ne!(EntityType::new("App::Group").unwrap(), name);
+    assert_ne!(EntityType::new("User").unwrap(), name);
+    assert_eq!(
+        EntityType::from(&name),
+        EntityType::new("App::User").unwrap()
+    );
+}
+
+#[test]
+fn entity_uid_display() {
+    assert_snapshot!(uid("App::User", "a\"b"), @r#"App::User::"a\"b""#);
+    assert_snapshot!(uid("App::User", "bob's"), @r#"App::User::"bob's""#);
+}
+
+// -- literal --
+
+#[test]
+fn literal_conversion() {
+    let name = Name::new(vec![], Identifier::new("User").unwrap());
+    let reference = EntityReference::new(name, Cow::Borrowed("alice"));
+
+    assert_eq!(
+        Value::from(&Literal::Bool(BoolLiteral::new(true))),
+        Value::Bool(true)
+    );
+    assert_eq!(
+        Value::from(&Literal::String(StringLiteral::new(Cow::Borrowed("hi")))),
+        Value::from("hi")
+    );
+    assert_eq!(
+        Value::from(&Literal::Entity(reference)),
+        Value::Entity(uid("User", "alice"))
+    );
+}
+
+#[test]
+fn value_kind() {
+    assert_eq!(Value::from(1).kind(), ValueKind::Long);
+    assert_eq!(Value::from(Set::empty()).kind(), ValueKind::Set);
+
+    let decimal = Extension::new("decimal", "1.0").unwrap();
+    assert_eq!(Value::from(decimal).kind(), ValueKind::Decimal);
+    assert_snapshot!(ValueKind::IpAddress, @"ipaddr");
+}
+
+// -- decimal --
+
+#[test]
+fn decimal_valid() {
+    assert_eq!(Decimal::new("1.5").unwrap().scaled(), 15_000);
+    assert_eq!(Decimal::new("-0.0001").unwrap().scaled(), -1);
+    assert_eq!(
+        Decimal::new("922337203685477.5807").unwrap().scaled(),
+        i64::MAX
+    );
+    assert_eq!(
+        Decimal::new("-922337203685477.5808").unwrap().scaled(),
+        i64::MIN
+    );
+}
+
+#[test]
+fn decimal_invalid() {
+    for text in [
+        "1",
+        "1.",
+        ".1",
+        "1.23456",
+        "a.0",
+        "+1.0",
+        "922337203685477.5808",
+    ] {
+        assert!(Decimal::new(text).is_err(), "{text}");
+    }
+}
+
+#[test]
+fn decimal_display() {
+    assert_snapshot!(Decimal::new("-1.5").unwrap(), @"-1.5000");
+    assert_snapshot!(Decimal::new("-0.25").unwrap(), @"-0.2500");
+}
+
+// -- ip --
+
+#[test]
+fn ip_valid() {
+    let address = IpAddress::new("10.1.2.3").unwrap();
+    assert!(address.is_ipv4());
+    assert_eq!(address.prefix(), 32);
+
+    let address = IpAddress::new("::1").unwrap();
+    assert!(address.is_ipv6());
+    assert!(address.is_loopback());
+}
+
+#[test]
+fn ip_invalid() {
+    for text in [
+        "10.0.0.0/33",
+        "10.0.0.0/08",
+        "10.0.0.0/",
+        "::ffff:1.2.3.4",
+        "::/129",
+        "host",
+    ] {
+        assert!(IpAddress::new(text).is_err(), "{text}");
+    }
+}
+
+#[test]
+fn ip_ranges() {
+    let network = IpAddress::new("10.0.0.0/8").unwrap();
+    assert!(IpAddress::new("10.1.0.0/16").unwrap().is_in_range(&network));
+    assert!(!IpAddress::new("11.0.0.0").unwrap().is_in_range(&network));
+    assert!(!IpAddress::new("10.0.0.0/7").unwrap().is_in_range(&network));
+    assert!(!IpAddress::new("::a00:0").unwrap().is_in_range(&network));
+
+    assert!(IpAddress::new("127.0.0.1").unwrap().is_loopback());
+    assert!(IpAddress::new("224.1.1.1").unwrap().is_multicast());
+    assert!(IpAddress::new("ff02::1").unwrap().is_multicast());
+    assert!(!IpAddress::new("127.0.0.0/7").unwrap().is_loopback());
+}
+
+#[test]
+fn ip_display() {
+    assert_snapshot!(Extension::new("ip", "192.168.0.1/24").unwrap(), @r#"ip("192.168.0.1/24")"#);
+}
+
+// -- datetime --
+
+#[test]
+fn datetime_formats() {
+    let date = Datetime::new("2024-10-15").unwrap();
+    assert_eq!(date.milliseconds(), 1_728_950_400_000);
+
+    let utc = Datetime::new("2024-10-15T11:35:00Z").unwrap();
+    let millis = Datetime::new("2024-10-15T11:35:00.100Z").unwrap();
+    let offset = Datetime::new("2024-10-15T12:35:00+0100").unwrap();
+
+    assert_eq!(millis.milliseconds() - utc.milliseconds(), 100);
+    assert_eq!(utc, offset);
+    assert_eq!(
+        Datetime::new("1969-12-31").unwrap().milliseconds(),
+        -86_400_000
+    );
+}
+
+#[test]
+fn datetime_invalid() {
+    for text in [
+        "2024-13-01",
+        "2023-02-29",
+        "2024-10-15T",
+        "2024-10-15T24:00:00Z",
+        "2024-10-15T11:35:00",
+        "2024-10-15T11:35:00.1Z",
+        "2024-10-15T11:35:00+01",
+        "24-10-15",
+    ] {
+        assert!(Datetime::new(text).is_err(), "{text}");
+    }
+}
+
+#[test]
+fn datetime_arithmetic() {
+    let datetime = Datetime::new("1969-12-31T23:00:00Z").unwrap();
+    let day = Duration::new("1d").unwrap();
+
+    assert_snapshot!(datetime.to_date().unwrap(), @"1969-12-31T00:00:00.000Z");
+    assert_snapshot!(datetime.to_time(), @"23h");
+    assert_snapshot!(datetime.offset(day).unwrap(), @"1970-01-01T23:00:00.000Z");
+    assert_eq!(
+        datetime.offset(day).unwrap().duration_since(datetime),
+        Some(day)
+    );
+    assert_eq!(Datetime::from_milliseconds(i64::MAX).offset(day), None);
+}
+
+// -- duration --
+
+#[test]
+fn duration_valid() {
+    assert_eq!(
+        Duration::new("1d2h3m4s5ms").unwrap().to_milliseconds(),
+        93_784_005
+    );
+    assert_eq!(Duration::new("-90m").unwrap().to_hours(), -1);
+    assert_eq!(Duration::new("2m").unwrap().to_seconds(), 120);
+}
+
+#[test]
+fn duration_invalid() {
+    for text in ["", "-", "1", "1x", "1h1d", "1s1s", "d", "1d-1h"] {
+        assert!(Duration::new(text).is_err(), "{text}");
+    }
+}
+
+#[test]
+fn duration_display() {
+    assert_snapshot!(Duration::new("90m").unwrap(), @"1h30m");
+    assert_snapshot!(Duration::new("-1500ms").unwrap(), @"-1s500ms");
+    assert_snapshot!(Duration::new("0d").unwrap(), @"0ms");
+}
+
+// -- extension --
+
+#[test]
+fn extension_unknown() {
+    let error = Extension::new("unknown", "").unwrap_err();
+    assert_eq!(
+        error,
+        ExtensionError::UnknownFunction {
+            name: "unknown".into()
+        }
+    );
+}
+
+#[test]
+fn extension_error_display() {
+    let error = Extension::new("decimal", "1.23456").unwrap_err();
+    assert_snapshot!(error, @"`1.23456` is not a valid decimal");
+}
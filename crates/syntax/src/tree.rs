@@ -124,6 +124,28 @@ impl<'a> Node<'a> {
         data.start..data.end
     }
 
+    /// Returns the byte range of this node, without surrounding whitespace and comments.
+    #[must_use]
+    pub fn trimmed_range(&self) -> Range<usize> {
+        let mut tokens = self.descendants().filter(|node| {
+            let kind = node.kind();
+            let range = node.range();
+
+            kind.token().is_some()
+                && !kind.is_whitespace()
+                && !kind.is_newline()
+                && !kind.is_comment()
+                && range.start < range.end
+        });
+
+        let Some(first) = tokens.next() else {
+            return self.range();
+        };
+
+        let last = tokens.last().unwrap_or(first);
+        first.range().start..last.range().end
+    }
+
     /// Returns the source text covered by this node.
     #[must_use]
     pub fn text(&self) -> &'a str {
@@ -435,15 +457,15 @@ impl<'a> Iterator for Descendants<'a> {
             let mut start = index;
 
             self.current = loop {
+                if start == self.root {
+                    break None;
+                }
+
                 let current = &self.tree[start];
                 if let Some(next) = current.next {
                     break Some(next);
                 }
 
-                if start == self.root {
-                    break None;
-                }
-
                 start = current.parent?;
             };
         }
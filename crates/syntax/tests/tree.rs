@@ -0,0 +1,42 @@
+use alloc::vec::Vec;
+
+use duramen_lexer::TokenKind;
+use duramen_syntax::{Builder, Group, Node, Tree};
+
+extern crate alloc;
+
+/// Builds `a;b;` as two policies under a shared root.
+fn build(source: &str) -> Tree<'_> {
+    let mut builder = Builder::new();
+    let root = builder.open(Group::Policies);
+
+    for _ in 0..2 {
+        let policy = builder.open(Group::Policy);
+        builder.token(TokenKind::Identifier, 1);
+        builder.token(TokenKind::Semicolon, 1);
+        builder.close(&policy);
+    }
+
+    builder.close(&root);
+    builder.build(source)
+}
+
+fn texts(node: Node<'_>) -> Vec<&str> {
+    node.descendants().map(|node| node.text()).collect()
+}
+
+#[test]
+fn descendants_stay_in_subtree() {
+    let tree = build("a;b;");
+    let root = tree.root().unwrap();
+    assert_eq!(texts(root), ["a;b;", "a;", "a", ";", "b;", "b", ";"]);
+
+    let policy = root.child(Group::Policy).unwrap();
+    assert_eq!(texts(policy), ["a;", "a", ";"]);
+
+    let identifier = policy.first();
+    assert_eq!(texts(identifier), ["a"]);
+
+    let semicolon = policy.last();
+    assert_eq!(texts(semicolon), [";"]);
+}
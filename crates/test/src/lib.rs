@@ -1,9 +1,13 @@
 #![cfg_attr(doc, doc = include_str!("../README.md"))]
 
+extern crate alloc;
+
+use alloc::sync::Arc;
 use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
 
 pub use duramen_diagnostic::Diagnostic;
+use duramen_runtime::{EntityType, EntityUid, Record, Value};
 pub use rand;
 pub use similar_asserts::*;
 use walkdir::WalkDir;
@@ -28,6 +32,26 @@ impl<'a> TestContext<'a> {
     }
 }
 
+/// Builds an entity UID from a type name and an ID.
+///
+/// # Panics
+///
+/// Panics if `kind` is not a valid entity type name.
+#[must_use]
+#[expect(clippy::unwrap_used, reason = "Testing")]
+pub fn uid(kind: &str, id: &str) -> EntityUid {
+    EntityUid::new(EntityType::new(kind).unwrap(), Arc::from(id))
+}
+
+/// Builds a record from attribute names and values.
+#[must_use]
+pub fn record<const N: usize>(attributes: [(&str, Value); N]) -> Record {
+    attributes
+        .into_iter()
+        .map(|(name, value)| (Arc::from(name), value))
+        .collect()
+}
+
 /// Runs tests across the entire corpus.
 #[macro_export]
 macro_rules! corpus {
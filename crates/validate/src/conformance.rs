@@ -0,0 +1,265 @@
+use alloc::format;
+use alloc::string::{String, ToString as _};
+use alloc::vec::Vec;
+
+use duramen_runtime::{Entities, Entity, EntityUid, Extension, Record, Request, Value};
+
+use crate::error::{ConformanceError, EntityConformanceError, RequestError};
+use crate::schema::ValidatorSchema;
+use crate::types::{RecordType, Type};
+
+/// Returns whether `uid` is a declared value, if its entity type is enumerated.
+fn is_declared_id(schema: &ValidatorSchema, uid: &EntityUid) -> bool {
+    schema
+        .entity(uid.kind())
+        .and_then(|entity| entity.ids())
+        .is_none_or(|ids| ids.iter().any(|id| id == uid.id()))
+}
+
+/// Describes the type of a value, naming entity types.
+fn describe(value: &Value) -> String {
+    match value {
+        Value::Entity(uid) => uid.kind().to_string(),
+        _ => value.kind().to_string(),
+    }
+}
+
+/// Checks that a value has the given type.
+pub fn check_value(
+    schema: &ValidatorSchema,
+    value: &Value,
+    ty: &Type,
+    path: &str,
+    errors: &mut Vec<ConformanceError>,
+) {
+    let matches = match (ty, value) {
+        (Type::Bool, Value::Bool(_))
+        | (Type::Long, Value::Long(_))
+        | (Type::String, Value::String(_))
+        | (Type::Decimal, Value::Extension(Extension::Decimal(_)))
+        | (Type::IpAddress, Value::Extension(Extension::IpAddress(_)))
+        | (Type::Datetime, Value::Extension(Extension::Datetime(_)))
+        | (Type::Duration, Value::Extension(Extension::Duration(_))) => true,
+        (Type::Entity(kind), Value::Entity(uid)) if uid.kind() == kind => {
+            if !is_declared_id(schema, uid) {
+                errors.push(ConformanceError::InvalidEnumId {
+                    path: String::from(path),
+                    uid: uid.clone(),
+                });
+            }
+
+            true
+        }
+        (Type::Set(element), Value::Set(set)) => {
+            for value in set {
+                check_value(schema, value, element, path, errors);
+            }
+
+            true
+        }
+        (Type::Record(record), Value::Record(value)) => {
+            check_record(schema, value, record, path, errors);
+            true
+        }
+        _ => false,
+    };
+
+    if !matches {
+        errors.push(ConformanceError::UnexpectedType {
+            path: String::from(path),
+            expected: ty.clone(),
+            found: describe(value),
+        });
+    }
+}
+
+/// Returns the path of an attribute, which is just its name at the top level.
+fn attribute_path(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        String::from(name)
+    } else {
+        format!("{path}.{name}")
+    }
+}
+
+/// Checks that a record has every required attribute, and no undeclared ones.
+pub fn check_record(
+    schema: &ValidatorSchema,
+    record: &Record,
+    ty: &RecordType,
+    path: &str,
+    errors: &mut Vec<ConformanceError>,
+) {
+    for (name, attribute) in ty.iter() {
+        let path = attribute_path(path, name);
+
+        match record.get(name) {
+            Some(value) => check_value(schema, value, attribute.ty(), &path, errors),
+            None if attribute.is_required() => {
+                errors.push(ConformanceError::MissingAttribute { path });
+            }
+            None => {}
+        }
+    }
+
+    for (name, _) in record.iter() {
+        if ty.get(name).is_none() {
+            errors.push(ConformanceError::UndeclaredAttribute {
+                path: attribute_path(path, name),
+            });
+        }
+    }
+}
+
+/// Checks that a request is allowed by the action's `appliesTo` declaration.
+pub fn check_request(schema: &ValidatorSchema, request: &Request) -> Vec<RequestError> {
+    let mut errors = Vec::new();
+
+    let Some(action) = schema.action(request.action()) else {
+        errors.push(RequestError::UnknownAction {
+            uid: request.action().clone(),
+        });
+
+        return errors;
+    };
+
+    let principal = request.principal();
+    if !action.principals().contains(principal.kind()) {
+        errors.push(RequestError::InvalidPrincipal {
+            action: request.action().clone(),
+            kind: principal.kind().clone(),
+            expected: action.principals().to_vec(),
+        });
+    } else if !is_declared_id(schema, principal) {
+        errors.push(RequestError::InvalidEnumId {
+            uid: principal.clone(),
+        });
+    }
+
+    let resource = request.resource();
+    if !action.resources().contains(resource.kind()) {
+        errors.push(RequestError::InvalidResource {
+            action: request.action().clone(),
+            kind: resource.kind().clone(),
+            expected: action.resources().to_vec(),
+        });
+    } else if !is_declared_id(schema, resource) {
+        errors.push(RequestError::InvalidEnumId {
+            uid: resource.clone(),
+        });
+    }
+
+    let mut context = Vec::new();
+    check_record(
+        schema,
+        request.context(),
+        action.context(),
+        "context",
+        &mut context,
+    );
+
+    errors.extend(
+        context
+            .into_iter()
+            .map(|error| RequestError::InvalidContext { error }),
+    );
+
+    errors
+}
+
+/// Checks that every entity in a store conforms to its entity type declaration.
+pub fn check_entities(
+    schema: &ValidatorSchema,
+    entities: &Entities,
+) -> Vec<EntityConformanceError> {
+    let mut errors = Vec::new();
+
+    for entity in entities.iter() {
+        check_entity(schema, entity, &mut errors);
+    }
+
+    errors
+}
+
+fn check_entity(
+    schema: &ValidatorSchema,
+    entity: &Entity,
+    errors: &mut Vec<EntityConformanceError>,
+) {
+    let uid = entity.uid();
+
+    if schema.is_action_type(uid.kind()) {
+        let Some(action) = schema.action(uid) else {
+            errors.push(EntityConformanceError::UnknownAction { uid: uid.clone() });
+            return;
+        };
+
+        for parent in entity.parents() {
+            if !action.parents().contains(parent) {
+                errors.push(EntityConformanceError::InvalidParent {
+                    uid: uid.clone(),
+                    parent: parent.clone(),
+                });
+            }
+        }
+
+        return;
+    }
+
+    let Some(declaration) = schema.entity(uid.kind()) else {
+        errors.push(EntityConformanceError::UnknownEntityType { uid: uid.clone() });
+        return;
+    };
+
+    if !is_declared_id(schema, uid) {
+        errors.push(EntityConformanceError::InvalidEnumId { uid: uid.clone() });
+    }
+
+    for parent in entity.parents() {
+        if !declaration.parents().contains(parent.kind()) {
+            errors.push(EntityConformanceError::InvalidParent {
+                uid: uid.clone(),
+                parent: parent.clone(),
+            });
+        }
+    }
+
+    let mut attributes = Vec::new();
+    check_record(
+        schema,
+        entity.attributes(),
+        declaration.attributes(),
+        "",
+        &mut attributes,
+    );
+
+    errors.extend(
+        attributes
+            .into_iter()
+            .map(|error| EntityConformanceError::InvalidAttribute {
+                uid: uid.clone(),
+                error,
+            }),
+    );
+
+    match declaration.tags() {
+        Some(ty) => {
+            let mut tags = Vec::new();
+            for (name, value) in entity.tags().iter() {
+                check_value(schema, value, ty, name, &mut tags);
+            }
+
+            errors.extend(
+                tags.into_iter()
+                    .map(|error| EntityConformanceError::InvalidTag {
+                        uid: uid.clone(),
+                        error,
+                    }),
+            );
+        }
+        None if !entity.tags().is_empty() => {
+            errors.push(EntityConformanceError::UnexpectedTags { uid: uid.clone() });
+        }
+        None => {}
+    }
+}
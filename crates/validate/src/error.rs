@@ -0,0 +1,536 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+use duramen_diagnostic::Diagnostic;
+use duramen_runtime::{EntityType, EntityUid, ExtensionError};
+
+use crate::types::Type;
+
+/// An error found while resolving the names in a schema.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum SchemaError {
+    /// A type name is not a common type, entity type, or built-in type.
+    UndefinedType {
+        name: String,
+        span: Option<Range<usize>>,
+    },
+    /// An entity type name is not declared.
+    UndefinedEntityType {
+        name: String,
+        span: Option<Range<usize>>,
+    },
+    /// A name that must be an entity type refers to a common or built-in type.
+    NotAnEntityType {
+        name: String,
+        span: Option<Range<usize>>,
+    },
+    /// An action group is not declared.
+    UndefinedAction {
+        uid: EntityUid,
+        span: Option<Range<usize>>,
+    },
+    /// A common type is defined in terms of itself.
+    CommonTypeCycle {
+        path: Vec<String>,
+        span: Option<Range<usize>>,
+    },
+    /// An action group contains itself.
+    ActionCycle {
+        path: Vec<EntityUid>,
+        span: Option<Range<usize>>,
+    },
+    /// An action context refers to a type that is not a record.
+    NonRecordContext {
+        name: String,
+        span: Option<Range<usize>>,
+    },
+    /// A value type names more than one entity type.
+    EntityTypeSet { span: Option<Range<usize>> },
+}
+
+impl SchemaError {
+    /// Returns the byte span of the declaration containing the error, if known.
+    #[must_use]
+    pub const fn span(&self) -> Option<&Range<usize>> {
+        match self {
+            Self::UndefinedType { span, .. }
+            | Self::UndefinedEntityType { span, .. }
+            | Self::NotAnEntityType { span, .. }
+            | Self::UndefinedAction { span, .. }
+            | Self::CommonTypeCycle { span, .. }
+            | Self::ActionCycle { span, .. }
+            | Self::NonRecordContext { span, .. }
+            | Self::EntityTypeSet { span } => span.as_ref(),
+        }
+    }
+}
+
+/// Writes a cycle like `` `A` -> `B` -> `A` ``.
+fn write_cycle<T: fmt::Display>(f: &mut fmt::Formatter<'_>, path: &[T]) -> fmt::Result {
+    for item in path {
+        write!(f, "`{item}` -> ")?;
+    }
+
+    match path.first() {
+        Some(first) => write!(f, "`{first}`"),
+        None => Ok(()),
+    }
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UndefinedType { name, .. } => write!(f, "undefined type `{name}`"),
+            Self::UndefinedEntityType { name, .. } => write!(f, "undefined entity type `{name}`"),
+            Self::NotAnEntityType { name, .. } => write!(f, "`{name}` is not an entity type"),
+            Self::UndefinedAction { uid, .. } => write!(f, "undefined action `{uid}`"),
+            Self::CommonTypeCycle { path, .. } => {
+                f.write_str("common type refers to itself: ")?;
+                write_cycle(f, path)
+            }
+            Self::ActionCycle { path, .. } => {
+                f.write_str("action group contains itself: ")?;
+                write_cycle(f, path)
+            }
+            Self::NonRecordContext { name, .. } => {
+                write!(f, "context type `{name}` is not a record")
+            }
+            Self::EntityTypeSet { .. } => {
+                f.write_str("a value type cannot name more than one entity type")
+            }
+        }
+    }
+}
+
+impl core::error::Error for SchemaError {}
+
+impl From<SchemaError> for Diagnostic {
+    fn from(value: SchemaError) -> Self {
+        let mut diagnostic = Self::error(format!("{value}"));
+
+        if let Some(span) = value.span() {
+            diagnostic = diagnostic.with_label(span.clone(), "in this declaration");
+        }
+
+        match value {
+            SchemaError::NotAnEntityType { .. } => {
+                diagnostic.with_note("`appliesTo` and `in` lists can only name entity types")
+            }
+            SchemaError::CommonTypeCycle { .. } => {
+                diagnostic.with_note("common types cannot be recursive")
+            }
+            SchemaError::EntityTypeSet { .. } => {
+                diagnostic.with_note("strict validation requires each value to have a single type")
+            }
+            _ => diagnostic,
+        }
+    }
+}
+
+/// An error found while validating a policy against a schema.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum ValidationError {
+    /// No request allowed by the schema matches the policy scope.
+    ImpossiblePolicy { span: Option<Range<usize>> },
+    /// An entity type is not declared in the schema.
+    UnknownEntityType {
+        name: String,
+        suggestion: Option<String>,
+        span: Option<Range<usize>>,
+    },
+    /// An action is not declared in the schema.
+    UnknownAction {
+        uid: EntityUid,
+        span: Option<Range<usize>>,
+    },
+    /// An entity of an enumerated type uses an undeclared id.
+    InvalidEnumId {
+        uid: EntityUid,
+        span: Option<Range<usize>>,
+    },
+    /// An attribute is not declared on the entity or record type.
+    UnknownAttribute {
+        ty: Type,
+        attribute: String,
+        suggestion: Option<String>,
+        span: Option<Range<usize>>,
+    },
+    /// An optional attribute is read without first checking `has`.
+    UnsafeAttributeAccess {
+        attribute: String,
+        span: Option<Range<usize>>,
+    },
+    /// A tag is read from an entity type that does not declare tags.
+    MissingTags {
+        kind: EntityType,
+        span: Option<Range<usize>>,
+    },
+    /// A tag is read without first checking `hasTag`.
+    UnsafeTagAccess { span: Option<Range<usize>> },
+    /// An operand has the wrong type.
+    UnexpectedType {
+        expected: &'static str,
+        found: Type,
+        span: Option<Range<usize>>,
+    },
+    /// Two operands must have the same type, but do not.
+    IncompatibleTypes {
+        left: Type,
+        right: Type,
+        span: Option<Range<usize>>,
+    },
+    /// The elements of a set literal have different types.
+    HeterogeneousSet { span: Option<Range<usize>> },
+    /// A set literal is empty, so its element type is unknown.
+    EmptySet { span: Option<Range<usize>> },
+    /// The extension function is not known.
+    UnknownFunction {
+        name: String,
+        span: Option<Range<usize>>,
+    },
+    /// An extension function was called with the wrong number of arguments.
+    WrongArity {
+        function: String,
+        expected: usize,
+        found: usize,
+        span: Option<Range<usize>>,
+    },
+    /// An extension constructor received an invalid literal.
+    InvalidExtension {
+        error: ExtensionError,
+        span: Option<Range<usize>>,
+    },
+}
+
+impl ValidationError {
+    /// Returns the byte span of the offending source, if known.
+    #[must_use]
+    pub const fn span(&self) -> Option<&Range<usize>> {
+        match self {
+            Self::ImpossiblePolicy { span }
+            | Self::UnknownEntityType { span, .. }
+            | Self::UnknownAction { span, .. }
+            | Self::InvalidEnumId { span, .. }
+            | Self::UnknownAttribute { span, .. }
+            | Self::UnsafeAttributeAccess { span, .. }
+            | Self::MissingTags { span, .. }
+            | Self::UnsafeTagAccess { span }
+            | Self::UnexpectedType { span, .. }
+            | Self::IncompatibleTypes { span, .. }
+            | Self::HeterogeneousSet { span }
+            | Self::EmptySet { span }
+            | Self::UnknownFunction { span, .. }
+            | Self::WrongArity { span, .. }
+            | Self::InvalidExtension { span, .. } => span.as_ref(),
+        }
+    }
+
+    /// Returns the short message used to label the span.
+    const fn label(&self) -> &'static str {
+        match self {
+            Self::ImpossiblePolicy { .. } => "impossible policy",
+            Self::UnknownEntityType { .. } => "unknown entity type",
+            Self::UnknownAction { .. } => "unknown action",
+            Self::InvalidEnumId { .. } => "undeclared enum id",
+            Self::UnknownAttribute { .. } => "unknown attribute",
+            Self::UnsafeAttributeAccess { .. } => "attribute may be missing",
+            Self::MissingTags { .. } => "no tags declared",
+            Self::UnsafeTagAccess { .. } => "tag may be missing",
+            Self::UnexpectedType { expected, .. } => expected,
+            Self::IncompatibleTypes { .. } => "incompatible types",
+            Self::HeterogeneousSet { .. } => "mixed element types",
+            Self::EmptySet { .. } => "empty set",
+            Self::UnknownFunction { .. } => "unknown function",
+            Self::WrongArity { .. } => "wrong number of arguments",
+            Self::InvalidExtension { .. } => "invalid extension value",
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ImpossiblePolicy { .. } => f.write_str("policy does not apply to any request"),
+            Self::UnknownEntityType { name, .. } => write!(f, "unknown entity type `{name}`"),
+            Self::UnknownAction { uid, .. } => write!(f, "unknown action `{uid}`"),
+            Self::InvalidEnumId { uid, .. } => {
+                write!(f, "`{uid}` is not a declared value of `{}`", uid.kind())
+            }
+            Self::UnknownAttribute { ty, attribute, .. } => {
+                write!(f, "`{ty}` does not have the attribute `{attribute}`")
+            }
+            Self::UnsafeAttributeAccess { attribute, .. } => {
+                write!(
+                    f,
+                    "attribute `{attribute}` is optional and may not be present"
+                )
+            }
+            Self::MissingTags { kind, .. } => write!(f, "entity type `{kind}` does not have tags"),
+            Self::UnsafeTagAccess { .. } => f.write_str("tag may not be present"),
+            Self::UnexpectedType {
+                expected, found, ..
+            } => write!(f, "type error: expected {expected}, found `{found}`"),
+            Self::IncompatibleTypes { left, right, .. } => {
+                write!(f, "type error: `{left}` and `{right}` are not compatible")
+            }
+            Self::HeterogeneousSet { .. } => {
+                f.write_str("set elements must all have the same type")
+            }
+            Self::EmptySet { .. } => f.write_str("empty set literals are not allowed"),
+            Self::UnknownFunction { name, .. } => {
+                write!(f, "`{name}` is not a known extension function")
+            }
+            Self::WrongArity {
+                function,
+                expected,
+                found,
+                ..
+            } => write!(
+                f,
+                "`{function}` expects {expected} argument(s), found {found}"
+            ),
+            Self::InvalidExtension { error, .. } => error.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for ValidationError {}
+
+impl From<ValidationError> for Diagnostic {
+    fn from(value: ValidationError) -> Self {
+        let mut diagnostic = match &value {
+            ValidationError::InvalidExtension { error, .. } => Self::from(error.clone()),
+            ValidationError::ImpossiblePolicy { .. } => Self::error(format!("{value}"))
+                .with_note("no action in the schema applies to the principal and resource types"),
+            ValidationError::UnknownEntityType {
+                suggestion: Some(suggestion),
+                ..
+            }
+            | ValidationError::UnknownAttribute {
+                suggestion: Some(suggestion),
+                ..
+            } => Self::error(format!("{value}")).with_note(format!("did you mean `{suggestion}`?")),
+            ValidationError::UnsafeAttributeAccess { .. } => Self::error(format!("{value}"))
+                .with_note("use `has` to check for optional attributes before accessing them"),
+            ValidationError::UnsafeTagAccess { .. } => Self::error(format!("{value}"))
+                .with_note("use `hasTag` to check for a tag before accessing it"),
+            ValidationError::EmptySet { .. } => Self::error(format!("{value}"))
+                .with_note("strict validation cannot infer the element type of `[]`"),
+            _ => Self::error(format!("{value}")),
+        };
+
+        if let Some(span) = value.span() {
+            diagnostic = diagnostic.with_label(span.clone(), value.label());
+        }
+
+        diagnostic
+    }
+}
+
+/// A runtime value that does not conform to its declared type.
+///
+/// The `path` locates the offending value, like `context.address.zip`.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum ConformanceError {
+    /// A required attribute is missing.
+    MissingAttribute { path: String },
+    /// An attribute is not declared in the schema.
+    UndeclaredAttribute { path: String },
+    /// A value has the wrong type.
+    UnexpectedType {
+        path: String,
+        expected: Type,
+        found: String,
+    },
+    /// An entity id is not one of the values of an enumerated entity type.
+    InvalidEnumId { path: String, uid: EntityUid },
+}
+
+impl fmt::Display for ConformanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingAttribute { path } => write!(f, "missing required attribute `{path}`"),
+            Self::UndeclaredAttribute { path } => write!(f, "undeclared attribute `{path}`"),
+            Self::UnexpectedType {
+                path,
+                expected,
+                found,
+            } => write!(f, "`{path}` should be `{expected}`, found `{found}`"),
+            Self::InvalidEnumId { path, uid } => write!(
+                f,
+                "`{path}` is `{uid}`, which is not a declared value of `{}`",
+                uid.kind()
+            ),
+        }
+    }
+}
+
+impl core::error::Error for ConformanceError {}
+
+impl From<ConformanceError> for Diagnostic {
+    fn from(value: ConformanceError) -> Self {
+        Self::error(format!("{value}"))
+    }
+}
+
+/// An error found while validating an authorization request against a schema.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum RequestError {
+    /// The action is not declared.
+    UnknownAction { uid: EntityUid },
+    /// The action does not apply to the principal's entity type.
+    InvalidPrincipal {
+        action: EntityUid,
+        kind: EntityType,
+        expected: Vec<EntityType>,
+    },
+    /// The action does not apply to the resource's entity type.
+    InvalidResource {
+        action: EntityUid,
+        kind: EntityType,
+        expected: Vec<EntityType>,
+    },
+    /// The principal or resource is not a declared value of its enumerated entity type.
+    InvalidEnumId { uid: EntityUid },
+    /// The context does not conform to the action's context type.
+    InvalidContext { error: ConformanceError },
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownAction { uid } => write!(f, "unknown action `{uid}`"),
+            Self::InvalidPrincipal { action, kind, .. } => {
+                write!(
+                    f,
+                    "action `{action}` does not apply to principals of type `{kind}`"
+                )
+            }
+            Self::InvalidResource { action, kind, .. } => {
+                write!(
+                    f,
+                    "action `{action}` does not apply to resources of type `{kind}`"
+                )
+            }
+            Self::InvalidEnumId { uid } => {
+                write!(f, "`{uid}` is not a declared value of `{}`", uid.kind())
+            }
+            Self::InvalidContext { error } => write!(f, "invalid context: {error}"),
+        }
+    }
+}
+
+impl core::error::Error for RequestError {}
+
+/// Formats entity types as a list like `` `A`, `B`, or `C` ``.
+struct Expected<'a>(&'a [EntityType]);
+
+impl fmt::Display for Expected<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let last = self.0.len().saturating_sub(1);
+
+        for (index, kind) in self.0.iter().enumerate() {
+            match index {
+                0 => {}
+                1 if last == 1 => f.write_str(" or ")?,
+                _ if index == last => f.write_str(", or ")?,
+                _ => f.write_str(", ")?,
+            }
+
+            write!(f, "`{kind}`")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<RequestError> for Diagnostic {
+    fn from(value: RequestError) -> Self {
+        let diagnostic = Self::error(format!("{value}"));
+
+        match &value {
+            RequestError::InvalidPrincipal { expected, .. }
+            | RequestError::InvalidResource { expected, .. }
+                if expected.is_empty() =>
+            {
+                diagnostic.with_note("the action has no `appliesTo` declaration")
+            }
+            RequestError::InvalidPrincipal { expected, .. }
+            | RequestError::InvalidResource { expected, .. } => {
+                diagnostic.with_note(format!("expected {}", Expected(expected)))
+            }
+            _ => diagnostic,
+        }
+    }
+}
+
+/// An entity that does not conform to its schema declaration.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum EntityConformanceError {
+    /// The entity type is not declared.
+    UnknownEntityType { uid: EntityUid },
+    /// An action entity is not declared.
+    UnknownAction { uid: EntityUid },
+    /// The entity is not a declared value of its enumerated entity type.
+    InvalidEnumId { uid: EntityUid },
+    /// The entity type may not be a member of the parent's entity type.
+    InvalidParent { uid: EntityUid, parent: EntityUid },
+    /// An attribute does not conform to its declared type.
+    InvalidAttribute {
+        uid: EntityUid,
+        error: ConformanceError,
+    },
+    /// The entity has tags, but its entity type does not declare them.
+    UnexpectedTags { uid: EntityUid },
+    /// A tag value does not conform to the declared tag type.
+    InvalidTag {
+        uid: EntityUid,
+        error: ConformanceError,
+    },
+}
+
+impl fmt::Display for EntityConformanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownEntityType { uid } => {
+                write!(f, "`{uid}` has undeclared entity type `{}`", uid.kind())
+            }
+            Self::UnknownAction { uid } => write!(f, "unknown action `{uid}`"),
+            Self::InvalidEnumId { uid } => {
+                write!(f, "`{uid}` is not a declared value of `{}`", uid.kind())
+            }
+            Self::InvalidParent { uid, parent } => {
+                write!(f, "`{uid}` cannot be a member of `{parent}`")
+            }
+            Self::InvalidAttribute { uid, error } => {
+                write!(f, "invalid attribute on `{uid}`: {error}")
+            }
+            Self::UnexpectedTags { uid } => write!(
+                f,
+                "`{uid}` has tags, but `{}` does not declare any",
+                uid.kind()
+            ),
+            Self::InvalidTag { uid, error } => write!(f, "invalid tag on `{uid}`: {error}"),
+        }
+    }
+}
+
+impl core::error::Error for EntityConformanceError {}
+
+impl From<EntityConformanceError> for Diagnostic {
+    fn from(value: EntityConformanceError) -> Self {
+        match &value {
+            EntityConformanceError::InvalidParent { uid, parent } => {
+                Self::error(format!("{value}")).with_note(format!(
+                    "`{}` is not declared `in` `{}`",
+                    uid.kind(),
+                    parent.kind()
+                ))
+            }
+            _ => Self::error(format!("{value}")),
+        }
+    }
+}
@@ -1,5 +1,29 @@
 #![cfg_attr(doc, doc = include_str!("../README.md"))]
 #![no_std]
+extern crate alloc;
 
 #[cfg(feature = "std")]
 extern crate std;
+
+pub(crate) use rustc_hash::FxBuildHasher;
+
+pub(crate) type IndexMap<K, V> = indexmap::IndexMap<K, V, FxBuildHasher>;
+pub(crate) type IndexSet<T> = indexmap::IndexSet<T, FxBuildHasher>;
+
+mod conformance;
+
+mod error;
+pub use error::{
+    ConformanceError, EntityConformanceError, RequestError, SchemaError, ValidationError,
+};
+
+mod schema;
+pub use schema::{ActionSchema, EntitySchema, ValidatorSchema};
+
+mod typechecker;
+
+mod types;
+pub use types::{AttributeType, RecordType, Type};
+
+mod validator;
+pub use validator::Validator;
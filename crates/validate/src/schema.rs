@@ -0,0 +1,309 @@
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use duramen_ast as ast;
+use duramen_diagnostic::Diagnostics;
+use duramen_runtime::{EntityType, EntityUid, Hint, TypeHints};
+
+use crate::error::SchemaError;
+use crate::types::{AttributeType, RecordType, Type};
+use crate::{IndexMap, IndexSet};
+
+mod action_schema;
+pub use action_schema::ActionSchema;
+
+mod entity_schema;
+pub use entity_schema::EntitySchema;
+
+mod resolver;
+use resolver::{Resolver, qualify};
+
+/// A schema with every type name resolved to its fully qualified form, and every common type
+/// expanded to its definition.
+#[derive(Clone, Debug, Default)]
+pub struct ValidatorSchema {
+    common_types: IndexMap<String, Type>,
+    entities: IndexMap<EntityType, EntitySchema>,
+    actions: IndexMap<EntityUid, ActionSchema>,
+}
+
+impl ValidatorSchema {
+    /// Resolves the entity types and actions declared in a schema.
+    ///
+    /// # Errors
+    ///
+    /// Returns diagnostics if a type name is undefined, a common type or action group refers to
+    /// itself, or an `appliesTo` or `in` list names a type that is not an entity type.
+    pub fn new(schema: &ast::Schema<'_>) -> Result<Self, Diagnostics> {
+        let mut names = Resolver::new(schema);
+        let common_types = names.expand_common_types();
+
+        let mut entities = IndexMap::default();
+        let mut actions = IndexMap::default();
+        let mut spans = IndexMap::default();
+
+        for namespace in schema.namespaces() {
+            let prefix = namespace.name().map(ToString::to_string);
+            let prefix = prefix.as_deref();
+
+            for declaration in namespace.declarations() {
+                match declaration {
+                    ast::Declaration::Entity(entity) => {
+                        names.set_span(entity.span());
+                        let resolved = names.entity(entity.kind(), prefix);
+
+                        for name in entity.names() {
+                            if let Some(kind) = qualify(prefix, name.as_str()) {
+                                entities.insert(kind, resolved.clone());
+                            }
+                        }
+                    }
+                    ast::Declaration::Action(action) => {
+                        let Some(kind) = qualify(prefix, "Action") else {
+                            continue;
+                        };
+
+                        names.set_span(action.span());
+                        let resolved = names.action(action, prefix);
+
+                        for name in action.names() {
+                            let uid = EntityUid::new(kind.clone(), Arc::from(name));
+                            spans.insert(uid.clone(), action.span());
+                            actions.insert(uid, resolved.clone());
+                        }
+                    }
+                    ast::Declaration::Type(_) => {}
+                }
+            }
+        }
+
+        let resolved = Self {
+            common_types,
+            entities,
+            actions,
+        };
+
+        let mut errors = names.into_errors();
+        resolved.check_actions(&spans, &mut errors);
+
+        if errors.is_empty() {
+            return Ok(resolved);
+        }
+
+        let mut diagnostics = Diagnostics::new();
+        for error in errors {
+            diagnostics.push(error);
+        }
+
+        Err(diagnostics)
+    }
+
+    /// Checks that action groups are declared and do not contain themselves.
+    fn check_actions(
+        &self,
+        spans: &IndexMap<EntityUid, Option<Range<usize>>>,
+        errors: &mut Vec<SchemaError>,
+    ) {
+        for (index, (uid, action)) in self.actions.iter().enumerate() {
+            let span = spans.get(uid).cloned().flatten();
+
+            for parent in action.parents() {
+                let error = SchemaError::UndefinedAction {
+                    uid: parent.clone(),
+                    span: span.clone(),
+                };
+
+                if !self.actions.contains_key(parent) && !errors.contains(&error) {
+                    errors.push(error);
+                }
+            }
+
+            if let Some(cycle) = self.action_cycle(index)
+                && cycle.iter().all(|&member| member >= index)
+            {
+                let path = cycle
+                    .iter()
+                    .filter_map(|&member| self.actions.get_index(member))
+                    .map(|(uid, _)| uid.clone())
+                    .collect();
+
+                errors.push(SchemaError::ActionCycle { path, span });
+            }
+        }
+    }
+
+    /// Returns a path of action groups from `start` back to itself, if there is one.
+    fn action_cycle(&self, start: usize) -> Option<Vec<usize>> {
+        let mut path = vec![start];
+        let mut seen = IndexSet::default();
+
+        self.find_action_path(start, start, &mut path, &mut seen)
+            .then_some(path)
+    }
+
+    fn find_action_path(
+        &self,
+        from: usize,
+        to: usize,
+        path: &mut Vec<usize>,
+        seen: &mut IndexSet<usize>,
+    ) -> bool {
+        let Some((_, action)) = self.actions.get_index(from) else {
+            return false;
+        };
+
+        for parent in action.parents() {
+            let Some(next) = self.actions.get_index_of(parent) else {
+                continue;
+            };
+
+            if next == to {
+                return true;
+            }
+
+            if seen.insert(next) {
+                path.push(next);
+                if self.find_action_path(next, to, path, seen) {
+                    return true;
+                }
+
+                path.pop();
+            }
+        }
+
+        false
+    }
+
+    /// Returns the expanded definition of a common type, by its fully qualified name.
+    #[must_use]
+    pub fn common_type(&self, name: &str) -> Option<&Type> {
+        self.common_types.get(name)
+    }
+
+    /// Returns an iterator over the common types and their expanded definitions.
+    pub fn common_types(&self) -> impl Iterator<Item = (&str, &Type)> {
+        self.common_types
+            .iter()
+            .map(|(name, ty)| (name.as_str(), ty))
+    }
+
+    /// Returns the declaration of an entity type.
+    #[must_use]
+    pub fn entity(&self, kind: &EntityType) -> Option<&EntitySchema> {
+        self.entities.get(kind)
+    }
+
+    /// Returns an iterator over the declared entity types.
+    pub fn entities(&self) -> impl Iterator<Item = (&EntityType, &EntitySchema)> {
+        self.entities.iter()
+    }
+
+    /// Returns the attributes of an entity type.
+    #[must_use]
+    pub fn attributes(&self, kind: &EntityType) -> Option<&RecordType> {
+        self.entity(kind).map(EntitySchema::attributes)
+    }
+
+    /// Returns the declaration of an action.
+    #[must_use]
+    pub fn action(&self, uid: &EntityUid) -> Option<&ActionSchema> {
+        self.actions.get(uid)
+    }
+
+    /// Returns an iterator over the declared actions.
+    pub fn actions(&self) -> impl Iterator<Item = (&EntityUid, &ActionSchema)> {
+        self.actions.iter()
+    }
+
+    /// Returns the context type of an action.
+    #[must_use]
+    pub fn context(&self, uid: &EntityUid) -> Option<&RecordType> {
+        self.action(uid).map(ActionSchema::context)
+    }
+
+    /// Returns whether `kind` is the action entity type of some namespace.
+    #[must_use]
+    pub fn is_action_type(&self, kind: &EntityType) -> bool {
+        self.actions.keys().any(|uid| uid.kind() == kind)
+    }
+
+    /// Returns whether entities of type `kind` may be `in` an entity of type `ancestor`.
+    #[must_use]
+    pub fn is_descendant_type(&self, kind: &EntityType, ancestor: &EntityType) -> bool {
+        let mut stack = vec![kind];
+        let mut seen = IndexSet::default();
+
+        while let Some(current) = stack.pop() {
+            if current == ancestor {
+                return true;
+            }
+
+            if seen.insert(current)
+                && let Some(entity) = self.entity(current)
+            {
+                stack.extend(entity.parents());
+            }
+        }
+
+        false
+    }
+
+    /// Returns whether `uid` is `ancestor`, or a member of it through action groups.
+    #[must_use]
+    pub fn is_descendant_action(&self, uid: &EntityUid, ancestor: &EntityUid) -> bool {
+        let mut stack = vec![uid];
+        let mut seen = IndexSet::default();
+
+        while let Some(current) = stack.pop() {
+            if current == ancestor {
+                return true;
+            }
+
+            if seen.insert(current)
+                && let Some(action) = self.action(current)
+            {
+                stack.extend(action.parents());
+            }
+        }
+
+        false
+    }
+}
+
+/// Decodes entity and context JSON with the types declared in the schema.
+impl TypeHints for ValidatorSchema {
+    type Record = RecordType;
+    type Type = Type;
+
+    fn attribute_types(&self, kind: &EntityType) -> Option<&RecordType> {
+        self.attributes(kind)
+    }
+
+    fn tag_type(&self, kind: &EntityType) -> Option<&Type> {
+        self.entity(kind).and_then(EntitySchema::tags)
+    }
+
+    fn context_type(&self, action: &EntityUid) -> Option<&RecordType> {
+        self.context(action)
+    }
+
+    fn attribute_type<'a>(&'a self, record: &'a RecordType, name: &str) -> Option<&'a Type> {
+        record.get(name).map(AttributeType::ty)
+    }
+
+    fn hint<'a>(&'a self, ty: &'a Type) -> Hint<'a, Type, RecordType> {
+        match ty {
+            Type::Entity(_) => Hint::Entity,
+            Type::Decimal => Hint::Extension("decimal"),
+            Type::IpAddress => Hint::Extension("ip"),
+            Type::Datetime => Hint::Extension("datetime"),
+            Type::Duration => Hint::Extension("duration"),
+            Type::Set(element) => Hint::Set(element),
+            Type::Record(record) => Hint::Record(record),
+            Type::Bool | Type::Long | Type::String => Hint::Other,
+        }
+    }
+}
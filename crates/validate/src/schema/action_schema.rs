@@ -0,0 +1,56 @@
+use alloc::vec::Vec;
+
+use duramen_runtime::{EntityType, EntityUid};
+
+use crate::types::RecordType;
+
+/// A resolved action declaration.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct ActionSchema {
+    principals: Vec<EntityType>,
+    resources: Vec<EntityType>,
+    context: RecordType,
+    parents: Vec<EntityUid>,
+}
+
+impl ActionSchema {
+    /// Creates an action.
+    #[must_use]
+    pub const fn new(
+        principals: Vec<EntityType>,
+        resources: Vec<EntityType>,
+        context: RecordType,
+        parents: Vec<EntityUid>,
+    ) -> Self {
+        Self {
+            principals,
+            resources,
+            context,
+            parents,
+        }
+    }
+
+    /// Returns the principal types the action applies to.
+    #[must_use]
+    pub fn principals(&self) -> &[EntityType] {
+        &self.principals
+    }
+
+    /// Returns the resource types the action applies to.
+    #[must_use]
+    pub fn resources(&self) -> &[EntityType] {
+        &self.resources
+    }
+
+    /// Returns the type of the request context.
+    #[must_use]
+    pub const fn context(&self) -> &RecordType {
+        &self.context
+    }
+
+    /// Returns the action groups this action is a member of.
+    #[must_use]
+    pub fn parents(&self) -> &[EntityUid] {
+        &self.parents
+    }
+}
@@ -0,0 +1,61 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use duramen_runtime::EntityType;
+
+use crate::types::{RecordType, Type};
+
+/// A resolved entity type declaration.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct EntitySchema {
+    attributes: RecordType,
+    parents: Vec<EntityType>,
+    tags: Option<Type>,
+    ids: Option<Vec<String>>,
+}
+
+impl EntitySchema {
+    /// Creates a standard entity type.
+    #[must_use]
+    pub const fn new(attributes: RecordType, parents: Vec<EntityType>, tags: Option<Type>) -> Self {
+        Self {
+            attributes,
+            parents,
+            tags,
+            ids: None,
+        }
+    }
+
+    /// Creates an enumerated entity type with a fixed set of ids.
+    #[must_use]
+    pub fn enumerated(ids: Vec<String>) -> Self {
+        Self {
+            ids: Some(ids),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the entity attributes.
+    #[must_use]
+    pub const fn attributes(&self) -> &RecordType {
+        &self.attributes
+    }
+
+    /// Returns the entity types that entities of this type may be members of.
+    #[must_use]
+    pub fn parents(&self) -> &[EntityType] {
+        &self.parents
+    }
+
+    /// Returns the type of tag values, if the entity type has tags.
+    #[must_use]
+    pub const fn tags(&self) -> Option<&Type> {
+        self.tags.as_ref()
+    }
+
+    /// Returns the declared ids, if this is an enumerated entity type.
+    #[must_use]
+    pub fn ids(&self) -> Option<&[String]> {
+        self.ids.as_deref()
+    }
+}
@@ -0,0 +1,475 @@
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::mem;
+use core::ops::Range;
+
+use duramen_ast as ast;
+use duramen_runtime::{EntityType, EntityUid};
+
+use crate::error::SchemaError;
+use crate::schema::{ActionSchema, EntitySchema};
+use crate::types::{AttributeType, RecordType, Type};
+use crate::{IndexMap, IndexSet};
+
+/// Joins a namespace and a basename with `::`.
+pub fn join(namespace: Option<&str>, basename: &str) -> String {
+    match namespace {
+        Some(namespace) => format!("{namespace}::{basename}"),
+        None => String::from(basename),
+    }
+}
+
+/// Joins a namespace and a basename into an entity type.
+pub fn qualify(namespace: Option<&str>, basename: &str) -> Option<EntityType> {
+    EntityType::new(&join(namespace, basename)).ok()
+}
+
+/// Returns the primitive or extension type with the given name.
+fn builtin(name: &str) -> Option<Type> {
+    let name = name.strip_prefix("__cedar::").unwrap_or(name);
+
+    match name {
+        "Bool" => Some(Type::Bool),
+        "Long" => Some(Type::Long),
+        "String" => Some(Type::String),
+        "decimal" => Some(Type::Decimal),
+        "ipaddr" => Some(Type::IpAddress),
+        "datetime" => Some(Type::Datetime),
+        "duration" => Some(Type::Duration),
+        _ => None,
+    }
+}
+
+/// What a type name in the schema refers to.
+enum Resolved {
+    Common(String),
+    Entity(String),
+    Builtin(Type),
+}
+
+/// A common type declaration, with the namespace its names are resolved in.
+struct CommonType<'s, 'a> {
+    namespace: Option<String>,
+    definition: &'s ast::TypeExpression<'a>,
+    span: Option<Range<usize>>,
+}
+
+/// Looks up type names using Cedar's rules: the current namespace, then the empty namespace,
+/// then the built-in types.
+pub struct Resolver<'s, 'a> {
+    common: IndexMap<String, CommonType<'s, 'a>>,
+    entities: IndexSet<String>,
+    /// Common types that have already been expanded.
+    expanded: IndexMap<String, Type>,
+    /// The declaration being resolved, used to locate errors.
+    span: Option<Range<usize>>,
+    /// Set while expanding a common type, whose errors are reported at its own declaration.
+    quiet: bool,
+    errors: Vec<SchemaError>,
+}
+
+impl<'s, 'a> Resolver<'s, 'a> {
+    #[must_use]
+    pub fn new(schema: &'s ast::Schema<'a>) -> Self {
+        let mut common = IndexMap::default();
+        let mut entities = IndexSet::default();
+
+        for namespace in schema.namespaces() {
+            let prefix = namespace.name().map(ToString::to_string);
+
+            for declaration in namespace.declarations() {
+                match declaration {
+                    ast::Declaration::Type(declaration) => {
+                        let name = join(prefix.as_deref(), declaration.name().as_str());
+                        common.insert(
+                            name,
+                            CommonType {
+                                namespace: prefix.clone(),
+                                definition: declaration.definition(),
+                                span: declaration.span(),
+                            },
+                        );
+                    }
+                    ast::Declaration::Entity(declaration) => {
+                        for name in declaration.names() {
+                            entities.insert(join(prefix.as_deref(), name.as_str()));
+                        }
+                    }
+                    ast::Declaration::Action(_) => {}
+                }
+            }
+        }
+
+        Self {
+            common,
+            entities,
+            expanded: IndexMap::default(),
+            span: None,
+            quiet: false,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Sets the span of the declaration being resolved.
+    pub const fn set_span(&mut self, span: Option<Range<usize>>) {
+        self.span = span;
+    }
+
+    /// Consumes the resolver, returning the errors found.
+    #[must_use]
+    pub fn into_errors(self) -> Vec<SchemaError> {
+        self.errors
+    }
+
+    fn error(&mut self, error: SchemaError) {
+        if !self.quiet && !self.errors.contains(&error) {
+            self.errors.push(error);
+        }
+    }
+
+    /// Expands every common type definition, checking for undefined names and cycles.
+    ///
+    /// Returns the expanded types of the common types that are not part of a cycle.
+    pub fn expand_common_types(&mut self) -> IndexMap<String, Type> {
+        for index in 0..self.common.len() {
+            let Some((key, common)) = self.common.get_index(index) else {
+                continue;
+            };
+
+            let (key, namespace, definition) =
+                (key.clone(), common.namespace.clone(), common.definition);
+            self.span = common.span.clone();
+
+            let ty = self.ty(definition, namespace.as_deref(), &mut vec![key.clone()]);
+
+            match self.cycle(index) {
+                Some(cycle) if cycle.iter().all(|&member| member >= index) => {
+                    let path = cycle
+                        .iter()
+                        .filter_map(|&member| self.common.get_index(member))
+                        .map(|(name, _)| name.clone())
+                        .collect();
+
+                    self.error(SchemaError::CommonTypeCycle {
+                        path,
+                        span: self.span.clone(),
+                    });
+                }
+                Some(_) => {}
+                None => {
+                    if let Some(ty) = ty {
+                        self.expanded.insert(key, ty);
+                    }
+                }
+            }
+        }
+
+        self.expanded.clone()
+    }
+
+    /// Returns a path of common types from `start` back to itself, if there is one.
+    fn cycle(&self, start: usize) -> Option<Vec<usize>> {
+        let mut path = vec![start];
+        let mut seen = IndexSet::default();
+
+        self.find_path(start, start, &mut path, &mut seen)
+            .then_some(path)
+    }
+
+    fn find_path(
+        &self,
+        from: usize,
+        to: usize,
+        path: &mut Vec<usize>,
+        seen: &mut IndexSet<usize>,
+    ) -> bool {
+        let Some((_, common)) = self.common.get_index(from) else {
+            return false;
+        };
+
+        let mut dependencies = Vec::new();
+        self.dependencies(
+            common.definition,
+            common.namespace.as_deref(),
+            &mut dependencies,
+        );
+
+        for next in dependencies {
+            if next == to {
+                return true;
+            }
+
+            if seen.insert(next) {
+                path.push(next);
+                if self.find_path(next, to, path, seen) {
+                    return true;
+                }
+
+                path.pop();
+            }
+        }
+
+        false
+    }
+
+    /// Collects the common types a type expression refers to directly.
+    fn dependencies(
+        &self,
+        expression: &ast::TypeExpression<'_>,
+        namespace: Option<&str>,
+        dependencies: &mut Vec<usize>,
+    ) {
+        match expression {
+            ast::TypeExpression::Reference(name) => {
+                if let Some(Resolved::Common(key)) = self.resolve(name, namespace)
+                    && let Some(index) = self.common.get_index_of(&key)
+                {
+                    dependencies.push(index);
+                }
+            }
+            ast::TypeExpression::Set(element) => {
+                self.dependencies(element, namespace, dependencies);
+            }
+            ast::TypeExpression::Record(record) => {
+                for (_, attribute) in record.attributes() {
+                    self.dependencies(attribute.definition(), namespace, dependencies);
+                }
+            }
+            ast::TypeExpression::Entity(_) | ast::TypeExpression::Enum(_) => {}
+        }
+    }
+
+    /// Returns the fully qualified names `name` may refer to, in lookup order.
+    fn candidates(name: &ast::Name<'_>, namespace: Option<&str>) -> Vec<String> {
+        if !name.path().is_empty() {
+            return vec![name.to_string()];
+        }
+
+        let basename = name.basename().as_str();
+        match namespace {
+            Some(_) => vec![join(namespace, basename), String::from(basename)],
+            None => vec![String::from(basename)],
+        }
+    }
+
+    fn resolve(&self, name: &ast::Name<'_>, namespace: Option<&str>) -> Option<Resolved> {
+        for candidate in Self::candidates(name, namespace) {
+            if self.common.contains_key(&candidate) {
+                return Some(Resolved::Common(candidate));
+            }
+
+            if self.entities.contains(&candidate) {
+                return Some(Resolved::Entity(candidate));
+            }
+        }
+
+        builtin(&name.to_string()).map(Resolved::Builtin)
+    }
+
+    /// Resolves a name that must refer to an entity type.
+    pub fn entity_type(
+        &mut self,
+        name: &ast::Name<'_>,
+        namespace: Option<&str>,
+    ) -> Option<EntityType> {
+        match self.resolve(name, namespace) {
+            Some(Resolved::Entity(key)) => EntityType::new(&key).ok(),
+            Some(Resolved::Common(_) | Resolved::Builtin(_)) => {
+                self.error(SchemaError::NotAnEntityType {
+                    name: name.to_string(),
+                    span: self.span.clone(),
+                });
+
+                None
+            }
+            None => {
+                self.error(SchemaError::UndefinedEntityType {
+                    name: name.to_string(),
+                    span: self.span.clone(),
+                });
+
+                None
+            }
+        }
+    }
+
+    /// Resolves a type name, expanding common types.
+    ///
+    /// Returns `None` if the name is undefined, or part of a common type cycle.
+    fn reference(
+        &mut self,
+        name: &ast::Name<'_>,
+        namespace: Option<&str>,
+        visiting: &mut Vec<String>,
+    ) -> Option<Type> {
+        match self.resolve(name, namespace) {
+            Some(Resolved::Common(key)) => {
+                if let Some(ty) = self.expanded.get(&key) {
+                    return Some(ty.clone());
+                }
+
+                if visiting.contains(&key) {
+                    return None;
+                }
+
+                let common = self.common.get(&key)?;
+                let (scope, definition) = (common.namespace.clone(), common.definition);
+
+                visiting.push(key);
+                let quiet = mem::replace(&mut self.quiet, true);
+                let ty = self.ty(definition, scope.as_deref(), visiting);
+                self.quiet = quiet;
+                visiting.pop();
+
+                ty
+            }
+            Some(Resolved::Entity(key)) => EntityType::new(&key).ok().map(Type::Entity),
+            Some(Resolved::Builtin(ty)) => Some(ty),
+            None => {
+                self.error(SchemaError::UndefinedType {
+                    name: name.to_string(),
+                    span: self.span.clone(),
+                });
+
+                None
+            }
+        }
+    }
+
+    fn ty(
+        &mut self,
+        expression: &ast::TypeExpression<'_>,
+        namespace: Option<&str>,
+        visiting: &mut Vec<String>,
+    ) -> Option<Type> {
+        match expression {
+            ast::TypeExpression::Reference(name) => self.reference(name, namespace, visiting),
+            ast::TypeExpression::Set(element) => {
+                self.ty(element, namespace, visiting).map(Type::set)
+            }
+            ast::TypeExpression::Record(record) => Some(Type::Record(self.record(
+                record.attributes(),
+                namespace,
+                visiting,
+            ))),
+            ast::TypeExpression::Entity(types) => {
+                let mut types = types.iter();
+
+                if let (Some(name), None) = (types.next(), types.next()) {
+                    return self.entity_type(name, namespace).map(Type::Entity);
+                }
+
+                // Strict validation has no union types for values of several entity types.
+                self.error(SchemaError::EntityTypeSet {
+                    span: self.span.clone(),
+                });
+
+                None
+            }
+            ast::TypeExpression::Enum(_) => Some(Type::String),
+        }
+    }
+
+    fn record<'d, 'e, I>(
+        &mut self,
+        attributes: I,
+        namespace: Option<&str>,
+        visiting: &mut Vec<String>,
+    ) -> RecordType
+    where
+        'e: 'd,
+        I: IntoIterator<Item = (&'d str, &'d ast::AttributeDeclaration<'e>)>,
+    {
+        let mut record = RecordType::empty();
+
+        for (name, attribute) in attributes {
+            if let Some(ty) = self.ty(attribute.definition(), namespace, visiting) {
+                record.insert(name, AttributeType::new(ty, attribute.optionality()));
+            }
+        }
+
+        record
+    }
+
+    /// Resolves an entity type declaration.
+    pub fn entity(&mut self, kind: &ast::EntityKind<'_>, namespace: Option<&str>) -> EntitySchema {
+        match kind {
+            ast::EntityKind::Standard(entity) => {
+                let attributes = self.record(entity.attributes(), namespace, &mut Vec::new());
+
+                let parents = entity
+                    .parents()
+                    .filter_map(|parent| self.entity_type(parent, namespace))
+                    .collect();
+
+                let tags = entity
+                    .tags()
+                    .and_then(|tags| self.ty(tags, namespace, &mut Vec::new()));
+
+                EntitySchema::new(attributes, parents, tags)
+            }
+            ast::EntityKind::Enum(ids) => {
+                EntitySchema::enumerated(ids.variants().map(String::from).collect())
+            }
+        }
+    }
+
+    /// Resolves an action declaration.
+    pub fn action(
+        &mut self,
+        action: &ast::ActionDeclaration<'_>,
+        namespace: Option<&str>,
+    ) -> ActionSchema {
+        let parents = action
+            .parents()
+            .filter_map(|parent| {
+                let kind = match parent.kind() {
+                    Some(kind) if !kind.path().is_empty() => EntityType::from(kind),
+                    Some(kind) => qualify(namespace, kind.basename().as_str())?,
+                    None => qualify(namespace, "Action")?,
+                };
+
+                Some(EntityUid::new(kind, Arc::from(parent.id())))
+            })
+            .collect();
+
+        let Some(applies_to) = action.applies_to() else {
+            return ActionSchema::new(Vec::new(), Vec::new(), RecordType::empty(), parents);
+        };
+
+        let principals = applies_to
+            .principals()
+            .filter_map(|name| self.entity_type(name, namespace))
+            .collect();
+
+        let resources = applies_to
+            .resources()
+            .filter_map(|name| self.entity_type(name, namespace))
+            .collect();
+
+        let context = match applies_to.context() {
+            Some(ast::ContextType::Record(record)) => {
+                self.record(record.attributes(), namespace, &mut Vec::new())
+            }
+            Some(ast::ContextType::Reference(name)) => {
+                match self.reference(name, namespace, &mut Vec::new()) {
+                    Some(Type::Record(record)) => record,
+                    Some(_) => {
+                        self.error(SchemaError::NonRecordContext {
+                            name: name.to_string(),
+                            span: self.span.clone(),
+                        });
+
+                        RecordType::empty()
+                    }
+                    None => RecordType::empty(),
+                }
+            }
+            None => RecordType::empty(),
+        };
+
+        ActionSchema::new(principals, resources, context, parents)
+    }
+}
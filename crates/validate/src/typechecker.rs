@@ -0,0 +1,827 @@
+use alloc::string::{String, ToString as _};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use duramen_ast::{
+    BinaryOperator, EntityReference, Expression, ExpressionKind, Literal, Name, SlotKind,
+    UnaryOperator, Variable,
+};
+use duramen_runtime::{EntityType, EntityUid, Extension};
+use duramen_suggest::suggest;
+
+use crate::IndexSet;
+use crate::error::ValidationError;
+use crate::schema::{EntitySchema, ValidatorSchema};
+use crate::types::{AttributeType, RecordType, Type};
+
+/// Extension functions, with their parameter and return types.
+const SIGNATURES: &[(&str, &[Type], Type)] = &[
+    ("ip", &[Type::String], Type::IpAddress),
+    ("decimal", &[Type::String], Type::Decimal),
+    ("datetime", &[Type::String], Type::Datetime),
+    ("duration", &[Type::String], Type::Duration),
+    ("lessThan", &[Type::Decimal, Type::Decimal], Type::Bool),
+    (
+        "lessThanOrEqual",
+        &[Type::Decimal, Type::Decimal],
+        Type::Bool,
+    ),
+    ("greaterThan", &[Type::Decimal, Type::Decimal], Type::Bool),
+    (
+        "greaterThanOrEqual",
+        &[Type::Decimal, Type::Decimal],
+        Type::Bool,
+    ),
+    ("isIpv4", &[Type::IpAddress], Type::Bool),
+    ("isIpv6", &[Type::IpAddress], Type::Bool),
+    ("isLoopback", &[Type::IpAddress], Type::Bool),
+    ("isMulticast", &[Type::IpAddress], Type::Bool),
+    ("isInRange", &[Type::IpAddress, Type::IpAddress], Type::Bool),
+    ("offset", &[Type::Datetime, Type::Duration], Type::Datetime),
+    (
+        "durationSince",
+        &[Type::Datetime, Type::Datetime],
+        Type::Duration,
+    ),
+    ("toDate", &[Type::Datetime], Type::Datetime),
+    ("toTime", &[Type::Datetime], Type::Duration),
+    ("toMilliseconds", &[Type::Duration], Type::Long),
+    ("toSeconds", &[Type::Duration], Type::Long),
+    ("toMinutes", &[Type::Duration], Type::Long),
+    ("toHours", &[Type::Duration], Type::Long),
+    ("toDays", &[Type::Duration], Type::Long),
+];
+
+/// Types that support `<`, `<=`, `>`, and `>=`.
+const COMPARABLE: &[Type] = &[Type::Long, Type::Datetime, Type::Duration];
+
+/// A fact known to hold when an expression evaluates to `true`.
+///
+/// Expressions are keyed by their Cedar source, so `principal.manager has name` and
+/// `principal.manager.name` refer to the same base.
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+enum Capability {
+    Attribute(String, String),
+    Tag(String, String),
+}
+
+type Capabilities = IndexSet<Capability>;
+
+/// Returns the capabilities established when `expression` evaluates to `true`.
+fn effects(expression: &Expression<'_>) -> Capabilities {
+    match expression.kind() {
+        ExpressionKind::HasAttribute {
+            expression: base,
+            attribute,
+        } => Capabilities::from_iter([Capability::Attribute(
+            base.to_string(),
+            attribute.to_string(),
+        )]),
+        ExpressionKind::BinaryApp {
+            operator: BinaryOperator::HasTag,
+            left,
+            right,
+        } => Capabilities::from_iter([Capability::Tag(left.to_string(), right.to_string())]),
+        ExpressionKind::And { left, right } => {
+            let mut capabilities = effects(left);
+            capabilities.extend(effects(right));
+            capabilities
+        }
+        ExpressionKind::Or { left, right } => effects(left)
+            .intersection(&effects(right))
+            .cloned()
+            .collect(),
+        ExpressionKind::If {
+            test,
+            consequent,
+            alternate,
+        } => {
+            let mut capabilities = effects(test);
+            capabilities.extend(effects(consequent));
+            capabilities
+                .intersection(&effects(alternate))
+                .cloned()
+                .collect()
+        }
+        _ => Capabilities::default(),
+    }
+}
+
+/// Describes a type in an error message.
+const fn describe(ty: &Type) -> &'static str {
+    match ty {
+        Type::Bool => "`Bool`",
+        Type::Long => "`Long`",
+        Type::String => "`String`",
+        Type::Entity(_) => "an entity",
+        Type::Set(_) => "a set",
+        Type::Record(_) => "a record",
+        Type::Decimal => "`decimal`",
+        Type::IpAddress => "`ipaddr`",
+        Type::Datetime => "`datetime`",
+        Type::Duration => "`duration`",
+    }
+}
+
+/// Returns whether two values may be compared with `==`.
+///
+/// Entities of different types are never equal, but comparing them is not an error.
+fn compatible(left: &Type, right: &Type) -> bool {
+    matches!((left, right), (Type::Entity(_), Type::Entity(_))) || left.lub(right).is_some()
+}
+
+/// Checks that an entity literal refers to a declared entity type, action, or enum id.
+pub fn check_entity(
+    schema: &ValidatorSchema,
+    uid: &EntityUid,
+    span: Option<Range<usize>>,
+) -> Option<ValidationError> {
+    if schema.is_action_type(uid.kind()) {
+        return schema
+            .action(uid)
+            .is_none()
+            .then(|| ValidationError::UnknownAction {
+                uid: uid.clone(),
+                span,
+            });
+    }
+
+    let Some(entity) = schema.entity(uid.kind()) else {
+        return check_entity_type(schema, uid.kind(), span);
+    };
+
+    match entity.ids() {
+        Some(ids) if !ids.iter().any(|id| id == uid.id()) => Some(ValidationError::InvalidEnumId {
+            uid: uid.clone(),
+            span,
+        }),
+        _ => None,
+    }
+}
+
+/// Checks that an entity type is declared in the schema.
+pub fn check_entity_type(
+    schema: &ValidatorSchema,
+    kind: &EntityType,
+    span: Option<Range<usize>>,
+) -> Option<ValidationError> {
+    if schema.entity(kind).is_some() || schema.is_action_type(kind) {
+        return None;
+    }
+
+    let candidates: Vec<&str> = schema.entities().map(|(name, _)| name.as_str()).collect();
+
+    Some(ValidationError::UnknownEntityType {
+        name: kind.to_string(),
+        suggestion: suggest(kind.as_str(), &candidates).map(String::from),
+        span,
+    })
+}
+
+/// Typechecks policy conditions for a single request environment.
+pub struct Typechecker<'s> {
+    schema: &'s ValidatorSchema,
+    principal: &'s EntityType,
+    action: &'s EntityUid,
+    resource: &'s EntityType,
+    context: &'s RecordType,
+    errors: Vec<ValidationError>,
+}
+
+impl<'s> Typechecker<'s> {
+    /// Creates a typechecker for requests with the given principal type, action, and resource
+    /// type.
+    #[must_use]
+    pub const fn new(
+        schema: &'s ValidatorSchema,
+        principal: &'s EntityType,
+        action: &'s EntityUid,
+        resource: &'s EntityType,
+        context: &'s RecordType,
+    ) -> Self {
+        Self {
+            schema,
+            principal,
+            action,
+            resource,
+            context,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Checks that a policy condition is a boolean expression.
+    pub fn check_condition(&mut self, body: &Expression<'_>) {
+        self.expect(body, &Capabilities::default(), &Type::Bool);
+    }
+
+    /// Consumes the typechecker, returning the errors found.
+    #[must_use]
+    pub fn into_errors(self) -> Vec<ValidationError> {
+        self.errors
+    }
+
+    fn error(&mut self, error: ValidationError) {
+        if !self.errors.contains(&error) {
+            self.errors.push(error);
+        }
+    }
+
+    /// Checks that an expression has the expected type.
+    fn expect(
+        &mut self,
+        expression: &Expression<'_>,
+        capabilities: &Capabilities,
+        expected: &Type,
+    ) -> Option<()> {
+        let found = self.check(expression, capabilities)?;
+        if found == *expected {
+            return Some(());
+        }
+
+        self.error(ValidationError::UnexpectedType {
+            expected: describe(expected),
+            found,
+            span: expression.span(),
+        });
+
+        None
+    }
+
+    /// Checks that an expression is an entity, returning its type.
+    fn expect_entity(
+        &mut self,
+        expression: &Expression<'_>,
+        capabilities: &Capabilities,
+    ) -> Option<EntityType> {
+        match self.check(expression, capabilities)? {
+            Type::Entity(kind) => Some(kind),
+            found => {
+                self.error(ValidationError::UnexpectedType {
+                    expected: "an entity",
+                    found,
+                    span: expression.span(),
+                });
+
+                None
+            }
+        }
+    }
+
+    /// Checks that an expression is a set, returning its element type.
+    fn expect_set(
+        &mut self,
+        expression: &Expression<'_>,
+        capabilities: &Capabilities,
+    ) -> Option<Type> {
+        match self.check(expression, capabilities)? {
+            Type::Set(element) => Some(*element),
+            found => {
+                self.error(ValidationError::UnexpectedType {
+                    expected: "a set",
+                    found,
+                    span: expression.span(),
+                });
+
+                None
+            }
+        }
+    }
+
+    /// Checks that an expression can be the target of `in`.
+    fn expect_ancestor(
+        &mut self,
+        expression: &Expression<'_>,
+        capabilities: &Capabilities,
+    ) -> Option<()> {
+        match self.check(expression, capabilities)? {
+            Type::Entity(_) => Some(()),
+            Type::Set(element) if matches!(*element, Type::Entity(_)) => Some(()),
+            found => {
+                self.error(ValidationError::UnexpectedType {
+                    expected: "an entity or set of entities",
+                    found,
+                    span: expression.span(),
+                });
+
+                None
+            }
+        }
+    }
+
+    fn check(&mut self, expression: &Expression<'_>, capabilities: &Capabilities) -> Option<Type> {
+        let span = expression.span();
+
+        match expression.kind() {
+            ExpressionKind::Literal(literal) => Some(self.literal(literal, span)),
+            ExpressionKind::Variable(variable) => Some(match variable {
+                Variable::Principal => Type::Entity(self.principal.clone()),
+                Variable::Action => Type::Entity(self.action.kind().clone()),
+                Variable::Resource => Type::Entity(self.resource.clone()),
+                Variable::Context => Type::Record(self.context.clone()),
+            }),
+            ExpressionKind::Slot(SlotKind::Principal) => Some(Type::Entity(self.principal.clone())),
+            ExpressionKind::Slot(SlotKind::Resource) => Some(Type::Entity(self.resource.clone())),
+            ExpressionKind::If {
+                test,
+                consequent,
+                alternate,
+            } => {
+                let mut narrowed = capabilities.clone();
+                narrowed.extend(effects(test));
+
+                let constant = self.evaluate(test);
+                let test = self.expect(test, capabilities, &Type::Bool);
+
+                // A branch that can never be taken in this environment is not checked.
+                match constant {
+                    Some(true) => {
+                        let consequent = self.check(consequent, &narrowed);
+                        test.and(consequent)
+                    }
+                    Some(false) => {
+                        let alternate = self.check(alternate, capabilities);
+                        test.and(alternate)
+                    }
+                    None => {
+                        let consequent = self.check(consequent, &narrowed);
+                        let alternate = self.check(alternate, capabilities);
+                        let ((), consequent, alternate) = (test?, consequent?, alternate?);
+
+                        self.unify(&consequent, &alternate, span)
+                    }
+                }
+            }
+            ExpressionKind::And { left, right } => {
+                let mut narrowed = capabilities.clone();
+                narrowed.extend(effects(left));
+
+                let constant = self.evaluate(left);
+                let left = self.expect(left, capabilities, &Type::Bool);
+                if constant == Some(false) {
+                    return left.map(|()| Type::Bool);
+                }
+
+                let right = self.expect(right, &narrowed, &Type::Bool);
+                left.and(right).map(|()| Type::Bool)
+            }
+            ExpressionKind::Or { left, right } => {
+                let constant = self.evaluate(left);
+                let left = self.expect(left, capabilities, &Type::Bool);
+                if constant == Some(true) {
+                    return left.map(|()| Type::Bool);
+                }
+
+                let right = self.expect(right, capabilities, &Type::Bool);
+                left.and(right).map(|()| Type::Bool)
+            }
+            ExpressionKind::UnaryApp { operator, operand } => match operator {
+                UnaryOperator::Not => {
+                    self.expect(operand, capabilities, &Type::Bool)?;
+                    Some(Type::Bool)
+                }
+                UnaryOperator::Negate => {
+                    self.expect(operand, capabilities, &Type::Long)?;
+                    Some(Type::Long)
+                }
+                UnaryOperator::IsEmpty => {
+                    self.expect_set(operand, capabilities)?;
+                    Some(Type::Bool)
+                }
+            },
+            ExpressionKind::BinaryApp {
+                operator,
+                left,
+                right,
+            } => self.binary(*operator, left, right, capabilities, span),
+            ExpressionKind::GetAttribute {
+                expression: base,
+                attribute,
+            } => self.get_attribute(base, attribute, capabilities, span),
+            ExpressionKind::HasAttribute {
+                expression: base, ..
+            } => match self.check(base, capabilities)? {
+                Type::Entity(_) | Type::Record(_) => Some(Type::Bool),
+                found => {
+                    self.error(ValidationError::UnexpectedType {
+                        expected: "an entity or record",
+                        found,
+                        span: base.span(),
+                    });
+
+                    None
+                }
+            },
+            ExpressionKind::Like { expression, .. } => {
+                self.expect(expression, capabilities, &Type::String)?;
+                Some(Type::Bool)
+            }
+            ExpressionKind::Is { expression, kind } => {
+                let entity = self.expect_entity(expression, capabilities);
+                let known = self.known_type(kind, span);
+                entity.and(known).map(|()| Type::Bool)
+            }
+            ExpressionKind::IsIn {
+                expression,
+                kind,
+                target,
+            } => {
+                let entity = self.expect_entity(expression, capabilities);
+                let known = self.known_type(kind, span);
+                let target = self.expect_ancestor(target, capabilities);
+                entity.and(known).and(target).map(|()| Type::Bool)
+            }
+            ExpressionKind::ExtensionCall {
+                function,
+                arguments,
+            } => self.extension_call(function, arguments, capabilities, span),
+            ExpressionKind::Set(elements) => self.set(elements, capabilities, span),
+            ExpressionKind::Record(record) => {
+                let mut ty = RecordType::empty();
+                let mut valid = true;
+
+                for (name, value) in record.iter() {
+                    match self.check(value, capabilities) {
+                        Some(value) => ty.insert(name, AttributeType::required(value)),
+                        None => valid = false,
+                    }
+                }
+
+                valid.then_some(Type::Record(ty))
+            }
+        }
+    }
+
+    /// Returns the value of a boolean expression, if it is the same for every request in this
+    /// environment.
+    ///
+    /// This covers `is` tests and `==` between entities of different types, so that policies
+    /// like `principal is User && principal.name == "alice"` also apply to other principal types.
+    fn evaluate(&self, expression: &Expression<'_>) -> Option<bool> {
+        match expression.kind() {
+            ExpressionKind::Literal(Literal::Bool(value)) => Some(value.value()),
+            ExpressionKind::Is { expression, kind } => {
+                let found = self.static_type(expression)?;
+                Some(found == Type::Entity(EntityType::from(kind)))
+            }
+            ExpressionKind::IsIn {
+                expression, kind, ..
+            } => {
+                let found = self.static_type(expression)?;
+                (found != Type::Entity(EntityType::from(kind))).then_some(false)
+            }
+            ExpressionKind::BinaryApp {
+                operator: operator @ (BinaryOperator::Equal | BinaryOperator::NotEqual),
+                left,
+                right,
+            } => {
+                let left = self.static_type(left)?;
+                let right = self.static_type(right)?;
+                let disjoint =
+                    matches!((&left, &right), (Type::Entity(_), Type::Entity(_))) && left != right;
+
+                disjoint.then_some(*operator == BinaryOperator::NotEqual)
+            }
+            ExpressionKind::UnaryApp {
+                operator: UnaryOperator::Not,
+                operand,
+            } => self.evaluate(operand).map(|value| !value),
+            ExpressionKind::And { left, right } => {
+                match (self.evaluate(left), self.evaluate(right)) {
+                    (Some(false), _) | (_, Some(false)) => Some(false),
+                    (Some(true), Some(true)) => Some(true),
+                    _ => None,
+                }
+            }
+            ExpressionKind::Or { left, right } => {
+                match (self.evaluate(left), self.evaluate(right)) {
+                    (Some(true), _) | (_, Some(true)) => Some(true),
+                    (Some(false), Some(false)) => Some(false),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the type of a variable, entity literal, or attribute access, without reporting
+    /// errors.
+    fn static_type(&self, expression: &Expression<'_>) -> Option<Type> {
+        match expression.kind() {
+            ExpressionKind::Variable(variable) => Some(match variable {
+                Variable::Principal => Type::Entity(self.principal.clone()),
+                Variable::Action => Type::Entity(self.action.kind().clone()),
+                Variable::Resource => Type::Entity(self.resource.clone()),
+                Variable::Context => Type::Record(self.context.clone()),
+            }),
+            ExpressionKind::Slot(SlotKind::Principal) => Some(Type::Entity(self.principal.clone())),
+            ExpressionKind::Slot(SlotKind::Resource) => Some(Type::Entity(self.resource.clone())),
+            ExpressionKind::Literal(Literal::Entity(reference)) => {
+                Some(Type::Entity(EntityUid::from(reference).kind().clone()))
+            }
+            ExpressionKind::GetAttribute {
+                expression: base,
+                attribute,
+            } => {
+                let ty = self.static_type(base)?;
+                let record = match &ty {
+                    Type::Entity(kind) => self.schema.entity(kind)?.attributes(),
+                    Type::Record(record) => record,
+                    _ => return None,
+                };
+
+                record.get(attribute).map(|found| found.ty().clone())
+            }
+            _ => None,
+        }
+    }
+
+    fn literal(&mut self, literal: &Literal<'_>, span: Option<Range<usize>>) -> Type {
+        match literal {
+            Literal::Bool(_) => Type::Bool,
+            Literal::Integer(_) => Type::Long,
+            Literal::String(_) => Type::String,
+            Literal::Entity(reference) => self.entity_reference(reference, span),
+        }
+    }
+
+    fn entity_reference(
+        &mut self,
+        reference: &EntityReference<'_>,
+        span: Option<Range<usize>>,
+    ) -> Type {
+        let uid = EntityUid::from(reference);
+        if let Some(error) = check_entity(self.schema, &uid, span) {
+            self.error(error);
+        }
+
+        Type::Entity(uid.kind().clone())
+    }
+
+    fn known_type(&mut self, kind: &Name<'_>, span: Option<Range<usize>>) -> Option<()> {
+        match check_entity_type(self.schema, &EntityType::from(kind), span) {
+            Some(error) => {
+                self.error(error);
+                None
+            }
+            None => Some(()),
+        }
+    }
+
+    /// Returns the type of both branches of an `if`, which must agree.
+    fn unify(&mut self, left: &Type, right: &Type, span: Option<Range<usize>>) -> Option<Type> {
+        let ty = left.lub(right);
+        if ty.is_none() {
+            self.error(ValidationError::IncompatibleTypes {
+                left: left.clone(),
+                right: right.clone(),
+                span,
+            });
+        }
+
+        ty
+    }
+
+    fn binary(
+        &mut self,
+        operator: BinaryOperator,
+        left: &Expression<'_>,
+        right: &Expression<'_>,
+        capabilities: &Capabilities,
+        span: Option<Range<usize>>,
+    ) -> Option<Type> {
+        match operator {
+            BinaryOperator::Equal | BinaryOperator::NotEqual => {
+                let left = self.check(left, capabilities);
+                let right = self.check(right, capabilities);
+                self.compatible(&left?, &right?, span)?;
+                Some(Type::Bool)
+            }
+            BinaryOperator::Less
+            | BinaryOperator::LessEqual
+            | BinaryOperator::Greater
+            | BinaryOperator::GreaterEqual => {
+                let left_type = self.check(left, capabilities);
+                let right_type = self.check(right, capabilities);
+                let (left_type, right_type) = (left_type?, right_type?);
+
+                if !COMPARABLE.contains(&left_type) {
+                    self.error(ValidationError::UnexpectedType {
+                        expected: "`Long`, `datetime`, or `duration`",
+                        found: left_type,
+                        span: left.span(),
+                    });
+
+                    return None;
+                }
+
+                if right_type != left_type {
+                    self.error(ValidationError::UnexpectedType {
+                        expected: describe(&left_type),
+                        found: right_type,
+                        span: right.span(),
+                    });
+
+                    return None;
+                }
+
+                Some(Type::Bool)
+            }
+            BinaryOperator::Add | BinaryOperator::Subtract | BinaryOperator::Multiply => {
+                let left = self.expect(left, capabilities, &Type::Long);
+                let right = self.expect(right, capabilities, &Type::Long);
+                left.and(right).map(|()| Type::Long)
+            }
+            BinaryOperator::In => {
+                let left = self.expect_entity(left, capabilities);
+                let right = self.expect_ancestor(right, capabilities);
+                left.and(right).map(|()| Type::Bool)
+            }
+            BinaryOperator::Contains => {
+                let element = self.expect_set(left, capabilities);
+                let value = self.check(right, capabilities);
+                self.compatible(&element?, &value?, span)?;
+                Some(Type::Bool)
+            }
+            BinaryOperator::ContainsAll | BinaryOperator::ContainsAny => {
+                let left = self.expect_set(left, capabilities);
+                let right = self.expect_set(right, capabilities);
+                self.compatible(&left?, &right?, span)?;
+                Some(Type::Bool)
+            }
+            BinaryOperator::GetTag => {
+                let kind = self.expect_entity(left, capabilities);
+                let tag = self.expect(right, capabilities, &Type::String);
+                let (kind, ()) = (kind?, tag?);
+
+                let Some(tags) = self.schema.entity(&kind).and_then(|entity| entity.tags()) else {
+                    self.error(ValidationError::MissingTags { kind, span });
+                    return None;
+                };
+
+                let capability = Capability::Tag(left.to_string(), right.to_string());
+                if !capabilities.contains(&capability) {
+                    self.error(ValidationError::UnsafeTagAccess { span });
+                }
+
+                Some(tags.clone())
+            }
+            BinaryOperator::HasTag => {
+                let kind = self.expect_entity(left, capabilities);
+                let tag = self.expect(right, capabilities, &Type::String);
+                kind.and(tag).map(|()| Type::Bool)
+            }
+        }
+    }
+
+    /// Checks that two values may be compared for equality or membership.
+    fn compatible(&mut self, left: &Type, right: &Type, span: Option<Range<usize>>) -> Option<()> {
+        if compatible(left, right) {
+            return Some(());
+        }
+
+        self.error(ValidationError::IncompatibleTypes {
+            left: left.clone(),
+            right: right.clone(),
+            span,
+        });
+
+        None
+    }
+
+    fn get_attribute(
+        &mut self,
+        base: &Expression<'_>,
+        attribute: &str,
+        capabilities: &Capabilities,
+        span: Option<Range<usize>>,
+    ) -> Option<Type> {
+        let schema = self.schema;
+        let ty = self.check(base, capabilities)?;
+
+        let record = match &ty {
+            Type::Entity(kind) => schema.entity(kind).map(EntitySchema::attributes),
+            Type::Record(record) => Some(record),
+            _ => {
+                self.error(ValidationError::UnexpectedType {
+                    expected: "an entity or record",
+                    found: ty,
+                    span: base.span(),
+                });
+
+                return None;
+            }
+        };
+
+        let Some(found) = record.and_then(|record| record.get(attribute)) else {
+            let suggestion = record.and_then(|record| {
+                let candidates: Vec<&str> = record.iter().map(|(name, _)| name).collect();
+                suggest(attribute, &candidates).map(String::from)
+            });
+
+            self.error(ValidationError::UnknownAttribute {
+                ty: ty.clone(),
+                attribute: String::from(attribute),
+                suggestion,
+                span,
+            });
+
+            return None;
+        };
+
+        let capability = Capability::Attribute(base.to_string(), String::from(attribute));
+        if !found.is_required() && !capabilities.contains(&capability) {
+            self.error(ValidationError::UnsafeAttributeAccess {
+                attribute: String::from(attribute),
+                span,
+            });
+        }
+
+        Some(found.ty().clone())
+    }
+
+    fn extension_call(
+        &mut self,
+        function: &Name<'_>,
+        arguments: &[Expression<'_>],
+        capabilities: &Capabilities,
+        span: Option<Range<usize>>,
+    ) -> Option<Type> {
+        let name = function.to_string();
+
+        let Some((_, parameters, output)) =
+            SIGNATURES.iter().find(|(candidate, ..)| *candidate == name)
+        else {
+            self.error(ValidationError::UnknownFunction { name, span });
+            return None;
+        };
+
+        if parameters.len() != arguments.len() {
+            self.error(ValidationError::WrongArity {
+                function: name,
+                expected: parameters.len(),
+                found: arguments.len(),
+                span,
+            });
+
+            return None;
+        }
+
+        let mut valid = true;
+        for (argument, parameter) in arguments.iter().zip(*parameters) {
+            valid &= self.expect(argument, capabilities, parameter).is_some();
+        }
+
+        if !valid {
+            return None;
+        }
+
+        // Constructor arguments are almost always literals, so they can be checked up front.
+        if let ([Type::String], [argument]) = (*parameters, arguments)
+            && let ExpressionKind::Literal(Literal::String(text)) = argument.kind()
+            && let Err(error) = Extension::new(&name, text.value())
+        {
+            self.error(ValidationError::InvalidExtension {
+                error,
+                span: argument.span(),
+            });
+        }
+
+        Some(output.clone())
+    }
+
+    fn set(
+        &mut self,
+        elements: &[Expression<'_>],
+        capabilities: &Capabilities,
+        span: Option<Range<usize>>,
+    ) -> Option<Type> {
+        if elements.is_empty() {
+            self.error(ValidationError::EmptySet { span });
+            return None;
+        }
+
+        let types: Vec<Option<Type>> = elements
+            .iter()
+            .map(|element| self.check(element, capabilities))
+            .collect();
+
+        let mut element: Option<Type> = None;
+        for ty in types {
+            let ty = ty?;
+
+            element = match element {
+                None => Some(ty),
+                Some(element) => {
+                    let Some(lub) = element.lub(&ty) else {
+                        self.error(ValidationError::HeterogeneousSet { span });
+                        return None;
+                    };
+
+                    Some(lub)
+                }
+            };
+        }
+
+        element.map(Type::set)
+    }
+}
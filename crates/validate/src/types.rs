@@ -0,0 +1,81 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use duramen_runtime::EntityType;
+
+mod attribute_type;
+pub use attribute_type::AttributeType;
+
+mod record_type;
+pub use record_type::RecordType;
+
+/// The type of a Cedar expression or schema attribute.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Type {
+    Bool,
+    Long,
+    String,
+    Entity(EntityType),
+    Set(Box<Self>),
+    Record(RecordType),
+    Decimal,
+    IpAddress,
+    Datetime,
+    Duration,
+}
+
+impl Type {
+    /// Returns the least upper bound of two types.
+    ///
+    /// Strict validation has no union types, so entity types and records must match exactly.
+    #[must_use]
+    pub fn lub(&self, other: &Self) -> Option<Self> {
+        match (self, other) {
+            (Self::Set(left), Self::Set(right)) => left.lub(right).map(Self::set),
+            (Self::Record(left), Self::Record(right)) => left.lub(right).map(Self::Record),
+            _ if self == other => Some(self.clone()),
+            _ => None,
+        }
+    }
+
+    /// Creates a set type.
+    #[must_use]
+    pub fn set(element: Self) -> Self {
+        Self::Set(Box::new(element))
+    }
+
+    /// Returns the entity type, if this is an entity.
+    #[must_use]
+    pub const fn as_entity(&self) -> Option<&EntityType> {
+        match self {
+            Self::Entity(kind) => Some(kind),
+            _ => None,
+        }
+    }
+
+    /// Returns the element type, if this is a set.
+    #[must_use]
+    pub fn as_set(&self) -> Option<&Self> {
+        match self {
+            Self::Set(element) => Some(element),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool => f.write_str("Bool"),
+            Self::Long => f.write_str("Long"),
+            Self::String => f.write_str("String"),
+            Self::Entity(kind) => kind.fmt(f),
+            Self::Set(element) => write!(f, "Set<{element}>"),
+            Self::Record(record) => record.fmt(f),
+            Self::Decimal => f.write_str("decimal"),
+            Self::IpAddress => f.write_str("ipaddr"),
+            Self::Datetime => f.write_str("datetime"),
+            Self::Duration => f.write_str("duration"),
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use duramen_ast::Optionality;
+
+use crate::types::Type;
+
+/// The type of a record or entity attribute, and whether it is required.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct AttributeType {
+    ty: Type,
+    optionality: Optionality,
+}
+
+impl AttributeType {
+    /// Creates an attribute type.
+    #[must_use]
+    pub const fn new(ty: Type, optionality: Optionality) -> Self {
+        Self { ty, optionality }
+    }
+
+    /// Creates a required attribute type.
+    #[must_use]
+    pub const fn required(ty: Type) -> Self {
+        Self::new(ty, Optionality::Required)
+    }
+
+    /// Returns the type of the attribute value.
+    #[must_use]
+    pub const fn ty(&self) -> &Type {
+        &self.ty
+    }
+
+    /// Returns whether the attribute is required or optional.
+    #[must_use]
+    pub const fn optionality(&self) -> Optionality {
+        self.optionality
+    }
+
+    /// Returns whether the attribute is required.
+    #[must_use]
+    pub const fn is_required(&self) -> bool {
+        matches!(self.optionality, Optionality::Required)
+    }
+}
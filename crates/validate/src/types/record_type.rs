@@ -0,0 +1,97 @@
+use alloc::string::String;
+use core::fmt;
+
+use duramen_ast::Optionality;
+
+use crate::IndexMap;
+use crate::types::AttributeType;
+
+/// The attributes of a record or entity type, in declaration order.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct RecordType {
+    attributes: IndexMap<String, AttributeType>,
+}
+
+impl RecordType {
+    /// Creates a record type with no attributes.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Adds an attribute, replacing any earlier attribute with the same name.
+    pub fn insert<N: Into<String>>(&mut self, name: N, attribute: AttributeType) {
+        self.attributes.insert(name.into(), attribute);
+    }
+
+    /// Returns the attribute with the given name.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&AttributeType> {
+        self.attributes.get(name)
+    }
+
+    /// Returns the number of attributes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.attributes.len()
+    }
+
+    /// Returns whether the record has no attributes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty()
+    }
+
+    /// Returns an iterator over attribute names and types.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &AttributeType)> {
+        self.attributes
+            .iter()
+            .map(|(name, attribute)| (name.as_str(), attribute))
+    }
+
+    /// Returns the least upper bound of two record types, which must have the same attributes.
+    pub(crate) fn lub(&self, other: &Self) -> Option<Self> {
+        if self.len() != other.len() {
+            return None;
+        }
+
+        let mut record = Self::empty();
+        for (name, left) in self.iter() {
+            let right = other.get(name)?;
+            if left.optionality() != right.optionality() {
+                return None;
+            }
+
+            let ty = left.ty().lub(right.ty())?;
+            record.insert(name, AttributeType::new(ty, left.optionality()));
+        }
+
+        Some(record)
+    }
+}
+
+/// Writes the record in Cedar schema syntax, like `{ name: String, age?: Long }`.
+impl fmt::Display for RecordType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("{}");
+        }
+
+        f.write_str("{ ")?;
+
+        for (index, (name, attribute)) in self.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+
+            f.write_str(name)?;
+            if attribute.optionality() == Optionality::Optional {
+                f.write_str("?")?;
+            }
+
+            write!(f, ": {}", attribute.ty())?;
+        }
+
+        f.write_str(" }")
+    }
+}
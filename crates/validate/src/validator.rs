@@ -0,0 +1,207 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use duramen_ast as ast;
+use duramen_diagnostic::Diagnostics;
+use duramen_runtime::{Entities, EntityType, EntityUid, Request};
+
+use crate::conformance;
+use crate::error::{EntityConformanceError, RequestError, ValidationError};
+use crate::schema::ValidatorSchema;
+use crate::typechecker::{self, Typechecker};
+
+/// Validates policies against a schema in strict mode.
+#[derive(Clone, Debug)]
+pub struct Validator {
+    schema: ValidatorSchema,
+}
+
+impl Validator {
+    /// Creates a validator for a resolved schema.
+    #[must_use]
+    pub const fn new(schema: ValidatorSchema) -> Self {
+        Self { schema }
+    }
+
+    /// Returns the schema policies are validated against.
+    #[must_use]
+    pub const fn schema(&self) -> &ValidatorSchema {
+        &self.schema
+    }
+
+    /// Validates a request before it is authorized.
+    ///
+    /// Checks that the action is declared, that it applies to the principal and resource types,
+    /// and that the context conforms to the action's context type.
+    #[must_use]
+    pub fn validate_request(&self, request: &Request) -> Vec<RequestError> {
+        conformance::check_request(&self.schema, request)
+    }
+
+    /// Validates that every entity in a store conforms to the schema.
+    ///
+    /// Checks entity types, attributes, parents, tags, and enumerated ids.
+    #[must_use]
+    pub fn validate_entities(&self, entities: &Entities) -> Vec<EntityConformanceError> {
+        conformance::check_entities(&self.schema, entities)
+    }
+
+    /// Validates every policy, returning a diagnostic for each error.
+    #[must_use]
+    pub fn validate(&self, policies: &ast::Policies<'_>) -> Diagnostics {
+        let mut diagnostics = Diagnostics::new();
+
+        for policy in policies.policies() {
+            for error in self.validate_policy(policy) {
+                diagnostics.push(error);
+            }
+        }
+
+        diagnostics
+    }
+
+    /// Validates a single policy or template.
+    ///
+    /// The policy is typechecked once for every principal type, action, and resource type that
+    /// the schema allows and the policy scope matches.
+    #[must_use]
+    pub fn validate_policy(&self, policy: &ast::Policy<'_>) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+
+        self.check_scope(
+            policy.principal().constraint(),
+            policy.principal().span(),
+            &mut errors,
+        );
+        self.check_scope(
+            policy.resource().constraint(),
+            policy.resource().span(),
+            &mut errors,
+        );
+
+        let action_span = policy.action_span().or_else(|| policy.span());
+        for reference in policy.action().entities() {
+            let uid = EntityUid::from(reference);
+            if let Some(error) = typechecker::check_entity(&self.schema, &uid, action_span.clone())
+            {
+                push(&mut errors, error);
+            }
+        }
+
+        let mut matched = false;
+
+        for (uid, action) in self.schema.actions() {
+            if !self.matches_action(policy.action(), uid) {
+                continue;
+            }
+
+            for principal in action.principals() {
+                if !self.matches_scope(policy.principal().constraint(), principal) {
+                    continue;
+                }
+
+                for resource in action.resources() {
+                    if !self.matches_scope(policy.resource().constraint(), resource) {
+                        continue;
+                    }
+
+                    matched = true;
+
+                    let mut checker =
+                        Typechecker::new(&self.schema, principal, uid, resource, action.context());
+
+                    for condition in policy.conditions() {
+                        checker.check_condition(condition.body());
+                    }
+
+                    for error in checker.into_errors() {
+                        push(&mut errors, error);
+                    }
+                }
+            }
+        }
+
+        if !matched && errors.is_empty() {
+            errors.push(ValidationError::ImpossiblePolicy {
+                span: policy.span(),
+            });
+        }
+
+        errors
+    }
+
+    /// Checks that the entity types and entities in a principal or resource scope are declared.
+    fn check_scope(
+        &self,
+        constraint: &ast::ScopeConstraint<'_>,
+        span: Option<Range<usize>>,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let (kind, target) = match constraint {
+            ast::ScopeConstraint::Any => (None, None),
+            ast::ScopeConstraint::Equal(target) | ast::ScopeConstraint::In(target) => {
+                (None, Some(target))
+            }
+            ast::ScopeConstraint::Is(kind) => (Some(kind), None),
+            ast::ScopeConstraint::IsIn(kind, target) => (Some(kind), Some(target)),
+        };
+
+        if let Some(kind) = kind
+            && let Some(error) =
+                typechecker::check_entity_type(&self.schema, &EntityType::from(kind), span.clone())
+        {
+            push(errors, error);
+        }
+
+        if let Some(ast::EntityOrSlot::Entity(reference)) = target
+            && let Some(error) =
+                typechecker::check_entity(&self.schema, &EntityUid::from(reference), span)
+        {
+            push(errors, error);
+        }
+    }
+
+    fn matches_action(&self, constraint: &ast::ActionConstraint<'_>, uid: &EntityUid) -> bool {
+        match constraint {
+            ast::ActionConstraint::Any => true,
+            ast::ActionConstraint::Equal(reference) => EntityUid::from(reference) == *uid,
+            ast::ActionConstraint::In(list) => list.iter().any(|reference| {
+                self.schema
+                    .is_descendant_action(uid, &EntityUid::from(reference))
+            }),
+        }
+    }
+
+    fn matches_scope(&self, constraint: &ast::ScopeConstraint<'_>, kind: &EntityType) -> bool {
+        match constraint {
+            ast::ScopeConstraint::Any | ast::ScopeConstraint::Equal(ast::EntityOrSlot::Slot) => {
+                true
+            }
+            ast::ScopeConstraint::Equal(ast::EntityOrSlot::Entity(reference)) => {
+                *kind == *reference.kind()
+            }
+            ast::ScopeConstraint::In(target) => self.matches_ancestor(target, kind),
+            ast::ScopeConstraint::Is(name) => *kind == *name,
+            ast::ScopeConstraint::IsIn(name, target) => {
+                *kind == *name && self.matches_ancestor(target, kind)
+            }
+        }
+    }
+
+    /// Returns whether entities of type `kind` may be `in` the target.
+    fn matches_ancestor(&self, target: &ast::EntityOrSlot<'_>, kind: &EntityType) -> bool {
+        match target {
+            ast::EntityOrSlot::Entity(reference) => self
+                .schema
+                .is_descendant_type(kind, &EntityType::from(reference.kind())),
+            ast::EntityOrSlot::Slot => true,
+        }
+    }
+}
+
+/// Records an error, unless an identical one was already found for another request.
+fn push(errors: &mut Vec<ValidationError>, error: ValidationError) {
+    if !errors.contains(&error) {
+        errors.push(error);
+    }
+}
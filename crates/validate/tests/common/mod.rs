@@ -0,0 +1,14 @@
+#![expect(clippy::unwrap_used, reason = "Tests")]
+
+use duramen_lowerer::SchemaLowerer;
+use duramen_parser::SchemaParser;
+use duramen_validate::{Validator, ValidatorSchema};
+
+/// Builds a validator for a schema, which must lower without diagnostics.
+pub fn validator(schema: &str) -> Validator {
+    let (tree, diagnostics) = SchemaParser::parse(schema);
+    let (schema, diagnostics) = SchemaLowerer::lower(&tree, diagnostics);
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+
+    Validator::new(ValidatorSchema::new(&schema).unwrap())
+}
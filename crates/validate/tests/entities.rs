@@ -0,0 +1,208 @@
+#![expect(clippy::unwrap_used, reason = "Tests")]
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use duramen_runtime::{Entities, EntitiesParser, Entity, Set};
+use duramen_test::insta::assert_snapshot;
+use duramen_test::{record, uid};
+
+extern crate alloc;
+
+mod common;
+use common::validator;
+
+const SCHEMA: &str = r#"
+    type Address = { city: String, zip?: Long };
+    type Network = { ip: ipaddr, subnets: Set<ipaddr> };
+
+    entity Group;
+    entity User in [Group] {
+        name: String,
+        address?: Address,
+        groups: Set<Group>,
+        manager?: User,
+        network?: Network,
+        limit?: decimal,
+    } tags Long;
+    entity Photo in [Album];
+    entity Album;
+    entity Color enum ["red", "green"];
+
+    action view, edit in [manage] appliesTo {
+        principal: User,
+        resource: Photo,
+        context: { ip: ipaddr, owner: User, since?: datetime, tags?: Set<String> }
+    };
+    action manage;
+"#;
+
+/// Lists each conformance error for a store.
+fn errors<const N: usize>(entities: [Entity; N]) -> String {
+    let entities = Entities::new(entities).unwrap();
+
+    validator(SCHEMA)
+        .validate_entities(&entities)
+        .iter()
+        .map(|error| format!("{error}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn valid() {
+    let entities = [
+        Entity::new(uid("Group", "admins")),
+        Entity::new(uid("User", "alice"))
+            .with_attributes(record([
+                ("name", "Alice".into()),
+                ("address", record([("city", "Dublin".into())]).into()),
+                (
+                    "groups",
+                    Set::from_iter([uid("Group", "admins").into()]).into(),
+                ),
+            ]))
+            .with_tags(record([("level", 3.into())]))
+            .with_parents([uid("Group", "admins")]),
+        Entity::new(uid("Album", "trip")),
+        Entity::new(uid("Photo", "beach")).with_parents([uid("Album", "trip")]),
+        Entity::new(uid("Color", "red")),
+        Entity::new(uid("Action", "manage")),
+        Entity::new(uid("Action", "view")).with_parents([uid("Action", "manage")]),
+    ];
+
+    assert_snapshot!(errors(entities), @"");
+}
+
+#[test]
+fn invalid() {
+    let entities = [
+        Entity::new(uid("Team", "core")),
+        Entity::new(uid("Color", "blue")),
+        Entity::new(uid("Action", "delete")),
+        Entity::new(uid("Album", "trip")),
+        Entity::new(uid("Action", "manage")),
+        Entity::new(uid("Action", "edit")).with_parents([uid("Action", "view")]),
+        Entity::new(uid("Action", "view")),
+        Entity::new(uid("User", "alice"))
+            .with_attributes(record([
+                ("address", record([("city", 1.into())]).into()),
+                (
+                    "groups",
+                    Set::from_iter([uid("Album", "trip").into()]).into(),
+                ),
+                ("age", 30.into()),
+            ]))
+            .with_tags(record([("level", "high".into())]))
+            .with_parents([uid("Album", "trip")]),
+        Entity::new(uid("Photo", "beach")).with_tags(record([("camera", "x100".into())])),
+    ];
+
+    assert_snapshot!(errors(entities), @r#"
+    `Team::"core"` has undeclared entity type `Team`
+    `Color::"blue"` is not a declared value of `Color`
+    unknown action `Action::"delete"`
+    `Action::"edit"` cannot be a member of `Action::"view"`
+    `User::"alice"` cannot be a member of `Album::"trip"`
+    invalid attribute on `User::"alice"`: missing required attribute `name`
+    invalid attribute on `User::"alice"`: `address.city` should be `String`, found `Long`
+    invalid attribute on `User::"alice"`: `groups` should be `Group`, found `Album`
+    invalid attribute on `User::"alice"`: undeclared attribute `age`
+    invalid tag on `User::"alice"`: `level` should be `Long`, found `String`
+    `Photo::"beach"` has tags, but `Photo` does not declare any
+    "#);
+}
+
+#[test]
+fn json() {
+    let source = r#"[
+        { "uid": { "type": "Group", "id": "admins" } },
+        {
+            "uid": { "type": "User", "id": "bob" },
+            "attrs": { "name": "Bob", "groups": [] }
+        },
+        {
+            "uid": { "type": "User", "id": "alice" },
+            "attrs": {
+                "name": "Alice",
+                "groups": [{ "type": "Group", "id": "admins" }],
+                "manager": { "type": "User", "id": "bob" },
+                "network": { "ip": "10.0.0.1", "subnets": ["10.0.0.0/8", { "__extn": { "fn": "ip", "arg": "::1" } }] },
+                "limit": "1.5"
+            },
+            "tags": { "level": 3 },
+            "parents": [{ "type": "Group", "id": "admins" }]
+        },
+        {
+            "uid": { "type": "Team", "id": "core" },
+            "attrs": { "lead": { "type": "User", "id": "alice" }, "ip": "10.0.0.1" }
+        }
+    ]"#;
+
+    let validator = validator(SCHEMA);
+    let entities = EntitiesParser::parse_with_hints(source, validator.schema()).unwrap();
+
+    let lines = entities
+        .iter()
+        .map(|entity| format!("{}: {}", entity.uid(), entity.attributes()))
+        .chain(
+            validator
+                .validate_entities(&entities)
+                .iter()
+                .map(|error| format!("{error}")),
+        )
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    assert_snapshot!(lines, @r#"
+    Group::"admins": {}
+    User::"bob": {"groups": [], "name": "Bob"}
+    User::"alice": {"groups": [Group::"admins"], "limit": decimal("1.5000"), "manager": User::"bob", "name": "Alice", "network": {"ip": ip("10.0.0.1/32"), "subnets": [ip("10.0.0.0/8"), ip("::1/128")]}}
+    Team::"core": {"ip": "10.0.0.1", "lead": {"id": "alice", "type": "User"}}
+    `Team::"core"` has undeclared entity type `Team`
+    "#);
+}
+
+#[test]
+fn json_errors() {
+    let source = r#"[
+        {
+            "uid": { "type": "User", "id": "alice" },
+            "attrs": { "name": "Alice", "groups": [], "limit": "lots", "manager": { "id": "bob" } }
+        }
+    ]"#;
+
+    let validator = validator(SCHEMA);
+    let diagnostics = EntitiesParser::parse_with_hints(source, validator.schema()).unwrap_err();
+
+    let messages = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let span = diagnostic.span().cloned().unwrap_or_default();
+            format!("{} @ `{}`", diagnostic.message(), &source[span])
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    assert_snapshot!(messages, @r#"
+    `lots` is not a valid decimal @ `"lots"`
+    missing field `type` @ `{ "id": "bob" }`
+    "#);
+}
+
+#[test]
+fn json_context() {
+    let source = r#"{
+        "ip": "192.168.0.1",
+        "owner": { "type": "User", "id": "alice" },
+        "since": "2024-01-01",
+        "tags": ["a", "b"]
+    }"#;
+
+    let validator = validator(SCHEMA);
+    let action = uid("Action", "view");
+    let context = EntitiesParser::parse_context(source, validator.schema(), &action).unwrap();
+
+    assert_snapshot!(format!("{context}"), @r#"{"ip": ip("192.168.0.1/32"), "owner": User::"alice", "since": datetime("2024-01-01T00:00:00.000Z"), "tags": ["a", "b"]}"#);
+}
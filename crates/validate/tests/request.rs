@@ -0,0 +1,151 @@
+#![expect(clippy::unwrap_used, reason = "Tests")]
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use duramen_diagnostic::Diagnostic;
+use duramen_runtime::{Extension, Record, Request, Set, Value};
+use duramen_test::insta::assert_snapshot;
+use duramen_test::{record, uid};
+
+extern crate alloc;
+
+mod common;
+use common::validator;
+
+const SCHEMA: &str = r#"
+    type Address = { city: String, zip?: Long };
+
+    entity User { name: String };
+    entity Photo;
+    entity Color enum ["red", "green"];
+
+    action view appliesTo {
+        principal: User,
+        resource: [Photo, Color],
+        context: { ip: ipaddr, address: Address, tags?: Set<String>, owner?: User }
+    };
+    action ping;
+"#;
+
+/// Lists each request error, with any notes.
+fn errors(request: &Request) -> String {
+    validator(SCHEMA)
+        .validate_request(request)
+        .into_iter()
+        .map(|error| {
+            let message = format!("{error}");
+            let diagnostic = Diagnostic::from(error);
+
+            diagnostic
+                .notes()
+                .iter()
+                .fold(message, |message, note| format!("{message} ({note})"))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn context() -> Record {
+    record([
+        ("ip", Extension::new("ip", "10.0.0.1").unwrap().into()),
+        ("address", record([("city", "Dublin".into())]).into()),
+    ])
+}
+
+#[test]
+fn valid() {
+    let request = Request::new(
+        uid("User", "alice"),
+        uid("Action", "view"),
+        uid("Photo", "1"),
+    )
+    .with_context(context());
+
+    assert_snapshot!(errors(&request), @"");
+
+    let request = Request::new(
+        uid("User", "alice"),
+        uid("Action", "view"),
+        uid("Color", "red"),
+    )
+    .with_context(record([
+        ("ip", Extension::new("ip", "::1").unwrap().into()),
+        (
+            "address",
+            record([("city", "Cork".into()), ("zip", 12.into())]).into(),
+        ),
+        (
+            "tags",
+            Set::from_iter([Value::from("a"), Value::from("b")]).into(),
+        ),
+        ("owner", uid("User", "bob").into()),
+    ]));
+
+    assert_snapshot!(errors(&request), @"");
+}
+
+#[test]
+fn scope() {
+    let request = Request::new(
+        uid("User", "alice"),
+        uid("Action", "delete"),
+        uid("Photo", "1"),
+    );
+    assert_snapshot!(errors(&request), @r#"unknown action `Action::"delete"`"#);
+
+    let request = Request::new(
+        uid("Photo", "1"),
+        uid("Action", "view"),
+        uid("User", "alice"),
+    )
+    .with_context(context());
+    assert_snapshot!(errors(&request), @r#"
+    action `Action::"view"` does not apply to principals of type `Photo` (expected `User`)
+    action `Action::"view"` does not apply to resources of type `User` (expected `Photo` or `Color`)
+    "#);
+
+    let request = Request::new(
+        uid("User", "alice"),
+        uid("Action", "view"),
+        uid("Color", "blue"),
+    )
+    .with_context(context());
+    assert_snapshot!(errors(&request), @r#"`Color::"blue"` is not a declared value of `Color`"#);
+
+    let request = Request::new(
+        uid("User", "alice"),
+        uid("Action", "ping"),
+        uid("Photo", "1"),
+    );
+    assert_snapshot!(errors(&request), @r#"
+    action `Action::"ping"` does not apply to principals of type `User` (the action has no `appliesTo` declaration)
+    action `Action::"ping"` does not apply to resources of type `Photo` (the action has no `appliesTo` declaration)
+    "#);
+}
+
+#[test]
+fn context_conformance() {
+    let request = Request::new(
+        uid("User", "alice"),
+        uid("Action", "view"),
+        uid("Photo", "1"),
+    )
+    .with_context(record([
+        ("ip", "10.0.0.1".into()),
+        ("address", record([("zip", "D02".into())]).into()),
+        ("tags", Set::from_iter([Value::from(1)]).into()),
+        ("owner", uid("Photo", "1").into()),
+        ("extra", true.into()),
+    ]));
+
+    assert_snapshot!(errors(&request), @r#"
+    invalid context: `context.ip` should be `ipaddr`, found `String`
+    invalid context: missing required attribute `context.address.city`
+    invalid context: `context.address.zip` should be `Long`, found `String`
+    invalid context: `context.tags` should be `String`, found `Long`
+    invalid context: `context.owner` should be `User`, found `Photo`
+    invalid context: undeclared attribute `context.extra`
+    "#);
+}
@@ -0,0 +1,197 @@
+#![expect(clippy::unwrap_used, reason = "Tests")]
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+
+use duramen_ast::visit_mut::walk_type_expression_mut;
+use duramen_ast::{self as ast, EntityTypeSet, Name, TypeExpression, VisitMut};
+use duramen_lowerer::SchemaLowerer;
+use duramen_parser::SchemaParser;
+use duramen_test::insta::assert_snapshot;
+use duramen_validate::ValidatorSchema;
+
+extern crate alloc;
+
+fn lower(source: &str) -> ast::Schema<'static> {
+    let (tree, diagnostics) = SchemaParser::parse(source);
+    let (schema, diagnostics) = SchemaLowerer::lower(&tree, diagnostics);
+    assert!(diagnostics.is_empty(), "{source}");
+
+    schema.into_owned()
+}
+
+/// Lists each schema error as its message and the declaration it points at.
+fn errors(source: &str) -> String {
+    resolve(&lower(source), source)
+}
+
+fn resolve(schema: &ast::Schema<'_>, source: &str) -> String {
+    let Err(diagnostics) = ValidatorSchema::new(schema) else {
+        return String::new();
+    };
+
+    diagnostics
+        .iter()
+        .map(|diagnostic| match diagnostic.span() {
+            Some(span) => format!("{} @ `{}`", diagnostic.message(), &source[span.clone()]),
+            None => String::from(diagnostic.message()),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn valid() {
+    let source = r#"
+        type Name = String;
+
+        entity User { name: Name, ip: ipaddr };
+
+        namespace App {
+            type Context = { user: User, shared: Name };
+
+            entity User in [Group];
+            entity Document { owner: User, editors: Set<User> };
+
+            action read appliesTo { principal: User, resource: Document, context: Context };
+            action write in [read];
+            action "audit" in [Action::"read"];
+        }
+
+        entity Group;
+    "#;
+
+    assert_snapshot!(errors(source), @"");
+}
+
+#[test]
+fn undefined_names() {
+    let source = r"
+        entity User in [Team] { address: Adress };
+        action view in [manage] appliesTo { principal: User, resource: Doc };
+        entity Flag { on: Boolean };
+    ";
+
+    assert_snapshot!(errors(source), @r#"
+    undefined type `Adress` @ `entity User in [Team] { address: Adress };`
+    undefined entity type `Team` @ `entity User in [Team] { address: Adress };`
+    undefined entity type `Doc` @ `action view in [manage] appliesTo { principal: User, resource: Doc };`
+    undefined type `Boolean` @ `entity Flag { on: Boolean };`
+    undefined action `Action::"manage"` @ `action view in [manage] appliesTo { principal: User, resource: Doc };`
+    "#);
+}
+
+#[test]
+fn not_entity_types() {
+    let source = r"
+        type Id = String;
+        type Ctx = Set<Long>;
+
+        entity User in [Id];
+        action view appliesTo { principal: String, resource: User, context: Ctx };
+    ";
+
+    assert_snapshot!(errors(source), @r#"
+    `Id` is not an entity type @ `entity User in [Id];`
+    `String` is not an entity type @ `action view appliesTo { principal: String, resource: User, context: Ctx };`
+    context type `Ctx` is not a record @ `action view appliesTo { principal: String, resource: User, context: Ctx };`
+    "#);
+}
+
+/// Replaces references to `Either` with the entity types `User` and `Group`, which the
+/// schema syntax cannot express.
+struct Either;
+
+impl<'a> VisitMut<'a> for Either {
+    fn visit_type_expression_mut(&mut self, expression: &mut TypeExpression<'a>) {
+        match expression {
+            TypeExpression::Reference(name) if name.to_string() == "Either" => {
+                let types = vec![Name::parse("User").unwrap(), Name::parse("Group").unwrap()];
+                *expression = TypeExpression::Entity(EntityTypeSet::new(types).unwrap());
+            }
+            _ => walk_type_expression_mut(self, expression),
+        }
+    }
+}
+
+#[test]
+fn entity_type_sets() {
+    let source = r"
+        entity Group;
+        entity User in [Group] { owner: Either, members: Set<Either> };
+    ";
+
+    let mut schema = lower(source);
+    Either.visit_schema_mut(&mut schema);
+
+    assert_snapshot!(resolve(&schema, source), @"a value type cannot name more than one entity type @ `entity User in [Group] { owner: Either, members: Set<Either> };`");
+}
+
+#[test]
+fn cycles() {
+    let source = r"
+        type A = { b: B };
+        type B = Set<A>;
+        type C = C;
+
+        entity User { a: A };
+
+        action view in [edit];
+        action edit in [view];
+    ";
+
+    assert_snapshot!(errors(source), @r#"
+    common type refers to itself: `A` -> `B` -> `A` @ `type A = { b: B };`
+    common type refers to itself: `C` -> `C` @ `type C = C;`
+    action group contains itself: `Action::"view"` -> `Action::"edit"` -> `Action::"view"` @ `action view in [edit];`
+    "#);
+}
+
+#[test]
+fn common_types() {
+    let source = r"
+        type Name = String;
+        type Address = { street: Name, zip?: Long };
+
+        namespace App {
+            type Addresses = Set<Address>;
+            type Context = { addresses: Addresses, tags?: Set<Name> };
+
+            entity User { home: Address, previous?: Addresses };
+            action view appliesTo { principal: User, resource: User, context: Context };
+        }
+    ";
+
+    let (tree, diagnostics) = SchemaParser::parse(source);
+    let (schema, diagnostics) = SchemaLowerer::lower(&tree, diagnostics);
+    assert!(diagnostics.is_empty(), "{source}");
+
+    let schema = ValidatorSchema::new(&schema).unwrap();
+
+    let mut lines: Vec<String> = schema
+        .common_types()
+        .map(|(name, ty)| format!("type {name} = {ty}"))
+        .collect();
+
+    for (kind, _) in schema.entities() {
+        if let Some(attributes) = schema.attributes(kind) {
+            lines.push(format!("entity {kind} {attributes}"));
+        }
+    }
+
+    for (uid, _) in schema.actions() {
+        if let Some(context) = schema.context(uid) {
+            lines.push(format!("context {uid} {context}"));
+        }
+    }
+
+    assert_snapshot!(lines.join("\n"), @r#"
+    type Name = String
+    type Address = { street: String, zip?: Long }
+    type App::Addresses = Set<{ street: String, zip?: Long }>
+    type App::Context = { addresses: Set<{ street: String, zip?: Long }>, tags?: Set<String> }
+    entity App::User { home: { street: String, zip?: Long }, previous?: Set<{ street: String, zip?: Long }> }
+    context App::Action::"view" { addresses: Set<{ street: String, zip?: Long }>, tags?: Set<String> }
+    "#);
+}
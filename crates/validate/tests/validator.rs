@@ -0,0 +1,213 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use duramen_diagnostic::Diagnostics;
+use duramen_lowerer::PolicyLowerer;
+use duramen_parser::PolicyParser;
+use duramen_test::anstream::adapter::strip_str;
+use duramen_test::insta::assert_snapshot;
+
+extern crate alloc;
+
+mod common;
+use common::validator;
+
+const SCHEMA: &str = r#"
+    type Address = { city: String, zip?: Long };
+
+    entity Group;
+    entity User in [Group] {
+        name: String,
+        age: Long,
+        address: Address,
+        manager?: User,
+        roles: Set<String>,
+    } tags String;
+    entity Photo in [Album] { owner: User, private: Bool, taken: datetime };
+    entity Album;
+    entity Color enum ["red", "green"];
+
+    action view, edit in [manage] appliesTo {
+        principal: User,
+        resource: [Photo, Album],
+        context: { ip: ipaddr, mfa?: Bool }
+    };
+    action manage;
+    action paint appliesTo { principal: User, resource: Color };
+"#;
+
+fn validate(source: &str) -> Diagnostics {
+    let (tree, diagnostics) = PolicyParser::parse(source);
+    let (policies, diagnostics) = PolicyLowerer::lower(&tree, diagnostics);
+    assert!(diagnostics.is_empty(), "{source}");
+
+    validator(SCHEMA).validate(&policies)
+}
+
+/// Lists each diagnostic as its message and the source it points at.
+fn errors(source: &str) -> String {
+    validate(source)
+        .iter()
+        .map(|diagnostic| match diagnostic.span() {
+            Some(span) => format!("{} @ `{}`", diagnostic.message(), &source[span.clone()]),
+            None => String::from(diagnostic.message()),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn valid() {
+    let source = r#"
+        permit (principal == User::"alice", action == Action::"view", resource is Photo in Album::"trip")
+        when {
+            resource.owner == principal &&
+            principal.age >= 18 &&
+            principal.roles.contains("admin") &&
+            principal.address.city like "Dub*" &&
+            context.ip.isInRange(ip("10.0.0.0/8")) &&
+            resource.taken < datetime("2024-01-01")
+        };
+
+        permit (principal, action in Action::"manage", resource is Photo)
+        when { principal has manager && principal.manager.name == "bob" }
+        unless { context has mfa && !context.mfa };
+
+        permit (principal, action == Action::"view", resource)
+        when { principal.hasTag("team") && principal.getTag("team") == "core" };
+
+        permit (principal, action == Action::"paint", resource == Color::"red");
+
+        permit (principal, action, resource)
+        when { principal.address has zip && principal.address.zip > 1000 };
+    "#;
+
+    assert_snapshot!(errors(source), @"");
+}
+
+#[test]
+fn unknown_names() {
+    let source = r#"
+        permit (principal == Usr::"alice", action == Action::"delete", resource);
+        permit (principal, action, resource == Color::"blue");
+        permit (principal, action, resource) when { resource is Phot };
+        permit (principal, action in [Action::"view", Action::"erase"], resource);
+    "#;
+
+    assert_snapshot!(errors(source), @r#"
+    unknown entity type `Usr` @ `principal == Usr::"alice"`
+    unknown action `Action::"delete"` @ `action == Action::"delete"`
+    `Color::"blue"` is not a declared value of `Color` @ `resource == Color::"blue"`
+    unknown entity type `Phot` @ `resource is Phot`
+    unknown action `Action::"erase"` @ `action in [Action::"view", Action::"erase"]`
+    "#);
+}
+
+#[test]
+fn attributes() {
+    let source = r#"
+        permit (principal, action == Action::"view", resource)
+        when { principal.nme == "alice" || principal.manager.age > 3 || principal.address.zip == 1 };
+    "#;
+
+    assert_snapshot!(errors(source), @r#"
+    `User` does not have the attribute `nme` @ `principal.nme`
+    attribute `manager` is optional and may not be present @ `principal.manager`
+    attribute `zip` is optional and may not be present @ `principal.address.zip`
+    "#);
+}
+
+#[test]
+fn types() {
+    let source = r#"
+        permit (principal, action == Action::"view", resource)
+        when {
+            principal.age + "one" == 2 &&
+            principal.name < "bob" &&
+            principal.age == "18" &&
+            [1, "two"].contains(1) &&
+            [].isEmpty() &&
+            (if context.ip.isLoopback() then 1 else "one") == 1 &&
+            principal.getTag("team") == "core" &&
+            ip("not an ip").isIpv4() &&
+            principal.age
+        };
+    "#;
+
+    assert_snapshot!(errors(source), @r#"
+    type error: expected `Long`, found `String` @ `"one"`
+    type error: expected `Long`, `datetime`, or `duration`, found `String` @ `principal.name`
+    type error: `Long` and `String` are not compatible @ `principal.age == "18"`
+    set elements must all have the same type @ `[1, "two"]`
+    empty set literals are not allowed @ `[]`
+    type error: `Long` and `String` are not compatible @ `(if context.ip.isLoopback() then 1 else "one")`
+    tag may not be present @ `principal.getTag("team")`
+    `not an ip` is not a valid IP address @ `"not an ip"`
+    type error: expected `Bool`, found `Long` @ `principal.age`
+    "#);
+}
+
+#[test]
+fn environments() {
+    let source = r#"
+        permit (principal, action == Action::"view", resource)
+        when { resource.owner == principal };
+
+        permit (principal, action == Action::"manage", resource);
+
+        permit (principal is User, action == Action::"view", resource is Color);
+    "#;
+
+    assert_snapshot!(errors(source), @r#"
+    `Album` does not have the attribute `owner` @ `resource.owner`
+    policy does not apply to any request @ `permit (principal, action == Action::"manage", resource);`
+    policy does not apply to any request @ `permit (principal is User, action == Action::"view", resource is Color);`
+    "#);
+}
+
+#[test]
+fn narrowing() {
+    let source = r#"
+        permit (principal, action == Action::"view", resource)
+        when { resource is Photo && resource.owner == principal };
+
+        permit (principal, action == Action::"view", resource)
+        when { resource is Album || resource.private };
+
+        permit (principal, action == Action::"view", resource)
+        when { if resource is Photo then resource.private else true };
+
+        permit (principal, action == Action::"view", resource)
+        when { !(resource is Album) && resource.taken < datetime("2024-01-01") };
+
+        permit (principal, action == Action::"view", resource)
+        when { resource == principal && resource.owner == principal };
+
+        permit (principal, action == Action::"view", resource)
+        when { resource is Album && resource.private };
+    "#;
+
+    assert_snapshot!(errors(source), @"`Album` does not have the attribute `private` @ `resource.private`");
+}
+
+#[test]
+fn render() {
+    let source = r#"permit (principal, action == Action::"view", resource) when { principal.mangaer == principal };"#;
+
+    let rendered = validate(source)
+        .iter()
+        .map(|diagnostic| diagnostic.render("test.cedar", source))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    assert_snapshot!(strip_str(&rendered).to_string(), @r#"
+    error: `User` does not have the attribute `mangaer`
+      ╭▸ test.cedar:1:63
+      │
+    1 │ permit (principal, action == Action::"view", resource) when { principal.mangaer == principal };
+      │                                                               ━━━━━━━━━━━━━━━━━ unknown attribute
+      ╰╴
+    note: did you mean `manager`?
+    "#);
+}